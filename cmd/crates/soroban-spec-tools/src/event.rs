@@ -0,0 +1,241 @@
+//! Schemas of contract events, used to decode the topics and data of events into named fields.
+//!
+//! Contract specs do not describe events yet, so schemas come from two places: the events of
+//! the token interface (SEP-41) for contracts that implement it, and user defined types named
+//! after an event with an `Event` suffix, e.g. a `TransferEvent` struct describes the data of
+//! `transfer` events.
+use serde_json::{Map, Value};
+use stellar_xdr::curr::{ScSpecEntry, ScSpecTypeDef as ScType, ScSpecTypeUdt, ScVal};
+
+use crate::{to_json, Error, Spec};
+
+/// Schema of the events with a given name, the symbol in their first topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    pub name: String,
+    /// Names of the topics after the name. Topics past these are decoded without names.
+    pub topics: Vec<String>,
+    /// Names of the elements of the data when it is a vec, e.g. `[amount, live_until_ledger]`.
+    pub data_fields: Vec<String>,
+    /// Type of the data, decoded with the spec when set.
+    pub data_type: Option<ScType>,
+}
+
+impl Schema {
+    fn new(name: &str, topics: &[&str], data_fields: &[&str]) -> Self {
+        Schema {
+            name: name.to_string(),
+            topics: topics.iter().map(ToString::to_string).collect(),
+            data_fields: data_fields.iter().map(ToString::to_string).collect(),
+            data_type: None,
+        }
+    }
+}
+
+/// Functions a contract must have for its events to be decoded as token events.
+const TOKEN_FUNCTIONS: [&str; 4] = ["balance", "transfer", "allowance", "decimals"];
+
+/// Events of the token interface (SEP-41). Stellar Asset Contracts add the asset as a last topic.
+pub fn token_events() -> Vec<Schema> {
+    vec![
+        Schema::new("transfer", &["from", "to", "asset"], &[]),
+        Schema::new("mint", &["admin", "to", "asset"], &[]),
+        Schema::new("burn", &["from", "asset"], &[]),
+        Schema::new("clawback", &["admin", "from", "asset"], &[]),
+        Schema::new(
+            "approve",
+            &["from", "spender", "asset"],
+            &["amount", "live_until_ledger"],
+        ),
+        Schema::new("set_admin", &["admin", "asset"], &[]),
+        Schema::new("set_authorized", &["admin", "id", "asset"], &[]),
+    ]
+}
+
+impl Spec {
+    /// Schemas of the events of the contract.
+    pub fn event_schemas(&self) -> Vec<Schema> {
+        let Some(entries) = &self.0 else {
+            return Vec::new();
+        };
+        let mut schemas = Vec::new();
+        let is_token = TOKEN_FUNCTIONS.iter().all(|name| {
+            entries.iter().any(|entry| {
+                matches!(entry, ScSpecEntry::FunctionV0(f) if f.name.to_utf8_string_lossy() == *name)
+            })
+        });
+        if is_token {
+            schemas.extend(token_events());
+        }
+        for entry in entries {
+            let udt_name = match entry {
+                ScSpecEntry::UdtStructV0(s) => s.name.to_utf8_string_lossy(),
+                ScSpecEntry::UdtUnionV0(u) => u.name.to_utf8_string_lossy(),
+                _ => continue,
+            };
+            let Some(event) = udt_name.strip_suffix("Event").filter(|n| !n.is_empty()) else {
+                continue;
+            };
+            let name = snake_case(event);
+            let Ok(udt_name) = udt_name.as_str().try_into() else {
+                continue;
+            };
+            schemas.retain(|schema| schema.name != name);
+            schemas.push(Schema {
+                name,
+                topics: Vec::new(),
+                data_fields: Vec::new(),
+                data_type: Some(ScType::Udt(ScSpecTypeUdt { name: udt_name })),
+            });
+        }
+        schemas
+    }
+
+    /// Decode an event into `{"name", "topics", "data"}`, with named topics and data when the
+    /// spec has a schema for the event.
+    pub fn decode_event(&self, topics: &[ScVal], data: &ScVal) -> Result<Value, Error> {
+        let name = match topics.first() {
+            Some(ScVal::Symbol(name)) => Some(name.to_utf8_string_lossy()),
+            _ => None,
+        };
+        let schema = name.as_ref().and_then(|name| {
+            self.event_schemas()
+                .into_iter()
+                .find(|schema| schema.name == *name)
+        });
+        let Some(schema) = schema else {
+            return Ok(serde_json::json!({
+                "name": name,
+                "topics": topics.iter().map(to_json).collect::<Result<Vec<_>, _>>()?,
+                "data": to_json(data)?,
+            }));
+        };
+
+        let mut named_topics = Map::new();
+        for (i, topic) in topics.iter().skip(1).enumerate() {
+            let key = schema
+                .topics
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("topic_{}", i + 1));
+            named_topics.insert(key, to_json(topic)?);
+        }
+        let data = match (&schema.data_type, data) {
+            (Some(data_type), _) => self
+                .xdr_to_json(data, data_type)
+                .or_else(|_| to_json(data))?,
+            (None, ScVal::Vec(Some(values))) if values.len() == schema.data_fields.len() => {
+                Value::Object(
+                    schema
+                        .data_fields
+                        .iter()
+                        .cloned()
+                        .zip(values.iter().map(to_json))
+                        .map(|(key, value)| value.map(|value| (key, value)))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            (None, data) => to_json(data)?,
+        };
+        Ok(serde_json::json!({
+            "name": schema.name,
+            "topics": named_topics,
+            "data": data,
+        }))
+    }
+}
+
+/// `TransferFrom` to `transfer_from`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        ScSpecFunctionV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSymbol, StringM, VecM,
+    };
+
+    fn function(name: &str) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: name.try_into().unwrap(),
+            inputs: VecM::default(),
+            outputs: VecM::default(),
+        })
+    }
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    #[test]
+    fn decodes_token_and_udt_events() {
+        let mut entries = TOKEN_FUNCTIONS.map(function).to_vec();
+        entries.push(ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "CounterIncrementedEvent".try_into().unwrap(),
+            fields: vec![ScSpecUdtStructFieldV0 {
+                doc: StringM::default(),
+                name: "count".try_into().unwrap(),
+                type_: ScType::U32,
+            }]
+            .try_into()
+            .unwrap(),
+        }));
+        let spec = Spec::new(entries);
+
+        let approve = spec
+            .decode_event(
+                &[symbol("approve"), symbol("alice"), symbol("bob")],
+                &ScVal::Vec(Some(
+                    vec![ScVal::I32(5), ScVal::U32(100)].try_into().unwrap(),
+                )),
+            )
+            .unwrap();
+        assert_eq!(
+            approve,
+            serde_json::json!({
+                "name": "approve",
+                "topics": {"from": "alice", "spender": "bob"},
+                "data": {"amount": 5, "live_until_ledger": 100},
+            })
+        );
+
+        let counter = spec
+            .decode_event(
+                &[symbol("counter_incremented")],
+                &ScVal::Map(Some(
+                    vec![stellar_xdr::curr::ScMapEntry {
+                        key: symbol("count"),
+                        val: ScVal::U32(2),
+                    }]
+                    .try_into()
+                    .unwrap(),
+                )),
+            )
+            .unwrap();
+        assert_eq!(counter["data"], serde_json::json!({"count": 2}));
+
+        let unknown = Spec::default()
+            .decode_event(&[symbol("hello")], &ScVal::U32(1))
+            .unwrap();
+        assert_eq!(
+            unknown,
+            serde_json::json!({"name": "hello", "topics": ["hello"], "data": 1})
+        );
+    }
+}
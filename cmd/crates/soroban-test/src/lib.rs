@@ -237,12 +237,14 @@ impl TestEnv {
                 rpc_url: Some(self.rpc_url.clone()),
                 rpc_headers: [].to_vec(),
                 network_passphrase: Some(LOCAL_NETWORK_PASSPHRASE.to_string()),
+                rpc_signing_secret: None,
                 network: None,
             },
             source_account: account.parse().unwrap(),
             locator: config::locator::Args {
                 global: false,
                 config_dir,
+                config_lock_timeout: None,
             },
             hd_path: None,
         }
@@ -260,10 +262,15 @@ impl TestEnv {
                 locator: config.locator.clone(),
                 filter_logs: Vec::default(),
                 quiet: false,
-                verbose: false,
+                verbose: 0,
                 very_verbose: false,
+                log_file: None,
+                otel_endpoint: None,
                 list: false,
                 no_cache: false,
+                yes: false,
+                no_input: false,
+                timeout: None,
             }),
             Some(&config),
         )
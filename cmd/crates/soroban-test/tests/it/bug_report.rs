@@ -0,0 +1,63 @@
+use assert_fs::TempDir;
+use soroban_test::TestEnv;
+
+use soroban_cli::config::network::passphrase::LOCAL;
+
+#[test]
+fn log_file_and_bug_report() {
+    let sandbox = TestEnv::default();
+    let dir = TempDir::new().unwrap();
+    let log = sandbox.dir().join("debug.jsonl");
+    sandbox
+        .new_assert_cmd("network")
+        .args(["add", "mine", "--log-file"])
+        .arg(&log)
+        .args(["--rpc-header", "Authorization: Bearer secret"])
+        .args([
+            "--rpc-url",
+            "http://127.0.0.1:1",
+            "--network-passphrase",
+            LOCAL,
+        ])
+        .assert()
+        .success();
+    let contents = std::fs::read_to_string(&log).unwrap();
+    let line: serde_json::Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+    assert_eq!(line["target"], "stellar_cli::log::command");
+    assert_eq!(line["fields"]["args"][7], "Authorization:<redacted>");
+    assert!(contents.contains(r#""send":{"id":0,"jsonrpc":"2.0","method":"getNetwork"}"#));
+    assert!(contents.contains("command succeeded"));
+
+    sandbox
+        .new_assert_cmd("bug-report")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .arg("--log-file")
+        .arg(&log)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("## Version"))
+        .stdout(predicates::str::contains("## Log"))
+        .stdout(predicates::str::contains("Authorization:<redacted>"));
+}
+
+#[test]
+fn timeout_cancels_the_command_and_logs_it() {
+    let sandbox = TestEnv::default();
+    let log = sandbox.dir().join("timeout.jsonl");
+    // Accepts connections and never responds
+    let rpc = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["settings", "--timeout", "1s", "--log-file"])
+        .arg(&log)
+        .args([
+            "--rpc-url",
+            &format!("http://{}", rpc.local_addr().unwrap()),
+        ])
+        .args(["--network-passphrase", LOCAL])
+        .assert()
+        .code(1)
+        .stderr(predicates::str::contains("timed out after 1s"));
+    let contents = std::fs::read_to_string(&log).unwrap();
+    assert!(contents.contains("command aborted"));
+}
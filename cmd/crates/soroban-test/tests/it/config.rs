@@ -138,7 +138,102 @@ fn set_and_remove_global_network() {
 }
 
 #[test]
-fn multiple_networks() {
+fn set_mainnet_confirm() {
+    let sandbox = TestEnv::default();
+    let dir = TempDir::new().unwrap();
+    sandbox
+        .new_assert_cmd("config")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["set", "safety.mainnet-confirm", "false"])
+        .assert()
+        .success();
+    let config = fs::read_to_string(dir.join("stellar").join("config.toml")).unwrap();
+    assert!(config.contains("[safety]\nmainnet-confirm = false"));
+
+    sandbox
+        .new_assert_cmd("config")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["set", "safety.mainnet-confirm", "maybe"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("expected `true` or `false`"));
+}
+
+#[test]
+fn set_upgrade_check() {
+    let sandbox = TestEnv::default();
+    let dir = TempDir::new().unwrap();
+    sandbox
+        .new_assert_cmd("config")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["set", "upgrade.check", "false"])
+        .assert()
+        .success();
+    let config = fs::read_to_string(dir.join("stellar").join("config.toml")).unwrap();
+    assert!(config.contains("[upgrade]\ncheck = false"));
+
+    sandbox
+        .new_assert_cmd("config")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["show", "--resolved"])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(r"upgrade\.check +false +# global").unwrap());
+}
+
+#[test]
+fn add_network_from_preset() {
+    let sandbox = TestEnv::default();
+    let dir = TempDir::new().unwrap();
+    let registry = sandbox.dir().join("presets.toml");
+    fs::write(
+        &registry,
+        format!(
+            "[presets.sandbox]\nrpc_url = \"https://127.0.0.1\"\nnetwork_passphrase = \"{LOCAL_NETWORK_PASSPHRASE}\"\n"
+        ),
+    )
+    .unwrap();
+
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["presets", "update"])
+        .arg(&registry)
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["presets", "ls"])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match("sandbox +registry +https://127.0.0.1").unwrap())
+        .stdout(predicates::str::contains("testnet"));
+
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["add", "mine", "--preset", "sandbox"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["ls", "--long"])
+        .assert()
+        .stdout(predicates::str::contains("https://127.0.0.1"))
+        .stdout(predicates::str::contains(LOCAL_NETWORK_PASSPHRASE));
+
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args(["add", "other", "--preset", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no network preset named missing"));
+}
+
+#[tokio::test]
+async fn multiple_networks() {
     let sandbox = TestEnv::default();
     let ls = || -> Vec<String> { ls(&sandbox) };
 
@@ -166,6 +261,7 @@ fn multiple_networks() {
         &sub_dir,
     )
     .run()
+    .await
     .unwrap();
 
     assert_eq!(ls().as_slice(), ["local2".to_owned(), "local3".to_owned()]);
@@ -212,6 +308,258 @@ fn generate_key() {
     );
 }
 
+#[test]
+fn rename_copy_and_remove_key() {
+    let sandbox = TestEnv::default();
+    add_test_id(sandbox.dir().as_ref());
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rename", "test_id", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["cp", "alice", "bob"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["cp", "alice", "bob"])
+        .assert()
+        .failure();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rm", "alice"])
+        .assert()
+        .failure();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rm", "--force", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("ls")
+        .assert()
+        .stdout("bob\n");
+}
+
+#[test]
+fn add_list_and_remove_labels() {
+    let sandbox = TestEnv::default();
+    let contract = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+    sandbox
+        .new_assert_cmd("addressbook")
+        .args(["add", "usdc", contract])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("addressbook")
+        .args(["add", "bad", "usdc"])
+        .assert()
+        .failure();
+    sandbox
+        .new_assert_cmd("addressbook")
+        .arg("ls")
+        .assert()
+        .stdout(format!("usdc {contract}\n"));
+    sandbox
+        .new_assert_cmd("addressbook")
+        .args(["rm", "usdc"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("addressbook")
+        .args(["rm", "usdc"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn no_input_fails_prompts_with_the_flag_to_pass() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["add", "alice", "--secret-key", "--no-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SOROBAN_SECRET_KEY"));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["add", "alice", "--seed-phrase", "--no-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SOROBAN_SEED_PHRASE"));
+    let name = add_test_id(sandbox.dir());
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rm", &name, "--no-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use --force"));
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SEED_PHRASE",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .args(["add", "alice", "--seed-phrase", "--no-input"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn show_resolved_config() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SC4ZPYELVR7S7EE7KZDZN3ETFTNQHHLTUL34NUAAWZG5OK2RGJ4V2U3Z",
+        )
+        .args(["add", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("config")
+        .args([
+            "show",
+            "--resolved",
+            "--source",
+            "alice",
+            "--rpc-url",
+            "http://127.0.0.1:1",
+            "--network-passphrase",
+            "x",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match(r"network\.rpc-url +http://127\.0\.0\.1:1 +# flag").unwrap(),
+        )
+        .stdout(predicates::str::is_match(r"network\.rpc-health +unreachable").unwrap())
+        .stdout(predicates::str::is_match(r"identity +alice +# flag").unwrap())
+        .stdout(predicates::str::is_match(r"identity\.address +G[A-Z2-7]{55} +# project").unwrap())
+        .stdout(predicates::str::is_match(r"safety\.mainnet-confirm +true +# default").unwrap());
+}
+
+#[test]
+fn generate_funded_set() {
+    let sandbox = TestEnv::default();
+    let fixture = sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--funded-set", "2", "--no-fund"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let fixture: serde_json::Value = serde_json::from_slice(&fixture).unwrap();
+    assert_eq!(fixture["seed_phrase"], DEFAULT_SEED_PHRASE);
+    let accounts = fixture["accounts"].as_array().unwrap();
+    assert_eq!(accounts.len(), 2);
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "--default-seed", "seed"])
+        .assert()
+        .success();
+    for (hd_path, (account, name)) in accounts.iter().zip(["alice", "bob"]).enumerate() {
+        let hd_path = hd_path.to_string();
+        let public_key = sandbox
+            .new_assert_cmd("keys")
+            .args(["address", "seed", "--hd-path", &hd_path])
+            .assert()
+            .success()
+            .stdout_as_str();
+        let secret_key = sandbox
+            .new_assert_cmd("keys")
+            .args(["show", "seed", "--hd-path", &hd_path])
+            .assert()
+            .success()
+            .stdout_as_str();
+        assert_eq!(account["name"], name);
+        assert_eq!(account["hd_path"].to_string(), hd_path);
+        assert_eq!(account["public_key"], public_key);
+        assert_eq!(account["secret_key"], secret_key);
+        assert_eq!(account["funded"], false);
+        sandbox
+            .new_assert_cmd("keys")
+            .args(["address", name])
+            .assert()
+            .success()
+            .stdout(format!("{public_key}\n"));
+    }
+}
+
+#[test]
+fn public_key_formats() {
+    let sandbox = TestEnv::default();
+    let key = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    for (format, expected) in [
+        ("strkey", key),
+        (
+            "hex",
+            "3b9911380efe988ba0a8900eb1cfe44f366f7dbe946bed077240f7f624df15c5",
+        ),
+        (
+            "muxed",
+            "MA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KAAAAAAAAAAAAFKBA",
+        ),
+        (
+            "contract-address",
+            "AAAAEgAAAAAAAAAAO5kROA7+mIugqJAOsc/kTzZvfb6Ua+0HckD39iTfFcU=",
+        ),
+    ] {
+        sandbox
+            .new_assert_cmd("keys")
+            .args(["public-key", key, "--format", format, "--muxed-id", "1"])
+            .assert()
+            .success()
+            .stdout(format!("{expected}\n"));
+    }
+}
+
+#[test]
+fn sign_and_verify_message() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "signer"])
+        .assert()
+        .success();
+    let signature = sandbox
+        .new_assert_cmd("keys")
+        .args(["sign-message", "signer", "--format", "hex"])
+        .write_stdin("I own this account")
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("keys")
+        .args([
+            "verify-message",
+            "signer",
+            "--message",
+            "I own this account",
+            "--signature",
+            &signature,
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args([
+            "verify-message",
+            "signer",
+            "--message",
+            "I own that account",
+            "--signature",
+            &signature,
+        ])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn generate_key_on_testnet() {
     if std::env::var("CI_TEST").is_err() {
@@ -290,6 +638,130 @@ fn use_env() {
         .stdout("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n");
 }
 
+#[test]
+fn show_secret_requires_confirmation() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "carol", "--no-fund"])
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["show-secret", "carol"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("without confirmation"));
+    // The global `--yes`, e.g. set with `STELLAR_YES` for Mainnet submissions, is not enough
+    sandbox
+        .new_assert_cmd("keys")
+        .env("STELLAR_YES", "true")
+        .args(["show-secret", "carol", "--yes"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("use --force"));
+
+    let secret = sandbox
+        .new_assert_cmd("keys")
+        .args(["show", "carol"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["show-secret", "carol", "--force", "--hd-path", "0"])
+        .assert()
+        .success()
+        .stdout(secret);
+
+    let seed_phrase = sandbox
+        .new_assert_cmd("keys")
+        .args(["show-secret", "carol", "--force"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        String::from_utf8(seed_phrase)
+            .unwrap()
+            .split_whitespace()
+            .count(),
+        12
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn show_secret_copy_clears_the_clipboard() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "carol", "--no-fund"])
+        .assert()
+        .success();
+    // A clipboard tool that keeps what it is given, and every copy in `history`
+    let bin = sandbox.dir().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let tool = bin.join("wl-copy");
+    fs::write(
+        &tool,
+        "#!/bin/sh\nd=$(dirname \"$0\")\ncat > \"$d/clipboard\"\n(cat \"$d/clipboard\"; echo) >> \"$d/history\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&tool, fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", bin.display(), std::env::var("PATH").unwrap());
+
+    sandbox
+        .new_assert_cmd("keys")
+        .env("PATH", &path)
+        .args([
+            "show-secret",
+            "carol",
+            "--force",
+            "--copy",
+            "--clear-after",
+            "1s",
+        ])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("Cleared the clipboard"));
+    let history = fs::read_to_string(bin.join("history")).unwrap();
+    assert_eq!(
+        history.lines().next().unwrap().split_whitespace().count(),
+        12
+    );
+    assert_eq!(fs::read_to_string(bin.join("clipboard")).unwrap(), "");
+
+    // Aborted while waiting to clear it
+    fs::remove_file(bin.join("history")).unwrap();
+    sandbox
+        .new_assert_cmd("keys")
+        .env("PATH", &path)
+        .args([
+            "show-secret",
+            "carol",
+            "--force",
+            "--copy",
+            "--timeout",
+            "1s",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("timed out after 1s"));
+    let history = fs::read_to_string(bin.join("history")).unwrap();
+    assert_eq!(history.lines().count(), 2);
+    assert_eq!(fs::read_to_string(bin.join("clipboard")).unwrap(), "");
+}
+
 #[test]
 fn config_dirs_precedence() {
     let sandbox = TestEnv::default();
@@ -1,11 +1,20 @@
+mod account;
+mod amm;
+mod asset;
 mod bindings;
+mod claimable_balance;
 mod constructor;
 mod cookbook;
 mod custom_types;
+mod dex;
 mod dotenv;
+mod fee;
 mod hello_world;
 mod keys;
+mod ledger;
+mod scenario;
 mod snapshot;
+mod trust;
 mod tx;
 mod util;
 mod wrap;
@@ -0,0 +1,142 @@
+use soroban_test::{AssertExt, TestEnv};
+
+fn address(sandbox: &TestEnv, name: &str) -> String {
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", name])
+        .assert()
+        .success()
+        .stdout_as_str()
+}
+
+fn new_account(sandbox: &TestEnv, name: &str) -> String {
+    sandbox.generate_account(name, None).assert().success();
+    address(sandbox, name)
+}
+
+#[tokio::test]
+async fn add_and_remove_signer() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let test = address(sandbox, "test");
+    let test1 = new_account(sandbox, "test1");
+    sandbox
+        .new_assert_cmd("account")
+        .args(["add-signer", test1.as_str(), "--weight", "2"])
+        .assert()
+        .success();
+    let after = client.get_account(&test).await.unwrap();
+    assert_eq!(after.signers.len(), 1);
+    assert_eq!(after.signers[0].key, test1.parse().unwrap());
+    assert_eq!(after.signers[0].weight, 2);
+
+    sandbox
+        .new_assert_cmd("account")
+        .args(["signers", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(test1.as_str()));
+
+    sandbox
+        .new_assert_cmd("account")
+        .args(["remove-signer", test1.as_str()])
+        .assert()
+        .success();
+    let after = client.get_account(&test).await.unwrap();
+    assert!(after.signers.is_empty());
+}
+
+#[tokio::test]
+async fn merge() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let test = address(sandbox, "test");
+    let test1 = new_account(sandbox, "test1");
+    let before = client.get_account(&test).await.unwrap();
+    let before1 = client.get_account(&test1).await.unwrap();
+    sandbox
+        .new_assert_cmd("account")
+        .args(["merge", "--source", "test1", "--into", test.as_str()])
+        .assert()
+        .success();
+    let after = client.get_account(&test).await.unwrap();
+    assert!(client.get_account(&test1).await.is_err());
+    assert_eq!(before.balance + before1.balance - 100, after.balance);
+}
+
+#[tokio::test]
+async fn create_sponsored() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let test = address(sandbox, "test");
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "bob"])
+        .assert()
+        .success();
+    let bob = address(sandbox, "bob");
+    sandbox
+        .new_assert_cmd("account")
+        .args([
+            "create",
+            "--destination",
+            "bob",
+            "--starting-balance",
+            "0",
+            "--sponsor",
+            "test",
+        ])
+        .assert()
+        .success();
+    let bob_account = client.get_account(&bob).await.unwrap();
+    assert_eq!(bob_account.balance, 0);
+
+    sandbox
+        .new_assert_cmd("sponsorship")
+        .args(["ls", &test])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(bob.as_str()));
+}
+
+#[tokio::test]
+async fn signers_required() {
+    let sandbox = &TestEnv::new();
+    let test1 = new_account(sandbox, "test1");
+    sandbox
+        .new_assert_cmd("account")
+        .args(["add-signer", test1.as_str(), "--weight", "1"])
+        .assert()
+        .success();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "bump-sequence", "--bump-to", "1000", "--build-only"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let report = sandbox
+        .new_assert_cmd("tx")
+        .args(["signers", "required", &tx, "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(report[0]["satisfied"], false);
+    assert_eq!(report[0]["missing"].as_array().unwrap().len(), 2);
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key", "test1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["signers", "required", &signed])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "low threshold 1, signed weight 1, satisfied",
+        ));
+}
@@ -0,0 +1,49 @@
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn deposit_and_withdraw() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "issue", "GBP", "--funder", "test", "--supply", "100000", "--no-sac",
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("amm")
+        .args([
+            "deposit",
+            "GBP:gbp-issuer",
+            "native",
+            "--max-amount-a",
+            "1000",
+            "--max-amount-b",
+            "10000000",
+            "--source",
+            "gbp-distributor",
+        ])
+        .assert()
+        .success();
+    let info = sandbox
+        .new_assert_cmd("amm")
+        .args(["info", "native", "GBP:gbp-issuer", "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert!(info.contains("\"amount\": \"1.0000000\""));
+    assert!(info.contains("\"amount\": \"0.0001000\""));
+    sandbox
+        .new_assert_cmd("amm")
+        .args([
+            "withdraw",
+            "native",
+            "GBP:gbp-issuer",
+            "--shares",
+            "1000",
+            "--source",
+            "gbp-distributor",
+        ])
+        .assert()
+        .success();
+}
@@ -0,0 +1,99 @@
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn issue_locked_asset_funded_by_test() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let stdout = sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "issue",
+            "USD",
+            "--funder",
+            "test",
+            "--home-domain",
+            "example.com",
+            "--supply",
+            "1000",
+            "--lock-issuer",
+            "--no-sac",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let issuer = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "usd-issuer"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(stdout, format!("USD:{issuer}"));
+
+    let account = client.get_account(&issuer).await.unwrap();
+    assert_eq!(account.thresholds.0[0], 0);
+    assert_eq!(account.home_domain.to_string(), "example.com");
+
+    let distributor = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "usd-distributor"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let account = client.get_account(&distributor).await.unwrap();
+    assert_eq!(account.num_sub_entries, 1);
+}
+
+#[tokio::test]
+async fn clawback_and_revoke() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "issue",
+            "EUR",
+            "--funder",
+            "test",
+            "--auth-revocable",
+            "--clawback",
+            "--supply",
+            "1000",
+            "--no-sac",
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "clawback",
+            "--asset",
+            "EUR:eur-issuer",
+            "--from",
+            "eur-distributor",
+            "--amount",
+            "400",
+            "--source",
+            "eur-issuer",
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["ls", "eur-distributor", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("\"balance\": \"0.0000600\""));
+    sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "set-trustline-flags",
+            "--asset",
+            "EUR:eur-issuer",
+            "--trustor",
+            "eur-distributor",
+            "--revoke",
+            "--source",
+            "eur-issuer",
+        ])
+        .assert()
+        .success();
+}
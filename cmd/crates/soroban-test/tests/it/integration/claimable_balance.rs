@@ -0,0 +1,37 @@
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn create_and_claim() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    sandbox.generate_account("alice", None).assert().success();
+    let alice = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "alice"])
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    let id = sandbox
+        .new_assert_cmd("claimable-balance")
+        .args([
+            "create",
+            "--amount",
+            "10000000",
+            "--claimant",
+            "alice=within:1d",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(id.len(), 72);
+
+    let before = client.get_account(&alice).await.unwrap();
+    sandbox
+        .new_assert_cmd("claimable-balance")
+        .args(["claim", "--source", "alice", "--balance-id", &id])
+        .assert()
+        .success();
+    let after = client.get_account(&alice).await.unwrap();
+    assert_eq!(before.balance + 10_000_000 - 100, after.balance);
+}
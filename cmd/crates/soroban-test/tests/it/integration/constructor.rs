@@ -1,4 +1,6 @@
 use assert_cmd::Command;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
 
 use soroban_cli::xdr::{
     self, CreateContractArgsV2, HostFunction, InvokeHostFunctionOp, Limits, OperationBody, ReadXdr,
@@ -6,7 +8,8 @@ use soroban_cli::xdr::{
 };
 use soroban_test::{AssertExt, TestEnv};
 
-use super::util::CONSTRUCTOR;
+use super::util::{CONSTRUCTOR, HELLO_WORLD};
+use crate::util::DEFAULT_CONTRACT_ID;
 
 fn constructor_cmd(sandbox: &TestEnv, value: u32, arg: &str) -> Command {
     let mut cmd = sandbox.new_assert_cmd("contract");
@@ -71,3 +74,80 @@ async fn deploy_constructor_contract() {
         .stdout_as_str();
     assert_eq!(res.trim(), value.to_string());
 }
+
+#[tokio::test]
+async fn deploy_manifest_and_resume() {
+    let sandbox = TestEnv::new();
+    let manifest = sandbox.dir().child("deployments.toml");
+    manifest
+        .write_str(&format!(
+            r#"
+            [contracts.one]
+            wasm = {one:?}
+            args = {{ counter = 1 }}
+
+            [contracts.two]
+            wasm = {two:?}
+            args = {{ counter = 2 }}
+
+            [contracts.hello]
+            wasm = {hello:?}
+            depends_on = ["one", "two"]
+            "#,
+            one = CONSTRUCTOR.path(),
+            two = CONSTRUCTOR.path(),
+            hello = HELLO_WORLD.path(),
+        ))
+        .unwrap();
+    let deploy = || {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["deploy", "--seq-strategy", "pool", "--manifest"])
+            .arg(manifest.path());
+        cmd
+    };
+
+    // `one` and `two` don't depend on each other and are deployed in parallel.
+    let ids = deploy().assert().success().stdout_as_str();
+    assert_eq!(ids.lines().count(), 3, "{ids}");
+    for (alias, counter) in [("one", "1"), ("two", "2")] {
+        let res = sandbox
+            .new_assert_cmd("contract")
+            .args(["invoke", "--id", alias, "--", "counter"])
+            .assert()
+            .success()
+            .stdout_as_str();
+        assert_eq!(res.trim(), counter);
+    }
+
+    // Deploying again reuses the contracts on the network.
+    deploy()
+        .assert()
+        .success()
+        .stdout(ids.clone() + "\n")
+        .stderr(predicate::str::contains("Deploying").not());
+
+    // An alias of a contract that isn't on the network is deployed again.
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "alias",
+            "add",
+            "two",
+            "--overwrite",
+            "--id",
+            DEFAULT_CONTRACT_ID,
+        ])
+        .assert()
+        .success();
+    let redeployed = deploy()
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Alias two of two is {DEFAULT_CONTRACT_ID}, which is not on the network"
+        )))
+        .stderr(predicate::str::contains("Deploying two"))
+        .stderr(predicate::str::contains("Deploying one").not())
+        .stdout_as_str();
+    assert_ne!(redeployed, ids);
+    assert!(!redeployed.contains(DEFAULT_CONTRACT_ID));
+}
@@ -35,6 +35,7 @@ async fn parse() {
     e_2_s_strukt(sandbox, id).await;
     number_arg(sandbox, id).await;
     number_arg_return_err(sandbox, id).await;
+    number_arg_expect(sandbox, id);
     i32(sandbox, id).await;
     i64(sandbox, id).await;
     negative_i32(sandbox, id).await;
@@ -197,6 +198,24 @@ async fn number_arg_return_err(sandbox: &TestEnv, id: &str) {
     println!("{res:#?}");
 }
 
+fn number_arg_expect(sandbox: &TestEnv, id: &str) {
+    let invoke_expecting = |flag: &str, expected: &str, arg: &str| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["invoke", "--id", id, flag, expected, "--"])
+            .args(["u32_fail_on_even", "--u32_", arg]);
+        cmd.assert()
+    };
+    invoke_expecting("--expect", "1", "1").success();
+    invoke_expecting("--expect", "3", "1")
+        .failure()
+        .stderr(predicates::str::contains(
+            "expected the return value 3, got 1",
+        ));
+    invoke_expecting("--expect-error", "1", "2").success();
+    invoke_expecting("--expect-error", "NumberMustBeOdd", "2").success();
+    invoke_expecting("--expect-error", "1", "1").failure();
+}
+
 fn void(sandbox: &TestEnv, id: &str) {
     invoke_custom(sandbox, id, "woid")
         .assert()
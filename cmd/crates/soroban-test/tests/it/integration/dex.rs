@@ -0,0 +1,62 @@
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn create_list_and_cancel_offer() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("asset")
+        .args([
+            "issue", "JPY", "--funder", "test", "--supply", "1000", "--no-sac",
+        ])
+        .assert()
+        .success();
+    let id = sandbox
+        .new_assert_cmd("dex")
+        .args([
+            "offer",
+            "create",
+            "--selling",
+            "JPY:jpy-issuer",
+            "--buying",
+            "native",
+            "--amount",
+            "500",
+            "--price",
+            "0.01",
+            "--source",
+            "jpy-distributor",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("dex")
+        .args(["offer", "ls", "jpy-distributor"])
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with(format!("{id} sell 0.0000500")));
+    sandbox
+        .new_assert_cmd("dex")
+        .args(["orderbook", "JPY:jpy-issuer", "native", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("\"price\": \"0.0100000\""));
+    sandbox
+        .new_assert_cmd("dex")
+        .args([
+            "offer",
+            "cancel",
+            "--offer-id",
+            &id,
+            "--source",
+            "jpy-distributor",
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("dex")
+        .args(["offer", "ls", "jpy-distributor"])
+        .assert()
+        .success()
+        .stdout("No offers\n");
+}
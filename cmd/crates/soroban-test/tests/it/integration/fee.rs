@@ -0,0 +1,48 @@
+use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope};
+use soroban_test::{AssertExt, TestEnv};
+
+fn bump_sequence_fee(sandbox: &TestEnv, fee_args: &[&str]) -> u32 {
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "bump-sequence", "--bump-to", "1", "--build-only"])
+        .args(fee_args)
+        .assert()
+        .success()
+        .stdout_as_str();
+    let TransactionEnvelope::Tx(env) =
+        TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 transaction envelope");
+    };
+    env.tx.fee
+}
+
+#[tokio::test]
+async fn fee_strategy_prices_from_fee_stats() {
+    let sandbox = &TestEnv::new();
+    assert!(bump_sequence_fee(sandbox, &["--fee-strategy", "p90"]) >= 100);
+}
+
+#[tokio::test]
+async fn fee_takes_precedence_over_strategy() {
+    let sandbox = &TestEnv::new();
+    let fee = bump_sequence_fee(
+        sandbox,
+        &["--inclusion-fee", "1234", "--fee-strategy", "p90"],
+    );
+    assert_eq!(fee, 1234);
+}
+
+#[tokio::test]
+async fn stats_json() {
+    let sandbox = &TestEnv::new();
+    let stats = sandbox
+        .new_assert_cmd("fee")
+        .args(["stats", "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let stats: serde_json::Value = serde_json::from_str(&stats).unwrap();
+    assert!(stats["soroban_inclusion_fee"]["p90"].as_u64().is_some());
+    assert!(stats["resource_fees"]["instructions_10k"].as_i64().unwrap() > 0);
+}
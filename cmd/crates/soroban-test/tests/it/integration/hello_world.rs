@@ -102,6 +102,7 @@ async fn invoke() {
     let config_locator = locator::Args {
         global: false,
         config_dir: Some(dir.to_path_buf()),
+        config_lock_timeout: None,
     };
     config_locator
         .write_identity(
@@ -237,6 +238,28 @@ async fn invoke_auth_with_different_test_account_fail(sandbox: &TestEnv, id: &st
     );
 }
 
+#[tokio::test]
+async fn invoke_read_only() {
+    let sandbox = &TestEnv::new();
+    let id = &deploy_hello(sandbox).await;
+    for _ in 0..2 {
+        sandbox
+            .new_assert_cmd("contract")
+            .args(["invoke", "--send=read", "--cache", "--id", id, "--"])
+            .args(["hello", "--world=world"])
+            .assert()
+            .stdout("[\"Hello\",\"world\"]\n")
+            .success();
+    }
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--send=read", "--id", id, "--"])
+        .args(["auth", "--addr=test", "--world=world"])
+        .assert()
+        .stderr(predicates::str::contains("auth is not read-only"))
+        .failure();
+}
+
 fn contract_data_read_failure(sandbox: &TestEnv, id: &str) {
     sandbox
         .new_assert_cmd("contract")
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn entry_get_account_and_trustline() {
+    let sandbox = &TestEnv::new();
+    sandbox.generate_account("issuer", None).assert().success();
+    let test = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "test"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["add", "USDC:issuer"])
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("ledger")
+        .args([
+            "entry",
+            "get",
+            "--account",
+            "test",
+            "--trustline",
+            "test:USDC:issuer",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "\"account_id\": \"{test}\""
+        )))
+        .stdout(predicates::str::contains("\"trustline\""));
+}
+
+#[tokio::test]
+async fn entries_at_ledger_from_the_archive() {
+    let sandbox = &TestEnv::new();
+    sandbox.generate_account("issuer", None).assert().success();
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["add", "USDC:issuer"])
+        .assert()
+        .success();
+    let entry: serde_json::Value = serde_json::from_str(
+        &sandbox
+            .new_assert_cmd("ledger")
+            .args(["entry", "get", "--trustline", "test:USDC:issuer"])
+            .assert()
+            .success()
+            .stdout_as_str(),
+    )
+    .unwrap();
+    let modified = entry["entries"][0]["last_modified_ledger"]
+        .as_u64()
+        .unwrap();
+
+    // Archives hold the state every 64 ledgers, wait for the first checkpoint after the
+    // trustline to be published.
+    let checkpoint = (modified / 64 + 1) * 64 - 1;
+    let at_ledger = format!("--at-ledger={checkpoint}");
+    let mut trustlines = None;
+    for _ in 0..180 {
+        if let Ok(output) = sandbox
+            .new_assert_cmd("trust")
+            .args(["ls", "test", "--output", "json", &at_ledger])
+            .ok()
+        {
+            trustlines = Some(String::from_utf8(output.stdout).unwrap());
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let trustlines = trustlines.expect("checkpoint was not published");
+    assert!(trustlines.contains("\"USDC:"), "{trustlines}");
+    assert!(
+        trustlines.contains("\"balance\": \"0.0000000\""),
+        "{trustlines}"
+    );
+
+    sandbox
+        .new_assert_cmd("ledger")
+        .args([
+            "entry",
+            "get",
+            "--trustline",
+            "test:USDC:issuer",
+            &at_ledger,
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "\"latest_ledger\": {checkpoint}"
+        )))
+        .stdout(predicates::str::contains("\"trustline\""));
+
+    // The checkpoint before the trustline was added doesn't have it.
+    if checkpoint > 64 {
+        sandbox
+            .new_assert_cmd("trust")
+            .args(["ls", "test", "--output", "json"])
+            .arg(format!("--at-ledger={}", checkpoint - 64))
+            .assert()
+            .success()
+            .stdout("[]\n");
+    }
+}
+
+#[tokio::test]
+async fn watch_stops_after_count() {
+    let sandbox = &TestEnv::new();
+    let lines = sandbox
+        .new_assert_cmd("ledger")
+        .args(["watch", "--count", "2", "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let sequences = lines
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["sequence"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(sequences.len(), 2);
+    assert_eq!(
+        sequences[0].as_u64().unwrap() + 1,
+        sequences[1].as_u64().unwrap()
+    );
+}
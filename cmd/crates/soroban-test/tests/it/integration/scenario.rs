@@ -0,0 +1,98 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use super::util::HELLO_WORLD;
+
+#[tokio::test]
+async fn run_scenario() {
+    let sandbox = &TestEnv::new();
+    let scenario = sandbox.dir().join("scenario.toml");
+    std::fs::write(
+        &scenario,
+        format!(
+            r#"
+[[step]]
+deploy = {{ wasm = {wasm:?}, alias = "hello" }}
+
+[[step]]
+name = "say hello"
+invoke = {{ contract = "hello", function = "hello", args = {{ world = "you" }} }}
+expect = ["Hello", "you"]
+
+[[step]]
+invoke = {{ contract = "hello", function = "auth", args = {{ addr = "test", world = "you" }}, send = true }}
+expect_events = [{{ topics = [{{ symbol = "auth" }}], data = {{ symbol = "you" }} }}]
+"#,
+            wasm = HELLO_WORLD.path().display().to_string(),
+        ),
+    )
+    .unwrap();
+    let report = sandbox
+        .new_assert_cmd("contract")
+        .arg("run")
+        .arg(&scenario)
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert!(
+        report.ends_with("3 passed, 0 failed, 0 not run"),
+        "{report}"
+    );
+
+    std::fs::write(
+        &scenario,
+        r#"
+[[step]]
+invoke = { contract = "hello", function = "hello", args = { world = "you" } }
+expect = ["Hello", "world"]
+
+[[step]]
+invoke = { contract = "hello", function = "hello", args = { world = "you" } }
+"#,
+    )
+    .unwrap();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("run")
+        .arg(&scenario)
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("FAIL 1/2"))
+        .stdout(predicates::str::contains("0 passed, 1 failed, 1 not run"));
+}
+
+#[tokio::test]
+async fn bench_against_baseline() {
+    let sandbox = &TestEnv::new();
+    let scenario = sandbox.dir().join("bench.toml");
+    std::fs::write(
+        &scenario,
+        format!(
+            r#"
+[[step]]
+deploy = {{ wasm = {wasm:?}, alias = "hello" }}
+
+[[step]]
+name = "inc"
+invoke = {{ contract = "hello", function = "inc" }}
+"#,
+            wasm = HELLO_WORLD.path().display().to_string(),
+        ),
+    )
+    .unwrap();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["bench", "--runs", "1"])
+        .arg(&scenario)
+        .assert()
+        .success();
+    let baseline = std::fs::read_to_string(sandbox.dir().join("bench.bench.json")).unwrap();
+    let baseline: serde_json::Value = serde_json::from_str(&baseline).unwrap();
+    assert!(baseline["steps"]["inc"]["instructions"].as_u64().unwrap() > 0);
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["bench", "--runs", "1"])
+        .arg(&scenario)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("inc"));
+}
@@ -0,0 +1,38 @@
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn add_ls_and_remove_with_issuer_alias() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    sandbox.generate_account("issuer", None).assert().success();
+    let test = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "test"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let before = client.get_account(&test).await.unwrap();
+
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["add", "USDC:issuer", "--limit", "1000"])
+        .assert()
+        .success();
+    let after = client.get_account(&test).await.unwrap();
+    assert_eq!(before.num_sub_entries + 1, after.num_sub_entries);
+
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["ls", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("\"limit\": \"0.0001000\""));
+
+    sandbox
+        .new_assert_cmd("trust")
+        .args(["remove", "USDC:issuer"])
+        .assert()
+        .success();
+    let after = client.get_account(&test).await.unwrap();
+    assert_eq!(before.num_sub_entries, after.num_sub_entries);
+}
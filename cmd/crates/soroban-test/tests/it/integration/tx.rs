@@ -1,5 +1,7 @@
 use soroban_cli::assembled::simulate_and_assemble_transaction;
-use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr};
+use soroban_cli::xdr::{
+    LedgerBounds, Limits, Preconditions, ReadXdr, TimeBounds, TransactionEnvelope, WriteXdr,
+};
 use soroban_test::{AssertExt, TestEnv};
 
 use crate::integration::util::{deploy_contract, DeployKind, HELLO_WORLD};
@@ -34,6 +36,73 @@ async fn simulate() {
     );
 }
 
+#[tokio::test]
+async fn contract_commands_apply_preconditions() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployKind::Normal, None).await;
+    let built = sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            &id,
+            "--build-only",
+            "--time-bounds",
+            ":1735689600",
+            "--ledger-bounds",
+            "10:1000000",
+            "--",
+            "hello",
+            "--world=world",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx_env = TransactionEnvelope::from_xdr_base64(&built, Limits::none()).unwrap();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(tx_env).unwrap();
+    let Preconditions::V2(cond) = tx.cond else {
+        panic!("expected v2 preconditions, got {:?}", tx.cond);
+    };
+    assert_eq!(
+        cond.time_bounds,
+        Some(TimeBounds {
+            min_time: 0.into(),
+            max_time: 1_735_689_600.into(),
+        })
+    );
+    assert_eq!(
+        cond.ledger_bounds,
+        Some(LedgerBounds {
+            min_ledger: 10,
+            max_ledger: 1_000_000,
+        })
+    );
+
+    let built = sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "asset",
+            "deploy",
+            "--asset",
+            "native",
+            "--build-only",
+            "--time-bounds",
+            ":1735689600",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx_env = TransactionEnvelope::from_xdr_base64(&built, Limits::none()).unwrap();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(tx_env).unwrap();
+    assert_eq!(
+        tx.cond,
+        Preconditions::Time(TimeBounds {
+            min_time: 0.into(),
+            max_time: 1_735_689_600.into(),
+        })
+    );
+}
+
 #[tokio::test]
 async fn txn_hash() {
     let sandbox = &TestEnv::new();
@@ -84,3 +153,52 @@ async fn build_simulate_sign_send() {
         .success()
         .stdout(predicates::str::contains("SUCCESS"));
 }
+
+#[tokio::test]
+async fn no_wait_then_wait() {
+    let sandbox = &TestEnv::new();
+    let hash = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "bump-sequence", "--bump-to", "1", "--no-wait"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(hash.len(), 64);
+    let status = sandbox
+        .new_assert_cmd("tx")
+        .args(["wait", &hash, "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let status: serde_json::Value = serde_json::from_str(&status).unwrap();
+    assert_eq!(status["status"], "SUCCESS");
+    assert_eq!(status["hash"], hash);
+}
+
+#[tokio::test]
+async fn dry_run_prints_signed_envelope() {
+    let sandbox = &TestEnv::new();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "bump-sequence", "--bump-to", "1000", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Dry run, not sending the transaction",
+        ))
+        .stdout_as_str();
+    let TransactionEnvelope::Tx(env) =
+        TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 transaction envelope");
+    };
+    assert_eq!(env.signatures.len(), 1);
+    let test = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "test"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let account = sandbox.client().get_account(&test).await.unwrap();
+    assert!(account.seq_num.0 < 1000);
+}
@@ -107,6 +107,80 @@ async fn payment() {
     assert_eq!(before.balance - 10_000_100, after.balance);
 }
 
+#[tokio::test]
+async fn payment_to_label_of_config_dir() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let (_, test1) = setup_accounts(sandbox);
+    let secret = sandbox
+        .new_assert_cmd("keys")
+        .args(["show", "test"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let config_dir = sandbox.dir().join("other");
+    let config_dir = config_dir.to_str().unwrap();
+    sandbox
+        .new_assert_cmd("addressbook")
+        .args(["add", "bob", test1.as_str(), "--config-dir", config_dir])
+        .assert()
+        .success();
+    let before = client.get_account(&test1).await.unwrap();
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            "bob",
+            "--amount",
+            ONE_XLM.to_string().as_str(),
+            "--source-account",
+            secret.as_str(),
+            "--config-dir",
+            config_dir,
+        ])
+        .assert()
+        .success();
+    let after = client.get_account(&test1).await.unwrap();
+    assert_eq!(ONE_XLM, after.balance - before.balance);
+}
+
+#[tokio::test]
+async fn concurrent_payments_with_pooled_sequence_numbers() {
+    let sandbox = &TestEnv::new();
+    let client = soroban_rpc::Client::new(&sandbox.rpc_url).unwrap();
+    let (test, test1) = setup_accounts(sandbox);
+    let before = client.get_account(&test).await.unwrap();
+    let test1_before = client.get_account(&test1).await.unwrap();
+    let pay = || {
+        sandbox
+            .new_assert_cmd("tx")
+            .args([
+                "new",
+                "payment",
+                "--destination",
+                test1.as_str(),
+                "--amount",
+                ONE_XLM.to_string().as_str(),
+                "--seq-strategy",
+                "pool",
+            ])
+            .assert()
+            .success();
+    };
+    std::thread::scope(|s| {
+        let first = s.spawn(pay);
+        let second = s.spawn(pay);
+        first.join().unwrap();
+        second.join().unwrap();
+    });
+    let after = client.get_account(&test).await.unwrap();
+    assert_eq!(before.seq_num.0 + 2, after.seq_num.0);
+    let test1_after = client.get_account(&test1).await.unwrap();
+    assert_eq!(2 * ONE_XLM, test1_after.balance - test1_before.balance);
+}
+
 #[tokio::test]
 async fn bump_sequence() {
     let sandbox = &TestEnv::new();
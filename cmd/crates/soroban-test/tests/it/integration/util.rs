@@ -86,7 +86,8 @@ pub async fn deploy_contract(
             commands::txn_result::TxnEnvelopeResult::TxnEnvelope(e) => {
                 return e.to_xdr_base64(Limits::none()).unwrap()
             }
-            commands::txn_result::TxnEnvelopeResult::Res(_) => todo!(),
+            commands::txn_result::TxnEnvelopeResult::Res(_)
+            | commands::txn_result::TxnEnvelopeResult::Submitted(_) => todo!(),
         },
         DeployKind::Normal => (),
     }
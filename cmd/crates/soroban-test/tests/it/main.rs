@@ -1,4 +1,5 @@
 mod arg_parsing;
+mod bug_report;
 mod build;
 mod config;
 mod help;
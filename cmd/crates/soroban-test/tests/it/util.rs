@@ -2,7 +2,10 @@ use std::path::Path;
 
 use soroban_cli::{
     commands::contract,
-    config::{locator::KeyType, secret::Secret},
+    config::{
+        locator::{KeyType, DEFAULT_LOCK_TIMEOUT},
+        secret::Secret,
+    },
 };
 use soroban_test::{TestEnv, Wasm, TEST_ACCOUNT};
 
@@ -26,7 +29,7 @@ pub fn add_key(dir: &Path, name: &str, kind: SecretKind, data: &str) {
     };
 
     KeyType::Identity
-        .write(name, &secret, &dir.join(".soroban"))
+        .write(name, &secret, &dir.join(".soroban"), DEFAULT_LOCK_TIMEOUT)
         .unwrap();
 }
 
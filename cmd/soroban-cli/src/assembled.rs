@@ -13,6 +13,7 @@ use soroban_rpc::{LogEvents, LogResources};
 
 pub(crate) const DEFAULT_TRANSACTION_FEES: u32 = 100;
 
+#[tracing::instrument(name = "simulate", skip_all, err(level = "debug"))]
 pub async fn simulate_and_assemble_transaction(
     client: &soroban_rpc::Client,
     tx: &Transaction,
@@ -1,12 +1,34 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use clap::CommandFactory;
 use dotenvy::dotenv;
-use tracing_subscriber::{fmt, EnvFilter};
+use tokio::sync::oneshot;
+use tracing::Instrument;
+use tracing_subscriber::{
+    filter::{filter_fn, FilterExt},
+    fmt,
+    layer::SubscriberExt,
+    EnvFilter, Layer,
+};
 
+use crate::commands::version;
 use crate::config::Config;
+use crate::input;
+use crate::interrupt;
+use crate::log::{file, otel};
 use crate::print::Print;
 use crate::upgrade_check::upgrade_check;
 use crate::{commands, Root};
 
+/// How long an aborted command has to be cancelled before the process exits without it.
+const ABORT_GRACE: Duration = Duration::from_secs(2);
+
 #[tokio::main]
 pub async fn main() {
     let _ = dotenv().unwrap_or_default();
@@ -48,7 +70,84 @@ pub async fn main() {
     });
 
     // Now use root to setup the logger
-    if let Some(level) = root.global_args.log_level() {
+    let otlp = setup_logger(&root);
+
+    // Spawn a thread to check if a new version exists.
+    // It depends on logger, so we need to place it after
+    // the code block that initializes the logger.
+    tokio::spawn(async move {
+        upgrade_check(root.global_args.quiet).await;
+    });
+
+    if root.global_args.no_input {
+        input::disable();
+    }
+
+    let printer = Print::new(root.global_args.quiet);
+
+    // Watch for Ctrl-C and the timeout on another task, and cancel the command when they come,
+    // dropping it so that its config locks and sequence number reservations are released.
+    // Commands blocked outside of the runtime, such as on a prompt, cannot be cancelled, so the
+    // process exits if the command has not been cancelled within `ABORT_GRACE`.
+    let abort = interrupt::abort(root.global_args.timeout);
+    let quiet = root.global_args.quiet;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (abort_tx, abort_rx) = oneshot::channel();
+    tokio::spawn({
+        let cancelled = cancelled.clone();
+        async move {
+            let abort = abort.await;
+            let _ = abort_tx.send(abort);
+            tokio::time::sleep(ABORT_GRACE).await;
+            if !cancelled.load(Ordering::SeqCst) {
+                interrupt::report(&Print::new(quiet), abort);
+                std::process::exit(abort.exit_code());
+            }
+        }
+    });
+
+    let span = tracing::info_span!(
+        target: file::COMMAND_TARGET,
+        "command",
+        command = root.command_names.join(" ")
+    );
+    let res = tokio::select! {
+        res = root.run().instrument(span.clone()) => Ok(res),
+        Ok(abort) = abort_rx => {
+            cancelled.store(true, Ordering::SeqCst);
+            Err(abort)
+        }
+    };
+    span.in_scope(|| match &res {
+        Ok(Ok(())) => tracing::debug!(target: file::COMMAND_TARGET, "command succeeded"),
+        Ok(Err(e)) => tracing::debug!(target: file::COMMAND_TARGET, error = %e, "command failed"),
+        Err(abort) => tracing::debug!(target: file::COMMAND_TARGET, %abort, "command aborted"),
+    });
+    drop(span);
+    if let Err(abort) = res {
+        interrupt::report(&printer, abort);
+    }
+    if let Some(otlp) = otlp {
+        if let Err(e) = otlp.export().await {
+            printer.warnln(e.to_string());
+        }
+    }
+    match res {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            printer.errorln(format!("error: {e}"));
+            std::process::exit(1);
+        }
+        Err(abort) => std::process::exit(abort.exit_code()),
+    }
+}
+
+// Set up the logger to stderr, the structured log file, and the trace export, returning the
+// exporter to send the spans to once the command is done.
+// `Metadata::is_event` as a filter fn doesn't satisfy the filter's lifetime bounds
+#[allow(clippy::redundant_closure_for_method_calls)]
+fn setup_logger(root: &Root) -> Option<otel::Otlp> {
+    let stderr_layer = root.global_args.log_level().map(|level| {
         let mut e_filter = EnvFilter::from_default_env()
             .add_directive("hyper=off".parse().unwrap())
             .add_directive(format!("stellar_cli={level}").parse().unwrap())
@@ -66,28 +165,55 @@ pub async fn main() {
             );
         }
 
-        let builder = fmt::Subscriber::builder()
-            .with_env_filter(e_filter)
+        // Only events, the spans are for the structured log and the trace export
+        fmt::layer()
             .with_ansi(false)
-            .with_writer(std::io::stderr);
+            .with_writer(std::io::stderr)
+            .with_filter(e_filter.and(filter_fn(|metadata| metadata.is_event())))
+    });
+
+    // The bug report reads the log file of the last run instead of writing it
+    let file_layer = root
+        .global_args
+        .log_file()
+        .filter(|_| !matches!(root.cmd, commands::Cmd::BugReport(_)))
+        .map(|path| {
+            file::JsonLines::create(&path)
+                .unwrap_or_else(|e| {
+                    eprintln!("creating log file {}: {e}", path.display());
+                    std::process::exit(1);
+                })
+                .with_filter(EnvFilter::new(file::FILTER))
+        });
+
+    let otlp = root.global_args.otel_endpoint.as_deref().map(|endpoint| {
+        otel::Otlp::new(endpoint).unwrap_or_else(|e| {
+            eprintln!("invalid OpenTelemetry endpoint {endpoint}: {e}");
+            std::process::exit(1);
+        })
+    });
+    let otel_layer = otlp
+        .clone()
+        .map(|otlp| otlp.with_filter(EnvFilter::new(otel::FILTER)));
 
-        let subscriber = builder.finish();
+    if stderr_layer.is_some() || file_layer.is_some() || otel_layer.is_some() {
+        let subscriber = tracing_subscriber::registry()
+            .with(stderr_layer)
+            .with(file_layer)
+            .with(otel_layer);
         tracing::subscriber::set_global_default(subscriber)
             .expect("Failed to set the global tracing subscriber");
     }
 
-    // Spawn a thread to check if a new version exists.
-    // It depends on logger, so we need to place it after
-    // the code block that initializes the logger.
-    tokio::spawn(async move {
-        upgrade_check(root.global_args.quiet).await;
-    });
+    tracing::debug!(
+        target: file::COMMAND_TARGET,
+        version = version::pkg(),
+        git = version::git(),
+        args = %serde_json::Value::from(file::redact_args(std::env::args_os())),
+        "running command"
+    );
 
-    let printer = Print::new(root.global_args.quiet);
-    if let Err(e) = root.run().await {
-        printer.errorln(format!("error: {e}"));
-        std::process::exit(1);
-    }
+    otlp
 }
 
 // Load ~/.config/stellar/config.toml defaults as env vars.
@@ -95,6 +221,7 @@ fn set_env_from_config() {
     if let Ok(config) = Config::new() {
         set_env_value_from_config("STELLAR_ACCOUNT", config.defaults.identity);
         set_env_value_from_config("STELLAR_NETWORK", config.defaults.network);
+        set_env_value_from_config("STELLAR_FEE_STRATEGY", config.defaults.fee_strategy);
     }
 }
 
@@ -0,0 +1,21 @@
+use clap::{command, Parser};
+
+use crate::{commands::tx, xdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Signer to add, e.g. an account `G...`, a pre-authorized transaction `T...`, or a hash `X...`
+    pub signer: xdr::SignerKey,
+    /// Weight of the signer, a number from 1-255 (inclusive)
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..))]
+    pub weight: u8,
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        super::set_signer(cmd.signer.clone(), cmd.weight)
+    }
+}
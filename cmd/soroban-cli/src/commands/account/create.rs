@@ -0,0 +1,68 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx, txn_result::TxnEnvelopeResult},
+    config::{
+        address::{self, Address},
+        secret,
+    },
+    tx::ONE_XLM,
+    xdr::{self, Limits, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Account to create, e.g. an identity or `GBX...`. With `--sponsor` it must be an identity
+    /// with a secret key, as the new account signs the end of the sponsorship.
+    #[arg(long)]
+    pub destination: Address,
+    /// Initial balance in stroops of the account, can be 0 when the account is sponsored
+    #[arg(long, default_value_t = ONE_XLM)]
+    pub starting_balance: i64,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let (destination, memo) = self.tx.resolve_destination(&self.destination)?;
+        let destination = destination.account_id();
+        let op = xdr::OperationBody::CreateAccount(xdr::CreateAccountOp {
+            destination: destination.clone(),
+            starting_balance: self.starting_balance,
+        });
+        let tx = self.tx.sponsored_tx(op, Some(destination), memo).await?;
+        let signers = if self.tx.sponsor.is_some() && !self.tx.fee.build_only {
+            vec![self
+                .destination
+                .resolve_secret(&config.locator)?
+                .key_pair(config.hd_path)?]
+        } else {
+            Vec::new()
+        };
+        match self
+            .tx
+            .handle_tx_with_signers(tx, signers, global_args)
+            .await?
+        {
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
+            TxnEnvelopeResult::Res(_) => {}
+        }
+        Ok(())
+    }
+}
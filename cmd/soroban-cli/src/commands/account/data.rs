@@ -0,0 +1,87 @@
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::commands::global;
+
+pub mod get;
+pub mod ls;
+pub mod rm;
+pub mod set;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Set a data entry of the source account, creating it if it does not exist
+    Set(set::Cmd),
+    /// Get a data entry of an account
+    Get(get::Cmd),
+    /// Remove a data entry of the source account
+    Rm(rm::Cmd),
+    /// List the data entries of an account, using Horizon
+    Ls(ls::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] super::super::tx::args::Error),
+    #[error(transparent)]
+    Get(#[from] get::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Set(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::Get(cmd) => cmd.run().await?,
+            Cmd::Rm(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::Ls(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// The value as UTF-8 when it is printable text, as base64 otherwise
+    Text,
+    /// The value as base64
+    Base64,
+    /// The name and the value as base64, and as UTF-8 when it is text
+    Json,
+}
+
+impl Output {
+    fn format(self, name: &str, value: &[u8]) -> String {
+        let text = std::str::from_utf8(value)
+            .ok()
+            .filter(|text| !text.chars().any(char::is_control));
+        match self {
+            Output::Text => text.map_or_else(|| base64.encode(value), ToString::to_string),
+            Output::Base64 => base64.encode(value),
+            Output::Json => json!({
+                "name": name,
+                "value": base64.encode(value),
+                "text": text,
+            })
+            .to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_text_and_binary_values() {
+        assert_eq!(Output::Text.format("domain", b"example.com"), "example.com");
+        assert_eq!(Output::Text.format("proof", &[0, 1, 2]), "AAEC");
+        assert_eq!(
+            Output::Json.format("proof", &[0, 1, 2]),
+            r#"{"name":"proof","text":null,"value":"AAEC"}"#
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use clap::{arg, command, Parser};
+
+use super::Output;
+use crate::{
+    config::{self, address::Address, network},
+    rpc,
+    xdr::{self, LedgerEntryData, LedgerKey, LedgerKeyData, Limits, ReadXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("account {account} has no data entry {name:?}")]
+    NotFound { account: String, name: String },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of the entry
+    pub name: xdr::StringM<64>,
+    /// Account to get the entry of, e.g. `GBX...` or an identity, defaults to `STELLAR_ACCOUNT`
+    #[arg(long, env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Format of the value
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let account_id = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id();
+        let client = self.config.get_network()?.rpc_client()?;
+        let key = LedgerKey::Data(LedgerKeyData {
+            account_id: account_id.clone(),
+            data_name: self.name.clone().into(),
+        });
+        let entry = client
+            .get_ledger_entries(&[key])
+            .await?
+            .entries
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .map(|entry| LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none()))
+            .transpose()?;
+        let Some(LedgerEntryData::Data(data)) = entry else {
+            return Err(Error::NotFound {
+                account: account_id.to_string(),
+                name: self.name.to_utf8_string_lossy(),
+            });
+        };
+        println!(
+            "{}",
+            self.output
+                .format(&self.name.to_utf8_string_lossy(), &data.data_value)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::{arg, command, Parser};
+
+use super::Output;
+use crate::{
+    config::{self, address::Address, network},
+    horizon,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error("invalid base64 value of data entry {0:?} in Horizon response")]
+    InvalidValue(String),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account to list the entries of, e.g. `GBX...` or an identity, defaults to
+    /// `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Format of the values. With `text` and `base64` each entry is printed as `name: value`
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let account_id = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id()
+            .to_string();
+        let network = self.config.get_network()?;
+        let account = self.horizon.client(&network)?.account(&account_id).await?;
+        let Some(data) = account["data"].as_object() else {
+            return Ok(());
+        };
+        for (name, value) in data {
+            let value = value
+                .as_str()
+                .and_then(|value| base64.decode(value).ok())
+                .ok_or_else(|| Error::InvalidValue(name.clone()))?;
+            let formatted = self.output.format(name, &value);
+            match self.output {
+                Output::Json => println!("{formatted}"),
+                Output::Text | Output::Base64 => println!("{name}: {formatted}"),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use clap::{command, Parser};
+
+use crate::{commands::tx, xdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Name of the entry to remove
+    pub name: xdr::StringM<64>,
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        xdr::OperationBody::ManageData(xdr::ManageDataOp {
+            data_name: cmd.name.clone().into(),
+            data_value: None,
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use clap::{command, Parser};
+
+use crate::{commands::tx, xdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Name of the entry, up to 64 bytes
+    pub name: xdr::StringM<64>,
+    /// Value of the entry as UTF-8 text, up to 64 bytes
+    #[arg(
+        required_unless_present = "base64",
+        conflicts_with = "base64",
+        value_parser = parse_text
+    )]
+    pub value: Option<xdr::BytesM<64>>,
+    /// Value of the entry as base64, for binary values up to 64 bytes
+    #[arg(long, value_parser = parse_base64)]
+    pub base64: Option<xdr::BytesM<64>>,
+}
+
+fn parse_text(value: &str) -> Result<xdr::BytesM<64>, String> {
+    value
+        .as_bytes()
+        .try_into()
+        .map_err(|_| format!("{} bytes is longer than 64 bytes", value.len()))
+}
+
+fn parse_base64(value: &str) -> Result<xdr::BytesM<64>, String> {
+    let bytes = STANDARD.decode(value).map_err(|e| e.to_string())?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| format!("{len} bytes is longer than 64 bytes"))
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        xdr::OperationBody::ManageData(xdr::ManageDataOp {
+            data_name: cmd.name.clone().into(),
+            data_value: cmd
+                .value
+                .clone()
+                .or_else(|| cmd.base64.clone())
+                .map(Into::into),
+        })
+    }
+}
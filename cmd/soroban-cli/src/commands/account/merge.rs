@@ -0,0 +1,26 @@
+use clap::{command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::address::Address,
+    xdr,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Account receiving the balance of the source account, e.g. `GBX...`, `MBX...`, a federation address, or an address book label
+    #[arg(long)]
+    pub into: Address,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), tx::args::Error> {
+        let (into, memo) = self.tx.resolve_destination(&self.into)?;
+        self.tx
+            .handle_and_print_with_memo(xdr::OperationBody::AccountMerge(into), memo, global_args)
+            .await
+    }
+}
@@ -0,0 +1,81 @@
+use clap::Parser;
+
+use crate::xdr;
+
+use super::global;
+
+pub mod add_signer;
+pub mod create;
+pub mod data;
+pub mod merge;
+pub mod remove_signer;
+pub mod signers;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// Add a signer to an account, or update the weight of an existing signer
+    /// Learn more about signers operations and key weight:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/security/signatures-multisig#multisig
+    AddSigner(add_signer::Cmd),
+    /// Create and fund a new account, with `--sponsor` the sponsor pays its base reserve
+    Create(create::Cmd),
+    /// Set, get, remove, and list the data entries of an account
+    #[command(subcommand)]
+    Data(data::Cmd),
+    /// Merge the source account into another account, transferring its XLM balance and removing it from the ledger
+    Merge(merge::Cmd),
+    /// Remove a signer from an account
+    RemoveSigner(remove_signer::Cmd),
+    /// Set thresholds, master key weight, home domain, and flags of an account
+    /// Learn more about flags:
+    /// https://developers.stellar.org/docs/learn/glossary#flags
+    SetOptions(super::tx::new::set_options::Cmd),
+    /// List the signers and thresholds of an account
+    Signers(signers::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] super::tx::args::Error),
+    #[error(transparent)]
+    Create(#[from] create::Error),
+    #[error(transparent)]
+    Signers(#[from] signers::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::AddSigner(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::Create(cmd) => cmd.run(global_args).await?,
+            Cmd::Data(cmd) => cmd.run(global_args).await?,
+            Cmd::Merge(cmd) => cmd.run(global_args).await?,
+            Cmd::RemoveSigner(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::SetOptions(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::Signers(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+/// A `SetOptions` operation that sets the weight of `key`, removing the signer when the weight is 0.
+fn set_signer(key: xdr::SignerKey, weight: u8) -> xdr::OperationBody {
+    xdr::OperationBody::SetOptions(xdr::SetOptionsOp {
+        inflation_dest: None,
+        clear_flags: None,
+        set_flags: None,
+        master_weight: None,
+        low_threshold: None,
+        med_threshold: None,
+        high_threshold: None,
+        home_domain: None,
+        signer: Some(xdr::Signer {
+            key,
+            weight: weight.into(),
+        }),
+    })
+}
@@ -0,0 +1,18 @@
+use clap::{command, Parser};
+
+use crate::{commands::tx, xdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Signer to remove, e.g. an account `G...`, a pre-authorized transaction `T...`, or a hash `X...`
+    pub signer: xdr::SignerKey,
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        super::set_signer(cmd.signer.clone(), 0)
+    }
+}
@@ -0,0 +1,83 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::json;
+
+use crate::{
+    config::{self, address::Address, network},
+    rpc,
+    xdr::AccountEntry,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per signer, followed by the thresholds
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account to list the signers of, e.g. `GBX...` or an identity, defaults to `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let address = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id()
+            .to_string();
+        let client = self.config.get_network()?.rpc_client()?;
+        let AccountEntry {
+            thresholds,
+            signers,
+            ..
+        } = client.get_account(&address).await?;
+        let [master_weight, low, med, high] = thresholds.0;
+        match self.output {
+            Output::Json => {
+                let mut all = vec![json!({ "key": address, "weight": master_weight })];
+                all.extend(
+                    signers
+                        .iter()
+                        .map(|s| json!({ "key": s.key.to_string(), "weight": s.weight })),
+                );
+                let out = json!({
+                    "signers": all,
+                    "thresholds": { "low": low, "med": med, "high": high },
+                });
+                println!("{}", serde_json::to_string_pretty(&out)?);
+            }
+            Output::Text => {
+                println!("{address} {master_weight} (master key)");
+                for signer in signers.iter() {
+                    println!("{} {}", signer.key, signer.weight);
+                }
+                println!("thresholds: low {low}, med {med}, high {high}");
+            }
+        }
+        Ok(())
+    }
+}
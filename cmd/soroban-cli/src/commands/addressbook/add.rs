@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use clap::command;
+
+use crate::{
+    commands::global,
+    config::{addressbook::Entry, locator},
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("{0} is not an account (G...) or contract (C...) address")]
+    InvalidAddress(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Label of the address, e.g. `treasury`
+    pub label: String,
+
+    /// Account (G...) or contract (C...) address
+    pub address: String,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if stellar_strkey::ed25519::PublicKey::from_str(&self.address).is_err()
+            && stellar_strkey::Contract::from_str(&self.address).is_err()
+        {
+            return Err(Error::InvalidAddress(self.address.clone()));
+        }
+        self.config_locator.write_label(
+            &self.label,
+            &Entry {
+                address: self.address.clone(),
+            },
+        )?;
+        print.checkln(format!("Labelled {} '{}'", self.address, self.label));
+        Ok(())
+    }
+}
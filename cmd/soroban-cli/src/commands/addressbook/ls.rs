@@ -0,0 +1,25 @@
+use clap::command;
+
+use crate::config::locator;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        for (label, entry) in self.config_locator.list_labels()? {
+            println!("{label} {}", entry.address);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use clap::Parser;
+
+use crate::commands::global;
+
+pub mod add;
+pub mod ls;
+pub mod rm;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Label an account or contract address, shown instead of the address in human-readable
+    /// output and accepted wherever the address is expected
+    Add(add::Cmd),
+
+    /// List the labels and their addresses
+    Ls(ls::Cmd),
+
+    /// Remove a label
+    Rm(rm::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Add(#[from] add::Error),
+
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+
+    #[error(transparent)]
+    Rm(#[from] rm::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Add(cmd) => cmd.run(global_args)?,
+            Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Rm(cmd) => cmd.run(global_args)?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use clap::command;
+
+use crate::{commands::global, config::locator, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("Failed to find label '{0}'")]
+    NotFound(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Label to remove
+    pub label: String,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if self.config_locator.read_label(&self.label)?.is_none() {
+            return Err(Error::NotFound(self.label.clone()));
+        }
+        self.config_locator.remove_label(&self.label)?;
+        print.checkln(format!("Removed label '{}'", self.label));
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::asset::Asset,
+    horizon, xdr,
+};
+
+use super::{price, submit, Error, Pool};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First asset of the pool, `native` or `CODE:ISSUER`, where the issuer can be an identity
+    pub asset_a: Asset,
+    /// Second asset of the pool
+    pub asset_b: Asset,
+    /// Most of the first asset to deposit, in stroops
+    #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+    pub max_amount_a: i64,
+    /// Most of the second asset to deposit, in stroops
+    #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+    pub max_amount_b: i64,
+    /// How far in basis points the price of the pool can move from its current price, or from the
+    /// ratio of the maximum amounts when the pool is empty, before the deposit fails
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u16).range(0..10_000))]
+    pub slippage_bps: u16,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let pool = Pool::new(
+            &self.asset_a,
+            &self.asset_b,
+            &config.locator,
+            config.hd_path,
+        )?;
+        let client = self.horizon.client(&config.get_network()?)?;
+        let state = pool.state(&client).await?;
+        let (max_amount_a, max_amount_b) = pool.ordered(self.max_amount_a, self.max_amount_b);
+        // The price of a deposit is the ratio of asset A to asset B deposited.
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = if state.reserve_a > 0 && state.reserve_b > 0 {
+            state.reserve_a as f64 / state.reserve_b as f64
+        } else {
+            max_amount_a as f64 / max_amount_b as f64
+        };
+        let slippage = f64::from(self.slippage_bps) / 10_000.0;
+
+        let mut ops = Vec::new();
+        let account_id = self.tx.source_account()?.account_id().to_string();
+        if !self.has_trustline(&client, &account_id, &state.id).await? {
+            ops.push(xdr::OperationBody::ChangeTrust(xdr::ChangeTrustOp {
+                line: xdr::ChangeTrustAsset::PoolShare(pool.parameters()),
+                limit: i64::MAX,
+            }));
+        }
+        ops.push(xdr::OperationBody::LiquidityPoolDeposit(
+            xdr::LiquidityPoolDepositOp {
+                liquidity_pool_id: pool.id()?,
+                max_amount_a,
+                max_amount_b,
+                min_price: price(ratio * (1.0 - slippage)),
+                max_price: price(ratio * (1.0 + slippage)),
+            },
+        ));
+        submit(&self.tx, ops, global_args).await
+    }
+
+    /// Whether the account holds a trustline to the shares of the pool, which is missing when
+    /// the account itself is.
+    async fn has_trustline(
+        &self,
+        client: &horizon::Client,
+        account_id: &str,
+        pool_id: &str,
+    ) -> Result<bool, Error> {
+        let account = match client.account(account_id).await {
+            Ok(account) => account,
+            Err(horizon::Error::Status { status, .. })
+                if status == reqwest::StatusCode::NOT_FOUND =>
+            {
+                return Ok(false)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(account["balances"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|balance| balance["liquidity_pool_id"] == pool_id))
+    }
+}
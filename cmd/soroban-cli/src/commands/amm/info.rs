@@ -0,0 +1,79 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::json;
+
+use crate::{
+    config::{self, asset::Asset},
+    horizon,
+};
+
+use super::{Error, Pool};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per field
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First asset of the pool, `native` or `CODE:ISSUER`, where the issuer can be an identity
+    pub asset_a: Asset,
+    /// Second asset of the pool
+    pub asset_b: Asset,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let pool = Pool::new(&self.asset_a, &self.asset_b, &self.config.locator, None)?;
+        let client = self.horizon.client(&self.config.get_network()?)?;
+        let state = pool.state(&client).await?;
+        let (asset_a, asset_b) = pool.ordered(&pool.asset_a, &pool.asset_b);
+        let (reserve_a, reserve_b) = pool.ordered(state.reserve_a, state.reserve_b);
+        let (asset_a, asset_b) = (
+            horizon::asset_string(asset_a),
+            horizon::asset_string(asset_b),
+        );
+        // The price of the first asset in the second, as the pool would trade before fees.
+        #[allow(clippy::cast_precision_loss)]
+        let price = (reserve_a > 0).then(|| reserve_b as f64 / reserve_a as f64);
+        match self.output {
+            Output::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "id": state.id,
+                    "fee_bp": state.fee_bp,
+                    "reserves": [
+                        { "asset": asset_a, "amount": horizon::amount(reserve_a) },
+                        { "asset": asset_b, "amount": horizon::amount(reserve_b) },
+                    ],
+                    "total_shares": horizon::amount(state.total_shares),
+                    "total_trustlines": state.trustlines,
+                    "price": price,
+                }))?
+            ),
+            Output::Text => {
+                println!("id: {}", state.id);
+                println!("fee: {} bps", state.fee_bp);
+                println!("reserve {asset_a}: {}", horizon::amount(reserve_a));
+                println!("reserve {asset_b}: {}", horizon::amount(reserve_b));
+                println!("total shares: {}", horizon::amount(state.total_shares));
+                println!("trustlines: {}", state.trustlines);
+                match price {
+                    Some(price) => println!("price: {price} {asset_b} per {asset_a}"),
+                    None => println!("price: none, the pool is empty"),
+                }
+            }
+        }
+        Ok(())
+    }
+}
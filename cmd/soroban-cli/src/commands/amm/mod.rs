@@ -0,0 +1,279 @@
+use clap::Parser;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::{self, asset, locator},
+    horizon,
+    tx::builder::{self, TxExt},
+    xdr::{self, Limits, WriteXdr},
+};
+
+use super::{global, tx, txn_result::TxnEnvelopeResult};
+
+pub mod deposit;
+pub mod info;
+pub mod withdraw;
+
+/// Fee of the constant product pools, the only kind of classic pool, in basis points.
+pub const POOL_FEE_BP: i32 = 30;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// Deposit into the liquidity pool of two assets, creating the trustline to its pool shares
+    /// when missing. The deposit fails if the price of the pool moved more than the slippage.
+    /// Learn more about liquidity pools:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/sdex/liquidity-on-stellar-sdex-liquidity-pools
+    Deposit(deposit::Cmd),
+    /// Withdraw pool shares from the liquidity pool of two assets, receiving at least the share
+    /// of the reserves less the slippage
+    Withdraw(withdraw::Cmd),
+    /// Show the id, reserves, fee, and shares of the liquidity pool of two assets
+    Info(info::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Builder(#[from] builder::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("a liquidity pool is between two different assets")]
+    SameAsset,
+    #[error("the liquidity pool {0} has no reserves to withdraw")]
+    EmptyPool(String),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Deposit(cmd) => cmd.run(global_args).await?,
+            Cmd::Withdraw(cmd) => cmd.run(global_args).await?,
+            Cmd::Info(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+/// The constant product pool of two assets, whose assets are in the order of the protocol.
+#[derive(Debug, Clone)]
+pub struct Pool {
+    pub asset_a: xdr::Asset,
+    pub asset_b: xdr::Asset,
+    /// Whether the assets were given as B then A.
+    pub swapped: bool,
+}
+
+impl Pool {
+    pub fn new(
+        first: &asset::Asset,
+        second: &asset::Asset,
+        locator: &locator::Args,
+        hd_path: Option<usize>,
+    ) -> Result<Self, Error> {
+        let first = first.resolve(locator, hd_path)?.0;
+        let second = second.resolve(locator, hd_path)?.0;
+        if first == second {
+            return Err(Error::SameAsset);
+        }
+        let swapped = first > second;
+        let (asset_a, asset_b) = if swapped {
+            (second, first)
+        } else {
+            (first, second)
+        };
+        Ok(Self {
+            asset_a,
+            asset_b,
+            swapped,
+        })
+    }
+
+    pub fn parameters(&self) -> xdr::LiquidityPoolParameters {
+        xdr::LiquidityPoolParameters::LiquidityPoolConstantProduct(
+            xdr::LiquidityPoolConstantProductParameters {
+                asset_a: self.asset_a.clone(),
+                asset_b: self.asset_b.clone(),
+                fee: POOL_FEE_BP,
+            },
+        )
+    }
+
+    /// The id of the pool, the hash of its parameters.
+    pub fn id(&self) -> Result<xdr::PoolId, xdr::Error> {
+        let hash = Sha256::digest(self.parameters().to_xdr(Limits::none())?);
+        Ok(xdr::PoolId(xdr::Hash(hash.into())))
+    }
+
+    /// Order a pair of values given for the assets in the order they were given as A then B.
+    pub fn ordered<T>(&self, first: T, second: T) -> (T, T) {
+        if self.swapped {
+            (second, first)
+        } else {
+            (first, second)
+        }
+    }
+
+    /// The reserves and shares of the pool from Horizon, which are zero when it doesn't exist
+    /// yet.
+    pub async fn state(&self, client: &horizon::Client) -> Result<State, Error> {
+        let id = hex::encode(self.id()?.0 .0);
+        let record = match client.get(&format!("liquidity_pools/{id}"), &[]).await {
+            Ok(record) => record,
+            Err(horizon::Error::Status { status, .. })
+                if status == reqwest::StatusCode::NOT_FOUND =>
+            {
+                return Ok(State {
+                    id,
+                    ..State::default()
+                })
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let amount = |value: &Value| horizon::parse_amount(value.as_str().unwrap_or("0"));
+        let reserves = record["reserves"].as_array().cloned().unwrap_or_default();
+        let reserve = |asset: &xdr::Asset| -> Result<i64, Error> {
+            let name = horizon::asset_string(asset);
+            reserves
+                .iter()
+                .find(|r| r["asset"] == name.as_str())
+                .map_or(Ok(0), |r| Ok(amount(&r["amount"])?))
+        };
+        Ok(State {
+            reserve_a: reserve(&self.asset_a)?,
+            reserve_b: reserve(&self.asset_b)?,
+            total_shares: amount(&record["total_shares"])?,
+            trustlines: record["total_trustlines"]
+                .as_str()
+                .and_then(|t| t.parse().ok())
+                .unwrap_or_default(),
+            fee_bp: record["fee_bp"].as_u64().unwrap_or(30),
+            id,
+        })
+    }
+}
+
+/// The reserves and shares of a pool, in stroops.
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub id: String,
+    pub reserve_a: i64,
+    pub reserve_b: i64,
+    pub total_shares: i64,
+    pub trustlines: u64,
+    pub fee_bp: u64,
+}
+
+/// Build a transaction of `ops` from the source account of `tx_args`, then print it or submit
+/// it.
+pub async fn submit(
+    tx_args: &tx::Args,
+    ops: Vec<xdr::OperationBody>,
+    global_args: &global::Args,
+) -> Result<(), Error> {
+    let mut ops = ops.into_iter();
+    let tx = tx_args
+        .tx(ops.next().expect("pool operations are never empty"))
+        .await?;
+    let operations = tx
+        .operations
+        .iter()
+        .cloned()
+        .chain(ops.map(|body| xdr::Operation {
+            source_account: None,
+            body,
+        }))
+        .collect();
+    let tx = tx.with_operations_keeping_fee_per_op(operations)?;
+    match tx_args.handle_tx(tx, global_args).await? {
+        TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+        TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
+        TxnEnvelopeResult::Res(_) => {}
+    }
+    Ok(())
+}
+
+/// `amount` less `bps` basis points.
+pub fn less_bps(amount: i64, bps: u16) -> i64 {
+    let amount = i128::from(amount) * i128::from(10_000 - bps) / 10_000;
+    i64::try_from(amount).unwrap_or(i64::MAX)
+}
+
+/// The closest fraction to `value` whose numerator and denominator fit a price, found with its
+/// continued fraction.
+#[allow(clippy::cast_possible_truncation)]
+pub fn price(value: f64) -> xdr::Price {
+    let max = f64::from(i32::MAX);
+    if !value.is_finite() || value <= 0.0 {
+        return xdr::Price { n: 1, d: i32::MAX };
+    }
+    if value >= max {
+        return xdr::Price { n: i32::MAX, d: 1 };
+    }
+    let (mut n0, mut d0, mut n1, mut d1) = (0.0, 1.0, 1.0, 0.0);
+    let mut x = value;
+    loop {
+        let a = x.floor();
+        let (n2, d2) = (a * n1 + n0, a * d1 + d0);
+        if n2 > max || d2 > max {
+            break;
+        }
+        (n0, d0, n1, d1) = (n1, d1, n2, d2);
+        let fraction = x - a;
+        if fraction < 1e-12 {
+            break;
+        }
+        x = 1.0 / fraction;
+    }
+    if n1 < 1.0 {
+        return xdr::Price { n: 1, d: i32::MAX };
+    }
+    xdr::Price {
+        n: n1 as i32,
+        d: d1 as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_orders_assets_and_derives_id() {
+        let locator = locator::Args::default();
+        let usdc = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+            .parse()
+            .unwrap();
+        let native = "native".parse().unwrap();
+        let pool = Pool::new(&usdc, &native, &locator, None).unwrap();
+        assert!(pool.swapped);
+        assert_eq!(pool.asset_a, xdr::Asset::Native);
+        assert_eq!(pool.ordered(1, 2), (2, 1));
+        let reversed = Pool::new(&native, &usdc, &locator, None).unwrap();
+        assert_eq!(pool.id().unwrap(), reversed.id().unwrap());
+        assert!(matches!(
+            Pool::new(&native, &native, &locator, None),
+            Err(Error::SameAsset)
+        ));
+    }
+
+    #[test]
+    fn prices_approximate_ratios() {
+        assert_eq!(price(2.5), xdr::Price { n: 5, d: 2 });
+        assert_eq!(price(1.0 / 3.0), xdr::Price { n: 1, d: 3 });
+        let p = price(std::f64::consts::PI);
+        assert!((f64::from(p.n) / f64::from(p.d) - std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(less_bps(10_000, 100), 9_900);
+    }
+}
@@ -0,0 +1,76 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::asset::Asset,
+    horizon, xdr,
+};
+
+use super::{less_bps, submit, Error, Pool};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First asset of the pool, `native` or `CODE:ISSUER`, where the issuer can be an identity
+    pub asset_a: Asset,
+    /// Second asset of the pool
+    pub asset_b: Asset,
+    /// Pool shares to withdraw, in stroops
+    #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+    pub shares: i64,
+    /// Least of the first asset to receive, in stroops, defaults to the share of its reserve less
+    /// the slippage
+    #[arg(long)]
+    pub min_amount_a: Option<i64>,
+    /// Least of the second asset to receive, in stroops, defaults to the share of its reserve
+    /// less the slippage
+    #[arg(long)]
+    pub min_amount_b: Option<i64>,
+    /// How far in basis points the amounts received can fall below the current share of the
+    /// reserves, when the minimum amounts are not given
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u16).range(0..10_000))]
+    pub slippage_bps: u16,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let pool = Pool::new(
+            &self.asset_a,
+            &self.asset_b,
+            &config.locator,
+            config.hd_path,
+        )?;
+        let (min_amount_a, min_amount_b) = match (self.min_amount_a, self.min_amount_b) {
+            (Some(a), Some(b)) => pool.ordered(a, b),
+            (given_a, given_b) => {
+                let client = self.horizon.client(&config.get_network()?)?;
+                let state = pool.state(&client).await?;
+                if state.total_shares == 0 {
+                    return Err(Error::EmptyPool(state.id));
+                }
+                let share = |reserve: i64| {
+                    let amount = i128::from(reserve) * i128::from(self.shares)
+                        / i128::from(state.total_shares);
+                    less_bps(i64::try_from(amount).unwrap_or(i64::MAX), self.slippage_bps)
+                };
+                let (given_a, given_b) = pool.ordered(given_a, given_b);
+                (
+                    given_a.unwrap_or_else(|| share(state.reserve_a)),
+                    given_b.unwrap_or_else(|| share(state.reserve_b)),
+                )
+            }
+        };
+        let op = xdr::OperationBody::LiquidityPoolWithdraw(xdr::LiquidityPoolWithdrawOp {
+            liquidity_pool_id: pool.id()?,
+            amount: self.shares,
+            min_amount_a,
+            min_amount_b,
+        });
+        submit(&self.tx, vec![op], global_args).await
+    }
+}
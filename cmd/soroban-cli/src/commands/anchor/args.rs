@@ -0,0 +1,88 @@
+use clap::{arg, command, ValueEnum};
+
+use crate::{
+    commands::auth,
+    config,
+    print::Print,
+    sep::{stellar_toml, transfer, StellarToml},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Auth(#[from] auth::sep10::Error),
+    #[error(transparent)]
+    StellarToml(#[from] stellar_toml::Error),
+    #[error(transparent)]
+    Transfer(#[from] transfer::Error),
+}
+
+/// Transfer protocol implemented by the anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Sep {
+    /// SEP-6 programmatic deposit and withdrawal
+    #[value(name = "6")]
+    Sep6,
+    /// SEP-24 hosted (interactive) deposit and withdrawal
+    #[value(name = "24")]
+    Sep24,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Anchor {
+    /// Home domain of the anchor, e.g. `testanchor.stellar.org`
+    #[arg(long)]
+    pub home_domain: String,
+    /// Transfer protocol to use
+    #[arg(long, value_enum, default_value = "24")]
+    pub sep: Sep,
+}
+
+impl Anchor {
+    /// Find the transfer server for the selected protocol in the anchor's stellar.toml.
+    pub async fn server(&self, token: Option<String>) -> Result<transfer::Server, Error> {
+        let toml = StellarToml::fetch(&self.home_domain).await?;
+        let url = match self.sep {
+            Sep::Sep6 => toml.transfer_server(&self.home_domain)?,
+            Sep::Sep24 => toml.transfer_server_sep0024(&self.home_domain)?,
+        };
+        Ok(transfer::Server::new(url, token)?)
+    }
+}
+
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
+    #[command(flatten)]
+    pub anchor: Anchor,
+    /// SEP-10 JWT to use instead of authenticating the source account with `stellar auth sep10`
+    #[arg(long, env = "STELLAR_ANCHOR_JWT")]
+    pub jwt: Option<String>,
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+impl Args {
+    /// Authenticate with the anchor, then return its transfer server.
+    pub async fn server(&self, print: &Print) -> Result<transfer::Server, Error> {
+        let token = match &self.jwt {
+            Some(jwt) => jwt.clone(),
+            None => {
+                auth::sep10::Cmd {
+                    home_domain: self.anchor.home_domain.clone(),
+                    auth_endpoint: None,
+                    server_signing_key: None,
+                    client_domain: None,
+                    client_domain_signer: None,
+                    muxed_id: None,
+                    memo: None,
+                    config: self.config.clone(),
+                }
+                .token(print)
+                .await?
+            }
+        };
+        self.anchor.server(Some(token)).await
+    }
+}
@@ -0,0 +1,29 @@
+use clap::{command, Parser};
+
+use super::args::{self, Anchor};
+use crate::sep::transfer;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub anchor: Anchor,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Args(#[from] args::Error),
+    #[error(transparent)]
+    Transfer(#[from] transfer::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let info = self.anchor.server(None).await?.info().await?;
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        Ok(())
+    }
+}
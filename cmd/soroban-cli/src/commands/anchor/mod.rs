@@ -0,0 +1,49 @@
+use clap::Parser;
+
+use super::global;
+use crate::sep::transfer::Kind;
+
+pub mod args;
+pub mod info;
+pub mod status;
+pub mod transfer;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Print the assets and fields supported by an anchor's transfer server
+    Info(info::Cmd),
+    /// Start a deposit of an asset onto the network through an anchor
+    ///
+    /// Authenticates with SEP-10 (unless `--jwt` is given), then requests deposit instructions
+    /// (SEP-6) or the URL of the interactive deposit flow (SEP-24).
+    Deposit(transfer::Cmd),
+    /// Start a withdrawal of an asset off the network through an anchor
+    ///
+    /// Authenticates with SEP-10 (unless `--jwt` is given), then requests withdrawal instructions
+    /// (SEP-6) or the URL of the interactive withdrawal flow (SEP-24).
+    Withdraw(transfer::Cmd),
+    /// Print the status of a deposit or withdrawal, optionally waiting for it to finish
+    Status(status::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Info(#[from] info::Error),
+    #[error(transparent)]
+    Status(#[from] status::Error),
+    #[error(transparent)]
+    Transfer(#[from] transfer::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Info(cmd) => cmd.run().await?,
+            Cmd::Deposit(cmd) => cmd.run(Kind::Deposit, global_args).await?,
+            Cmd::Withdraw(cmd) => cmd.run(Kind::Withdraw, global_args).await?,
+            Cmd::Status(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use clap::{arg, command, Parser};
+use serde_json::Value;
+
+use super::args;
+use crate::{
+    commands::global,
+    interrupt,
+    print::Print,
+    sep::transfer::{self, FINAL_STATUSES, USER_ACTION_STATUSES},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub args: args::Args,
+    /// ID of the transaction returned by the anchor
+    #[arg(long)]
+    pub id: String,
+    #[command(flatten)]
+    pub wait: Wait,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Wait {
+    /// Poll the transaction until it completes, fails, or needs action from the user, or until
+    /// `--timeout` runs out
+    #[arg(long)]
+    pub wait: bool,
+    /// Seconds between polls when waiting
+    #[arg(long, default_value = "5", requires = "wait")]
+    pub interval: u64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Args(#[from] args::Error),
+    #[error(transparent)]
+    Transfer(#[from] transfer::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(
+        "transaction {id} is still {status}, stopped waiting for it before --timeout runs out"
+    )]
+    StillPending { id: String, status: String },
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let server = self.args.server(&print).await?;
+        let transaction = if self.wait.wait {
+            self.wait.poll(&server, &self.id, &print).await?
+        } else {
+            server.transaction(&self.id).await?
+        };
+        println!("{}", serde_json::to_string_pretty(&transaction)?);
+        Ok(())
+    }
+}
+
+impl Wait {
+    /// Poll the status of transaction `id` until there is nothing more for the anchor to do, or
+    /// there is no time for another poll before the deadline of `--timeout`.
+    pub async fn poll(
+        &self,
+        server: &transfer::Server,
+        id: &str,
+        print: &Print,
+    ) -> Result<Value, Error> {
+        let mut last = None;
+        loop {
+            let transaction = server.transaction(id).await?;
+            let status = transfer::status(&transaction)
+                .unwrap_or("unknown")
+                .to_string();
+            if last.as_ref() != Some(&status) {
+                print.infoln(format!("Transaction {id} is {status}"));
+            }
+            if FINAL_STATUSES.contains(&status.as_str()) {
+                return Ok(transaction);
+            }
+            if USER_ACTION_STATUSES.contains(&status.as_str()) {
+                print.warnln(format!("Transaction {id} is waiting on the user"));
+                return Ok(transaction);
+            }
+            let Some(delay) = next_poll(self.interval, interrupt::remaining()) else {
+                return Err(Error::StillPending {
+                    id: id.to_string(),
+                    status,
+                });
+            };
+            last = Some(status);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// How long to wait before the next poll, `None` when the `remaining` time before the deadline
+/// is shorter than the `interval` between polls.
+fn next_poll(interval: u64, remaining: Option<Duration>) -> Option<Duration> {
+    let interval = Duration::from_secs(interval);
+    match remaining {
+        Some(remaining) if remaining < interval => None,
+        _ => Some(interval),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn polls_until_the_deadline() {
+        assert_eq!(next_poll(5, None), Some(Duration::from_secs(5)));
+        assert_eq!(
+            next_poll(5, Some(Duration::from_secs(60))),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(next_poll(5, Some(Duration::from_secs(4))), None);
+        assert_eq!(next_poll(0, Some(Duration::ZERO)), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn stops_polling_at_final_and_user_action_statuses() {
+        let mut server = mockito::Server::new_async().await;
+        let anchor = transfer::Server::new(&server.url(), None).unwrap();
+        let wait = Wait {
+            wait: true,
+            interval: 0,
+        };
+        for status in ["completed", "pending_user_transfer_start"] {
+            let mock = server
+                .mock("GET", "/transaction")
+                .match_query(mockito::Matcher::UrlEncoded("id".into(), "1".into()))
+                .with_body(json!({ "transaction": { "id": "1", "status": status } }).to_string())
+                .expect(1)
+                .create_async()
+                .await;
+            let transaction = wait.poll(&anchor, "1", &Print::new(true)).await.unwrap();
+            assert_eq!(transfer::status(&transaction), Some(status));
+            mock.assert_async().await;
+        }
+    }
+}
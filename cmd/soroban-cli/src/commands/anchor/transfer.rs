@@ -0,0 +1,126 @@
+use clap::{arg, command, Parser};
+use itertools::Itertools;
+
+use super::{
+    args::{self, Sep},
+    status,
+};
+use crate::{
+    commands::global,
+    config,
+    print::Print,
+    sep::transfer::{self, Kind},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub args: args::Args,
+    /// Code of the asset to transfer, e.g. `USDC`, or `native` for XLM
+    #[arg(long)]
+    pub asset: String,
+    /// Amount to transfer, in units of the asset
+    #[arg(long)]
+    pub amount: Option<String>,
+    /// Additional field to send to the anchor, e.g. `--field type=bank_account`. Can be repeated.
+    #[arg(long = "field", num_args = 1, value_parser = parse_field, action = clap::ArgAction::Append)]
+    pub fields: Vec<(String, String)>,
+    /// Open the interactive URL returned by a SEP-24 anchor in the browser
+    #[arg(long)]
+    pub open: bool,
+    #[command(flatten)]
+    pub wait: status::Wait,
+}
+
+fn parse_field(s: &str) -> Result<(String, String), Error> {
+    s.splitn(2, '=')
+        .map(str::trim)
+        .next_tuple()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| Error::Field(s.to_string()))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Args(#[from] args::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Transfer(#[from] transfer::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Status(#[from] status::Error),
+    #[error("field {0:?} must be in the form 'key=value'")]
+    Field(String),
+    #[error("failed to open {url}: {error}")]
+    Open { url: String, error: std::io::Error },
+    #[error("anchor did not return a transaction id to wait on")]
+    MissingId,
+}
+
+impl Cmd {
+    pub async fn run(&self, kind: Kind, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let account = self.args.config.source_account()?.to_string();
+        let mut params = vec![
+            ("asset_code".to_string(), self.asset.clone()),
+            ("account".to_string(), account),
+        ];
+        if let Some(amount) = &self.amount {
+            params.push(("amount".to_string(), amount.clone()));
+        }
+        params.extend(self.fields.iter().cloned());
+
+        let server = self.args.server(&print).await?;
+        let id = match self.args.anchor.sep {
+            Sep::Sep6 => {
+                let response = server.sep6(kind, &params).await?;
+                println!("{}", serde_json::to_string_pretty(&response)?);
+                response
+                    .get("id")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string)
+            }
+            Sep::Sep24 => {
+                let interactive = server.interactive(kind, &params).await?;
+                println!("{}", serde_json::to_string_pretty(&interactive)?);
+                print.linkln(&interactive.url);
+                if self.open {
+                    open::that(&interactive.url).map_err(|error| Error::Open {
+                        url: interactive.url.clone(),
+                        error,
+                    })?;
+                }
+                Some(interactive.id)
+            }
+        };
+
+        if self.wait.wait {
+            let id = id.ok_or(Error::MissingId)?;
+            let transaction = self.wait.poll(&server, &id, &print).await?;
+            println!("{}", serde_json::to_string_pretty(&transaction)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fields() {
+        assert_eq!(
+            parse_field("type=bank_account").unwrap(),
+            ("type".to_string(), "bank_account".to_string())
+        );
+        assert_eq!(
+            parse_field(" memo = a=b ").unwrap(),
+            ("memo".to_string(), "a=b".to_string())
+        );
+        assert!(matches!(parse_field("type"), Err(Error::Field(f)) if f == "type"));
+    }
+}
@@ -0,0 +1,63 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::{
+        address::{self, Address},
+        asset::{self, Asset},
+    },
+    xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error("the native asset cannot be clawed back")]
+    Native,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset to claw back as `CODE:ISSUER`, where the issuer can be an identity. The source
+    /// account must be the issuer, with clawback enabled on the trustline
+    #[arg(long)]
+    pub asset: Asset,
+    /// Account to claw back from, e.g. an identity or `GBX...`
+    #[arg(long)]
+    pub from: Address,
+    /// Amount in stroops to claw back, burning it
+    #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+    pub amount: i64,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let op = xdr::OperationBody::Clawback(xdr::ClawbackOp {
+            asset: issued(&self.asset, &self.tx)?,
+            from: self
+                .from
+                .resolve_muxed_account(&config.locator, config.hd_path)?,
+            amount: self.amount,
+        });
+        self.tx.handle_and_print(op, global_args).await?;
+        Ok(())
+    }
+}
+
+/// `asset` with its issuer alias resolved with the config of `tx`, which must not be native.
+pub fn issued(asset: &Asset, tx: &tx::Args) -> Result<xdr::Asset, Error> {
+    let asset = asset.resolve(&tx.config.locator, tx.config.hd_path)?;
+    if matches!(asset.0, xdr::Asset::Native) {
+        return Err(Error::Native);
+    }
+    Ok(asset.0)
+}
@@ -0,0 +1,25 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{claimable_balance, tx},
+    xdr,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Id of the claimable balance to claw back, as printed by `claimable-balance create` or
+    /// `ls`. The source account must be the issuer of its asset
+    #[arg(long, value_parser = claimable_balance::parse_balance_id)]
+    pub balance_id: xdr::ClaimableBalanceId,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        xdr::OperationBody::ClawbackClaimableBalance(xdr::ClawbackClaimableBalanceOp {
+            balance_id: cmd.balance_id.clone(),
+        })
+    }
+}
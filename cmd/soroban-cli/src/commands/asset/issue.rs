@@ -0,0 +1,546 @@
+use std::io::{self, BufRead, Write};
+
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{
+        contract::deploy::asset as sac,
+        global, tx,
+        txn_result::{TxnEnvelopeResult, TxnResult},
+        NetworkRunnable,
+    },
+    config::{
+        self,
+        address::{self, Address},
+        locator, network,
+        secret::{self, Secret},
+    },
+    fee, horizon, input,
+    print::Print,
+    rpc,
+    tx::{
+        builder::{self, TxExt},
+        ONE_XLM,
+    },
+    xdr::{self, Limits, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Builder(#[from] builder::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Fee(#[from] fee::Error),
+    #[error(transparent)]
+    Sac(#[from] sac::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Preconditions(#[from] tx::preconditions::Error),
+    #[error("the asset code is required with --no-input or when not run in a terminal, pass it as the first argument")]
+    CodeRequired,
+    #[error("invalid asset code {0:?}, expected 1 to 12 letters and digits")]
+    InvalidCode(String),
+    #[error("invalid answer {answer:?} for {question}")]
+    InvalidAnswer { question: String, answer: String },
+    #[error("Failed to read the answer: {0}")]
+    Prompt(io::Error),
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Code of the asset, e.g. `USDC`. Without it in a terminal, the issuance is set up by
+    /// answering prompts, with the other options as the default answers
+    pub code: Option<String>,
+    /// Identity of the issuing account, generated when it doesn't exist. Defaults to
+    /// `<code>-issuer`
+    #[arg(long)]
+    pub issuer: Option<String>,
+    /// Identity of the distribution account the supply is minted to, generated when it doesn't
+    /// exist. Defaults to `<code>-distributor`
+    #[arg(long)]
+    pub distributor: Option<String>,
+    /// Account that creates the issuer and distribution accounts when they are not on the
+    /// network, e.g. an identity. Without it they are funded by friendbot
+    #[arg(long)]
+    pub funder: Option<Address>,
+    /// Balance in stroops of the accounts created by `--funder`
+    #[arg(long, default_value_t = 10 * ONE_XLM, requires = "funder")]
+    pub starting_balance: i64,
+    /// Home domain of the issuer, whose stellar.toml describes the asset
+    #[arg(long)]
+    pub home_domain: Option<xdr::StringM<32>>,
+    /// Require the issuer to authorize trustlines before they can hold the asset
+    #[arg(long)]
+    pub auth_required: bool,
+    /// Allow the issuer to revoke the authorization of trustlines, freezing their balance
+    #[arg(long)]
+    pub auth_revocable: bool,
+    /// Allow the issuer to claw back the asset from trustlines created from now on
+    #[arg(long, requires = "auth_revocable")]
+    pub clawback: bool,
+    /// Amount in stroops minted to the distribution account
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(i64).range(0..))]
+    pub supply: i64,
+    /// Lock the issuer after minting by setting the weight of its master key to 0, so that no
+    /// more of the asset can ever be minted
+    #[arg(long)]
+    pub lock_issuer: bool,
+    /// Don't deploy the Stellar Asset Contract of the asset
+    #[arg(long)]
+    pub no_sac: bool,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+    #[command(flatten)]
+    pub fee: fee::Args,
+    #[command(flatten)]
+    pub preconditions: tx::preconditions::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let cmd = match &self.code {
+            Some(_) => self.clone(),
+            None => self.prompted()?,
+        };
+        cmd.issue(&print, global_args).await
+    }
+
+    async fn issue(&self, print: &Print, global_args: &global::Args) -> Result<(), Error> {
+        let code = self.code.clone().ok_or(Error::CodeRequired)?;
+        let locator = &self.config.locator;
+        let network = self.config.get_network()?;
+        let client = network.rpc_client()?;
+
+        let issuer_name = self
+            .issuer
+            .clone()
+            .unwrap_or_else(|| default_name(&code, "issuer"));
+        let distributor_name = self
+            .distributor
+            .clone()
+            .unwrap_or_else(|| default_name(&code, "distributor"));
+        let issuer = identity(locator, &issuer_name, print)?;
+        let distributor = identity(locator, &distributor_name, print)?;
+        let issuer_id = account_id(&issuer)?;
+        let distributor_id = account_id(&distributor)?;
+        let asset = asset(&code, issuer_id.clone())?;
+
+        let mut missing = Vec::new();
+        for (name, id) in [
+            (&issuer_name, &issuer_id),
+            (&distributor_name, &distributor_id),
+        ] {
+            if client.get_account(&id.to_string()).await.is_ok() {
+                continue;
+            }
+            if self.funder.is_some() {
+                missing.push(id.clone());
+            } else {
+                network.fund_address(&public_key(id)).await?;
+                print.checkln(format!("Funded {name} with friendbot"));
+            }
+        }
+        if self.lock_issuer && self.auth_revocable {
+            print.warnln("The issuer is locked, so it can never revoke or claw back the asset");
+        }
+
+        let ops = self.operations(&issuer_id, &distributor_id, &asset, &missing);
+        let source = match &self.funder {
+            Some(funder) if !missing.is_empty() => funder.clone(),
+            _ => Address::AliasOrSecret(issuer_name.clone()),
+        };
+        let tx_args = tx::Args {
+            fee: self.fee.clone(),
+            config: self.config_for(source),
+            sponsor: None,
+            memo: tx::memo::Args::default(),
+            preconditions: self.preconditions.clone(),
+        };
+        let source_account = tx_args.source_account()?;
+        let seq_num = tx_args
+            .config
+            .next_sequence_number(source_account.clone().account_id(), self.fee.seq_strategy)
+            .await?;
+        let fee_per_op = self.fee.inclusion_fee(&network, false).await?;
+        let first = ops
+            .first()
+            .expect("issuing always sets up a trustline")
+            .clone();
+        let tx = xdr::Transaction::new_tx(source_account, fee_per_op, seq_num, first)
+            .add_cond(tx_args.preconditions.preconditions()?)
+            .with_operations_keeping_fee_per_op(ops)?;
+
+        let signers = vec![issuer.key_pair(None)?, distributor.key_pair(None)?];
+        match tx_args
+            .handle_tx_with_signers(tx, signers, global_args)
+            .await?
+        {
+            TxnEnvelopeResult::TxnEnvelope(tx) => {
+                println!("{}", tx.to_xdr_base64(Limits::none())?);
+                return Ok(());
+            }
+            TxnEnvelopeResult::Submitted(hash) => {
+                println!("{hash}");
+                return Ok(());
+            }
+            TxnEnvelopeResult::Res(_) => {}
+        }
+        let asset_string = horizon::asset_string(&asset.0);
+        print.checkln(format!(
+            "Issued {asset_string}, minting {} stroops to {distributor_name}",
+            self.supply
+        ));
+        println!("{asset_string}");
+
+        if self.no_sac {
+            return Ok(());
+        }
+        self.deploy_sac(asset, distributor_name, print, global_args)
+            .await
+    }
+
+    async fn deploy_sac(
+        &self,
+        asset: builder::Asset,
+        source: String,
+        print: &Print,
+        global_args: &global::Args,
+    ) -> Result<(), Error> {
+        let deploy = sac::Cmd {
+            asset,
+            config: self.config_for(Address::AliasOrSecret(source)),
+            fee: self.fee.clone(),
+            preconditions: self.preconditions.clone(),
+        };
+        if let TxnResult::Res(contract) = deploy
+            .run_against_rpc_server(Some(global_args), None)
+            .await?
+        {
+            print.checkln(format!("Deployed the Stellar Asset Contract {contract}"));
+            println!("{contract}");
+        }
+        Ok(())
+    }
+
+    fn config_for(&self, source_account: Address) -> config::Args {
+        config::Args {
+            network: self.config.network.clone(),
+            source_account,
+            hd_path: None,
+            locator: self.config.locator.clone(),
+        }
+    }
+
+    /// The operations of the issuance, in order: creating the `missing` accounts, setting up the
+    /// issuer, the trustline of the distributor and its authorization, minting, and locking.
+    fn operations(
+        &self,
+        issuer: &xdr::AccountId,
+        distributor: &xdr::AccountId,
+        asset: &builder::Asset,
+        missing: &[xdr::AccountId],
+    ) -> Vec<xdr::Operation> {
+        let by = |account: &xdr::AccountId, body| xdr::Operation {
+            source_account: Some(account.clone().into()),
+            body,
+        };
+        let mut ops: Vec<_> = missing
+            .iter()
+            .map(|destination| xdr::Operation {
+                source_account: None,
+                body: xdr::OperationBody::CreateAccount(xdr::CreateAccountOp {
+                    destination: destination.clone(),
+                    starting_balance: self.starting_balance,
+                }),
+            })
+            .collect();
+        let mut flags = 0;
+        for (set, flag) in [
+            (self.auth_required, xdr::AccountFlags::RequiredFlag),
+            (self.auth_revocable, xdr::AccountFlags::RevocableFlag),
+            (self.clawback, xdr::AccountFlags::ClawbackEnabledFlag),
+        ] {
+            if set {
+                flags |= flag as u32;
+            }
+        }
+        if flags != 0 || self.home_domain.is_some() {
+            ops.push(by(
+                issuer,
+                xdr::OperationBody::SetOptions(xdr::SetOptionsOp {
+                    set_flags: (flags != 0).then_some(flags),
+                    home_domain: self.home_domain.clone().map(Into::into),
+                    ..set_options()
+                }),
+            ));
+        }
+        ops.push(by(
+            distributor,
+            xdr::OperationBody::ChangeTrust(xdr::ChangeTrustOp {
+                line: asset.into(),
+                limit: i64::MAX,
+            }),
+        ));
+        if self.auth_required {
+            ops.push(by(
+                issuer,
+                xdr::OperationBody::SetTrustLineFlags(xdr::SetTrustLineFlagsOp {
+                    trustor: distributor.clone(),
+                    asset: asset.into(),
+                    clear_flags: 0,
+                    set_flags: xdr::TrustLineFlags::AuthorizedFlag as u32,
+                }),
+            ));
+        }
+        if self.supply > 0 {
+            ops.push(by(
+                issuer,
+                xdr::OperationBody::Payment(xdr::PaymentOp {
+                    destination: distributor.clone().into(),
+                    asset: asset.into(),
+                    amount: self.supply,
+                }),
+            ));
+        }
+        if self.lock_issuer {
+            ops.push(by(
+                issuer,
+                xdr::OperationBody::SetOptions(xdr::SetOptionsOp {
+                    master_weight: Some(0),
+                    ..set_options()
+                }),
+            ));
+        }
+        ops
+    }
+
+    /// The command with the answers to prompts for each option, with the options given as the
+    /// default answers.
+    fn prompted(&self) -> Result<Self, Error> {
+        if !input::interactive() {
+            return Err(Error::CodeRequired);
+        }
+        let mut cmd = self.clone();
+        let code = ask("Asset code", "")?;
+        if code.is_empty() {
+            return Err(Error::CodeRequired);
+        }
+        cmd.issuer = Some(ask(
+            "Issuer identity",
+            &self
+                .issuer
+                .clone()
+                .unwrap_or_else(|| default_name(&code, "issuer")),
+        )?);
+        cmd.distributor = Some(ask(
+            "Distribution identity",
+            &self
+                .distributor
+                .clone()
+                .unwrap_or_else(|| default_name(&code, "distributor")),
+        )?);
+        let home_domain = ask(
+            "Home domain of the issuer, empty for none",
+            &self
+                .home_domain
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        )?;
+        cmd.home_domain = if home_domain.is_empty() {
+            None
+        } else {
+            Some(home_domain.parse().map_err(|_| Error::InvalidAnswer {
+                question: "the home domain".to_string(),
+                answer: home_domain.clone(),
+            })?)
+        };
+        cmd.auth_required = ask_bool(
+            "Require authorizing trustlines to the asset?",
+            self.auth_required,
+        )?;
+        cmd.auth_revocable = ask_bool(
+            "Allow revoking the authorization of trustlines?",
+            self.auth_revocable,
+        )?;
+        cmd.clawback =
+            cmd.auth_revocable && ask_bool("Allow clawing back the asset?", self.clawback)?;
+        let supply = ask(
+            "Supply in stroops to mint to the distribution account",
+            &self.supply.to_string(),
+        )?;
+        cmd.supply =
+            supply
+                .parse()
+                .ok()
+                .filter(|supply| *supply >= 0)
+                .ok_or(Error::InvalidAnswer {
+                    question: "the supply".to_string(),
+                    answer: supply,
+                })?;
+        cmd.lock_issuer = ask_bool(
+            "Lock the issuer after minting, so that no more can ever be minted?",
+            self.lock_issuer,
+        )?;
+        cmd.no_sac = !ask_bool("Deploy the Stellar Asset Contract?", !self.no_sac)?;
+        cmd.code = Some(code);
+        Ok(cmd)
+    }
+}
+
+fn default_name(code: &str, role: &str) -> String {
+    format!("{}-{role}", code.to_lowercase())
+}
+
+/// The secret of identity `name`, generated when it doesn't exist.
+fn identity(locator: &locator::Args, name: &str, print: &Print) -> Result<Secret, Error> {
+    if locator.identity_exists(name)? {
+        return Ok(locator.read_identity(name)?);
+    }
+    let secret = Secret::from_seed(None, 12, secret::Language::English)?;
+    locator.write_identity(name, &secret)?;
+    print.checkln(format!("Generated identity {name}"));
+    Ok(secret)
+}
+
+fn account_id(secret: &Secret) -> Result<xdr::AccountId, Error> {
+    Ok(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(
+        secret.public_key(None)?.0.into(),
+    )))
+}
+
+fn public_key(id: &xdr::AccountId) -> stellar_strkey::ed25519::PublicKey {
+    let xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key))) = id;
+    stellar_strkey::ed25519::PublicKey(*key)
+}
+
+fn asset(code: &str, issuer: xdr::AccountId) -> Result<builder::Asset, Error> {
+    let invalid = || Error::InvalidCode(code.to_string());
+    if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(invalid());
+    }
+    Ok(builder::Asset(
+        match code.parse::<xdr::AssetCode>().map_err(|_| invalid())? {
+            xdr::AssetCode::CreditAlphanum4(asset_code) => {
+                xdr::Asset::CreditAlphanum4(xdr::AlphaNum4 { asset_code, issuer })
+            }
+            xdr::AssetCode::CreditAlphanum12(asset_code) => {
+                xdr::Asset::CreditAlphanum12(xdr::AlphaNum12 { asset_code, issuer })
+            }
+        },
+    ))
+}
+
+fn set_options() -> xdr::SetOptionsOp {
+    xdr::SetOptionsOp {
+        inflation_dest: None,
+        clear_flags: None,
+        set_flags: None,
+        master_weight: None,
+        low_threshold: None,
+        med_threshold: None,
+        high_threshold: None,
+        home_domain: None,
+        signer: None,
+    }
+}
+
+/// Ask `question` on stderr, returning the trimmed answer or `default` when it is empty.
+fn ask(question: &str, default: &str) -> Result<String, Error> {
+    if default.is_empty() {
+        eprint!("{question}: ");
+    } else {
+        eprint!("{question} [{default}]: ");
+    }
+    io::stderr().flush().map_err(Error::Prompt)?;
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .map_err(Error::Prompt)?;
+    let answer = answer.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+fn ask_bool(question: &str, default: bool) -> Result<bool, Error> {
+    let answer = ask(question, if default { "Y/n" } else { "y/N" })?;
+    match answer.as_str() {
+        "Y/n" | "y" | "Y" | "yes" => Ok(true),
+        "y/N" | "n" | "N" | "no" => Ok(false),
+        _ => Err(Error::InvalidAnswer {
+            question: question.to_string(),
+            answer,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_of_a_locked_regulated_asset() {
+        let issuer: xdr::AccountId = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        let distributor: xdr::AccountId =
+            "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI"
+                .parse()
+                .unwrap();
+        let cmd = Cmd::parse_from([
+            "issue",
+            "USD",
+            "--auth-required",
+            "--auth-revocable",
+            "--clawback",
+            "--supply",
+            "1000",
+            "--lock-issuer",
+        ]);
+        let asset = asset("USD", issuer.clone()).unwrap();
+        let ops = cmd.operations(&issuer, &distributor, &asset, &[]);
+        let bodies: Vec<_> = ops.iter().map(|op| op.body.name()).collect();
+        assert_eq!(
+            bodies,
+            [
+                "SetOptions",
+                "ChangeTrust",
+                "SetTrustLineFlags",
+                "Payment",
+                "SetOptions"
+            ]
+        );
+        let xdr::OperationBody::SetOptions(set_options) = &ops[0].body else {
+            panic!("expected set options");
+        };
+        assert_eq!(set_options.set_flags, Some(0b1011));
+        assert_eq!(ops[1].source_account, Some(distributor.into()));
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        let issuer: xdr::AccountId = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        assert!(asset("US D", issuer.clone()).is_err());
+        assert!(asset("THIRTEENCHARS", issuer).is_err());
+    }
+}
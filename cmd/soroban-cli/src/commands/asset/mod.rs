@@ -0,0 +1,52 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod clawback;
+pub mod clawback_claimable_balance;
+pub mod issue;
+pub mod set_trustline_flags;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// Issue a classic asset: create the issuer and distribution accounts, set the home domain
+    /// and authorization flags of the issuer, mint the supply to the distribution account,
+    /// optionally lock the issuer, and deploy the Stellar Asset Contract. Without an asset code in
+    /// a terminal, each step is asked for
+    /// Learn more about issuing assets:
+    /// https://developers.stellar.org/docs/tokens/how-to-issue-an-asset
+    Issue(issue::Cmd),
+    /// Claw back an amount of an asset from an account, burning it. Use `--build-only` to sign
+    /// the operation separately when the issuer has multiple signers
+    /// Learn more about clawbacks:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/transactions-specialized/clawbacks
+    Clawback(clawback::Cmd),
+    /// Claw back a claimable balance of an asset, burning it
+    ClawbackClaimableBalance(clawback_claimable_balance::Cmd),
+    /// Authorize, partially authorize, or revoke a trustline to an asset, or stop clawbacks from
+    /// it
+    SetTrustlineFlags(set_trustline_flags::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Issue(#[from] issue::Error),
+    #[error(transparent)]
+    Clawback(#[from] clawback::Error),
+    #[error(transparent)]
+    Tx(#[from] super::tx::args::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Issue(cmd) => cmd.run(global_args).await?,
+            Cmd::Clawback(cmd) => cmd.run(global_args).await?,
+            Cmd::ClawbackClaimableBalance(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::SetTrustlineFlags(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use clap::{arg, command, ArgGroup, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::{address::Address, asset::Asset},
+    xdr::{self, TrustLineFlags},
+};
+
+use super::clawback::{issued, Error};
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+#[command(group(
+    ArgGroup::new("flags")
+        .required(true)
+        .multiple(true)
+        .args(["authorize", "authorize_to_maintain_liabilities", "revoke", "clear_clawback"])
+))]
+pub struct Cmd {
+    /// Asset of the trustline as `CODE:ISSUER`, where the issuer can be an identity. The source
+    /// account must be the issuer
+    #[arg(long)]
+    pub asset: Asset,
+    /// Account holding the trustline, e.g. an identity or `GBX...`
+    #[arg(long)]
+    pub trustor: Address,
+    /// Fully authorize the trustline to hold and transact with the asset
+    #[arg(long, conflicts_with_all = ["authorize_to_maintain_liabilities", "revoke"])]
+    pub authorize: bool,
+    /// Authorize the trustline only to maintain its offers, not to otherwise transact
+    #[arg(long, conflicts_with = "revoke")]
+    pub authorize_to_maintain_liabilities: bool,
+    /// Revoke the authorization of the trustline, freezing its balance and removing its offers.
+    /// The issuer needs the auth revocable flag
+    #[arg(long)]
+    pub revoke: bool,
+    /// Stop the issuer from clawing back the balance of the trustline, which cannot be undone
+    #[arg(long)]
+    pub clear_clawback: bool,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let (set_flags, clear_flags) = self.flags();
+        let op = xdr::OperationBody::SetTrustLineFlags(xdr::SetTrustLineFlagsOp {
+            trustor: self
+                .trustor
+                .resolve_muxed_account(&config.locator, config.hd_path)?
+                .account_id(),
+            asset: issued(&self.asset, &self.tx)?,
+            clear_flags,
+            set_flags,
+        });
+        self.tx.handle_and_print(op, global_args).await?;
+        Ok(())
+    }
+
+    /// The flags to set and to clear. The authorization flags are exclusive, so setting one
+    /// clears the other.
+    fn flags(&self) -> (u32, u32) {
+        let authorized = TrustLineFlags::AuthorizedFlag as u32;
+        let maintain = TrustLineFlags::AuthorizedToMaintainLiabilitiesFlag as u32;
+        let (set, mut clear) = if self.authorize {
+            (authorized, maintain)
+        } else if self.authorize_to_maintain_liabilities {
+            (maintain, authorized)
+        } else if self.revoke {
+            (0, authorized | maintain)
+        } else {
+            (0, 0)
+        };
+        if self.clear_clawback {
+            clear |= TrustLineFlags::TrustlineClawbackEnabledFlag as u32;
+        }
+        (set, clear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(extra: &[&str]) -> (u32, u32) {
+        let mut argv = vec![
+            "set-trustline-flags",
+            "--asset",
+            "USD:GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            "--trustor",
+            "alice",
+            "--source",
+            "issuer",
+        ];
+        argv.extend_from_slice(extra);
+        Cmd::parse_from(argv).flags()
+    }
+
+    #[test]
+    fn authorization_flags_are_exclusive() {
+        assert_eq!(flags(&["--authorize"]), (1, 2));
+        assert_eq!(flags(&["--authorize-to-maintain-liabilities"]), (2, 1));
+        assert_eq!(flags(&["--revoke", "--clear-clawback"]), (0, 7));
+        assert!(Cmd::try_parse_from(["set-trustline-flags", "--asset", "native"]).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod sep10;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Authenticate with a SEP-10 web auth server and print the resulting JWT
+    ///
+    /// Fetches a challenge from the auth endpoint of the home domain, validates it, signs it with
+    /// the source account, and exchanges it for a token.
+    /// Learn more: <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0010.md>
+    Sep10(sep10::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Sep10(#[from] sep10::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Sep10(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use clap::{arg, command, Parser};
+use url::Url;
+
+use crate::{
+    commands::global,
+    config::{self, locator, network, secret},
+    print::Print,
+    sep::{
+        sep10::{self, ChallengeRequest, Validation},
+        stellar_toml, StellarToml,
+    },
+    signer::{self, LocalKey, Signer, SignerKind},
+    xdr::{self, MuxedAccount, MuxedAccountMed25519, TransactionEnvelope},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Home domain of the server to authenticate with, e.g. `testanchor.stellar.org`.
+    /// Its stellar.toml is used to find the `WEB_AUTH_ENDPOINT` and `SIGNING_KEY`.
+    #[arg(long)]
+    pub home_domain: String,
+    /// Auth endpoint to use instead of the `WEB_AUTH_ENDPOINT` in the home domain's stellar.toml
+    #[arg(long)]
+    pub auth_endpoint: Option<Url>,
+    /// Server signing key to use instead of the `SIGNING_KEY` in the home domain's stellar.toml
+    #[arg(long)]
+    pub server_signing_key: Option<stellar_strkey::ed25519::PublicKey>,
+    /// Domain of the client application, requesting a challenge that must also be signed by the
+    /// client domain's signing key
+    #[arg(long, requires = "client_domain_signer")]
+    pub client_domain: Option<String>,
+    /// Identity or secret key matching the `SIGNING_KEY` of `--client-domain`
+    #[arg(long, requires = "client_domain")]
+    pub client_domain_signer: Option<String>,
+    /// Authenticate as the muxed account (`M…`) with this ID, derived from the source account
+    #[arg(long, conflicts_with = "memo")]
+    pub muxed_id: Option<u64>,
+    /// Memo ID identifying a user of a shared source account
+    #[arg(long)]
+    pub memo: Option<u64>,
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    StellarToml(#[from] stellar_toml::Error),
+    #[error(transparent)]
+    Sep10(#[from] sep10::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error("invalid auth endpoint {0}")]
+    InvalidAuthEndpoint(String),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        println!("{}", self.token(&print).await?);
+        Ok(())
+    }
+
+    /// Run the SEP-10 flow, returning the JWT issued by the server.
+    pub async fn token(&self, print: &Print) -> Result<String, Error> {
+        let network = self.config.get_network()?;
+        let key = self.config.key_pair()?;
+        let public_key = xdr::Uint256(key.verifying_key().to_bytes());
+        let account = match self.muxed_id {
+            Some(id) => MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+                id,
+                ed25519: public_key,
+            }),
+            None => MuxedAccount::Ed25519(public_key),
+        };
+        let client_domain_key = self
+            .client_domain_signer
+            .as_deref()
+            .map(|signer| -> Result<_, Error> {
+                Ok(self.config.locator.key(signer)?.key_pair(None)?)
+            })
+            .transpose()?;
+
+        let (endpoint, server_key) = self.endpoint_and_server_key().await?;
+        let home_domain = stellar_toml::bare_domain(&self.home_domain)?;
+        print.globeln(format!("Requesting challenge from {endpoint}"));
+        let challenge = sep10::fetch_challenge(
+            &endpoint,
+            &ChallengeRequest {
+                account: &account,
+                home_domain: &home_domain,
+                client_domain: self.client_domain.as_deref(),
+                memo: self.memo,
+            },
+        )
+        .await?;
+        if let Some(server) = &challenge.network_passphrase {
+            if server != &network.network_passphrase {
+                return Err(sep10::Error::NetworkPassphraseMismatch {
+                    expected: network.network_passphrase,
+                    server: server.clone(),
+                }
+                .into());
+            }
+        }
+
+        let envelope = challenge.envelope()?;
+        let web_auth_domain = stellar_toml::bare_domain(endpoint.as_str())?;
+        sep10::validate_challenge(
+            &envelope,
+            &Validation {
+                server_key: server_key.0,
+                client_account: &account,
+                home_domain: &home_domain,
+                web_auth_domain: &web_auth_domain,
+                network_passphrase: &network.network_passphrase,
+                client_domain_key: client_domain_key
+                    .as_ref()
+                    .map(|k| k.verifying_key().to_bytes()),
+                memo: self.memo,
+            },
+        )?;
+        print.checkln("Challenge is valid");
+
+        let signed = std::iter::once(key).chain(client_domain_key).try_fold(
+            envelope,
+            |envelope, key| -> Result<TransactionEnvelope, Error> {
+                let signer = Signer {
+                    kind: SignerKind::Local(LocalKey { key }),
+                    print: print.clone(),
+                };
+                Ok(signer.sign_tx_env(&envelope, &network)?)
+            },
+        )?;
+
+        print.globeln(format!("Submitting signed challenge to {endpoint}"));
+        let token = sep10::submit_challenge(&endpoint, &signed).await?;
+        print.checkln("Authenticated");
+        Ok(token)
+    }
+
+    async fn endpoint_and_server_key(
+        &self,
+    ) -> Result<(Url, stellar_strkey::ed25519::PublicKey), Error> {
+        if let (Some(endpoint), Some(key)) = (&self.auth_endpoint, &self.server_signing_key) {
+            return Ok((endpoint.clone(), *key));
+        }
+        let toml = StellarToml::fetch(&self.home_domain).await?;
+        let endpoint = if let Some(endpoint) = &self.auth_endpoint {
+            endpoint.clone()
+        } else {
+            let endpoint = toml.web_auth_endpoint(&self.home_domain)?;
+            Url::parse(endpoint).map_err(|_| Error::InvalidAuthEndpoint(endpoint.to_string()))?
+        };
+        let key = match self.server_signing_key {
+            Some(key) => key,
+            None => toml.signing_key(&self.home_domain)?.parse()?,
+        };
+        Ok((endpoint, key))
+    }
+}
@@ -0,0 +1,124 @@
+use std::{fmt::Write as _, fs, io, path::PathBuf};
+
+use clap::Parser;
+
+use crate::{
+    commands::{global, version},
+    config::{data, locator, Config},
+    log::file,
+    print::Print,
+};
+
+const NEW_ISSUE_URL: &str = "https://github.com/stellar/stellar-cli/issues/new";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error("reading log file {path}: {error}")]
+    ReadLog { path: PathBuf, error: io::Error },
+    #[error("writing bug report {path}: {error}")]
+    Write { path: PathBuf, error: io::Error },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Write the report to this file instead of stdout
+    #[arg(long)]
+    pub out_file: Option<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let log_path = match &global_args.log_file {
+            Some(path) => path.clone(),
+            None => file::default_path()?,
+        };
+        let log = match fs::read_to_string(&log_path) {
+            Ok(log) => Some(log),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                print.warnln(format!(
+                    "No log of a previous run at {}, run the command again with `-vv` or `--log-file` to include one",
+                    log_path.display()
+                ));
+                None
+            }
+            Err(error) => {
+                return Err(Error::ReadLog {
+                    path: log_path,
+                    error,
+                })
+            }
+        };
+        let report = report(&global_args.locator, log.as_deref().map(|l| (&log_path, l)))?;
+        if let Some(path) = &self.out_file {
+            fs::write(path, report).map_err(|error| Error::Write {
+                path: path.clone(),
+                error,
+            })?;
+            print.checkln(format!(
+                "Wrote the bug report to {}, attach it to a new issue at {NEW_ISSUE_URL}",
+                path.display()
+            ));
+        } else {
+            print!("{report}");
+        }
+        Ok(())
+    }
+}
+
+fn report(locator: &locator::Args, log: Option<(&PathBuf, &str)>) -> Result<String, Error> {
+    let mut report = String::from("## Version\n\n");
+    let _ = writeln!(report, "```\nstellar {}\n```\n", version::long());
+    let _ = writeln!(
+        report,
+        "## System\n\n- OS: {} {}\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    let config = Config::new()?;
+    report.push_str("## Config\n\n");
+    for (name, value) in [
+        ("network", &config.defaults.network),
+        ("identity", &config.defaults.identity),
+        ("fee strategy", &config.defaults.fee_strategy),
+    ] {
+        let _ = writeln!(
+            report,
+            "- Default {name}: {}",
+            value.as_deref().unwrap_or("none")
+        );
+    }
+    let _ = writeln!(
+        report,
+        "- Mainnet confirmation: {}",
+        config.safety.mainnet_confirm()
+    );
+    for (name, network, location) in locator.list_networks_long()? {
+        let url = file::redact_url(&network.rpc_url);
+        let _ = writeln!(report, "- Network {name} ({location}): {url}");
+    }
+    let mut vars = std::env::vars()
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with("STELLAR_") || name.starts_with("SOROBAN_"))
+        .collect::<Vec<_>>();
+    vars.sort();
+    if !vars.is_empty() {
+        let _ = writeln!(report, "- Environment variables set: {}", vars.join(", "));
+    }
+
+    if let Some((path, log)) = log {
+        let _ = writeln!(
+            report,
+            "\n## Log\n\nFrom `{}`:\n\n```jsonl\n{}\n```",
+            path.display(),
+            log.trim_end()
+        );
+    }
+    Ok(report)
+}
@@ -1,5 +1,7 @@
 use std::{fs, io::ErrorKind};
 
+use clap::arg;
+
 use crate::config::{data, locator};
 
 #[derive(thiserror::Error, Debug)]
@@ -14,15 +16,25 @@ pub enum Error {
 
 #[derive(Debug, clap::Parser, Clone)]
 #[group(skip)]
-pub struct Cmd {}
+pub struct Cmd {
+    /// Only delete what was fetched from networks, e.g. Wasm, contract specs, and the network
+    /// passphrases of RPC servers. Keeps the action log
+    #[arg(long)]
+    pub fetched: bool,
+}
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        let binding = data::project_dir()?;
-        let dir = binding.data_dir();
-        match fs::remove_dir_all(dir) {
-            Err(err) if err.kind() == ErrorKind::NotFound => (),
-            r => r?,
+        let dirs = if self.fetched {
+            data::fetched_dirs()?
+        } else {
+            vec![data::project_dir()?.data_dir().to_path_buf()]
+        };
+        for dir in dirs {
+            match fs::remove_dir_all(dir) {
+                Err(err) if err.kind() == ErrorKind::NotFound => (),
+                r => r?,
+            }
         }
         Ok(())
     }
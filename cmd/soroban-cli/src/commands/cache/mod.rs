@@ -6,7 +6,7 @@ pub mod path;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
-    /// Delete the cache
+    /// Delete the cache, or only what was fetched from networks with `--fetched`
     Clean(clean::Cmd),
     /// Show the location of the cache
     Path(path::Cmd),
@@ -0,0 +1,21 @@
+use clap::{arg, command, Parser};
+
+use crate::{commands::tx, xdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    /// Id of the claimable balance, as printed by `claimable-balance create` or `ls`
+    #[arg(long, value_parser = super::parse_balance_id)]
+    pub balance_id: xdr::ClaimableBalanceId,
+}
+
+impl From<&Cmd> for xdr::OperationBody {
+    fn from(cmd: &Cmd) -> Self {
+        xdr::OperationBody::ClaimClaimableBalance(xdr::ClaimClaimableBalanceOp {
+            balance_id: cmd.balance_id.clone(),
+        })
+    }
+}
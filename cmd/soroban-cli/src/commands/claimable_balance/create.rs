@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx, txn_result::TxnEnvelopeResult},
+    config::{
+        address::{self, Address},
+        asset::{self, Asset},
+    },
+    print::Print,
+    tx::builder::{self, claim_predicate, ClaimPredicate},
+    xdr::{self, Limits, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Memo(#[from] tx::memo::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset to lock in the balance, `native` or `CODE:ISSUER` where the issuer can be an identity
+    #[arg(long, default_value = "native")]
+    pub asset: Asset,
+    /// Amount of the asset in stroops
+    #[arg(long)]
+    pub amount: i64,
+    /// Account that can claim the balance, optionally followed by `=<predicate>`, e.g.
+    /// `--claimant alice=within:7d`. Repeat for up to 10 claimants.
+    /// A predicate is `unconditional` (the default), `before:<time>`, `after:<time>`,
+    /// `within:<duration>`, or combines predicates with `not(..)`, `and(..,..)`, and `or(..,..)`.
+    /// Times are unix timestamps or RFC 3339, durations are seconds or e.g. `12h`.
+    #[arg(long = "claimant", required = true, num_args = 1)]
+    pub claimants: Vec<Claimant>,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+#[derive(Clone, Debug)]
+pub struct Claimant {
+    pub account: Address,
+    pub predicate: ClaimPredicate,
+}
+
+impl FromStr for Claimant {
+    type Err = claim_predicate::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (account, predicate) = match value.split_once('=') {
+            Some((account, predicate)) => (account, predicate.parse()?),
+            None => (
+                value,
+                builder::ClaimPredicate(xdr::ClaimPredicate::Unconditional),
+            ),
+        };
+        Ok(Claimant {
+            account: Address::AliasOrSecret(account.to_string()),
+            predicate,
+        })
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let config = &self.tx.config;
+        let mut memo = None;
+        let mut claimants = Vec::new();
+        for claimant in &self.claimants {
+            let (destination, required) = self.tx.resolve_destination(&claimant.account)?;
+            memo = tx::memo::require(memo, required)?;
+            claimants.push(xdr::Claimant::ClaimantTypeV0(xdr::ClaimantV0 {
+                destination: destination.account_id(),
+                predicate: claimant.predicate.0.clone(),
+            }));
+        }
+        let op = xdr::OperationBody::CreateClaimableBalance(xdr::CreateClaimableBalanceOp {
+            asset: self.asset.resolve(&config.locator, config.hd_path)?.into(),
+            amount: self.amount,
+            claimants: claimants.try_into()?,
+        });
+        let tx = self.tx.sponsored_tx(op, None, memo).await?;
+        // The operation is not the first one when it is wrapped in a sponsorship.
+        let op_index = tx
+            .operations
+            .iter()
+            .position(|op| matches!(op.body, xdr::OperationBody::CreateClaimableBalance(_)))
+            .unwrap_or_default();
+        let id = super::balance_id(
+            tx.source_account.clone().account_id(),
+            tx.seq_num.clone(),
+            u32::try_from(op_index).unwrap_or_default(),
+        )?;
+        let id = super::balance_id_hex(&id)?;
+        match self.tx.handle_tx(tx, global_args).await? {
+            TxnEnvelopeResult::TxnEnvelope(tx_env) => {
+                print.infoln(format!("Claimable balance id once submitted: {id}"));
+                println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+            }
+            TxnEnvelopeResult::Submitted(hash) => {
+                print.infoln(format!("Claimable balance id once included: {id}"));
+                println!("{hash}");
+            }
+            TxnEnvelopeResult::Res(_) => println!("{id}"),
+        }
+        Ok(())
+    }
+}
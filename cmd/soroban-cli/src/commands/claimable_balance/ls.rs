@@ -0,0 +1,117 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{
+        self,
+        address::{self, Address},
+        asset::{self, Asset},
+    },
+    horizon,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per balance followed by its claimants and predicates
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Claimant to list the balances of, e.g. `GBX...` or an identity, defaults to `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Only list balances of this asset, `native` or `CODE:ISSUER`
+    #[arg(long)]
+    pub asset: Option<Asset>,
+    /// Maximum number of balances to list
+    #[arg(long, default_value = "200")]
+    pub limit: usize,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let account_id = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id();
+        let client = self.horizon.client(&self.config.get_network()?)?;
+        let mut query = vec![
+            ("claimant", account_id.to_string()),
+            ("limit", self.limit.min(200).to_string()),
+        ];
+        if let Some(asset) = &self.asset {
+            let asset = asset.resolve(&self.config.locator, None)?;
+            query.push(("asset", horizon::asset_string(&asset.into())));
+        }
+        let balances = client
+            .records("claimable_balances", &query, Some(self.limit))
+            .await?
+            .iter()
+            .map(balance)
+            .collect::<Vec<_>>();
+        match self.output {
+            Output::Json => println!("{}", serde_json::to_string_pretty(&balances)?),
+            Output::Text => {
+                if balances.is_empty() {
+                    println!("No claimable balances");
+                }
+                for b in &balances {
+                    println!(
+                        "{} {} {}",
+                        str(&b["id"]),
+                        str(&b["amount"]),
+                        str(&b["asset"])
+                    );
+                    for claimant in b["claimants"].as_array().into_iter().flatten() {
+                        println!(
+                            "  {} {}",
+                            str(&claimant["destination"]),
+                            claimant["predicate"]
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn str(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+/// The fields of a Horizon claimable balance that describe who can claim what and when.
+fn balance(record: &Value) -> Value {
+    json!({
+        "id": record["id"],
+        "asset": record["asset"],
+        "amount": record["amount"],
+        "sponsor": record["sponsor"],
+        "claimants": record["claimants"],
+    })
+}
@@ -0,0 +1,117 @@
+use clap::Parser;
+use sha2::{Digest, Sha256};
+
+use crate::xdr::{self, Limits, ReadXdr, WriteXdr};
+
+use super::global;
+
+pub mod claim;
+pub mod create;
+pub mod ls;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// Lock an amount of an asset in a claimable balance that the claimants can claim when their predicate holds
+    /// Learn more about claimable balances:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/transactions-specialized/claimable-balances
+    Create(create::Cmd),
+    /// Claim a claimable balance with the source account
+    Claim(claim::Cmd),
+    /// List the claimable balances an account can claim
+    Ls(ls::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Create(#[from] create::Error),
+    #[error(transparent)]
+    Tx(#[from] super::tx::args::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Create(cmd) => cmd.run(global_args).await?,
+            Cmd::Claim(cmd) => cmd.tx.handle_and_print(cmd, global_args).await?,
+            Cmd::Ls(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+/// The id of the claimable balance created by the operation at `op_index` of a transaction, which
+/// is the hash of the operation id.
+pub fn balance_id(
+    source_account: xdr::AccountId,
+    seq_num: xdr::SequenceNumber,
+    op_index: u32,
+) -> Result<xdr::ClaimableBalanceId, xdr::Error> {
+    let preimage = xdr::HashIdPreimage::OpId(xdr::HashIdPreimageOperationId {
+        source_account,
+        seq_num,
+        op_num: op_index,
+    });
+    let hash = Sha256::digest(preimage.to_xdr(Limits::none())?);
+    Ok(xdr::ClaimableBalanceId::ClaimableBalanceIdTypeV0(
+        xdr::Hash(hash.into()),
+    ))
+}
+
+/// Format a balance id as Horizon does, the hex encoded XDR of the id.
+pub fn balance_id_hex(id: &xdr::ClaimableBalanceId) -> Result<String, xdr::Error> {
+    Ok(hex::encode(id.to_xdr(Limits::none())?))
+}
+
+/// Parse a balance id from the hex encoded XDR used by Horizon, or the hex encoded hash alone.
+pub fn parse_balance_id(value: &str) -> Result<xdr::ClaimableBalanceId, String> {
+    let invalid =
+        || format!("invalid claimable balance id {value:?}, expected 72 or 64 hex characters");
+    let bytes = hex::decode(value).map_err(|_| invalid())?;
+    match bytes.len() {
+        32 => Ok(xdr::ClaimableBalanceId::ClaimableBalanceIdTypeV0(
+            xdr::Hash(bytes.try_into().map_err(|_| invalid())?),
+        )),
+        36 => xdr::ClaimableBalanceId::from_xdr(bytes, Limits::none()).map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_id_round_trips_through_hex() {
+        let account = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        let id = balance_id(account, xdr::SequenceNumber(42), 0).unwrap();
+        let hex = balance_id_hex(&id).unwrap();
+        assert!(hex.starts_with("00000000"));
+        assert_eq!(parse_balance_id(&hex).unwrap(), id);
+        assert_eq!(parse_balance_id(&hex[8..]).unwrap(), id);
+    }
+
+    #[test]
+    fn balance_id_hashes_the_operation_id() {
+        // sha256 of the XDR of `HashIdPreimage::OpId` for the account, sequence number, and
+        // operation index, encoded by hand from the XDR definitions.
+        let account: xdr::AccountId = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        let id = balance_id(account.clone(), xdr::SequenceNumber(42), 0).unwrap();
+        assert_eq!(
+            balance_id_hex(&id).unwrap(),
+            "00000000efc0cd443b02dc4de81c3f42feb47e9371ed0833f06149c5c59962c05a6472e2"
+        );
+        let id = balance_id(account, xdr::SequenceNumber(1_234_567_890_123), 2).unwrap();
+        assert_eq!(
+            balance_id_hex(&id).unwrap(),
+            "00000000819d849f92052c7e490c6448ceea131bb0548c985c0f68400406c788317e8888"
+        );
+    }
+}
@@ -85,10 +85,14 @@ pub fn build_host_function_parameters(
                     let cmd = crate::commands::keys::address::Cmd {
                         name: s.clone(),
                         hd_path: Some(0),
+                        format: crate::commands::keys::address::Format::Strkey,
+                        muxed_id: 0,
                         locator: config.locator.clone(),
                     };
                     if let Ok(address) = cmd.public_key() {
                         s = address.to_string();
+                    } else if let Ok(Some(address)) = config.locator.read_label(&s) {
+                        s = address;
                     }
                     if let Ok(key) = cmd.private_key() {
                         signers.push(key);
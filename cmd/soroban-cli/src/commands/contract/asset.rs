@@ -1,4 +1,5 @@
 use super::{deploy, id};
+use crate::commands::global;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
@@ -17,10 +18,10 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
             Cmd::Id(id) => id.run()?,
-            Cmd::Deploy(asset) => asset.run().await?,
+            Cmd::Deploy(asset) => asset.run(global_args).await?,
         }
         Ok(())
     }
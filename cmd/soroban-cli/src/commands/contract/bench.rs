@@ -0,0 +1,339 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use soroban_rpc::SimulateTransactionResponse;
+
+use crate::{
+    commands::{contract::run, global},
+    config, fee,
+    print::Print,
+    rpc,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Run(#[from] run::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error("step {step} ({name}): {error}")]
+    Step {
+        step: usize,
+        name: String,
+        error: run::StepError,
+    },
+    #[error("reading baseline {0:?}: {1}")]
+    CannotReadBaseline(PathBuf, std::io::Error),
+    #[error("writing baseline {0:?}: {1}")]
+    CannotWriteBaseline(PathBuf, std::io::Error),
+    #[error("{0} metrics regressed by more than {1}%")]
+    Regressions(usize, f64),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Scenario with the invocations to benchmark, in the format of `stellar contract run`.
+    /// Deploy steps are sent, invoke steps are only simulated
+    pub scenario: PathBuf,
+    /// Number of times to simulate each invocation, the median of the runs is recorded
+    #[arg(long, default_value = "3")]
+    pub runs: usize,
+    /// Baseline to compare against, written if it does not exist. Defaults to the scenario with
+    /// a `.bench.json` extension
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+    /// Percentage a metric can grow by before it is reported as a regression
+    #[arg(long, default_value = "5")]
+    pub threshold: f64,
+    /// Overwrite the baseline with the results of this run instead of comparing against it
+    #[arg(long)]
+    pub update: bool,
+    /// Format of the report
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub fee: fee::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// A table of the metrics of each invocation and their change from the baseline
+    Text,
+    /// The comparison of each metric as JSON
+    Json,
+}
+
+/// Resources used by a simulated invocation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Metrics {
+    instructions: u64,
+    memory_bytes: u64,
+    resource_fee: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+/// Metrics of each invoke step of a scenario, by step name, see `Baseline::key`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Baseline {
+    steps: BTreeMap<String, Metrics>,
+}
+
+#[derive(Debug, Serialize)]
+struct Comparison {
+    step: String,
+    metric: &'static str,
+    baseline: Option<u64>,
+    current: u64,
+    change: Option<f64>,
+    regression: bool,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let scenario = run::read_scenario(&self.scenario)?;
+        let base_dir = self.scenario.parent().unwrap_or(Path::new("."));
+        let step_args = global::Args {
+            quiet: true,
+            ..global_args.clone()
+        };
+        let mut current = Baseline::default();
+        for (i, step) in scenario.steps.iter().enumerate() {
+            let name = step.name();
+            let step_error = |error: run::StepError| Error::Step {
+                step: i + 1,
+                name: name.clone(),
+                error,
+            };
+            let config = step.config(&self.config)?;
+            if let Some(deploy) = &step.deploy {
+                print.infoln(format!("Deploying for {name}"));
+                deploy
+                    .run(&config, &self.fee, base_dir, &step_args)
+                    .await
+                    .map_err(step_error)?;
+            } else if let Some(invoke) = &step.invoke {
+                print.infoln(format!("Simulating {name} {} times", self.runs));
+                let cmd = invoke
+                    .cmd(&config, &self.fee)
+                    .map_err(|e| step_error(e.into()))?;
+                let mut runs = Vec::with_capacity(self.runs);
+                for _ in 0..self.runs.max(1) {
+                    let sim_res = cmd
+                        .simulate(Some(&step_args), Some(&config))
+                        .await
+                        .map_err(|e| step_error(e.into()))?;
+                    runs.push(Metrics::from_simulation(&sim_res)?);
+                }
+                current
+                    .steps
+                    .insert(current.key(&name), Metrics::median(&runs));
+            }
+        }
+
+        let path = self.baseline_path();
+        if self.update || !path.exists() {
+            fs::write(&path, serde_json::to_string_pretty(&current)?)
+                .map_err(|e| Error::CannotWriteBaseline(path.clone(), e))?;
+            print.saveln(format!("Saved baseline to {}", path.display()));
+            return Ok(());
+        }
+        let baseline: Baseline = serde_json::from_str(
+            &fs::read_to_string(&path).map_err(|e| Error::CannotReadBaseline(path.clone(), e))?,
+        )?;
+        let comparisons = compare(&baseline, &current, self.threshold);
+        match self.output {
+            Output::Text => {
+                for comparison in &comparisons {
+                    println!("{}", comparison.line());
+                }
+            }
+            Output::Json => println!("{}", serde_json::to_string_pretty(&comparisons)?),
+        }
+        let regressions = comparisons.iter().filter(|c| c.regression).count();
+        if regressions > 0 {
+            return Err(Error::Regressions(regressions, self.threshold));
+        }
+        print.checkln(format!(
+            "No metric regressed by more than {}%",
+            self.threshold
+        ));
+        Ok(())
+    }
+
+    fn baseline_path(&self) -> PathBuf {
+        self.baseline
+            .clone()
+            .unwrap_or_else(|| self.scenario.with_extension("bench.json"))
+    }
+}
+
+impl Baseline {
+    /// Key to record the step named `name` under, numbering the repeats of a name, e.g.
+    /// `invoke counter inc (2)`, so that steps with the same name don't overwrite each other.
+    fn key(&self, name: &str) -> String {
+        let mut key = name.to_string();
+        let mut n = 1;
+        while self.steps.contains_key(&key) {
+            n += 1;
+            key = format!("{name} ({n})");
+        }
+        key
+    }
+}
+
+impl Metrics {
+    fn from_simulation(sim_res: &SimulateTransactionResponse) -> Result<Self, rpc::Error> {
+        let resources = sim_res.transaction_data()?.resources;
+        Ok(Metrics {
+            instructions: sim_res.cost.cpu_insns,
+            memory_bytes: sim_res.cost.mem_bytes,
+            resource_fee: sim_res.min_resource_fee,
+            read_bytes: u64::from(resources.read_bytes),
+            write_bytes: u64::from(resources.write_bytes),
+        })
+    }
+
+    /// Median of each metric over `runs`.
+    fn median(runs: &[Metrics]) -> Metrics {
+        let median = |metric: fn(&Metrics) -> u64| {
+            let mut values = runs.iter().map(metric).collect::<Vec<_>>();
+            values.sort_unstable();
+            values.get(values.len() / 2).copied().unwrap_or_default()
+        };
+        Metrics {
+            instructions: median(|m| m.instructions),
+            memory_bytes: median(|m| m.memory_bytes),
+            resource_fee: median(|m| m.resource_fee),
+            read_bytes: median(|m| m.read_bytes),
+            write_bytes: median(|m| m.write_bytes),
+        }
+    }
+
+    fn values(&self) -> [(&'static str, u64); 5] {
+        [
+            ("instructions", self.instructions),
+            ("memory_bytes", self.memory_bytes),
+            ("resource_fee", self.resource_fee),
+            ("read_bytes", self.read_bytes),
+            ("write_bytes", self.write_bytes),
+        ]
+    }
+}
+
+/// Compare each metric of `current` against `baseline`, a regression being a growth of more
+/// than `threshold` percent.
+#[allow(clippy::cast_precision_loss)]
+fn compare(baseline: &Baseline, current: &Baseline, threshold: f64) -> Vec<Comparison> {
+    let mut comparisons = Vec::new();
+    for (step, metrics) in &current.steps {
+        let base = baseline.steps.get(step);
+        for (i, (metric, value)) in metrics.values().into_iter().enumerate() {
+            let base_value = base.map(|base| base.values()[i].1);
+            let change = base_value.and_then(|base_value| {
+                (base_value > 0)
+                    .then(|| (value as f64 - base_value as f64) / base_value as f64 * 100.0)
+            });
+            let regression = match (base_value, change) {
+                (_, Some(change)) => change > threshold,
+                (Some(0), None) => value > 0,
+                (_, None) => false,
+            };
+            comparisons.push(Comparison {
+                step: step.clone(),
+                metric,
+                baseline: base_value,
+                current: value,
+                change,
+                regression,
+            });
+        }
+    }
+    comparisons
+}
+
+impl Comparison {
+    fn line(&self) -> String {
+        let baseline = self
+            .baseline
+            .map_or_else(|| "-".to_string(), |b| b.to_string());
+        let change = self
+            .change
+            .map_or_else(|| "new".to_string(), |c| format!("{c:+.1}%"));
+        let mut line = format!(
+            "{:<30} {:<13} {baseline:>12} {:>12} {change:>8}",
+            self.step, self.metric, self.current
+        );
+        if self.regression {
+            line.push_str("  REGRESSION");
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_regressions_above_threshold() {
+        let metrics = |instructions, write_bytes| Metrics {
+            instructions,
+            memory_bytes: 1_000,
+            resource_fee: 100,
+            read_bytes: 10,
+            write_bytes,
+        };
+        let baseline = Baseline {
+            steps: [("inc".to_string(), metrics(1_000_000, 0))].into(),
+        };
+        let current = Baseline {
+            steps: [
+                ("inc".to_string(), metrics(1_100_000, 8)),
+                ("new".to_string(), metrics(5, 5)),
+            ]
+            .into(),
+        };
+        let comparisons = compare(&baseline, &current, 5.0);
+        let regressed = comparisons
+            .iter()
+            .filter(|c| c.regression)
+            .map(|c| (c.step.as_str(), c.metric))
+            .collect::<Vec<_>>();
+        assert_eq!(regressed, [("inc", "instructions"), ("inc", "write_bytes")]);
+        assert_eq!(
+            comparisons[0].line(),
+            "inc                            instructions       1000000      1100000   +10.0%  REGRESSION"
+        );
+        assert_eq!(
+            Metrics::median(&[metrics(3, 0), metrics(1, 0), metrics(2, 0)]),
+            metrics(2, 0)
+        );
+    }
+
+    #[test]
+    fn numbers_repeated_step_names() {
+        let mut baseline = Baseline::default();
+        for name in ["inc", "inc", "get", "inc"] {
+            let key = baseline.key(name);
+            baseline.steps.insert(key, Metrics::default());
+        }
+        assert_eq!(
+            baseline.steps.keys().collect::<Vec<_>>(),
+            ["get", "inc", "inc (2)", "inc (3)"]
+        );
+    }
+}
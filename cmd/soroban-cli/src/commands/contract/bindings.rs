@@ -11,7 +11,7 @@ pub enum Cmd {
     Rust(rust::Cmd),
 
     /// Generate a TypeScript / JavaScript package
-    Typescript(typescript::Cmd),
+    Typescript(Box<typescript::Cmd>),
 }
 
 #[derive(thiserror::Error, Debug)]
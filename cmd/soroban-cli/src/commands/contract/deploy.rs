@@ -1,6 +1,7 @@
 use crate::commands::global;
 
 pub mod asset;
+pub mod manifest;
 pub mod wasm;
 
 #[derive(Debug, clap::Subcommand)]
@@ -22,7 +23,7 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
-            Cmd::Asset(asset) => asset.run().await?,
+            Cmd::Asset(asset) => asset.run(global_args).await?,
             Cmd::Wasm(wasm) => wasm.run(global_args).await?,
         }
         Ok(())
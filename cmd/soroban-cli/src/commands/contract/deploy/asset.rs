@@ -15,14 +15,16 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, network},
+    config::{self, data, network, Submitted},
     rpc::Error as SorobanRpcError,
-    tx::builder,
+    tx::builder::{self, TxExt},
     utils::contract_id_hash_from_asset,
 };
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error("error parsing int: {0}")]
     ParseIntError(#[from] ParseIntError),
     #[error(transparent)]
@@ -39,6 +41,8 @@ pub enum Error {
     Network(#[from] network::Error),
     #[error(transparent)]
     Builder(#[from] builder::Error),
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
 }
 
 impl From<Infallible> for Error {
@@ -58,13 +62,19 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
-        let res = self.run_against_rpc_server(None, None).await?.to_envelope();
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let res = self
+            .run_against_rpc_server(Some(global_args), None)
+            .await?
+            .to_envelope();
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
             TxnEnvelopeResult::Res(contract) => {
                 println!("{contract}");
             }
@@ -98,29 +108,40 @@ impl NetworkRunnable for Cmd {
         let account_details = client
             .get_account(&source_account.clone().to_string())
             .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence = config.sequence_number_after(
+            &account_details.account_id,
+            account_details.seq_num.into(),
+            self.fee.seq_strategy,
+        )?;
         let network_passphrase = &network.network_passphrase;
         let contract_id = contract_id_hash_from_asset(asset, network_passphrase);
         let tx = build_wrap_token_tx(
             asset,
             &contract_id,
-            sequence + 1,
-            self.fee.fee,
+            sequence,
+            self.fee.inclusion_fee(&network, true).await?,
             network_passphrase,
             source_account,
-        )?;
+        )?
+        .add_cond(self.preconditions.preconditions()?);
         if self.fee.build_only {
             return Ok(TxnResult::Txn(Box::new(tx)));
         }
         let txn = simulate_and_assemble_transaction(&client, &tx).await?;
-        let txn = self.fee.apply_to_assembled_txn(txn).transaction().clone();
+        let mut txn = self.fee.apply_to_assembled_txn(txn).transaction().clone();
         if self.fee.sim_only {
             return Ok(TxnResult::Txn(Box::new(txn)));
         }
-        let get_txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(txn).await?)
+        let reservation = config.reserve_sequence_number(&mut txn, &self.fee)?;
+        let tx_env = self.config.sign_with_local_key(txn).await?;
+        let get_txn_resp = match config
+            .submit(&network, &client, tx_env, reservation, &self.fee, args)
             .await?
-            .try_into()?;
+        {
+            Submitted::DryRun(tx_env) => return Ok(TxnResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnResult::Submitted(hash)),
+            Submitted::Done(res) => (*res).try_into()?,
+        };
         if args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
         }
@@ -0,0 +1,275 @@
+//! Manifests of contracts to deploy together, e.g. `deployments.toml`:
+//!
+//! ```toml
+//! [contracts.token]
+//! wasm = "target/wasm32-unknown-unknown/release/token.wasm"
+//! args = { admin = "alice" }
+//!
+//! [contracts.pool]
+//! wasm = "target/wasm32-unknown-unknown/release/pool.wasm"
+//! args = { token = "${token}", fee_bps = 30 }
+//! ```
+//!
+//! Contracts are deployed after the contracts their constructor arguments reference with
+//! `${name}`, which is replaced by the ID of that contract, and the ones in their `depends_on`.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::commands::contract::run::args_to_slop;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading manifest {path}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("parsing manifest {path}: {error}")]
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("contract {contract} of the manifest needs either `wasm` or `wasm_hash`")]
+    WasmNotProvided { contract: String },
+    #[error("contract {contract} of the manifest depends on {dependency}, which is not in it")]
+    UnknownDependency {
+        contract: String,
+        dependency: String,
+    },
+    #[error("contracts of the manifest depend on each other in a cycle: {0}")]
+    Cycle(String),
+    #[error("unterminated reference in argument {0:?}, expected `${{name}}`")]
+    UnterminatedReference(String),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub contracts: BTreeMap<String, Contract>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// Wasm file to install and deploy, relative to the manifest
+    pub wasm: Option<PathBuf>,
+    /// Hash of already installed Wasm to deploy
+    pub wasm_hash: Option<String>,
+    /// Alias to save the contract ID as, the name of the contract by default
+    pub alias: Option<String>,
+    pub salt: Option<String>,
+    /// Contracts to deploy before this one, in addition to the ones referenced in `args`
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Arguments of the constructor, by name
+    #[serde(default)]
+    pub args: toml::Table,
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path).map_err(|error| Error::Read {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut manifest: Self = toml::from_str(&contents).map_err(|error| Error::Parse {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for (name, contract) in &mut manifest.contracts {
+            match &contract.wasm {
+                Some(wasm) => contract.wasm = Some(dir.join(wasm)),
+                None if contract.wasm_hash.is_none() => {
+                    return Err(Error::WasmNotProvided {
+                        contract: name.clone(),
+                    })
+                }
+                None => {}
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Names of the contracts in the order to deploy them, in levels that only depend on the
+    /// contracts of the levels before them.
+    pub fn levels(&self) -> Result<Vec<Vec<&str>>, Error> {
+        let mut dependencies = BTreeMap::new();
+        for (name, contract) in &self.contracts {
+            let mut deps = BTreeSet::new();
+            for dependency in contract
+                .depends_on
+                .iter()
+                .cloned()
+                .chain(contract.references()?)
+            {
+                if !self.contracts.contains_key(&dependency) {
+                    return Err(Error::UnknownDependency {
+                        contract: name.clone(),
+                        dependency,
+                    });
+                }
+                deps.insert(dependency);
+            }
+            dependencies.insert(name.as_str(), deps);
+        }
+        let mut order: Vec<&str> = Vec::new();
+        let mut levels = Vec::new();
+        while !dependencies.is_empty() {
+            let ready = dependencies
+                .iter()
+                .filter(|(_, deps)| deps.iter().all(|d| order.contains(&d.as_str())))
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>();
+            if ready.is_empty() {
+                return Err(Error::Cycle(
+                    dependencies.keys().copied().collect::<Vec<_>>().join(", "),
+                ));
+            }
+            for name in &ready {
+                dependencies.remove(name);
+                order.push(name);
+            }
+            levels.push(ready);
+        }
+        Ok(levels)
+    }
+}
+
+impl Contract {
+    /// Names of the contracts referenced in the arguments.
+    pub fn references(&self) -> Result<Vec<String>, Error> {
+        let mut references = Vec::new();
+        for value in self.args.values() {
+            value_references(value, &mut references)?;
+        }
+        Ok(references)
+    }
+
+    /// The `--name value` arguments of the constructor, with the references replaced by the IDs
+    /// of the contracts deployed so far.
+    pub fn slop(&self, deployed: &BTreeMap<String, String>) -> Result<Vec<OsString>, Error> {
+        let args = self
+            .args
+            .iter()
+            .map(|(name, value)| (name.clone(), substitute(value, deployed)))
+            .collect();
+        Ok(args_to_slop(&args)?)
+    }
+}
+
+fn value_references(value: &toml::Value, references: &mut Vec<String>) -> Result<(), Error> {
+    match value {
+        toml::Value::String(s) => {
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("${") {
+                let end = rest[start..]
+                    .find('}')
+                    .ok_or_else(|| Error::UnterminatedReference(s.clone()))?;
+                references.push(rest[start + 2..start + end].to_string());
+                rest = &rest[start + end + 1..];
+            }
+        }
+        toml::Value::Array(values) => {
+            for value in values {
+                value_references(value, references)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for value in table.values() {
+                value_references(value, references)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn substitute(value: &toml::Value, deployed: &BTreeMap<String, String>) -> toml::Value {
+    match value {
+        toml::Value::String(s) => {
+            toml::Value::String(deployed.iter().fold(s.clone(), |s, (name, id)| {
+                s.replace(&format!("${{{name}}}"), id)
+            }))
+        }
+        toml::Value::Array(values) => {
+            toml::Value::Array(values.iter().map(|v| substitute(v, deployed)).collect())
+        }
+        toml::Value::Table(table) => toml::Value::Table(
+            table
+                .iter()
+                .map(|(k, v)| (k.clone(), substitute(v, deployed)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deploys_dependencies_first() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [contracts.pool]
+            wasm = "pool.wasm"
+            args = { token = "${token}", oracles = ["${oracle}"], fee = 30 }
+
+            [contracts.token]
+            wasm_hash = "abc"
+            depends_on = ["registry"]
+
+            [contracts.registry]
+            wasm = "registry.wasm"
+
+            [contracts.oracle]
+            wasm = "oracle.wasm"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.levels().unwrap(),
+            [vec!["oracle", "registry"], vec!["token"], vec!["pool"]]
+        );
+        let deployed = BTreeMap::from([
+            ("token".to_string(), "CTOKEN".to_string()),
+            ("oracle".to_string(), "CORACLE".to_string()),
+        ]);
+        assert_eq!(
+            manifest.contracts["pool"].slop(&deployed).unwrap(),
+            [
+                "--fee",
+                "30",
+                "--oracles",
+                r#"["CORACLE"]"#,
+                "--token",
+                "CTOKEN"
+            ]
+            .map(OsString::from)
+        );
+
+        let cycle: Manifest = toml::from_str(
+            r#"
+            [contracts.a]
+            wasm = "a.wasm"
+            depends_on = ["b"]
+
+            [contracts.b]
+            wasm = "b.wasm"
+            args = { a = "${a}" }
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(cycle.levels(), Err(Error::Cycle(_))));
+    }
+}
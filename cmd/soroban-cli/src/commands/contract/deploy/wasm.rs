@@ -1,7 +1,9 @@
 use std::array::TryFromSliceError;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsString;
 use std::fmt::Debug;
 use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
 
 use crate::xdr::{
     AccountId, ContractExecutable, ContractIdPreimage, ContractIdPreimageFromAddress,
@@ -17,27 +19,29 @@ use regex::Regex;
 use soroban_spec_tools::contract as contract_spec;
 
 use crate::{
-    assembled::simulate_and_assemble_transaction,
     commands::{
         contract::{self, arg_parsing, id::wasm::get_contract_id, install},
         global,
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable, HEADING_RPC,
     },
-    config::{self, data, locator, network},
+    config::{self, data, locator, network, sequence, Submitted},
+    diagnostic,
     print::Print,
     rpc,
-    utils::{self, rpc::get_remote_wasm_from_hash},
-    wasm,
+    tx::builder::TxExt,
+    utils, wasm,
 };
 
+use super::manifest;
+
 pub const CONSTRUCTOR_FUNCTION_NAME: &str = "__constructor";
 
 #[derive(Parser, Debug, Clone)]
 #[command(group(
     clap::ArgGroup::new("wasm_src")
         .required(true)
-        .args(&["wasm", "wasm_hash"]),
+        .args(&["wasm", "wasm_hash", "manifest"]),
 ))]
 #[group(skip)]
 pub struct Cmd {
@@ -47,6 +51,18 @@ pub struct Cmd {
     /// Hash of the already installed/deployed WASM file
     #[arg(long = "wasm-hash", conflicts_with = "wasm", group = "wasm_src")]
     pub wasm_hash: Option<String>,
+    /// TOML manifest of contracts to install and deploy together, each after the contracts it
+    /// depends on. Constructor arguments can reference the ID of another contract of the manifest
+    /// as `${name}`. Contracts whose alias is of a contract on the network are not deployed
+    /// again, so a deploy that failed part way is resumed by running it again. With
+    /// `--seq-strategy pool` the contracts that don't depend on each other are deployed in
+    /// parallel
+    #[arg(
+        long,
+        group = "wasm_src",
+        conflicts_with_all = ["alias", "salt", "CONTRACT_CONSTRUCTOR_ARGS"]
+    )]
+    pub manifest: Option<PathBuf>,
     /// Custom salt 32-byte salt for the token id
     #[arg(
         long,
@@ -57,6 +73,8 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
     pub ignore_checks: bool,
@@ -65,13 +83,18 @@ pub struct Cmd {
     /// configuration without asking for confirmation.
     #[arg(long, value_parser = clap::builder::ValueParser::new(alias_validator))]
     pub alias: Option<String>,
-    /// If provided, will be passed to the contract's `__constructor` function with provided arguments for that function as `--arg-name value`
+    /// Arguments of the contract's `__constructor` function, as `--arg-name value` or
+    /// `--arg-name-file-path file` like the arguments of `contract invoke`. Use `-- --help` to
+    /// list them from the spec of the Wasm. They are parsed and the constructor simulated before
+    /// the deploy is submitted
     #[arg(last = true, id = "CONTRACT_CONSTRUCTOR_ARGS")]
     pub slop: Vec<OsString>,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error(transparent)]
     Install(#[from] install::Error),
     #[error("error parsing int: {0}")]
@@ -124,16 +147,30 @@ pub enum Error {
     ArgParse(#[from] arg_parsing::Error),
     #[error("Only ed25519 accounts are allowed")]
     OnlyEd25519AccountsAllowed,
+    #[error(transparent)]
+    Diagnostic(#[from] diagnostic::Error),
+    #[error(transparent)]
+    Manifest(#[from] manifest::Error),
+    #[error("constructor arguments were given, but the contract has no `__constructor` function")]
+    NoConstructor,
+    #[error("deploying a manifest submits every transaction and waits for it, remove --build-only, --sim-only, --no-wait, and --dry-run")]
+    ManifestNotSubmitted,
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if let Some(manifest) = &self.manifest {
+            return self.deploy_manifest(manifest, global_args).await;
+        }
         let res = self
             .run_against_rpc_server(Some(global_args), None)
             .await?
             .to_envelope();
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
             TxnEnvelopeResult::Res(contract) => {
                 let network = self.config.get_network()?;
 
@@ -152,6 +189,103 @@ impl Cmd {
     }
 }
 
+impl Cmd {
+    /// Deploy the contracts of the manifest at `path` in the order of their dependencies, reusing
+    /// the ones already deployed under their alias. The contracts that don't depend on each other
+    /// are deployed concurrently with `--seq-strategy pool`, as each needs its own sequence number.
+    async fn deploy_manifest(&self, path: &Path, global_args: &global::Args) -> Result<(), Error> {
+        if self.fee.build_only || self.fee.sim_only || self.fee.no_wait || self.fee.dry_run {
+            return Err(Error::ManifestNotSubmitted);
+        }
+        let manifest = manifest::Manifest::read(path)?;
+        let mut deployed = BTreeMap::new();
+        for level in manifest.levels()? {
+            let deploys = level
+                .iter()
+                .map(|name| self.deploy_manifest_contract(&manifest, name, &deployed, global_args));
+            let ids = if self.fee.seq_strategy == sequence::Strategy::Pool {
+                futures::future::try_join_all(deploys).await?
+            } else {
+                let mut ids = Vec::new();
+                for deploy in deploys {
+                    ids.push(deploy.await?);
+                }
+                ids
+            };
+            for (name, id) in level.iter().zip(ids) {
+                deployed.insert((*name).to_string(), id.to_string());
+            }
+        }
+        for (name, id) in deployed {
+            println!("{name} {id}");
+        }
+        Ok(())
+    }
+
+    /// Deploy contract `name` of `manifest`, unless its alias is of a contract on the network.
+    async fn deploy_manifest_contract(
+        &self,
+        manifest: &manifest::Manifest,
+        name: &str,
+        deployed: &BTreeMap<String, String>,
+        global_args: &global::Args,
+    ) -> Result<stellar_strkey::Contract, Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let contract = &manifest.contracts[name];
+        let alias = alias_validator(contract.alias.as_deref().unwrap_or(name))?;
+        if let Some(id) = self
+            .config
+            .locator
+            .get_contract_id(&alias, &network.network_passphrase)?
+        {
+            match network.rpc_client()?.get_contract_data(&id.0).await {
+                Ok(_) => {
+                    print.infoln(format!("Using {name} already deployed as {id}"));
+                    return Ok(id);
+                }
+                Err(e) => {
+                    tracing::debug!("{e}");
+                    print.warnln(format!(
+                        "Alias {alias} of {name} is {id}, which is not on the network, deploying {name} again"
+                    ));
+                }
+            }
+        }
+        print.infoln(format!("Deploying {name}…"));
+        let cmd = Cmd {
+            wasm: contract.wasm.clone(),
+            wasm_hash: contract.wasm_hash.clone(),
+            manifest: None,
+            salt: contract.salt.clone(),
+            alias: Some(alias.clone()),
+            slop: contract.slop(deployed)?,
+            ..self.clone()
+        };
+        let TxnResult::Res(id) = cmd.run_against_rpc_server(Some(global_args), None).await? else {
+            return Err(Error::ManifestNotSubmitted);
+        };
+        self.config
+            .locator
+            .save_contract_id(&network.network_passphrase, &id, &alias)?;
+        Ok(id)
+    }
+}
+
+impl Cmd {
+    fn wasm_hash(&self) -> Result<Hash, Error> {
+        let wasm_hash = self.wasm_hash.as_ref().ok_or(Error::WasmNotProvided)?;
+        Ok(Hash(
+            utils::contract_id_from_str(wasm_hash)
+                .map_err(|e| Error::CannotParseWasmHash {
+                    wasm_hash: wasm_hash.clone(),
+                    error: e,
+                })?
+                .0,
+        ))
+    }
+}
+
 fn alias_validator(alias: &str) -> Result<String, Error> {
     let regex = Regex::new(r"^[a-zA-Z0-9_-]{1,30}$").unwrap();
 
@@ -177,40 +311,6 @@ impl NetworkRunnable for Cmd {
     ) -> Result<TxnResult<stellar_strkey::Contract>, Error> {
         let print = Print::new(global_args.map_or(false, |a| a.quiet));
         let config = config.unwrap_or(&self.config);
-        let wasm_hash = if let Some(wasm) = &self.wasm {
-            let hash = if self.fee.build_only || self.fee.sim_only {
-                wasm::Args { wasm: wasm.clone() }.hash()?
-            } else {
-                install::Cmd {
-                    wasm: wasm::Args { wasm: wasm.clone() },
-                    config: config.clone(),
-                    fee: self.fee.clone(),
-                    ignore_checks: self.ignore_checks,
-                }
-                .run_against_rpc_server(global_args, Some(config))
-                .await?
-                .into_result()
-                .expect("the value (hash) is expected because it should always be available since build-only is a shared parameter")
-            };
-            hex::encode(hash)
-        } else {
-            self.wasm_hash
-                .as_ref()
-                .ok_or(Error::WasmNotProvided)?
-                .to_string()
-        };
-
-        let wasm_hash = Hash(
-            utils::contract_id_from_str(&wasm_hash)
-                .map_err(|e| Error::CannotParseWasmHash {
-                    wasm_hash: wasm_hash.clone(),
-                    error: e,
-                })?
-                .0,
-        );
-
-        print.infoln(format!("Using wasm hash {wasm_hash}").as_str());
-
         let network = config.get_network()?;
         let salt: [u8; 32] = match &self.salt {
             Some(h) => soroban_spec_tools::utils::padded_hex_from_str(h, 32)
@@ -221,9 +321,7 @@ impl NetworkRunnable for Cmd {
         };
 
         let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
+        let no_cache = global_args.is_some_and(|a| a.no_cache);
 
         let MuxedAccount::Ed25519(bytes) = config.source_account()? else {
             return Err(Error::OnlyEd25519AccountsAllowed);
@@ -235,15 +333,18 @@ impl NetworkRunnable for Cmd {
         });
         let contract_id =
             get_contract_id(contract_id_preimage.clone(), &network.network_passphrase)?;
+
+        // Parse the constructor arguments, or print their help, before installing anything.
         let raw_wasm = if let Some(wasm) = self.wasm.as_ref() {
             wasm::Args { wasm: wasm.clone() }.read()?
         } else {
-            get_remote_wasm_from_hash(&client, &wasm_hash).await?
+            network.verify_rpc_passphrase(&client, no_cache).await?;
+            wasm::fetch_from_hash(&client, &self.wasm_hash()?, no_cache).await?
         };
         let entries = soroban_spec_tools::contract::Spec::new(&raw_wasm)?.spec;
         let res = soroban_spec_tools::Spec::new(entries.clone());
         let constructor_params = if let Ok(func) = res.find_function(CONSTRUCTOR_FUNCTION_NAME) {
-            if func.inputs.len() == 0 {
+            if func.inputs.len() == 0 && self.slop.is_empty() {
                 None
             } else {
                 let mut slop = vec![OsString::from(CONSTRUCTOR_FUNCTION_NAME)];
@@ -258,21 +359,67 @@ impl NetworkRunnable for Cmd {
                     .2,
                 )
             }
-        } else {
+        } else if self.slop.is_empty() {
             None
+        } else {
+            return Err(Error::NoConstructor);
         };
 
+        let wasm_hash = if let Some(wasm) = &self.wasm {
+            if self.fee.build_only || self.fee.sim_only {
+                wasm::Args { wasm: wasm.clone() }.hash()?
+            } else {
+                // The wasm has to be installed before the deploy can be simulated, so wait for
+                // the install even with `--no-wait`.
+                let installed = install::Cmd {
+                    wasm: wasm::Args { wasm: wasm.clone() },
+                    config: config.clone(),
+                    fee: crate::fee::Args {
+                        no_wait: false,
+                        ..self.fee.clone()
+                    },
+                    preconditions: self.preconditions.clone(),
+                    ignore_checks: self.ignore_checks,
+                }
+                .run_against_rpc_server(global_args, Some(config))
+                .await?;
+                match installed {
+                    TxnResult::Res(hash) => hash,
+                    TxnResult::TxnEnvelope(tx_env) => {
+                        print.warnln("The wasm is not installed yet, so only the install transaction is shown. Deploy again once it is installed");
+                        return Ok(TxnResult::TxnEnvelope(tx_env));
+                    }
+                    TxnResult::Txn(_) | TxnResult::Submitted(_) => unreachable!(
+                        "install waits for the transaction and is not build or sim only"
+                    ),
+                }
+            }
+        } else {
+            self.wasm_hash()?
+        };
+
+        print.infoln(format!("Using wasm hash {wasm_hash}").as_str());
+
+        network.verify_rpc_passphrase(&client, no_cache).await?;
+
         // Get the account sequence number
         let account_details = client.get_account(&source_account.to_string()).await?;
-        let sequence: i64 = account_details.seq_num.into();
-        let txn = Box::new(build_create_contract_tx(
-            wasm_hash,
-            sequence + 1,
-            self.fee.fee,
-            source_account,
-            contract_id_preimage,
-            constructor_params.as_ref(),
-        )?);
+        let sequence = config.sequence_number_after(
+            &account_details.account_id,
+            account_details.seq_num.into(),
+            self.fee.seq_strategy,
+        )?;
+        let txn = Box::new(
+            build_create_contract_tx(
+                wasm_hash,
+                sequence,
+                self.fee.inclusion_fee(&network, true).await?,
+                source_account,
+                contract_id_preimage,
+                constructor_params.as_ref(),
+            )?
+            .add_cond(self.preconditions.preconditions()?),
+        );
 
         if self.fee.build_only {
             print.checkln("Transaction built!");
@@ -281,21 +428,48 @@ impl NetworkRunnable for Cmd {
 
         print.infoln("Simulating deploy transaction…");
 
-        let txn = simulate_and_assemble_transaction(&client, &txn).await?;
-        let txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
+        // The constructor runs in the deploy, so name its errors with the spec of the contract.
+        let specs = HashMap::from([(Hash(contract_id.0), res)]);
+        let print_stack = |error: diagnostic::Error| {
+            diagnostic::print_stack(&print, error.events(), &specs);
+            error
+        };
+        let txn = diagnostic::simulate_and_assemble_transaction(&client, &txn)
+            .await
+            .map_err(print_stack)?;
+        let mut txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
 
         if self.fee.sim_only {
             print.checkln("Done!");
             return Ok(TxnResult::Txn(txn));
         }
 
-        print.globeln("Submitting deploy transaction…");
-        print.log_transaction(&txn, &network, true)?;
+        let reservation = config.reserve_sequence_number(&mut txn, &self.fee)?;
+        let tx_env = config.sign_with_local_key((*txn).clone()).await?;
+        if !self.fee.dry_run {
+            print.globeln("Submitting deploy transaction…");
+            print.log_transaction(&txn, &network, true)?;
+        }
 
-        let get_txn_resp = client
-            .send_transaction_polling(&config.sign_with_local_key(*txn).await?)
-            .await?
-            .try_into()?;
+        let submitted = config
+            .submit(
+                &network,
+                &client,
+                tx_env,
+                reservation,
+                &self.fee,
+                global_args,
+            )
+            .await
+            .map_err(|error| match error {
+                config::Error::Diagnostic(error) => Error::Diagnostic(print_stack(*error)),
+                error => error.into(),
+            })?;
+        let get_txn_resp = match submitted {
+            Submitted::DryRun(tx_env) => return Ok(TxnResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnResult::Submitted(hash)),
+            Submitted::Done(res) => (*res).try_into()?,
+        };
 
         if global_args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
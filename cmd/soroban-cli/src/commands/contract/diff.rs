@@ -0,0 +1,473 @@
+use std::{collections::BTreeMap, fmt::Debug, path::Path};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::Serialize;
+use soroban_spec_tools::contract;
+
+use crate::{
+    commands::{contract::info::shared, global},
+    config::{locator, network},
+    print::Print,
+    xdr::{ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtUnionCaseV0},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract to compare from: a Wasm file, a Wasm hash, or a contract id or alias
+    #[arg(long)]
+    pub old: String,
+    /// Contract to compare to: a Wasm file, a Wasm hash, or a contract id or alias
+    #[arg(long)]
+    pub new: String,
+    /// Format of the report
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per change, marked with `+` when added, `-` when removed, and `~` when changed
+    Text,
+    /// The list of changes as JSON
+    Json,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] shared::Error),
+    #[error(transparent)]
+    Spec(#[from] contract::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0} breaking change(s) between the contracts")]
+    Breaking(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Impact {
+    /// Existing callers keep working.
+    Compatible,
+    /// Existing callers of the contract break.
+    Breaking,
+    /// Values of the type already in contract storage can no longer be read.
+    Storage,
+}
+
+/// A difference between the interfaces of two contracts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub kind: Kind,
+    pub item: String,
+    pub detail: Option<String>,
+    pub impact: Impact,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let old = self.spec(&self.old, global_args).await?;
+        let new = self.spec(&self.new, global_args).await?;
+        let changes = diff(&old, &new);
+        match self.output {
+            Output::Text => {
+                for change in &changes {
+                    println!("{change}");
+                }
+            }
+            Output::Json => println!("{}", serde_json::to_string_pretty(&changes)?),
+        }
+        let breaking = changes
+            .iter()
+            .filter(|c| c.impact != Impact::Compatible)
+            .count();
+        if breaking > 0 {
+            return Err(Error::Breaking(breaking));
+        }
+        if changes.is_empty() {
+            print.checkln("The contract interfaces are identical");
+        } else {
+            print.checkln("No breaking changes");
+        }
+        Ok(())
+    }
+
+    async fn spec(
+        &self,
+        source: &str,
+        global_args: &global::Args,
+    ) -> Result<Vec<ScSpecEntry>, Error> {
+        let mut args = shared::Args {
+            network: self.network.clone(),
+            locator: self.locator.clone(),
+            ..Default::default()
+        };
+        if Path::new(source).is_file() {
+            args.wasm = Some(source.into());
+        } else if source.len() == 64 && hex::decode(source).is_ok() {
+            args.wasm_hash = Some(source.to_string());
+        } else {
+            args.contract_id = Some(source.parse().unwrap_or_else(|e| match e {}));
+        }
+        Ok(match shared::fetch_wasm(&args, global_args).await? {
+            Some(wasm) => contract::Spec::new(&wasm)?.spec,
+            None => {
+                contract::Spec::spec_to_base64(&soroban_sdk::token::StellarAssetSpec::spec_xdr())?.1
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.kind {
+            Kind::Added => '+',
+            Kind::Removed => '-',
+            Kind::Changed => '~',
+        };
+        write!(f, "{sign} {}", self.item)?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+        match self.impact {
+            Impact::Compatible => Ok(()),
+            Impact::Breaking => write!(f, " (breaking)"),
+            Impact::Storage => write!(f, " (breaks stored values)"),
+        }
+    }
+}
+
+/// Changes from the `old` spec to the `new` one.
+pub fn diff(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<Change> {
+    let (old, new) = (by_name(old), by_name(new));
+    let mut changes = Vec::new();
+    for (name, old_entry) in &old {
+        let item = format!("{} `{name}`", kind_name(old_entry));
+        let Some(new_entry) = new.get(name) else {
+            changes.push(Change {
+                kind: Kind::Removed,
+                item,
+                detail: None,
+                impact: removed_impact(old_entry),
+            });
+            continue;
+        };
+        let mut change = |detail: String, impact: Impact| {
+            changes.push(Change {
+                kind: Kind::Changed,
+                item: item.clone(),
+                detail: Some(detail),
+                impact,
+            });
+        };
+        match (old_entry, new_entry) {
+            (ScSpecEntry::FunctionV0(old), ScSpecEntry::FunctionV0(new)) => {
+                diff_function(old, new, &mut change);
+            }
+            (ScSpecEntry::UdtStructV0(old), ScSpecEntry::UdtStructV0(new)) => diff_members(
+                "field",
+                &old.fields
+                    .iter()
+                    .map(|f| (f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                    .collect::<Vec<_>>(),
+                &new.fields
+                    .iter()
+                    .map(|f| (f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                    .collect::<Vec<_>>(),
+                Impact::Storage,
+                Impact::Storage,
+                &mut change,
+            ),
+            (ScSpecEntry::UdtUnionV0(old), ScSpecEntry::UdtUnionV0(new)) => diff_members(
+                "case",
+                &old.cases.iter().map(union_case).collect::<Vec<_>>(),
+                &new.cases.iter().map(union_case).collect::<Vec<_>>(),
+                Impact::Compatible,
+                Impact::Storage,
+                &mut change,
+            ),
+            (ScSpecEntry::UdtEnumV0(old), ScSpecEntry::UdtEnumV0(new)) => diff_members(
+                "case",
+                &old.cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>(),
+                &new.cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>(),
+                Impact::Compatible,
+                Impact::Storage,
+                &mut change,
+            ),
+            (ScSpecEntry::UdtErrorEnumV0(old), ScSpecEntry::UdtErrorEnumV0(new)) => diff_members(
+                "error",
+                &old.cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>(),
+                &new.cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>(),
+                Impact::Compatible,
+                Impact::Breaking,
+                &mut change,
+            ),
+            (old_entry, new_entry) => change(
+                format!("is now a {}", kind_name(new_entry)),
+                removed_impact(old_entry),
+            ),
+        }
+    }
+    for (name, new_entry) in &new {
+        if !old.contains_key(name) {
+            changes.push(Change {
+                kind: Kind::Added,
+                item: format!("{} `{name}`", kind_name(new_entry)),
+                detail: None,
+                impact: Impact::Compatible,
+            });
+        }
+    }
+    changes
+}
+
+fn diff_function(
+    old: &ScSpecFunctionV0,
+    new: &ScSpecFunctionV0,
+    change: &mut impl FnMut(String, Impact),
+) {
+    for i in 0..old.inputs.len().max(new.inputs.len()) {
+        match (old.inputs.get(i), new.inputs.get(i)) {
+            (Some(o), Some(n)) => {
+                let (old_name, new_name) =
+                    (o.name.to_utf8_string_lossy(), n.name.to_utf8_string_lossy());
+                if old_name != new_name {
+                    change(
+                        format!("argument {} renamed `{old_name}` -> `{new_name}`", i + 1),
+                        Impact::Breaking,
+                    );
+                }
+                let (old_type, new_type) = (type_name(&o.type_), type_name(&n.type_));
+                if old_type != new_type {
+                    change(
+                        format!("argument `{new_name}` type {old_type} -> {new_type}"),
+                        Impact::Breaking,
+                    );
+                }
+            }
+            (Some(o), None) => change(
+                format!("argument `{}` removed", o.name.to_utf8_string_lossy()),
+                Impact::Breaking,
+            ),
+            (None, Some(n)) => change(
+                format!("argument `{}` added", n.name.to_utf8_string_lossy()),
+                Impact::Breaking,
+            ),
+            (None, None) => {}
+        }
+    }
+    let output = |f: &ScSpecFunctionV0| f.outputs.first().map_or("()".to_string(), type_name);
+    let (old_output, new_output) = (output(old), output(new));
+    if old_output != new_output {
+        change(
+            format!("return type {old_output} -> {new_output}"),
+            Impact::Breaking,
+        );
+    }
+}
+
+/// Compare named members of a type, e.g. the fields of a struct with their types. Added members
+/// have the `added` impact, removed and changed ones the `changed` impact.
+fn diff_members(
+    member: &str,
+    old: &[(String, String)],
+    new: &[(String, String)],
+    added: Impact,
+    changed: Impact,
+    change: &mut impl FnMut(String, Impact),
+) {
+    for (name, old_value) in old {
+        match new.iter().find(|(n, _)| n == name) {
+            None => change(format!("{member} `{name}` removed"), changed),
+            Some((_, new_value)) if new_value != old_value => change(
+                format!("{member} `{name}` {old_value} -> {new_value}"),
+                changed,
+            ),
+            Some(_) => {}
+        }
+    }
+    for (name, _) in new {
+        if !old.iter().any(|(n, _)| n == name) {
+            change(format!("{member} `{name}` added"), added);
+        }
+    }
+}
+
+fn by_name(entries: &[ScSpecEntry]) -> BTreeMap<String, &ScSpecEntry> {
+    entries
+        .iter()
+        .map(|entry| {
+            let name = match entry {
+                ScSpecEntry::FunctionV0(f) => f.name.to_utf8_string_lossy(),
+                ScSpecEntry::UdtStructV0(s) => s.name.to_utf8_string_lossy(),
+                ScSpecEntry::UdtUnionV0(u) => u.name.to_utf8_string_lossy(),
+                ScSpecEntry::UdtEnumV0(e) => e.name.to_utf8_string_lossy(),
+                ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_utf8_string_lossy(),
+            };
+            (name, entry)
+        })
+        .collect()
+}
+
+fn kind_name(entry: &ScSpecEntry) -> &'static str {
+    match entry {
+        ScSpecEntry::FunctionV0(_) => "function",
+        ScSpecEntry::UdtStructV0(_) => "struct",
+        ScSpecEntry::UdtUnionV0(_) => "union",
+        ScSpecEntry::UdtEnumV0(_) => "enum",
+        ScSpecEntry::UdtErrorEnumV0(_) => "error enum",
+    }
+}
+
+/// Removing a function or error breaks callers, removing a type breaks the values stored with it.
+fn removed_impact(entry: &ScSpecEntry) -> Impact {
+    match entry {
+        ScSpecEntry::FunctionV0(_) | ScSpecEntry::UdtErrorEnumV0(_) => Impact::Breaking,
+        _ => Impact::Storage,
+    }
+}
+
+fn union_case(case: &ScSpecUdtUnionCaseV0) -> (String, String) {
+    match case {
+        ScSpecUdtUnionCaseV0::VoidV0(v) => (v.name.to_utf8_string_lossy(), "()".to_string()),
+        ScSpecUdtUnionCaseV0::TupleV0(t) => (
+            t.name.to_utf8_string_lossy(),
+            format!(
+                "({})",
+                t.type_.iter().map(type_name).collect::<Vec<_>>().join(", ")
+            ),
+        ),
+    }
+}
+
+/// Name of a type as written in a contract, e.g. `Vec<Address>`.
+fn type_name(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Option(t) => format!("Option<{}>", type_name(&t.value_type)),
+        ScSpecTypeDef::Vec(t) => format!("Vec<{}>", type_name(&t.element_type)),
+        ScSpecTypeDef::Map(t) => format!(
+            "Map<{}, {}>",
+            type_name(&t.key_type),
+            type_name(&t.value_type)
+        ),
+        ScSpecTypeDef::Result(t) => format!(
+            "Result<{}, {}>",
+            type_name(&t.ok_type),
+            type_name(&t.error_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "({})",
+            t.value_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(t) => format!("BytesN<{}>", t.n),
+        ScSpecTypeDef::Udt(t) => t.name.to_utf8_string_lossy(),
+        ScSpecTypeDef::Void => "()".to_string(),
+        ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128
+        | ScSpecTypeDef::U256
+        | ScSpecTypeDef::I256
+        | ScSpecTypeDef::Bool => type_.name().to_lowercase(),
+        other => other.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScSpecFunctionInputV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0};
+
+    fn function(name: &str, inputs: &[(&str, ScSpecTypeDef)]) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            inputs: inputs
+                .iter()
+                .map(|(name, type_)| ScSpecFunctionInputV0 {
+                    doc: "".try_into().unwrap(),
+                    name: (*name).try_into().unwrap(),
+                    type_: type_.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: vec![].try_into().unwrap(),
+        })
+    }
+
+    fn state(count: ScSpecTypeDef) -> ScSpecEntry {
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "State".try_into().unwrap(),
+            fields: vec![ScSpecUdtStructFieldV0 {
+                doc: "".try_into().unwrap(),
+                name: "count".try_into().unwrap(),
+                type_: count,
+            }]
+            .try_into()
+            .unwrap(),
+        })
+    }
+
+    #[test]
+    fn reports_breaking_and_storage_changes() {
+        let old = [
+            function("inc", &[("by", ScSpecTypeDef::U32)]),
+            function("reset", &[]),
+            state(ScSpecTypeDef::U32),
+        ];
+        let new = [
+            function("inc", &[("by", ScSpecTypeDef::U64)]),
+            function("get", &[]),
+            state(ScSpecTypeDef::U64),
+        ];
+        let lines = diff(&old, &new)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "~ struct `State`: field `count` u32 -> u64 (breaks stored values)",
+                "~ function `inc`: argument `by` type u32 -> u64 (breaking)",
+                "- function `reset` (breaking)",
+                "+ function `get`",
+            ]
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::commands::global;
+
+pub mod replay;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Export the events of a contract over a range of ledgers as JSON lines, decoded with its
+    /// spec, to stdout, a file, or a webhook, e.g. to bootstrap an indexer
+    Replay(replay::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Replay(#[from] replay::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Replay(replay) => replay.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
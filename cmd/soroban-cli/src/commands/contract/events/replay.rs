@@ -0,0 +1,299 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use clap::{arg, command, Parser};
+use jsonrpsee_core::params::ObjectParams;
+use serde_json::{json, Value};
+use soroban_spec_tools::Spec;
+
+use crate::{
+    commands::global,
+    config::{self, locator, network},
+    get_spec::get_remote_contract_spec,
+    pagination::{self, Event, EventsPage},
+    print::Print,
+    utils::http,
+    xdr,
+};
+
+/// Largest number of events requested per page.
+const PAGE_LIMIT: usize = 1000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    JsonRpc(#[from] jsonrpsee_core::Error),
+    #[error("writing {path}: {error}")]
+    Write { path: PathBuf, error: io::Error },
+    #[error("posting event {id} to the webhook failed after {attempts} attempts: {error}, continue with {resume}")]
+    Webhook {
+        id: String,
+        attempts: u32,
+        error: String,
+        resume: String,
+    },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract id or alias of the contract
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: config::ContractAddress,
+    /// First ledger to replay the events of
+    #[arg(long, conflicts_with = "cursor", required_unless_present = "cursor")]
+    pub start_ledger: Option<u32>,
+    /// Cursor to continue replaying from, as printed when a replay stops
+    #[arg(long)]
+    pub cursor: Option<String>,
+    #[command(flatten)]
+    pub pagination: pagination::Args,
+    /// Topic filters, as with `stellar events --topic`
+    #[arg(long = "topic", num_args = 1..=5)]
+    pub topic_filters: Vec<String>,
+    /// Write the events to this file instead of stdout
+    #[arg(long, conflicts_with = "webhook")]
+    pub out_file: Option<PathBuf>,
+    /// POST each event as JSON to this URL instead of printing it
+    #[arg(long)]
+    pub webhook: Option<url::Url>,
+    /// Attempts to POST an event to the webhook before giving up
+    #[arg(long, default_value = "5", requires = "webhook")]
+    pub attempts: u32,
+    /// Do not decode events into named fields with the spec of the contract
+    #[arg(long)]
+    pub no_decode: bool,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+/// Where the events go.
+enum Sink {
+    Writer(Box<dyn Write>),
+    Webhook(url::Url),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&self.config.locator, &network.network_passphrase)?;
+        let spec = if self.no_decode {
+            None
+        } else {
+            self.spec(&contract_id, global_args).await
+        };
+        let mut sink = match (&self.out_file, &self.webhook) {
+            (Some(path), _) => Sink::Writer(Box::new(BufWriter::new(
+                File::create(path).map_err(|error| self.write_error(error))?,
+            ))),
+            (None, Some(url)) => Sink::Webhook(url.clone()),
+            (None, None) => Sink::Writer(Box::new(io::stdout())),
+        };
+        let mut filter = json!({ "type": "contract", "contractIds": [contract_id.to_string()] });
+        if !self.topic_filters.is_empty() {
+            filter["topics"] = self
+                .topic_filters
+                .iter()
+                .map(|topic| topic.split(',').collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+                .into();
+        }
+        let mut limiter = self.pagination.rate_limiter();
+        let mut cursor = self.cursor.clone();
+        let mut replayed = 0;
+        'pages: loop {
+            let mut params = ObjectParams::new();
+            let mut page_params = json!({ "limit": PAGE_LIMIT });
+            match &cursor {
+                Some(cursor) => page_params["cursor"] = json!(cursor),
+                None => params.insert("startLedger", self.start_ledger.unwrap_or_default())?,
+            }
+            params.insert("filters", [&filter])?;
+            params.insert("pagination", page_params)?;
+            limiter.wait().await;
+            let page: EventsPage = network.rpc_request("getEvents", params).await?;
+            if page.events.is_empty() {
+                break;
+            }
+            let page_cursor = page
+                .cursor
+                .clone()
+                .or_else(|| page.events.last().map(|e| e.id.clone()));
+            for event in page.events {
+                if self.pagination.past_end(event.ledger) {
+                    break 'pages;
+                }
+                let id = event.id.clone();
+                let json = normalize(event, spec.as_ref())?;
+                // Resume from the last event delivered, so none is skipped or sent twice
+                let resume = match &cursor {
+                    Some(cursor) => format!("--cursor {cursor}"),
+                    None => format!("--start-ledger {}", self.start_ledger.unwrap_or_default()),
+                };
+                self.deliver(&mut sink, &json, &id, &resume, &print).await?;
+                cursor = Some(id);
+                replayed += 1;
+            }
+            cursor = page_cursor.or(cursor);
+        }
+        if let Sink::Writer(writer) = &mut sink {
+            writer.flush().map_err(|error| self.write_error(error))?;
+        }
+        match &cursor {
+            Some(cursor) => print.checkln(format!(
+                "Replayed {replayed} events, continue with --cursor {cursor}"
+            )),
+            None => print.checkln(format!("Replayed {replayed} events")),
+        }
+        Ok(())
+    }
+
+    async fn deliver(
+        &self,
+        sink: &mut Sink,
+        json: &Value,
+        id: &str,
+        resume: &str,
+        print: &Print,
+    ) -> Result<(), Error> {
+        match sink {
+            Sink::Writer(writer) => {
+                serde_json::to_writer(&mut *writer, json)?;
+                writeln!(writer).map_err(|error| self.write_error(error))?;
+            }
+            Sink::Webhook(url) => {
+                let mut attempt = 1;
+                loop {
+                    let result = http::client()
+                        .post(url.clone())
+                        .json(json)
+                        .send()
+                        .await
+                        .and_then(reqwest::Response::error_for_status);
+                    match result {
+                        Ok(_) => break,
+                        Err(error) if attempt >= self.attempts => {
+                            return Err(Error::Webhook {
+                                id: id.to_string(),
+                                attempts: attempt,
+                                error: error.to_string(),
+                                resume: resume.to_string(),
+                            });
+                        }
+                        Err(error) => {
+                            let backoff = Duration::from_millis(500 * 2u64.pow(attempt - 1));
+                            print.warnln(format!(
+                                "Posting event {id} failed: {error}, retrying in {}",
+                                humantime::format_duration(backoff)
+                            ));
+                            tokio::time::sleep(backoff).await;
+                            attempt += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Spec of the contract, fetched from the network.
+    async fn spec(
+        &self,
+        contract_id: &stellar_strkey::Contract,
+        global_args: &global::Args,
+    ) -> Option<Spec> {
+        let entries = get_remote_contract_spec(
+            &contract_id.0,
+            &self.config.locator,
+            &self.config.network,
+            Some(global_args),
+            None,
+        )
+        .await
+        .map_err(|e| tracing::debug!("cannot fetch spec of {contract_id}: {e}"))
+        .ok()?;
+        Some(Spec::new(entries))
+    }
+
+    fn write_error(&self, error: io::Error) -> Error {
+        Error::Write {
+            path: self
+                .out_file
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("<stdout>")),
+            error,
+        }
+    }
+}
+
+/// The event as JSON, with its topics and value decoded from XDR, and named with the spec when it
+/// has a schema for the event.
+fn normalize(event: Event, spec: Option<&Spec>) -> Result<Value, Error> {
+    let (topics, value) = event.decode()?;
+    let mut json = json!({
+        "id": event.id,
+        "ledger": event.ledger,
+        "ledger_closed_at": event.ledger_closed_at,
+        "contract_id": event.contract_id,
+        "tx_hash": event.tx_hash,
+        "topics": topics,
+        "value": value,
+    });
+    if let Some(decoded) = spec.and_then(|spec| {
+        spec.decode_event(&topics, &value)
+            .map_err(|e| tracing::debug!("cannot decode event {}: {e}", event.id))
+            .ok()
+    }) {
+        json["decoded"] = decoded;
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{Limits, ScVal, WriteXdr};
+
+    #[test]
+    fn normalizes_events() {
+        let topic = ScVal::Symbol("transfer".try_into().unwrap());
+        let event = Event {
+            id: "0000000042-0000000001".to_string(),
+            ledger: 42,
+            ledger_closed_at: "2024-01-01T00:00:00Z".to_string(),
+            contract_id: "CA".to_string(),
+            tx_hash: Some("ab".to_string()),
+            topic: vec![topic.to_xdr_base64(Limits::none()).unwrap()],
+            value: ScVal::U32(7).to_xdr_base64(Limits::none()).unwrap(),
+        };
+        assert_eq!(
+            normalize(event, None).unwrap(),
+            json!({
+                "id": "0000000042-0000000001",
+                "ledger": 42,
+                "ledger_closed_at": "2024-01-01T00:00:00Z",
+                "contract_id": "CA",
+                "tx_hash": "ab",
+                "topics": [{ "symbol": "transfer" }],
+                "value": { "u32": 7 },
+            })
+        );
+    }
+}
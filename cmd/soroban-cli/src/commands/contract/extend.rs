@@ -2,9 +2,9 @@ use std::{fmt::Debug, path::Path, str::FromStr};
 
 use crate::xdr::{
     Error as XdrError, ExtendFootprintTtlOp, ExtensionPoint, LedgerEntry, LedgerEntryChange,
-    LedgerEntryData, LedgerFootprint, Limits, Memo, Operation, OperationBody, Preconditions,
-    SequenceNumber, SorobanResources, SorobanTransactionData, Transaction, TransactionExt,
-    TransactionMeta, TransactionMetaV3, TtlEntry, WriteXdr,
+    LedgerEntryData, LedgerFootprint, Limits, Memo, Operation, OperationBody, SequenceNumber,
+    SorobanResources, SorobanTransactionData, Transaction, TransactionExt, TransactionMeta,
+    TransactionMetaV3, TtlEntry, WriteXdr,
 };
 use clap::{command, Parser};
 
@@ -15,7 +15,7 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, locator, network},
+    config::{self, data, locator, network, Submitted},
     key, rpc, wasm, Pwd,
 };
 
@@ -36,6 +36,8 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
 }
 
 impl FromStr for Cmd {
@@ -55,6 +57,8 @@ impl Pwd for Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error("parsing key {key}: {error}")]
     CannotParseKey {
         key: String,
@@ -85,14 +89,20 @@ pub enum Error {
     Network(#[from] network::Error),
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
 }
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
-    pub async fn run(&self) -> Result<(), Error> {
-        let res = self.run_against_rpc_server(None, None).await?.to_envelope();
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let res = self
+            .run_against_rpc_server(Some(global_args), None)
+            .await?
+            .to_envelope();
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
             TxnEnvelopeResult::Res(ttl_ledger) => {
                 if self.ttl_ledger_only {
                     println!("{ttl_ledger}");
@@ -138,13 +148,17 @@ impl NetworkRunnable for Cmd {
         let account_details = client
             .get_account(&source_account.clone().to_string())
             .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence = config.sequence_number_after(
+            &account_details.account_id,
+            account_details.seq_num.into(),
+            self.fee.seq_strategy,
+        )?;
 
         let tx = Box::new(Transaction {
             source_account,
-            fee: self.fee.fee,
-            seq_num: SequenceNumber(sequence + 1),
-            cond: Preconditions::None,
+            fee: self.fee.inclusion_fee(&network, true).await?,
+            seq_num: SequenceNumber(sequence),
+            cond: self.preconditions.preconditions()?,
             memo: Memo::None,
             operations: vec![Operation {
                 source_account: None,
@@ -171,13 +185,20 @@ impl NetworkRunnable for Cmd {
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let tx = simulate_and_assemble_transaction(&client, &tx)
+        let mut tx = simulate_and_assemble_transaction(&client, &tx)
             .await?
             .transaction()
             .clone();
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(tx).await?)
-            .await?;
+        let reservation = config.reserve_sequence_number(&mut tx, &self.fee)?;
+        let tx_env = config.sign_with_local_key(tx).await?;
+        let res = match config
+            .submit(&network, &client, tx_env, reservation, &self.fee, args)
+            .await?
+        {
+            Submitted::DryRun(tx_env) => return Ok(TxnResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnResult::Submitted(hash)),
+            Submitted::Done(res) => *res,
+        };
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
@@ -107,7 +107,7 @@ impl NetworkRunnable for Cmd {
     type Result = Vec<u8>;
     async fn run_against_rpc_server(
         &self,
-        _args: Option<&global::Args>,
+        args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<Vec<u8>, Error> {
         let network = config.map_or_else(|| self.network(), |c| Ok(c.get_network()?))?;
@@ -116,6 +116,7 @@ impl NetworkRunnable for Cmd {
                 .contract_id
                 .resolve_contract_id(&self.locator, &network.network_passphrase)?,
             &network,
+            args.is_some_and(|a| a.no_cache),
         )
         .await?)
     }
@@ -0,0 +1,533 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{command, Parser};
+use heck::ToSnakeCase;
+
+use crate::{
+    commands::global,
+    print::Print,
+    wasm,
+    xdr::{
+        ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtStructV0,
+        ScSpecUdtUnionCaseV0, StringM,
+    },
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub wasm: wasm::Args,
+    /// Directory to write the test module to, the `tests` directory of the contract's crate
+    #[arg(long, default_value = "tests")]
+    pub output_dir: PathBuf,
+    /// Name of the test module, the name of the Wasm file by default
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Overwrite the test module if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error("{0:?} already exists, pass --overwrite to replace it")]
+    Exists(PathBuf),
+    #[error("writing {path:?}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("cannot get the name of the test module from {0:?}, pass --name")]
+    NoName(PathBuf),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let spec = self.wasm.parse()?.spec;
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => self
+                .wasm
+                .wasm
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| Error::NoName(self.wasm.wasm.clone()))?
+                .to_snake_case(),
+        };
+        let path = self.output_dir.join(format!("{name}.rs"));
+        if path.exists() && !self.overwrite {
+            return Err(Error::Exists(path));
+        }
+        let write_error = |error| Error::Write {
+            path: path.clone(),
+            error,
+        };
+        fs::create_dir_all(&self.output_dir).map_err(write_error)?;
+        let code = generate(&spec, &self.wasm_import_path());
+        fs::write(&path, code).map_err(write_error)?;
+        print.checkln(format!("Wrote tests of {name} to {path:?}"));
+        print.infoln(format!(
+            "The tests import {:?}, build the contract before running them",
+            self.wasm.wasm
+        ));
+        Ok(())
+    }
+
+    /// Path of the Wasm as `contractimport!` expects it, relative to the crate the tests are in,
+    /// which is the parent of the output directory.
+    fn wasm_import_path(&self) -> String {
+        let absolute = |path: &Path| {
+            std::path::absolute(path)
+                .ok()
+                .or_else(|| path.canonicalize().ok())
+                .unwrap_or_else(|| path.to_path_buf())
+        };
+        let wasm = absolute(&self.wasm.wasm);
+        let output_dir = absolute(&self.output_dir);
+        let path = output_dir
+            .parent()
+            .and_then(|crate_dir| pathdiff::diff_paths(&wasm, crate_dir))
+            .unwrap_or(wasm);
+        path.to_string_lossy().replace('\\', "/")
+    }
+}
+
+/// A Rust integration test module with a test calling each function of the contract with
+/// placeholder arguments, and a builder of each type of the arguments.
+pub fn generate(spec: &[ScSpecEntry], wasm: &str) -> String {
+    let udts = spec
+        .iter()
+        .filter_map(|entry| udt_name(entry).map(|name| (name, entry)))
+        .collect::<BTreeMap<_, _>>();
+    let functions = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some(f),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let constructor = functions
+        .iter()
+        .find(|f| f.name.to_utf8_string_lossy() == "__constructor");
+
+    let mut used = Vec::new();
+    for f in &functions {
+        for input in f.inputs.iter() {
+            used_udts(&input.type_, &udts, &mut used);
+        }
+    }
+
+    let mut tests = String::new();
+    for f in functions
+        .iter()
+        .filter(|f| !f.name.to_utf8_string_lossy().starts_with("__"))
+    {
+        tests.push('\n');
+        tests.push_str(&test(f, &udts));
+    }
+    for name in used {
+        tests.push('\n');
+        tests.push_str(&builder(udts[&name], &udts));
+    }
+    let (setup_lets, setup_args) = constructor.map_or_else(
+        || (String::new(), "()".to_string()),
+        |f| (bind_inputs(f, &udts), constructor_args(f)),
+    );
+    let imports = if tests.contains("Address::generate") || setup_lets.contains("Address::generate")
+    {
+        "{testutils::Address as _, Env}"
+    } else {
+        "Env"
+    };
+
+    format!(
+        r#"//! Tests of the contract's functions, generated by `stellar contract generate-tests`.
+//!
+//! Replace the placeholder arguments with meaningful ones and check the results.
+use soroban_sdk::{imports};
+
+mod contract {{
+    soroban_sdk::contractimport!(file = "{wasm}");
+}}
+
+fn setup(env: &Env) -> contract::Client<'_> {{
+    env.mock_all_auths();
+{setup_lets}    let contract_id = env.register(contract::WASM, {setup_args});
+    contract::Client::new(env, &contract_id)
+}}
+{tests}"#
+    )
+}
+
+fn test(f: &ScSpecFunctionV0, udts: &BTreeMap<String, &ScSpecEntry>) -> String {
+    let name = f.name.to_utf8_string_lossy();
+    let mut code = String::new();
+    for line in doc_lines(&f.doc) {
+        let _ = writeln!(code, "/// {line}");
+    }
+    let _ = writeln!(code, "#[test]\nfn {name}() {{");
+    code.push_str("    let env = Env::default();\n    let client = setup(&env);\n");
+    code.push_str(&bind_inputs(f, udts));
+    let args = f
+        .inputs
+        .iter()
+        .map(|input| format!("&{}", local_name(input)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match f.outputs.first() {
+        None => {
+            let _ = writeln!(code, "    client.{name}({args});");
+            code.push_str("    // TODO: check the effects of the call\n");
+        }
+        Some(output) => {
+            // The client panics on errors, returning the value of `Ok`.
+            let output = match output {
+                ScSpecTypeDef::Result(r) => &r.ok_type,
+                output => output,
+            };
+            let _ = writeln!(
+                code,
+                "    let _result: {} = client.{name}({args});",
+                type_ident(output)
+            );
+            code.push_str("    // TODO: check the result\n");
+        }
+    }
+    code.push_str("}\n");
+    code
+}
+
+/// A typed `let` of a placeholder value for each input of `f`, so that values such as `None` or
+/// `Vec::new` have a type wherever they are passed.
+fn bind_inputs(f: &ScSpecFunctionV0, udts: &BTreeMap<String, &ScSpecEntry>) -> String {
+    let mut code = String::new();
+    for input in f.inputs.iter() {
+        let _ = writeln!(
+            code,
+            "    let {}: {} = {};",
+            local_name(input),
+            type_ident(&input.type_),
+            value(&input.type_, udts),
+        );
+    }
+    code
+}
+
+fn constructor_args(f: &ScSpecFunctionV0) -> String {
+    tuple(f.inputs.iter().map(local_name).collect())
+}
+
+/// Name of the local holding the value of `input`, renamed if it would shadow a local of the
+/// generated code.
+fn local_name(input: &ScSpecFunctionInputV0) -> String {
+    let name = input.name.to_utf8_string_lossy();
+    if ["env", "client", "contract_id"].contains(&name.as_str()) {
+        format!("{name}_arg")
+    } else {
+        name
+    }
+}
+
+fn tuple(items: Vec<String>) -> String {
+    match items.as_slice() {
+        [item] => format!("({item},)"),
+        items => format!("({})", items.join(", ")),
+    }
+}
+
+/// Builder of a value of a user defined type, the first case of enums and unions.
+fn builder(entry: &ScSpecEntry, udts: &BTreeMap<String, &ScSpecEntry>) -> String {
+    let name = udt_name(entry).unwrap_or_default();
+    let body = match entry {
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { fields, .. }) => {
+            let tuple = fields
+                .iter()
+                .all(|f| f.name.to_utf8_string_lossy().parse::<usize>().is_ok());
+            if tuple {
+                let values = fields
+                    .iter()
+                    .map(|f| value(&f.type_, udts))
+                    .collect::<Vec<_>>();
+                format!("contract::{name}({})", values.join(", "))
+            } else {
+                let mut body = format!("contract::{name} {{\n");
+                for f in fields.iter() {
+                    let _ = writeln!(
+                        body,
+                        "        {}: {},",
+                        f.name.to_utf8_string_lossy(),
+                        value(&f.type_, udts)
+                    );
+                }
+                body.push_str("    }");
+                body
+            }
+        }
+        ScSpecEntry::UdtUnionV0(union) => match union.cases.first() {
+            Some(ScSpecUdtUnionCaseV0::VoidV0(case)) => {
+                format!("contract::{name}::{}", case.name.to_utf8_string_lossy())
+            }
+            Some(ScSpecUdtUnionCaseV0::TupleV0(case)) => format!(
+                "contract::{name}::{}({})",
+                case.name.to_utf8_string_lossy(),
+                case.type_
+                    .iter()
+                    .map(|t| value(t, udts))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            None => "unimplemented!()".to_string(),
+        },
+        ScSpecEntry::UdtEnumV0(e) => e.cases.first().map_or_else(
+            || "unimplemented!()".to_string(),
+            |case| format!("contract::{name}::{}", case.name.to_utf8_string_lossy()),
+        ),
+        ScSpecEntry::UdtErrorEnumV0(e) => e.cases.first().map_or_else(
+            || "unimplemented!()".to_string(),
+            |case| format!("contract::{name}::{}", case.name.to_utf8_string_lossy()),
+        ),
+        ScSpecEntry::FunctionV0(_) => unreachable!("functions are not types"),
+    };
+    let env = if body.contains("&env") { "env" } else { "_env" };
+    format!(
+        "fn {}({env}: &Env) -> contract::{name} {{\n    {body}\n}}\n",
+        builder_name(&name)
+    )
+}
+
+fn builder_name(udt: &str) -> String {
+    format!("build_{}", udt.to_snake_case())
+}
+
+fn udt_name(entry: &ScSpecEntry) -> Option<String> {
+    let name = match entry {
+        ScSpecEntry::UdtStructV0(s) => &s.name,
+        ScSpecEntry::UdtUnionV0(u) => &u.name,
+        ScSpecEntry::UdtEnumV0(e) => &e.name,
+        ScSpecEntry::UdtErrorEnumV0(e) => &e.name,
+        ScSpecEntry::FunctionV0(_) => return None,
+    };
+    Some(name.to_utf8_string_lossy())
+}
+
+/// User defined types that values of `type_` are built from, each after the ones it contains.
+fn used_udts(type_: &ScSpecTypeDef, udts: &BTreeMap<String, &ScSpecEntry>, used: &mut Vec<String>) {
+    match type_ {
+        ScSpecTypeDef::Udt(udt) => {
+            let name = udt.name.to_utf8_string_lossy();
+            if used.contains(&name) {
+                return;
+            }
+            used.push(name.clone());
+            let contained: Vec<&ScSpecTypeDef> = match udts.get(&name) {
+                Some(ScSpecEntry::UdtStructV0(s)) => s.fields.iter().map(|f| &f.type_).collect(),
+                Some(ScSpecEntry::UdtUnionV0(u)) => match u.cases.first() {
+                    Some(ScSpecUdtUnionCaseV0::TupleV0(case)) => case.type_.iter().collect(),
+                    _ => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+            for t in contained {
+                used_udts(t, udts, used);
+            }
+        }
+        ScSpecTypeDef::Result(r) => used_udts(&r.ok_type, udts, used),
+        ScSpecTypeDef::Tuple(t) => {
+            for t in t.value_types.iter() {
+                used_udts(t, udts, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The Rust type of `type_` as `contractimport!` generates it.
+fn type_ident(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Val => "soroban_sdk::Val".to_string(),
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Void => "()".to_string(),
+        ScSpecTypeDef::Error => "soroban_sdk::Error".to_string(),
+        ScSpecTypeDef::U32 => "u32".to_string(),
+        ScSpecTypeDef::I32 => "i32".to_string(),
+        ScSpecTypeDef::U64 => "u64".to_string(),
+        ScSpecTypeDef::I64 => "i64".to_string(),
+        ScSpecTypeDef::Timepoint => "soroban_sdk::Timepoint".to_string(),
+        ScSpecTypeDef::Duration => "soroban_sdk::Duration".to_string(),
+        ScSpecTypeDef::U128 => "u128".to_string(),
+        ScSpecTypeDef::I128 => "i128".to_string(),
+        ScSpecTypeDef::U256 => "soroban_sdk::U256".to_string(),
+        ScSpecTypeDef::I256 => "soroban_sdk::I256".to_string(),
+        ScSpecTypeDef::Bytes => "soroban_sdk::Bytes".to_string(),
+        ScSpecTypeDef::String => "soroban_sdk::String".to_string(),
+        ScSpecTypeDef::Symbol => "soroban_sdk::Symbol".to_string(),
+        ScSpecTypeDef::Address => "soroban_sdk::Address".to_string(),
+        ScSpecTypeDef::Option(o) => format!("Option<{}>", type_ident(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            type_ident(&r.ok_type),
+            type_ident(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("soroban_sdk::Vec<{}>", type_ident(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "soroban_sdk::Map<{}, {}>",
+            type_ident(&m.key_type),
+            type_ident(&m.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => tuple(t.value_types.iter().map(type_ident).collect()),
+        ScSpecTypeDef::BytesN(b) => format!("soroban_sdk::BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => format!("contract::{}", u.name.to_utf8_string_lossy()),
+    }
+}
+
+/// Placeholder value of `type_`, built with `env`.
+fn value(type_: &ScSpecTypeDef, udts: &BTreeMap<String, &ScSpecEntry>) -> String {
+    match type_ {
+        ScSpecTypeDef::Val => "soroban_sdk::Val::VOID.into()".to_string(),
+        ScSpecTypeDef::Bool => "false".to_string(),
+        ScSpecTypeDef::Void => "()".to_string(),
+        ScSpecTypeDef::Error => "soroban_sdk::Error::from_contract_error(0)".to_string(),
+        ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128 => "0".to_string(),
+        ScSpecTypeDef::Timepoint => "soroban_sdk::Timepoint::from_unix(&env, 0)".to_string(),
+        ScSpecTypeDef::Duration => "soroban_sdk::Duration::from_seconds(&env, 0)".to_string(),
+        ScSpecTypeDef::U256 => "soroban_sdk::U256::from_u32(&env, 0)".to_string(),
+        ScSpecTypeDef::I256 => "soroban_sdk::I256::from_i32(&env, 0)".to_string(),
+        ScSpecTypeDef::Bytes => "soroban_sdk::Bytes::new(&env)".to_string(),
+        ScSpecTypeDef::String => "soroban_sdk::String::from_str(&env, \"\")".to_string(),
+        ScSpecTypeDef::Symbol => "soroban_sdk::Symbol::new(&env, \"symbol\")".to_string(),
+        ScSpecTypeDef::Address => "soroban_sdk::Address::generate(&env)".to_string(),
+        ScSpecTypeDef::Option(_) => "None".to_string(),
+        ScSpecTypeDef::Result(r) => format!("Ok({})", value(&r.ok_type, udts)),
+        ScSpecTypeDef::Vec(_) => "soroban_sdk::Vec::new(&env)".to_string(),
+        ScSpecTypeDef::Map(_) => "soroban_sdk::Map::new(&env)".to_string(),
+        ScSpecTypeDef::Tuple(t) => tuple(t.value_types.iter().map(|t| value(t, udts)).collect()),
+        ScSpecTypeDef::BytesN(b) => {
+            format!("soroban_sdk::BytesN::from_array(&env, &[0; {}])", b.n)
+        }
+        ScSpecTypeDef::Udt(u) => {
+            let name = u.name.to_utf8_string_lossy();
+            if udts.contains_key(&name) {
+                format!("{}(&env)", builder_name(&name))
+            } else {
+                "unimplemented!()".to_string()
+            }
+        }
+    }
+}
+
+fn doc_lines(doc: &StringM<1024>) -> Vec<String> {
+    doc.to_utf8_string_lossy()
+        .lines()
+        .map(str::trim_end)
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult, ScSpecTypeVec, VecM};
+
+    const WASM: &[u8] = include_bytes!(
+        "../../../../../target/wasm32-unknown-unknown/test-wasms/test_custom_types.wasm"
+    );
+
+    #[test]
+    fn generates_a_test_per_function() {
+        let spec = soroban_spec::read::from_wasm(WASM).unwrap();
+        let code = generate(&spec, "../target/test_custom_types.wasm");
+        assert!(code.contains(r#"contractimport!(file = "../target/test_custom_types.wasm")"#));
+        assert!(code.contains("env.register(contract::WASM, ())"));
+        assert!(code.contains(
+            "fn hello() {\n    let env = Env::default();\n    let client = setup(&env);\n    let hello: soroban_sdk::Symbol = soroban_sdk::Symbol::new(&env, \"symbol\");\n    let _result: soroban_sdk::Symbol = client.hello(&hello);"
+        ));
+        assert!(code.contains("let strukt: contract::Test = build_test(&env);"));
+        assert!(code.contains("fn build_test(env: &Env) -> contract::Test {"));
+    }
+
+    fn function(name: &str, inputs: &[(&str, ScSpecTypeDef)]) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: name.try_into().unwrap(),
+            inputs: inputs
+                .iter()
+                .map(|(name, type_)| ScSpecFunctionInputV0 {
+                    doc: StringM::default(),
+                    name: (*name).try_into().unwrap(),
+                    type_: type_.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: VecM::default(),
+        })
+    }
+
+    #[test]
+    fn types_the_constructor_arguments() {
+        let u32_ = || Box::new(ScSpecTypeDef::U32);
+        let spec = [
+            function(
+                "__constructor",
+                &[
+                    (
+                        "admin",
+                        ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                            value_type: Box::new(ScSpecTypeDef::Address),
+                        })),
+                    ),
+                    (
+                        "limits",
+                        ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                            element_type: u32_(),
+                        })),
+                    ),
+                    (
+                        "fees",
+                        ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                            key_type: Box::new(ScSpecTypeDef::Symbol),
+                            value_type: u32_(),
+                        })),
+                    ),
+                    (
+                        "client",
+                        ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+                            ok_type: u32_(),
+                            error_type: Box::new(ScSpecTypeDef::Error),
+                        })),
+                    ),
+                ],
+            ),
+            function("set", &[("client", ScSpecTypeDef::U32)]),
+        ];
+        let code = generate(&spec, "contract.wasm");
+        assert!(code.contains(
+            "fn setup(env: &Env) -> contract::Client<'_> {
+    env.mock_all_auths();
+    let admin: Option<soroban_sdk::Address> = None;
+    let limits: soroban_sdk::Vec<u32> = soroban_sdk::Vec::new(&env);
+    let fees: soroban_sdk::Map<soroban_sdk::Symbol, u32> = soroban_sdk::Map::new(&env);
+    let client_arg: Result<u32, soroban_sdk::Error> = Ok(0);
+    let contract_id = env.register(contract::WASM, (admin, limits, fees, client_arg));"
+        ));
+        assert!(code.contains(
+            "    let client = setup(&env);
+    let client_arg: u32 = 0;
+    client.set(&client_arg);"
+        ));
+    }
+}
@@ -6,9 +6,12 @@ use soroban_spec_tools::contract;
 use soroban_spec_tools::contract::Spec;
 
 use crate::{
-    commands::contract::info::{
-        env_meta::Error::{NoEnvMetaPresent, NoSACEnvMeta},
-        shared::{self, fetch_wasm, MetasInfoOutput},
+    commands::{
+        contract::info::{
+            env_meta::Error::{NoEnvMetaPresent, NoSACEnvMeta},
+            shared::{self, fetch_wasm, MetasInfoOutput},
+        },
+        global,
     },
     xdr::{ScEnvMetaEntry, ScEnvMetaEntryInterfaceVersion},
 };
@@ -37,8 +40,8 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<String, Error> {
-        let bytes = fetch_wasm(&self.common).await?;
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let bytes = fetch_wasm(&self.common, global_args).await?;
 
         let Some(bytes) = bytes else {
             return Err(NoSACEnvMeta());
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use crate::commands::contract::info::interface::Error::NoInterfacePresent;
 use crate::commands::contract::info::shared;
 use crate::commands::contract::info::shared::fetch_wasm;
+use crate::commands::global;
 use clap::{command, Parser};
 use soroban_spec_rust::ToFormattedString;
 use soroban_spec_tools::contract;
@@ -43,8 +44,8 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<String, Error> {
-        let bytes = fetch_wasm(&self.common).await?;
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let bytes = fetch_wasm(&self.common, global_args).await?;
 
         let (base64, spec) = if bytes.is_none() {
             Spec::spec_to_base64(&soroban_sdk::token::StellarAssetSpec::spec_xdr())?
@@ -3,9 +3,8 @@ use std::path::PathBuf;
 use clap::arg;
 
 use crate::{
-    commands::contract::info::shared::Error::InvalidWasmHash,
+    commands::{contract::info::shared::Error::InvalidWasmHash, global},
     config::{self, locator, network},
-    utils::rpc::get_remote_wasm_from_hash,
     wasm::{self, Error::ContractIsStellarAsset},
     xdr,
 };
@@ -60,7 +59,8 @@ pub enum Error {
     Locator(#[from] locator::Error),
 }
 
-pub async fn fetch_wasm(args: &Args) -> Result<Option<Vec<u8>>, Error> {
+/// The Wasm of `args`, read from the cache of Wasm fetched from networks unless `--no-cache`.
+pub async fn fetch_wasm(args: &Args, global_args: &global::Args) -> Result<Option<Vec<u8>>, Error> {
     // Check if a local WASM file path is provided
     if let Some(path) = &args.wasm {
         // Read the WASM file and return its contents
@@ -80,15 +80,15 @@ pub async fn fetch_wasm(args: &Args) -> Result<Option<Vec<u8>>, Error> {
 
         let client = network.rpc_client()?;
 
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        network
+            .verify_rpc_passphrase(&client, global_args.no_cache)
             .await?;
 
-        get_remote_wasm_from_hash(&client, &hash).await?
+        wasm::fetch_from_hash(&client, &hash, global_args.no_cache).await?
     } else if let Some(contract_id) = &args.contract_id {
         let contract_id =
             contract_id.resolve_contract_id(&args.locator, &network.network_passphrase)?;
-        let res = wasm::fetch_from_contract(&contract_id, network).await;
+        let res = wasm::fetch_from_contract(&contract_id, network, global_args.no_cache).await;
         if let Some(ContractIsStellarAsset) = res.as_ref().err() {
             return Ok(None);
         }
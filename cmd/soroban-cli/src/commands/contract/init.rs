@@ -6,8 +6,8 @@ use std::{
     str,
 };
 
-use clap::Parser;
-use rust_embed::RustEmbed;
+use clap::{Parser, ValueEnum};
+use rust_embed::{EmbeddedFile, RustEmbed};
 
 use crate::{commands::global, error_on_use_of_removed_arg, print, utils};
 
@@ -24,10 +24,23 @@ pub struct Cmd {
 
     #[arg(
         long,
-        default_value = "hello-world",
-        long_help = "An optional flag to specify a new contract's name."
+        long_help = "An optional flag to specify a new contract's name. Defaults to the name of the template."
     )]
-    pub name: String,
+    pub name: Option<String>,
+
+    /// Example contract to start the new contract from
+    #[arg(long, value_enum, default_value_t)]
+    pub template: Template,
+
+    /// Add a frontend in `frontend/` that calls the contract through its generated TypeScript
+    /// bindings
+    #[arg(long, value_enum)]
+    pub frontend: Option<Frontend>,
+
+    /// Add GitHub Actions workflows in `.github/workflows/` to test the contracts on every push
+    /// and deploy the new contract to testnet
+    #[arg(long)]
+    pub with_github_actions: bool,
 
     // TODO: remove in future version (23+) https://github.com/stellar/stellar-cli/issues/1586
     #[arg(
@@ -52,6 +65,84 @@ pub struct Cmd {
     pub overwrite: bool,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Template {
+    /// Contract with a single function greeting its caller
+    #[default]
+    HelloWorld,
+    /// Fungible token implementing the token interface, with an admin that can mint
+    Token,
+    /// Constant product liquidity pool of two tokens
+    LiquidityPool,
+    /// Token balance that can be claimed by a set of claimants before or after a given time
+    Timelock,
+    /// Smart wallet authorizing transactions with a passkey
+    PasskeyWallet,
+}
+
+impl Template {
+    fn name(self) -> &'static str {
+        match self {
+            Template::HelloWorld => "hello-world",
+            Template::Token => "token",
+            Template::LiquidityPool => "liquidity-pool",
+            Template::Timelock => "timelock",
+            Template::PasskeyWallet => "passkey-wallet",
+        }
+    }
+
+    fn files(self) -> (TemplateFiles, TemplateFileGetter) {
+        match self {
+            Template::HelloWorld => (
+                Box::new(ContractTemplateFiles::iter()),
+                ContractTemplateFiles::get,
+            ),
+            Template::Token => (
+                Box::new(TokenTemplateFiles::iter()),
+                TokenTemplateFiles::get,
+            ),
+            Template::LiquidityPool => (
+                Box::new(LiquidityPoolTemplateFiles::iter()),
+                LiquidityPoolTemplateFiles::get,
+            ),
+            Template::Timelock => (
+                Box::new(TimelockTemplateFiles::iter()),
+                TimelockTemplateFiles::get,
+            ),
+            Template::PasskeyWallet => (
+                Box::new(PasskeyWalletTemplateFiles::iter()),
+                PasskeyWalletTemplateFiles::get,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Frontend {
+    /// React app built with Vite
+    React,
+    /// Astro site
+    Astro,
+}
+
+impl Frontend {
+    fn files(self) -> (TemplateFiles, TemplateFileGetter) {
+        match self {
+            Frontend::React => (
+                Box::new(ReactTemplateFiles::iter()),
+                ReactTemplateFiles::get,
+            ),
+            Frontend::Astro => (
+                Box::new(AstroTemplateFiles::iter()),
+                AstroTemplateFiles::get,
+            ),
+        }
+    }
+}
+
+type TemplateFiles = Box<dyn Iterator<Item = Cow<'static, str>>>;
+type TemplateFileGetter = fn(&str) -> Option<EmbeddedFile>;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("{0}: {1}")]
@@ -94,6 +185,34 @@ struct WorkspaceTemplateFiles;
 #[folder = "src/utils/contract-template"]
 struct ContractTemplateFiles;
 
+#[derive(RustEmbed)]
+#[folder = "src/utils/contract-templates/token"]
+struct TokenTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/contract-templates/liquidity-pool"]
+struct LiquidityPoolTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/contract-templates/timelock"]
+struct TimelockTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/contract-templates/passkey-wallet"]
+struct PasskeyWalletTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/frontend-templates/react"]
+struct ReactTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/frontend-templates/astro"]
+struct AstroTemplateFiles;
+
+#[derive(RustEmbed)]
+#[folder = "src/utils/github-actions-template"]
+struct GithubActionsTemplateFiles;
+
 struct Runner {
     args: Cmd,
     print: print::Print,
@@ -113,20 +232,40 @@ impl Runner {
             WorkspaceTemplateFiles::get,
         )?;
 
-        let contract_path = project_path.join("contracts").join(&self.args.name);
+        let contract_path = project_path.join("contracts").join(self.name());
         self.print
             .infoln(format!("Initializing contract at {contract_path:?}"));
 
         Self::create_dir_all(contract_path.as_path())?;
-        self.copy_template_files(
-            contract_path.as_path(),
-            &mut ContractTemplateFiles::iter(),
-            ContractTemplateFiles::get,
-        )?;
+        let (mut files, getter) = self.args.template.files();
+        self.copy_template_files(contract_path.as_path(), &mut files, getter)?;
+
+        if let Some(frontend) = self.args.frontend {
+            let frontend_path = project_path.join("frontend");
+            self.print
+                .infoln(format!("Initializing frontend at {frontend_path:?}"));
+            let (mut files, getter) = frontend.files();
+            self.copy_template_files(frontend_path.as_path(), &mut files, getter)?;
+        }
+
+        if self.args.with_github_actions {
+            self.copy_template_files(
+                project_path.join(".github").as_path(),
+                &mut GithubActionsTemplateFiles::iter(),
+                GithubActionsTemplateFiles::get,
+            )?;
+        }
 
         Ok(())
     }
 
+    fn name(&self) -> &str {
+        self.args
+            .name
+            .as_deref()
+            .unwrap_or(self.args.template.name())
+    }
+
     fn copy_template_files(
         &self,
         root_path: &Path,
@@ -165,9 +304,12 @@ impl Runner {
                 .map_err(Error::ConvertBytesToString)?
                 .to_string();
 
-            if is_toml {
-                let new_content = file_contents.replace("%contract-template%", &self.args.name);
-                file_contents = new_content;
+            for (placeholder, value) in [
+                ("%contract-template%", self.name().to_string()),
+                ("%contract-wasm%", self.name().replace('-', "_")),
+                ("%cli-version%", env!("CARGO_PKG_VERSION").to_string()),
+            ] {
+                file_contents = file_contents.replace(placeholder, &value);
             }
 
             if exists {
@@ -216,7 +358,10 @@ mod tests {
         let runner = Runner {
             args: Cmd {
                 project_path: project_dir.to_string_lossy().to_string(),
-                name: "hello_world".to_string(),
+                name: Some("hello_world".to_string()),
+                template: Template::HelloWorld,
+                frontend: None,
+                with_github_actions: false,
                 with_example: None,
                 frontend_template: None,
                 overwrite: false,
@@ -236,7 +381,10 @@ mod tests {
         let runner = Runner {
             args: Cmd {
                 project_path: project_dir.to_string_lossy().to_string(),
-                name: "contract2".to_string(),
+                name: Some("contract2".to_string()),
+                template: Template::HelloWorld,
+                frontend: None,
+                with_github_actions: false,
                 with_example: None,
                 frontend_template: None,
                 overwrite: false,
@@ -254,6 +402,54 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn test_init_template_with_frontend_and_github_actions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_dir = temp_dir.path().join(TEST_PROJECT_NAME);
+        let runner = Runner {
+            args: Cmd {
+                project_path: project_dir.to_string_lossy().to_string(),
+                name: None,
+                template: Template::LiquidityPool,
+                frontend: Some(Frontend::React),
+                with_github_actions: true,
+                with_example: None,
+                frontend_template: None,
+                overwrite: false,
+            },
+            print: print::Print::new(false),
+        };
+        runner.run().unwrap();
+
+        assert_base_template_files_exist(&project_dir);
+        assert_contract_files_exist(&project_dir, "liquidity-pool");
+        assert_contract_cargo_file_is_well_formed(&project_dir, "liquidity-pool");
+
+        let package_json = read_to_string(project_dir.join("frontend/package.json")).unwrap();
+        assert!(package_json.contains("release/liquidity_pool.wasm"));
+        assert!(package_json.contains("\"liquidity-pool\": \"file:./packages/liquidity-pool\""));
+        assert!(project_dir.join("frontend/src/App.tsx").exists());
+
+        let deploy = read_to_string(project_dir.join(".github/workflows/deploy.yml")).unwrap();
+        assert!(deploy.contains(&format!(
+            "stellar/stellar-cli@{}",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(project_dir.join(".github/workflows/test.yml").exists());
+
+        for entry in walkdir::WalkDir::new(&project_dir) {
+            let path = entry.unwrap().into_path();
+            if path.is_file() {
+                assert!(
+                    !read_to_string(&path).unwrap().contains("%contract"),
+                    "{path:?} has a placeholder left"
+                );
+            }
+        }
+
+        temp_dir.close().unwrap();
+    }
+
     // test helpers
     fn assert_base_template_files_exist(project_dir: &Path) {
         let expected_paths = ["contracts", "Cargo.toml", "README.md"];
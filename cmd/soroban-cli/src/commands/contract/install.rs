@@ -17,7 +17,7 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, network},
+    config::{self, data, network, Submitted},
     key,
     print::Print,
     rpc,
@@ -36,6 +36,8 @@ pub struct Cmd {
     #[command(flatten)]
     pub fee: crate::fee::Args,
     #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
+    #[command(flatten)]
     pub wasm: wasm::Args,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
@@ -44,6 +46,8 @@ pub struct Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error("error parsing int: {0}")]
     ParseIntError(#[from] ParseIntError),
     #[error("internal conversion error: {0}")]
@@ -78,6 +82,8 @@ pub enum Error {
     Data(#[from] data::Error),
     #[error(transparent)]
     Builder(#[from] builder::Error),
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
 }
 
 impl Cmd {
@@ -89,6 +95,7 @@ impl Cmd {
         match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
             TxnEnvelopeResult::Res(hash) => println!("{}", hex::encode(hash)),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
         };
         Ok(())
     }
@@ -99,6 +106,7 @@ impl NetworkRunnable for Cmd {
     type Error = Error;
     type Result = TxnResult<Hash>;
 
+    #[allow(clippy::too_many_lines)]
     async fn run_against_rpc_server(
         &self,
         args: Option<&global::Args>,
@@ -140,10 +148,20 @@ impl NetworkRunnable for Cmd {
         let account_details = client
             .get_account(&source_account.clone().to_string())
             .await?;
-        let sequence: i64 = account_details.seq_num.into();
-
-        let (tx_without_preflight, hash) =
-            build_install_contract_code_tx(&contract, sequence + 1, self.fee.fee, &source_account)?;
+        let sequence = config.sequence_number_after(
+            &account_details.account_id,
+            account_details.seq_num.into(),
+            self.fee.seq_strategy,
+        )?;
+
+        let (tx_without_preflight, hash) = build_install_contract_code_tx(
+            &contract,
+            sequence,
+            self.fee.inclusion_fee(&network, true).await?,
+            &source_account,
+        )?;
+        let tx_without_preflight =
+            tx_without_preflight.add_cond(self.preconditions.preconditions()?);
 
         if self.fee.build_only {
             return Ok(TxnResult::Txn(Box::new(tx_without_preflight)));
@@ -186,17 +204,25 @@ impl NetworkRunnable for Cmd {
         print.infoln("Simulating install transaction…");
 
         let txn = simulate_and_assemble_transaction(&client, &tx_without_preflight).await?;
-        let txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
+        let mut txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
 
         if self.fee.sim_only {
             return Ok(TxnResult::Txn(txn));
         }
 
-        print.globeln("Submitting install transaction…");
-
-        let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(*txn).await?)
-            .await?;
+        let reservation = config.reserve_sequence_number(&mut txn, &self.fee)?;
+        let tx_env = self.config.sign_with_local_key(*txn).await?;
+        if !self.fee.dry_run {
+            print.globeln("Submitting install transaction…");
+        }
+        let txn_resp = match config
+            .submit(&network, &client, tx_env, reservation, &self.fee, args)
+            .await?
+        {
+            Submitted::DryRun(tx_env) => return Ok(TxnResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnResult::Submitted(hash)),
+            Submitted::Done(res) => *res,
+        };
 
         if args.map_or(true, |a| !a.no_cache) {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
@@ -220,6 +246,7 @@ impl NetworkRunnable for Cmd {
                 },
                 config: config.clone(),
                 fee: self.fee.clone(),
+                preconditions: self.preconditions.clone(),
                 ledgers_to_extend: None,
                 ttl_ledger_only: true,
             }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::{Infallible, TryInto};
 use std::ffi::OsString;
 use std::num::ParseIntError;
@@ -13,16 +14,17 @@ use soroban_spec::read::FromWasmError;
 use super::super::events;
 use super::arg_parsing;
 use crate::{
-    assembled::simulate_and_assemble_transaction,
     commands::{
         contract::arg_parsing::{build_host_function_parameters, output_to_string},
         global,
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, locator, network},
+    config::{self, data, locator, network, Submitted},
+    diagnostic::{self, simulate_and_assemble_transaction},
     get_spec::{self, get_remote_contract_spec},
     print, rpc,
+    tx::builder::TxExt,
     xdr::{
         self, AccountEntry, AccountEntryExt, AccountId, ContractEvent, ContractEventType,
         DiagnosticEvent, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
@@ -54,9 +56,31 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
     /// Whether or not to send a transaction
     #[arg(long, value_enum, default_value_t, env = "STELLAR_SEND")]
     pub send: Send,
+    /// With `--send=read`, reuse the return value of the same invocation in the same ledger, for
+    /// scripts that poll view functions
+    #[arg(long)]
+    pub cache: bool,
+    /// Fail unless the return value, as printed, equals this JSON value, e.g. `42` or
+    /// `'["Hello","world"]'`. Values that are not JSON are compared as strings
+    #[arg(
+        long,
+        value_parser = |value: &str| Ok::<_, Infallible>(parse_expected(value)),
+        conflicts_with_all = ["expect_error", "build_only", "sim_only", "no_wait", "dry_run"]
+    )]
+    pub expect: Option<serde_json::Value>,
+    /// Fail unless the invocation fails with this contract error, given as its code, e.g. `1`,
+    /// or its name in the contract spec, e.g. `NumberMustBeOdd`
+    #[arg(long, conflicts_with_all = ["build_only", "sim_only", "no_wait", "dry_run"])]
+    pub expect_error: Option<String>,
+    /// When the invocation fails, e.g. exceeding its budget, print the CPU instructions, memory,
+    /// and other resources it used of the network's limits
+    #[arg(long)]
+    pub debug_budget: bool,
 }
 
 impl FromStr for Cmd {
@@ -76,6 +100,8 @@ impl Pwd for Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error("cannot add contract to ledger entries: {0}")]
     CannotAddContractToLedgerEntries(xdr::Error),
     #[error("reading file {0:?}: {1}")]
@@ -121,6 +147,24 @@ pub enum Error {
     GetSpecError(#[from] get_spec::Error),
     #[error(transparent)]
     ArgParsing(#[from] arg_parsing::Error),
+    #[error("expected the return value {expected}, got {actual}")]
+    UnexpectedReturnValue { expected: String, actual: String },
+    #[error("expected contract error {expected}, but the invocation succeeded with {actual}")]
+    MissingContractError { expected: String, actual: String },
+    #[error("expected contract error {expected}, got {actual}")]
+    UnexpectedContractError { expected: String, actual: String },
+    #[error("expected contract error {expected}, but the invocation failed with: {error}")]
+    NotAContractError { expected: String, error: Box<Error> },
+    #[error("no contract error named {0:?} in the contract spec")]
+    UnknownContractError(String),
+    #[error(transparent)]
+    Diagnostic(#[from] diagnostic::Error),
+    #[error("{function} is not read-only, its simulation {changes}. Send it with `--send=yes`")]
+    NotReadOnly { function: String, changes: String },
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
+    #[error("--cache only applies to read-only invocations, use it with `--send=read`")]
+    CacheWithoutRead,
 }
 
 impl From<Infallible> for Error {
@@ -131,16 +175,181 @@ impl From<Infallible> for Error {
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let res = self.invoke(global_args).await?.to_envelope();
-        match res {
+        let res = self.invoke(global_args).await;
+        if let Some(expected) = &self.expect_error {
+            return self.check_error(expected, res, global_args).await;
+        }
+        if let Err(Error::Diagnostic(error)) = &res {
+            self.print_stack(error.events(), global_args).await;
+            if self.debug_budget {
+                self.print_budget(error, global_args).await;
+            }
+        }
+        match res?.to_envelope() {
             TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
             TxnEnvelopeResult::Res(output) => {
                 println!("{output}");
+                if let Some(expected) = &self.expect {
+                    let actual = parse_expected(&output);
+                    if actual != *expected {
+                        return Err(Error::UnexpectedReturnValue {
+                            expected: expected.to_string(),
+                            actual: actual.to_string(),
+                        });
+                    }
+                    print::Print::new(global_args.quiet)
+                        .checkln("The return value matches the expected value");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `res` failed with the contract error `expected`, a code or a name in the spec.
+    pub async fn check_error(
+        &self,
+        expected: &str,
+        res: Result<TxnResult<String>, Error>,
+        global_args: &global::Args,
+    ) -> Result<(), Error> {
+        let error = match res {
+            Ok(res) => {
+                return Err(Error::MissingContractError {
+                    expected: expected.to_string(),
+                    actual: res.into_result().unwrap_or_default(),
+                })
+            }
+            Err(error) => error,
+        };
+        let actual = match &error {
+            Error::Diagnostic(error) => diagnostic::contract_error(error.events()),
+            _ => None,
+        };
+        let Some(actual) = actual else {
+            return Err(Error::NotAContractError {
+                expected: expected.to_string(),
+                error: Box::new(error),
+            });
+        };
+        let expected_code = match expected.parse::<u32>() {
+            Ok(code) => code,
+            Err(_) => {
+                self.contract_error_code_by_name(expected, global_args)
+                    .await?
             }
+        };
+        if actual != expected_code {
+            return Err(Error::UnexpectedContractError {
+                expected: expected.to_string(),
+                actual: format!("#{actual}"),
+            });
         }
+        print::Print::new(global_args.quiet).checkln(format!(
+            "The invocation failed with the expected contract error {expected}"
+        ));
         Ok(())
     }
 
+    async fn contract_error_code_by_name(
+        &self,
+        name: &str,
+        global_args: &global::Args,
+    ) -> Result<u32, Error> {
+        let spec_entries = if let Some(entries) = self.spec_entries()? {
+            entries
+        } else {
+            let network = self.config.get_network()?;
+            let contract_id = self
+                .contract_id
+                .resolve_contract_id(&self.config.locator, &network.network_passphrase)?;
+            get_remote_contract_spec(
+                &contract_id.0,
+                &self.config.locator,
+                &self.config.network,
+                Some(global_args),
+                Some(&self.config),
+            )
+            .await
+            .map_err(Error::from)?
+        };
+        spec_entries
+            .iter()
+            .filter_map(|entry| match entry {
+                ScSpecEntry::UdtErrorEnumV0(error_enum) => Some(error_enum.cases.iter()),
+                _ => None,
+            })
+            .flatten()
+            .find(|case| case.name.to_utf8_string_lossy() == name)
+            .map(|case| case.value)
+            .ok_or_else(|| Error::UnknownContractError(name.to_string()))
+    }
+
+    /// Print the call stack of a failed invocation, naming the contract errors with the specs of
+    /// the invoked contract and of the contracts that raised them.
+    async fn print_stack(&self, events: &[DiagnosticEvent], global_args: &global::Args) {
+        let mut specs = HashMap::new();
+        let mut contracts = diagnostic::erroring_contracts(events);
+        if let Ok(contract_id) = self.config.get_network().and_then(|network| {
+            Ok(self
+                .contract_id
+                .resolve_contract_id(&self.config.locator, &network.network_passphrase)?)
+        }) {
+            contracts.push(xdr::Hash(contract_id.0));
+        }
+        for contract in contracts {
+            if specs.contains_key(&contract) {
+                continue;
+            }
+            if let Ok(entries) = get_remote_contract_spec(
+                &contract.0,
+                &self.config.locator,
+                &self.config.network,
+                Some(global_args),
+                Some(&self.config),
+            )
+            .await
+            {
+                specs.insert(contract, soroban_spec_tools::Spec::new(entries));
+            }
+        }
+        diagnostic::print_stack(&print::Print::new(global_args.quiet), events, &specs);
+    }
+
+    /// Print what a failed invocation used of the network's limits, of the cost of its
+    /// simulation and its `core_metrics` diagnostic events.
+    async fn print_budget(&self, error: &diagnostic::Error, global_args: &global::Args) {
+        let print = print::Print::new(global_args.quiet);
+        let budget = match self
+            .config
+            .get_network()
+            .and_then(|network| Ok(network.rpc_client()?))
+        {
+            Ok(client) => diagnostic::Budget::fetch(&client)
+                .await
+                .map_err(|e| tracing::debug!("cannot fetch the network's limits: {e}"))
+                .ok()
+                .flatten(),
+            Err(e) => {
+                tracing::debug!("cannot fetch the network's limits: {e}");
+                None
+            }
+        };
+        let lines = diagnostic::budget_lines(
+            &diagnostic::core_metrics(error.events()),
+            error.cost(),
+            budget,
+        );
+        if lines.is_empty() {
+            print.warnln("The RPC reported no resources used by the invocation");
+            return;
+        }
+        print.infoln("Resources used by the invocation:");
+        for line in lines {
+            print.println(format!("  {line}"));
+        }
+    }
+
     pub async fn invoke(&self, global_args: &global::Args) -> Result<TxnResult<String>, Error> {
         self.run_against_rpc_server(Some(global_args), None).await
     }
@@ -173,11 +382,107 @@ impl Cmd {
                     ShouldSend::DefaultNo
                 }
             }
-            Send::No => ShouldSend::No,
+            Send::No | Send::Read => ShouldSend::No,
             Send::Yes => ShouldSend::Yes,
         })
     }
 
+    /// Simulate a read-only invocation from a default account and return its value, failing if
+    /// it changes the ledger. With `--cache`, the value of the same read in the same ledger is
+    /// reused.
+    async fn read(
+        &self,
+        network: &network::Network,
+        client: &Client,
+        function: &str,
+        spec: &soroban_spec_tools::Spec,
+        host_function_params: InvokeContractArgs,
+        global_args: Option<&global::Args>,
+    ) -> Result<(TxnResult<String>, Vec<DiagnosticEvent>), Error> {
+        let key = if self.cache && global_args.map_or(true, |a| !a.no_cache) {
+            let ledger = client.get_latest_ledger().await?.sequence;
+            let key = data::invoke_result_key(
+                &network.network_passphrase,
+                &host_function_params,
+                ledger,
+            )?;
+            if let Some(value) = data::read_invoke_result(&key)? {
+                tracing::debug!(
+                    "return value of {function} at ledger {ledger} read from the cache"
+                );
+                return Ok((output_to_string(spec, &value, function)?, Vec::new()));
+            }
+            Some(key)
+        } else {
+            None
+        };
+        let account_details = default_account_entry();
+        let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
+        let tx = build_invoke_contract_tx(
+            host_function_params,
+            i64::from(account_details.seq_num) + 1,
+            self.fee.fee.unwrap_or(crate::fee::DEFAULT_FEE),
+            account_id,
+        )?;
+        let assembled = simulate_and_assemble_transaction(client, &tx).await?;
+        let sim_res = assembled.sim_response();
+        let changes = [
+            (has_write(sim_res)?, "writes to the ledger"),
+            (has_published_event(sim_res)?, "publishes events"),
+            (has_auth(sim_res)?, "requires authorization"),
+        ]
+        .into_iter()
+        .filter_map(|(changes, what)| changes.then_some(what))
+        .collect::<Vec<_>>();
+        if !changes.is_empty() {
+            return Err(Error::NotReadOnly {
+                function: function.to_string(),
+                changes: changes.join(", "),
+            });
+        }
+        let value = sim_res.results()?[0].xdr.clone();
+        if let Some(key) = key {
+            data::write_invoke_result(&key, &value)?;
+        }
+        let events = sim_res.events()?;
+        crate::log::events(&events);
+        Ok((output_to_string(spec, &value, function)?, events))
+    }
+
+    /// Simulate the invocation from a default account, without signing or sending it.
+    pub async fn simulate(
+        &self,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<SimulateTransactionResponse, Error> {
+        let config = config.unwrap_or(&self.config);
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let spec_entries = get_remote_contract_spec(
+            &contract_id.0,
+            &config.locator,
+            &config.network,
+            global_args,
+            Some(config),
+        )
+        .await
+        .map_err(Error::from)?;
+        let (_, _, host_function_params, _) =
+            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config)?;
+        let account_details = default_account_entry();
+        let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
+        let tx = build_invoke_contract_tx(
+            host_function_params,
+            i64::from(account_details.seq_num) + 1,
+            self.fee.fee.unwrap_or(crate::fee::DEFAULT_FEE),
+            account_id,
+        )?;
+        let assembled = simulate_and_assemble_transaction(&network.rpc_client()?, &tx).await?;
+        Ok(assembled.sim_response().clone())
+    }
+
     // uses a default account to check if the tx should be sent after the simulation
     async fn should_send_after_sim(
         &self,
@@ -191,7 +496,7 @@ impl Cmd {
         let tx = build_invoke_contract_tx(
             host_function_params.clone(),
             sequence + 1,
-            self.fee.fee,
+            self.fee.fee.unwrap_or(crate::fee::DEFAULT_FEE),
             account_id,
         )?;
         let txn = simulate_and_assemble_transaction(&rpc_client, &tx).await?;
@@ -201,16 +506,17 @@ impl Cmd {
     }
 }
 
-#[async_trait::async_trait]
-impl NetworkRunnable for Cmd {
-    type Error = Error;
-    type Result = TxnResult<String>;
-
-    async fn run_against_rpc_server(
+impl Cmd {
+    /// Like `run_against_rpc_server`, also returning the diagnostic events of the invocation.
+    #[allow(clippy::too_many_lines)]
+    pub async fn invoke_with_events(
         &self,
         global_args: Option<&global::Args>,
         config: Option<&config::Args>,
-    ) -> Result<TxnResult<String>, Error> {
+    ) -> Result<(TxnResult<String>, Vec<DiagnosticEvent>), Error> {
+        if self.cache && self.send != Send::Read {
+            return Err(Error::CacheWithoutRead);
+        }
         let config = config.unwrap_or(&self.config);
         let network = config.get_network()?;
         tracing::trace!(?network);
@@ -238,13 +544,26 @@ impl NetworkRunnable for Cmd {
         let (function, spec, host_function_params, signers) =
             build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config)?;
 
+        if self.send == Send::Read {
+            return self
+                .read(
+                    &network,
+                    &client,
+                    &function,
+                    &spec,
+                    host_function_params,
+                    global_args,
+                )
+                .await;
+        }
+
         let should_send_tx = self
             .should_send_after_sim(host_function_params.clone(), client.clone())
             .await?;
 
         let account_details = if should_send_tx == ShouldSend::Yes {
-            client
-                .verify_network_passphrase(Some(&network.network_passphrase))
+            network
+                .verify_rpc_passphrase(&client, global_args.is_some_and(|a| a.no_cache))
                 .await?;
 
             client
@@ -253,23 +572,34 @@ impl NetworkRunnable for Cmd {
         } else {
             default_account_entry()
         };
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence = if should_send_tx == ShouldSend::Yes {
+            config.sequence_number_after(
+                &account_details.account_id,
+                account_details.seq_num.into(),
+                self.fee.seq_strategy,
+            )?
+        } else {
+            i64::from(account_details.seq_num) + 1
+        };
         let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
 
-        let tx = Box::new(build_invoke_contract_tx(
-            host_function_params.clone(),
-            sequence + 1,
-            self.fee.fee,
-            account_id,
-        )?);
+        let tx = Box::new(
+            build_invoke_contract_tx(
+                host_function_params.clone(),
+                sequence,
+                self.fee.inclusion_fee(&network, true).await?,
+                account_id,
+            )?
+            .add_cond(self.preconditions.preconditions()?),
+        );
         if self.fee.build_only {
-            return Ok(TxnResult::Txn(tx));
+            return Ok((TxnResult::Txn(tx), Vec::new()));
         }
         let txn = simulate_and_assemble_transaction(&client, &tx).await?;
         let assembled = self.fee.apply_to_assembled_txn(txn);
         let mut txn = Box::new(assembled.transaction().clone());
         if self.fee.sim_only {
-            return Ok(TxnResult::Txn(txn));
+            return Ok((TxnResult::Txn(txn), Vec::new()));
         }
         let sim_res = assembled.sim_response();
         if global_args.map_or(true, |a| !a.no_cache) {
@@ -283,9 +613,29 @@ impl NetworkRunnable for Cmd {
                 if let Some(tx) = config.sign_soroban_authorizations(&txn, &signers).await? {
                     txn = Box::new(tx);
                 }
-                let res = client
-                    .send_transaction_polling(&config.sign_with_local_key(*txn).await?)
-                    .await?;
+                let reservation = config.reserve_sequence_number(&mut txn, &self.fee)?;
+                let tx_env = config.sign_with_local_key(*txn).await?;
+                let submitted = config
+                    .submit(
+                        &network,
+                        &client,
+                        tx_env,
+                        reservation,
+                        &self.fee,
+                        global_args,
+                    )
+                    .await
+                    .map_err(|error| match error {
+                        config::Error::Diagnostic(error) => Error::Diagnostic(*error),
+                        error => error.into(),
+                    })?;
+                let res = match submitted {
+                    Submitted::DryRun(tx_env) => {
+                        return Ok((TxnResult::TxnEnvelope(tx_env), Vec::new()))
+                    }
+                    Submitted::Sent(hash) => return Ok((TxnResult::Submitted(hash), Vec::new())),
+                    Submitted::Done(res) => *res,
+                };
                 if !no_cache {
                     data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
                 }
@@ -304,7 +654,21 @@ impl NetworkRunnable for Cmd {
             }
         };
         crate::log::events(&events);
-        Ok(output_to_string(&spec, &return_value, &function)?)
+        Ok((output_to_string(&spec, &return_value, &function)?, events))
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkRunnable for Cmd {
+    type Error = Error;
+    type Result = TxnResult<String>;
+
+    async fn run_against_rpc_server(
+        &self,
+        global_args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<TxnResult<String>, Error> {
+        Ok(self.invoke_with_events(global_args, config).await?.0)
     }
 }
 
@@ -377,6 +741,11 @@ fn build_invoke_contract_tx(
     })
 }
 
+/// Parse an expected return value as JSON, falling back to a JSON string.
+pub fn parse_expected(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum, Default)]
 pub enum Send {
     /// Send transaction if simulation indicates there are ledger writes,
@@ -387,6 +756,10 @@ pub enum Send {
     No,
     /// Always send transaction
     Yes,
+    /// Do not send transaction, return the simulation result, and fail if the simulation
+    /// indicates ledger writes, published events, or auth required. Simulates once, without
+    /// looking up the source account or fees
+    Read,
 }
 
 #[derive(Debug, PartialEq)]
@@ -420,3 +793,30 @@ fn has_auth(sim_res: &SimulateTransactionResponse) -> Result<bool, Error> {
         .iter()
         .any(|SimulateHostFunctionResult { auth, .. }| !auth.is_empty()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_values() {
+        assert_eq!(parse_expected("42"), serde_json::json!(42));
+        assert_eq!(
+            parse_expected(r#"["Hello","world"]"#),
+            serde_json::json!(["Hello", "world"])
+        );
+        assert_eq!(parse_expected("hello"), serde_json::json!("hello"));
+    }
+
+    #[tokio::test]
+    async fn cache_requires_send_read() {
+        let cmd = Cmd {
+            cache: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            cmd.invoke_with_events(None, None).await,
+            Err(Error::CacheWithoutRead)
+        ));
+    }
+}
@@ -1,11 +1,15 @@
 pub mod alias;
 pub mod arg_parsing;
 pub mod asset;
+pub mod bench;
 pub mod bindings;
 pub mod build;
 pub mod deploy;
+pub mod diff;
+pub mod events;
 pub mod extend;
 pub mod fetch;
+pub mod generate_tests;
 pub mod id;
 pub mod info;
 pub mod init;
@@ -15,6 +19,9 @@ pub mod invoke;
 pub mod optimize;
 pub mod read;
 pub mod restore;
+pub mod run;
+pub mod spec;
+pub mod storage;
 
 use crate::commands::global;
 
@@ -28,6 +35,10 @@ pub enum Cmd {
     #[command(subcommand)]
     Alias(alias::Cmd),
 
+    /// Simulate the invocations of a `contract run` scenario and compare their CPU instructions,
+    /// memory, and fees against a baseline, failing if any grew by more than `--threshold`
+    Bench(bench::Cmd),
+
     /// Generate code client bindings for a contract
     #[command(subcommand)]
     Bindings(bindings::Cmd),
@@ -42,9 +53,30 @@ pub enum Cmd {
     /// Deploy a wasm contract
     Deploy(deploy::wasm::Cmd),
 
+    /// Compare the interfaces of two contracts, e.g. before an upgrade.
+    ///
+    /// Reports added, removed, and changed functions and types, and exits with an error if a
+    /// change breaks existing callers or values already in contract storage.
+    ///
+    /// stellar contract diff --old C... --new ./target/wasm32-unknown-unknown/release/hello.wasm
+    Diff(diff::Cmd),
+
+    /// Export the events of a contract
+    #[command(subcommand)]
+    Events(events::Cmd),
+
     /// Fetch a contract's Wasm binary
     Fetch(fetch::Cmd),
 
+    /// Generate a Rust test module with a test calling each function of a contract.
+    ///
+    /// The tests import the contract's Wasm with `contractimport!`, register it in a test
+    /// environment, and call each function with placeholder arguments built from its spec, to
+    /// be replaced with meaningful ones along with checks of the results.
+    ///
+    ///     stellar contract generate-tests --wasm ../../target/wasm32-unknown-unknown/release/hello.wasm
+    GenerateTests(generate_tests::Cmd),
+
     /// Generate the contract id for a given contract or asset
     #[command(subcommand)]
     Id(id::Cmd),
@@ -88,6 +120,29 @@ pub enum Cmd {
     ///
     /// If no keys are specificed the contract itself is restored.
     Restore(restore::Cmd),
+
+    /// Publish and fetch contract specs to and from a registry, to decode contracts that were
+    /// never built locally
+    #[command(subcommand)]
+    Spec(spec::Cmd),
+
+    /// List and get the storage entries of a contract, decoded with its spec
+    #[command(subcommand)]
+    Storage(storage::Cmd),
+
+    /// Run a scenario of deploys and invokes from a TOML or YAML file, checking the return
+    /// values, errors, and events of each step.
+    ///
+    /// Each `[[step]]` has a `deploy` or an `invoke` table, and optionally `expect`,
+    /// `expect_error`, and `expect_events`:
+    ///
+    ///     [[step]]
+    ///     deploy = { wasm = "hello.wasm", alias = "hello" }
+    ///
+    ///     [[step]]
+    ///     invoke = { contract = "hello", function = "hello", args = { world = "you" } }
+    ///     expect = ["Hello", "you"]
+    Run(run::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -98,6 +153,9 @@ pub enum Error {
     #[error(transparent)]
     Alias(#[from] alias::Error),
 
+    #[error(transparent)]
+    Bench(#[from] bench::Error),
+
     #[error(transparent)]
     Bindings(#[from] bindings::Error),
 
@@ -110,9 +168,18 @@ pub enum Error {
     #[error(transparent)]
     Deploy(#[from] deploy::wasm::Error),
 
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
+
+    #[error(transparent)]
+    Events(#[from] events::Error),
+
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
 
+    #[error(transparent)]
+    GenerateTests(#[from] generate_tests::Error),
+
     #[error(transparent)]
     Init(#[from] init::Error),
 
@@ -139,27 +206,43 @@ pub enum Error {
 
     #[error(transparent)]
     Restore(#[from] restore::Error),
+
+    #[error(transparent)]
+    Spec(#[from] spec::Error),
+
+    #[error(transparent)]
+    Storage(#[from] storage::Error),
+
+    #[error(transparent)]
+    Run(#[from] run::Error),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
-            Cmd::Asset(asset) => asset.run().await?,
+            Cmd::Asset(asset) => asset.run(global_args).await?,
+            Cmd::Bench(bench) => bench.run(global_args).await?,
             Cmd::Bindings(bindings) => bindings.run().await?,
             Cmd::Build(build) => build.run(global_args)?,
-            Cmd::Extend(extend) => extend.run().await?,
+            Cmd::Extend(extend) => extend.run(global_args).await?,
             Cmd::Alias(alias) => alias.run(global_args)?,
             Cmd::Deploy(deploy) => deploy.run(global_args).await?,
+            Cmd::Diff(diff) => diff.run(global_args).await?,
+            Cmd::Events(events) => events.run(global_args).await?,
             Cmd::Id(id) => id.run()?,
-            Cmd::Info(info) => info.run().await?,
+            Cmd::Info(info) => info.run(global_args).await?,
             Cmd::Init(init) => init.run(global_args)?,
             Cmd::Inspect(inspect) => inspect.run(global_args)?,
             Cmd::Install(install) => install.run(global_args).await?,
             Cmd::Invoke(invoke) => invoke.run(global_args).await?,
             Cmd::Optimize(optimize) => optimize.run()?,
             Cmd::Fetch(fetch) => fetch.run().await?,
+            Cmd::GenerateTests(generate_tests) => generate_tests.run(global_args)?,
             Cmd::Read(read) => read.run().await?,
-            Cmd::Restore(restore) => restore.run().await?,
+            Cmd::Restore(restore) => restore.run(global_args).await?,
+            Cmd::Spec(spec) => spec.run(global_args).await?,
+            Cmd::Storage(storage) => storage.run(global_args).await?,
+            Cmd::Run(run) => run.run(global_args).await?,
         }
         Ok(())
     }
@@ -2,9 +2,9 @@ use std::{fmt::Debug, path::Path, str::FromStr};
 
 use crate::xdr::{
     Error as XdrError, ExtensionPoint, LedgerEntry, LedgerEntryChange, LedgerEntryData,
-    LedgerFootprint, Limits, Memo, Operation, OperationBody, OperationMeta, Preconditions,
-    RestoreFootprintOp, SequenceNumber, SorobanResources, SorobanTransactionData, Transaction,
-    TransactionExt, TransactionMeta, TransactionMetaV3, TtlEntry, WriteXdr,
+    LedgerFootprint, Limits, Memo, Operation, OperationBody, OperationMeta, RestoreFootprintOp,
+    SequenceNumber, SorobanResources, SorobanTransactionData, Transaction, TransactionExt,
+    TransactionMeta, TransactionMetaV3, TtlEntry, WriteXdr,
 };
 use clap::{command, Parser};
 use stellar_strkey::DecodeError;
@@ -16,7 +16,7 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, locator, network},
+    config::{self, data, locator, network, Submitted},
     key, rpc, wasm, Pwd,
 };
 
@@ -35,6 +35,8 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub preconditions: crate::commands::tx::preconditions::Args,
 }
 
 impl FromStr for Cmd {
@@ -54,6 +56,8 @@ impl Pwd for Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
     #[error("parsing key {key}: {error}")]
     CannotParseKey {
         key: String,
@@ -87,17 +91,26 @@ pub enum Error {
     Data(#[from] data::Error),
     #[error(transparent)]
     Network(#[from] network::Error),
+    #[error(transparent)]
+    Preconditions(#[from] crate::commands::tx::preconditions::Error),
 }
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
-    pub async fn run(&self) -> Result<(), Error> {
-        let res = self.run_against_rpc_server(None, None).await?.to_envelope();
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let res = self
+            .run_against_rpc_server(Some(global_args), None)
+            .await?
+            .to_envelope();
         let expiration_ledger_seq = match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => {
                 println!("{}", tx.to_xdr_base64(Limits::none())?);
                 return Ok(());
             }
+            TxnEnvelopeResult::Submitted(hash) => {
+                println!("{hash}");
+                return Ok(());
+            }
             TxnEnvelopeResult::Res(res) => res,
         };
         if let Some(ledgers_to_extend) = self.ledgers_to_extend {
@@ -106,9 +119,10 @@ impl Cmd {
                 ledgers_to_extend,
                 config: self.config.clone(),
                 fee: self.fee.clone(),
+                preconditions: self.preconditions.clone(),
                 ttl_ledger_only: false,
             }
-            .run()
+            .run(global_args)
             .await?;
         } else {
             println!("New ttl ledger: {expiration_ledger_seq}");
@@ -139,13 +153,17 @@ impl NetworkRunnable for Cmd {
         let account_details = client
             .get_account(&source_account.clone().to_string())
             .await?;
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence = config.sequence_number_after(
+            &account_details.account_id,
+            account_details.seq_num.into(),
+            self.fee.seq_strategy,
+        )?;
 
-        let tx = Box::new(Transaction {
+        let mut tx = Box::new(Transaction {
             source_account,
-            fee: self.fee.fee,
-            seq_num: SequenceNumber(sequence + 1),
-            cond: Preconditions::None,
+            fee: self.fee.inclusion_fee(&network, true).await?,
+            seq_num: SequenceNumber(sequence),
+            cond: self.preconditions.preconditions()?,
             memo: Memo::None,
             operations: vec![Operation {
                 source_account: None,
@@ -171,9 +189,16 @@ impl NetworkRunnable for Cmd {
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(*tx).await?)
-            .await?;
+        let reservation = config.reserve_sequence_number(&mut tx, &self.fee)?;
+        let tx_env = config.sign_with_local_key(*tx).await?;
+        let res = match config
+            .submit(&network, &client, tx_env, reservation, &self.fee, args)
+            .await?
+        {
+            Submitted::DryRun(tx_env) => return Ok(TxnResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnResult::Submitted(hash)),
+            Submitted::Done(res) => *res,
+        };
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
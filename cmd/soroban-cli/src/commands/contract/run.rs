@@ -0,0 +1,525 @@
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::{contract::deploy, contract::invoke, global, NetworkRunnable},
+    config::{self, address::Address, locator, network},
+    fee,
+    print::Print,
+    xdr::{ContractEventBody, ContractEventType, DiagnosticEvent},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("reading scenario {0:?}: {1}")]
+    CannotReadScenario(PathBuf, std::io::Error),
+    #[error("parsing scenario {0:?}: {1}")]
+    CannotParseScenario(PathBuf, toml::de::Error),
+    #[error("parsing scenario {0:?}: {1}")]
+    CannotParseYamlScenario(PathBuf, serde_yaml::Error),
+    #[error("step {0} must have exactly one of `deploy` or `invoke`")]
+    InvalidStep(usize),
+    #[error("{failed} of {total} steps failed")]
+    StepsFailed { failed: usize, total: usize },
+}
+
+/// Failure of a single step, reported instead of stopping the run.
+#[derive(thiserror::Error, Debug)]
+pub enum StepError {
+    #[error(transparent)]
+    Deploy(#[from] deploy::wasm::Error),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("`expect`, `expect_error`, and `expect_events` cannot be used with `deploy`")]
+    ExpectOnDeploy,
+    #[error("expected an event {expected}, got {actual}")]
+    MissingEvent { expected: String, actual: String },
+    #[error("the transaction was not sent, so there is no result to check")]
+    NoResult,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Scenario to run, a TOML file with a list of `[[step]]` tables that deploy and invoke
+    /// contracts, and the return values, errors, and events to expect from them. Files with a
+    /// `.yaml` or `.yml` extension are read as YAML, with the steps in a `step` list
+    pub scenario: PathBuf,
+    /// Run the remaining steps after a step fails, instead of stopping
+    #[arg(long)]
+    pub keep_going: bool,
+    /// Format of the report
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub fee: fee::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per step, then a summary
+    Text,
+    /// The outcome of each step as JSON
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Scenario {
+    #[serde(default, rename = "step")]
+    pub(super) steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Step {
+    name: Option<String>,
+    /// Source account of the step, defaults to `--source-account`
+    source: Option<String>,
+    pub(super) deploy: Option<Deploy>,
+    pub(super) invoke: Option<Invoke>,
+    /// Expected return value of an invoke, as JSON
+    expect: Option<serde_json::Value>,
+    /// Expected contract error of an invoke, a code or a name in the contract spec
+    expect_error: Option<String>,
+    /// Contract events that the invoke must emit, in any order
+    #[serde(default)]
+    expect_events: Vec<ExpectedEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Deploy {
+    /// Path of the wasm, relative to the scenario file
+    wasm: Option<PathBuf>,
+    wasm_hash: Option<String>,
+    alias: Option<String>,
+    salt: Option<String>,
+    /// Arguments of the constructor, by name
+    #[serde(default)]
+    args: toml::Table,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Invoke {
+    /// Contract id or alias
+    contract: String,
+    function: String,
+    /// Arguments of the function, by name
+    #[serde(default)]
+    args: toml::Table,
+    /// Whether to send the transaction, decided from the simulation if not set
+    send: Option<bool>,
+}
+
+/// An event to match against the contract events of an invoke. Topics and data are `ScVal`s as
+/// JSON, e.g. `{ symbol = "transfer" }`; those that are not set match anything.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedEvent {
+    contract: Option<String>,
+    topics: Option<Vec<serde_json::Value>>,
+    data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct Outcome {
+    step: usize,
+    name: String,
+    passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let scenario = read_scenario(&self.scenario)?;
+        let base_dir = self.scenario.parent().unwrap_or(Path::new("."));
+        // The commands of the steps log a lot, only the outcome of each step is reported.
+        let step_args = global::Args {
+            quiet: true,
+            ..global_args.clone()
+        };
+        let total = scenario.steps.len();
+        let mut outcomes = Vec::new();
+        for (i, step) in scenario.steps.iter().enumerate() {
+            let number = i + 1;
+            let name = step.name();
+            print.infoln(format!("Step {number}/{total}: {name}"));
+            let res = self.run_step(step, base_dir, &step_args).await;
+            let outcome = Outcome {
+                step: number,
+                name,
+                passed: res.is_ok(),
+                error: res.as_ref().err().map(ToString::to_string),
+                result: res.ok().flatten(),
+            };
+            if self.output == Output::Text {
+                println!("{}", outcome.line(total));
+            }
+            let passed = outcome.passed;
+            outcomes.push(outcome);
+            if !passed && !self.keep_going {
+                break;
+            }
+        }
+        let failed = outcomes.iter().filter(|o| !o.passed).count();
+        match self.output {
+            Output::Text => println!(
+                "{} passed, {failed} failed, {} not run",
+                outcomes.len() - failed,
+                total - outcomes.len()
+            ),
+            Output::Json => println!("{}", serde_json::to_string_pretty(&outcomes)?),
+        }
+        if failed > 0 {
+            return Err(Error::StepsFailed { failed, total });
+        }
+        Ok(())
+    }
+
+    /// Run a step, returning the contract id of a deploy or the return value of an invoke.
+    async fn run_step(
+        &self,
+        step: &Step,
+        base_dir: &Path,
+        global_args: &global::Args,
+    ) -> Result<Option<String>, StepError> {
+        let config = step.config(&self.config)?;
+        match (&step.deploy, &step.invoke) {
+            (Some(deploy), None) => {
+                if step.expect.is_some()
+                    || step.expect_error.is_some()
+                    || !step.expect_events.is_empty()
+                {
+                    return Err(StepError::ExpectOnDeploy);
+                }
+                deploy
+                    .run(&config, &self.fee, base_dir, global_args)
+                    .await
+                    .map(Some)
+            }
+            (None, Some(invoke)) => {
+                step.run_invoke(invoke, &config, &self.fee, global_args)
+                    .await
+            }
+            _ => unreachable!("steps are validated when the scenario is read"),
+        }
+    }
+}
+
+pub(super) fn read_scenario(path: &Path) -> Result<Scenario, Error> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| Error::CannotReadScenario(path.to_path_buf(), e))?;
+    let scenario: Scenario = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&contents)
+            .map_err(|e| Error::CannotParseYamlScenario(path.to_path_buf(), e))?,
+        _ => toml::from_str(&contents)
+            .map_err(|e| Error::CannotParseScenario(path.to_path_buf(), e))?,
+    };
+    for (i, step) in scenario.steps.iter().enumerate() {
+        if step.deploy.is_some() == step.invoke.is_some() {
+            return Err(Error::InvalidStep(i + 1));
+        }
+    }
+    Ok(scenario)
+}
+
+impl Step {
+    /// Name of the step in reports, its `name` or a description of what it does.
+    pub(super) fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.describe())
+    }
+
+    /// `config` with the source account of the step.
+    pub(super) fn config(&self, config: &config::Args) -> Result<config::Args, config::Error> {
+        let mut config = config.clone();
+        if let Some(source) = &self.source {
+            config.source_account = source.parse::<Address>()?;
+        }
+        Ok(config)
+    }
+
+    fn describe(&self) -> String {
+        match (&self.deploy, &self.invoke) {
+            (Some(deploy), _) => {
+                format!("deploy {}", deploy.alias.as_deref().unwrap_or("contract"))
+            }
+            (_, Some(invoke)) => format!("invoke {} {}", invoke.contract, invoke.function),
+            _ => String::new(),
+        }
+    }
+
+    async fn run_invoke(
+        &self,
+        invoke: &Invoke,
+        config: &config::Args,
+        fee: &fee::Args,
+        global_args: &global::Args,
+    ) -> Result<Option<String>, StepError> {
+        let cmd = invoke.cmd(config, fee)?;
+        let res = cmd
+            .invoke_with_events(Some(global_args), Some(config))
+            .await;
+        if let Some(expected) = &self.expect_error {
+            let res = res.map(|(res, _)| res);
+            cmd.check_error(expected, res, global_args).await?;
+            return Ok(None);
+        }
+        let (res, events) = res?;
+        let output = res.into_result().ok_or(StepError::NoResult)?;
+        if let Some(expected) = &self.expect {
+            let actual = invoke::parse_expected(&output);
+            if actual != *expected {
+                return Err(invoke::Error::UnexpectedReturnValue {
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                }
+                .into());
+            }
+        }
+        let network = config.get_network()?;
+        for expected in &self.expect_events {
+            let contract = expected
+                .contract
+                .as_ref()
+                .map(|contract| {
+                    contract
+                        .parse::<config::ContractAddress>()
+                        .unwrap_or_else(|e| match e {})
+                        .resolve_contract_id(&config.locator, &network.network_passphrase)
+                })
+                .transpose()?;
+            if !events
+                .iter()
+                .any(|event| expected.matches(event, contract.as_ref()))
+            {
+                return Err(StepError::MissingEvent {
+                    expected: serde_json::to_string(expected)?,
+                    actual: serde_json::to_string(&contract_events(&events))?,
+                });
+            }
+        }
+        Ok(Some(output))
+    }
+}
+
+impl Invoke {
+    /// The `contract invoke` command of the step.
+    pub(super) fn cmd(
+        &self,
+        config: &config::Args,
+        fee: &fee::Args,
+    ) -> Result<invoke::Cmd, serde_json::Error> {
+        let mut slop = vec![OsString::from(&self.function)];
+        slop.extend(args_to_slop(&self.args)?);
+        Ok(invoke::Cmd {
+            contract_id: self.contract.parse().unwrap_or_else(|e| match e {}),
+            slop,
+            config: config.clone(),
+            fee: fee.clone(),
+            send: match self.send {
+                None => invoke::Send::Default,
+                Some(true) => invoke::Send::Yes,
+                Some(false) => invoke::Send::No,
+            },
+            ..Default::default()
+        })
+    }
+}
+
+impl Deploy {
+    pub(super) async fn run(
+        &self,
+        config: &config::Args,
+        fee: &fee::Args,
+        base_dir: &Path,
+        global_args: &global::Args,
+    ) -> Result<String, StepError> {
+        let cmd = deploy::wasm::Cmd {
+            wasm: self.wasm.as_ref().map(|wasm| base_dir.join(wasm)),
+            wasm_hash: self.wasm_hash.clone(),
+            salt: self.salt.clone(),
+            config: config.clone(),
+            fee: fee.clone(),
+            preconditions: crate::commands::tx::preconditions::Args::default(),
+            ignore_checks: false,
+            alias: self.alias.clone(),
+            slop: args_to_slop(&self.args)?,
+            manifest: None,
+        };
+        let contract = cmd
+            .run_against_rpc_server(Some(global_args), Some(config))
+            .await?
+            .into_result()
+            .ok_or(StepError::NoResult)?;
+        if let Some(alias) = &self.alias {
+            let network = config.get_network()?;
+            config
+                .locator
+                .save_contract_id(&network.network_passphrase, &contract, alias)?;
+        }
+        Ok(contract.to_string())
+    }
+}
+
+impl ExpectedEvent {
+    fn matches(
+        &self,
+        event: &DiagnosticEvent,
+        contract: Option<&stellar_strkey::Contract>,
+    ) -> bool {
+        let event = &event.event;
+        if event.type_ != ContractEventType::Contract {
+            return false;
+        }
+        if let Some(contract) = contract {
+            if event.contract_id.as_ref().map(|id| id.0) != Some(contract.0) {
+                return false;
+            }
+        }
+        let ContractEventBody::V0(body) = &event.body;
+        if let Some(topics) = &self.topics {
+            let actual = body
+                .topics
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>();
+            if actual.ok().as_ref() != Some(topics) {
+                return false;
+            }
+        }
+        if let Some(data) = &self.data {
+            if serde_json::to_value(&body.data).ok().as_ref() != Some(data) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The contract events among `events`, to report when an expected event is missing.
+fn contract_events(events: &[DiagnosticEvent]) -> Vec<&ContractEventBody> {
+    events
+        .iter()
+        .filter(|event| event.event.type_ == ContractEventType::Contract)
+        .map(|event| &event.event.body)
+        .collect()
+}
+
+/// Turn named arguments into the `--name value` arguments of the implicit contract CLI. Strings
+/// are passed as is and other values as JSON.
+pub(super) fn args_to_slop(args: &toml::Table) -> Result<Vec<OsString>, serde_json::Error> {
+    let mut slop = Vec::new();
+    for (name, value) in args {
+        slop.push(format!("--{name}").into());
+        slop.push(match value {
+            toml::Value::String(value) => value.into(),
+            value => serde_json::to_string(value)?.into(),
+        });
+    }
+    Ok(slop)
+}
+
+impl Outcome {
+    fn line(&self, total: usize) -> String {
+        let status = if self.passed { "PASS" } else { "FAIL" };
+        let mut line = format!("{status} {}/{total} {}", self.step, self.name);
+        if let Some(error) = &self.error {
+            line.push_str(&format!(": {error}"));
+        } else if let Some(result) = self.result.as_deref().filter(|r| !r.is_empty()) {
+            line.push_str(&format!(" => {result}"));
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scenario() {
+        let scenario: Scenario = toml::from_str(
+            r#"
+            [[step]]
+            deploy = { wasm = "hello.wasm", alias = "hello" }
+
+            [[step]]
+            name = "say hello"
+            invoke = { contract = "hello", function = "hello", args = { world = "you", n = 2 } }
+            expect = ["Hello", "you"]
+            expect_events = [{ topics = [{ symbol = "hello" }] }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scenario.steps.len(), 2);
+        assert_eq!(scenario.steps[0].describe(), "deploy hello");
+        let invoke = scenario.steps[1].invoke.as_ref().unwrap();
+        assert_eq!(
+            args_to_slop(&invoke.args).unwrap(),
+            ["--n", "2", "--world", "you"].map(OsString::from)
+        );
+        assert_eq!(
+            scenario.steps[1].expect,
+            Some(serde_json::json!(["Hello", "you"]))
+        );
+    }
+
+    #[test]
+    fn reads_yaml_scenario() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scenario.yaml");
+        fs::write(
+            &path,
+            r#"
+step:
+  - deploy: { wasm: hello.wasm, alias: hello }
+  - name: say hello
+    invoke: { contract: hello, function: hello, args: { world: you, n: 2 } }
+    expect: [Hello, you]
+"#,
+        )
+        .unwrap();
+        let scenario = read_scenario(&path).unwrap();
+        assert_eq!(scenario.steps.len(), 2);
+        assert_eq!(scenario.steps[0].describe(), "deploy hello");
+        let invoke = scenario.steps[1].invoke.as_ref().unwrap();
+        assert_eq!(
+            args_to_slop(&invoke.args).unwrap(),
+            ["--n", "2", "--world", "you"].map(OsString::from)
+        );
+        assert_eq!(
+            scenario.steps[1].expect,
+            Some(serde_json::json!(["Hello", "you"]))
+        );
+    }
+}
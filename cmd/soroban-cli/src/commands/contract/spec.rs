@@ -0,0 +1,35 @@
+use crate::commands::global;
+
+pub mod fetch;
+pub mod publish;
+pub mod registry;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Publish the spec of a contract to a registry, for others to decode its invocations and
+    /// events without its Wasm
+    Publish(publish::Cmd),
+
+    /// Fetch the spec of a contract from a registry into the local cache of specs, by the hash
+    /// of the Wasm of the contract, used to decode its invocations, events, and storage
+    /// until it is upgraded
+    Fetch(fetch::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Publish(#[from] publish::Error),
+    #[error(transparent)]
+    Fetch(#[from] fetch::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Publish(publish) => publish.run(global_args).await?,
+            Cmd::Fetch(fetch) => fetch.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use clap::{command, Parser};
+
+use crate::{
+    commands::global,
+    config::{self, data, locator, network},
+    get_spec::{self, contract_executable},
+    print::Print,
+    rpc,
+    xdr::ContractExecutable,
+};
+
+use super::registry;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    Registry(#[from] registry::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error("{0} is a Stellar Asset Contract, its spec is built in")]
+    StellarAssetContract(String),
+    #[error("the registry returned the spec of {found} for {expected}")]
+    WrongContract { expected: String, found: String },
+    #[error("the spec of {contract_id} was published for network {found:?}, not {expected:?}")]
+    WrongNetwork {
+        contract_id: String,
+        expected: String,
+        found: String,
+    },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract id or alias of the contract to fetch the spec of
+    pub contract_id: config::ContractAddress,
+    #[command(flatten)]
+    pub registry: registry::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let contract = self
+            .contract_id
+            .resolve_contract_id(&self.config.locator, &network.network_passphrase)?;
+        let contract_id = contract.to_string();
+        let record = self.registry.registry().fetch(&contract_id).await?;
+        if record.contract_id != contract_id {
+            return Err(Error::WrongContract {
+                expected: contract_id,
+                found: record.contract_id,
+            });
+        }
+        if record.network_passphrase != network.network_passphrase {
+            return Err(Error::WrongNetwork {
+                contract_id,
+                expected: network.network_passphrase,
+                found: record.network_passphrase,
+            });
+        }
+        let entries = record.entries()?;
+        // Specs are cached by the hash of the Wasm of the contract, so that the spec is no longer
+        // used once the contract is upgraded.
+        let client = network.rpc_client()?;
+        let ContractExecutable::Wasm(wasm_hash) = contract_executable(&client, &contract.0).await?
+        else {
+            return Err(Error::StellarAssetContract(contract_id));
+        };
+        data::write_spec(&wasm_hash.to_string(), &entries)?;
+        print.checkln(format!(
+            "Fetched the spec of {contract_id} ({} entries), used to decode its invocations and events",
+            entries.len()
+        ));
+        Ok(())
+    }
+}
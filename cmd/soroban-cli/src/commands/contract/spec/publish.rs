@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use clap::{command, Parser};
+
+use crate::{
+    commands::global,
+    config::{self, locator, network},
+    get_spec::{self, get_remote_contract_spec},
+    print::Print,
+    wasm,
+};
+
+use super::registry::{self, Record, Registry};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error(transparent)]
+    Registry(#[from] registry::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract id or alias of the contract to publish the spec of
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: config::ContractAddress,
+    /// Wasm of the contract to read the spec from, otherwise the spec is read from the Wasm
+    /// the contract is deployed with
+    #[arg(long)]
+    pub wasm: Option<PathBuf>,
+    #[command(flatten)]
+    pub registry: registry::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&self.config.locator, &network.network_passphrase)?;
+        let (entries, wasm_hash) = if let Some(wasm) = &self.wasm {
+            let wasm = wasm::Args { wasm: wasm.clone() };
+            (wasm.parse()?.spec, Some(wasm.hash()?.to_string()))
+        } else {
+            let entries = get_remote_contract_spec(
+                &contract_id.0,
+                &self.config.locator,
+                &self.config.network,
+                Some(global_args),
+                None,
+            )
+            .await?;
+            (entries, None)
+        };
+        let record = Record::new(
+            &contract_id.to_string(),
+            &network.network_passphrase,
+            wasm_hash,
+            &entries,
+        )?;
+        let registry = self.registry.registry();
+        let location = registry.publish(&record).await?;
+        print.checkln(format!(
+            "Published the spec of {contract_id} ({} entries) to {location}",
+            entries.len()
+        ));
+        if let Registry::Dir(dir) = registry {
+            print.infoln(format!(
+                "Commit and push {} to share it",
+                dir.join(format!("{contract_id}.json")).display()
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+//! Registries of contract specs, a web server or a directory such as a checkout of a git
+//! repository, holding a `<contract-id>.json` record per contract.
+use std::path::PathBuf;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    config::locator,
+    utils::http,
+    xdr::{self, ScSpecEntry, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("request to {url} failed: {error}")]
+    Request { url: Url, error: reqwest::Error },
+    #[error("registry {url} responded with {status}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+    },
+    #[error("the registry has no spec of {0}")]
+    NotFound(String),
+    #[error("reading {path}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("writing {path}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("invalid record of {contract_id}: {error}")]
+    InvalidRecord {
+        contract_id: String,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Registry of contract specs, the URL of a web server or the path of a directory, e.g. a
+    /// checkout of a git repository shared with others
+    #[arg(long, env = "STELLAR_SPEC_REGISTRY")]
+    pub registry: String,
+}
+
+/// The spec of a contract as kept in a registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub contract_id: String,
+    pub network_passphrase: String,
+    /// Hash of the Wasm the spec was read from, when published from a local Wasm file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_hash: Option<String>,
+    /// The spec entries, base64 encoded XDR as in the `contractspecv0` section of the Wasm.
+    pub spec: String,
+}
+
+impl Record {
+    pub fn new(
+        contract_id: &str,
+        network_passphrase: &str,
+        wasm_hash: Option<String>,
+        entries: &[ScSpecEntry],
+    ) -> Result<Self, Error> {
+        let mut spec = Vec::new();
+        for entry in entries {
+            spec.extend(entry.to_xdr(xdr::Limits::none())?);
+        }
+        Ok(Record {
+            contract_id: contract_id.to_string(),
+            network_passphrase: network_passphrase.to_string(),
+            wasm_hash,
+            spec: BASE64.encode(spec),
+        })
+    }
+
+    pub fn entries(&self) -> Result<Vec<ScSpecEntry>, Error> {
+        let invalid = |error: Box<dyn std::error::Error + Send + Sync>| Error::InvalidRecord {
+            contract_id: self.contract_id.clone(),
+            error,
+        };
+        let spec = BASE64
+            .decode(&self.spec)
+            .map_err(|e| invalid(Box::new(e)))?;
+        soroban_spec::read::parse_raw(&spec).map_err(|e| invalid(Box::new(e)))
+    }
+}
+
+/// Where a registry is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Registry {
+    Url(Url),
+    Dir(PathBuf),
+}
+
+impl Args {
+    pub fn registry(&self) -> Registry {
+        match Url::parse(&self.registry) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Registry::Url(url),
+            _ => Registry::Dir(PathBuf::from(&self.registry)),
+        }
+    }
+}
+
+impl Registry {
+    fn url(base: &Url, contract_id: &str) -> Url {
+        let mut url = base.clone();
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        url.join(&format!("{contract_id}.json"))
+            .unwrap_or_else(|_| base.clone())
+    }
+
+    pub async fn fetch(&self, contract_id: &str) -> Result<Record, Error> {
+        let json = match self {
+            Registry::Url(base) => {
+                let url = Self::url(base, contract_id);
+                tracing::debug!("fetching {url}");
+                let request = |error| Error::Request {
+                    url: url.clone(),
+                    error,
+                };
+                let response = http::client()
+                    .get(url.clone())
+                    .send()
+                    .await
+                    .map_err(request)?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Err(Error::NotFound(contract_id.to_string()));
+                }
+                if !response.status().is_success() {
+                    return Err(Error::Status {
+                        status: response.status(),
+                        url,
+                    });
+                }
+                response.text().await.map_err(request)?
+            }
+            Registry::Dir(dir) => {
+                let path = dir.join(format!("{contract_id}.json"));
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => json,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(Error::NotFound(contract_id.to_string()))
+                    }
+                    Err(error) => return Err(Error::Read { path, error }),
+                }
+            }
+        };
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Publish `record`, with a `PUT` of its JSON to web servers. Returns where it is.
+    pub async fn publish(&self, record: &Record) -> Result<String, Error> {
+        let json = serde_json::to_string_pretty(record)?;
+        match self {
+            Registry::Url(base) => {
+                let url = Self::url(base, &record.contract_id);
+                tracing::debug!("publishing to {url}");
+                let response = http::client()
+                    .put(url.clone())
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(json)
+                    .send()
+                    .await
+                    .map_err(|error| Error::Request {
+                        url: url.clone(),
+                        error,
+                    })?;
+                if !response.status().is_success() {
+                    return Err(Error::Status {
+                        status: response.status(),
+                        url,
+                    });
+                }
+                Ok(url.to_string())
+            }
+            Registry::Dir(dir) => {
+                let path = dir.join(format!("{}.json", record.contract_id));
+                std::fs::create_dir_all(dir)
+                    .and_then(|()| locator::write_atomic(&path, json + "\n"))
+                    .map_err(|error| Error::Write {
+                        path: path.clone(),
+                        error,
+                    })?;
+                Ok(path.display().to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScSpecFunctionV0, ScSpecTypeDef};
+
+    #[tokio::test]
+    async fn publishes_and_fetches_from_a_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Args {
+            registry: dir.path().display().to_string(),
+        }
+        .registry();
+        assert_eq!(registry, Registry::Dir(dir.path().to_path_buf()));
+        let contract_id = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+        let entries = vec![ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "".try_into().unwrap(),
+            name: "hello".try_into().unwrap(),
+            inputs: vec![].try_into().unwrap(),
+            outputs: vec![ScSpecTypeDef::U32].try_into().unwrap(),
+        })];
+        let record = Record::new(contract_id, "x", None, &entries).unwrap();
+        registry.publish(&record).await.unwrap();
+        let fetched = registry.fetch(contract_id).await.unwrap();
+        assert_eq!(fetched, record);
+        assert_eq!(fetched.entries().unwrap(), entries);
+        assert!(matches!(
+            registry.fetch("CA").await,
+            Err(Error::NotFound(_))
+        ));
+        assert_eq!(
+            Registry::url(
+                &Url::parse("https://specs.example.org/v1").unwrap(),
+                contract_id
+            )
+            .as_str(),
+            format!("https://specs.example.org/v1/{contract_id}.json")
+        );
+    }
+}
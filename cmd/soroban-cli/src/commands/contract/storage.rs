@@ -0,0 +1,34 @@
+use crate::commands::global;
+
+pub mod entries;
+pub mod get;
+pub mod ls;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// List the storage entries of a contract with their decoded keys, values, and TTLs.
+    ///
+    /// Lists every entry of the instance storage. RPC cannot enumerate persistent and temporary
+    /// storage, so the entries listed are those of the keys given with `--key` or `--key-xdr`,
+    /// and of the cases without values of the contract's `*Key` unions, e.g. `DataKey::Admin`.
+    Ls(ls::Cmd),
+
+    /// Get a storage entry of a contract with its decoded value and TTL
+    Get(get::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Entries(#[from] entries::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Ls(ls) => ls.run(global_args).await?,
+            Cmd::Get(get) => get.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
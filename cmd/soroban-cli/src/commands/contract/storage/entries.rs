@@ -0,0 +1,481 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use soroban_spec_tools::Spec;
+
+use crate::{
+    commands::{global, snapshot::at_ledger},
+    config::{self, addressbook::Book, locator, network},
+    get_spec::get_remote_contract_spec,
+    print::Print,
+    rpc::{self, FullLedgerEntry},
+    xdr::{
+        self, ContractDataDurability, ContractDataEntry, Hash, LedgerEntryData, LedgerKey,
+        LedgerKeyContractData, Limits, ReadXdr, ScAddress, ScContractInstance, ScSpecEntry,
+        ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtUnionCaseV0, ScSymbol, ScVal,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    AtLedger(#[from] at_ledger::Error),
+    #[error("parsing key {key}: {error}")]
+    CannotParseKey {
+        key: String,
+        error: soroban_spec_tools::Error,
+    },
+    #[error("either `--key` or `--key-xdr` is required")]
+    KeyIsRequired,
+    #[error("no {0} storage entry found for the key")]
+    NotFound(Durability),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Contract id or alias of the contract
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: config::ContractAddress,
+    /// Storage key (symbols only)
+    #[arg(long = "key")]
+    pub key: Vec<String>,
+    /// Storage key (base64-encoded XDR)
+    #[arg(long = "key-xdr")]
+    pub key_xdr: Vec<String>,
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    /// Keep polling the storage and print the entries that were added, changed, or removed
+    #[arg(long, conflicts_with = "at_ledger")]
+    pub watch: bool,
+    /// Time between polls with `--watch`, e.g. `5s` or `1m`
+    #[arg(long, default_value = "5s", value_parser = humantime::parse_duration)]
+    pub interval: Duration,
+    #[command(flatten)]
+    pub at_ledger: at_ledger::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per entry with its durability, key, value, and TTL
+    Text,
+    /// One JSON object per entry
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Durability {
+    /// Instance storage, stored with the contract instance and sharing its TTL
+    Instance,
+    /// Persistent storage
+    Persistent,
+    /// Temporary storage
+    Temporary,
+}
+
+impl std::fmt::Display for Durability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Durability::Instance => "instance",
+            Durability::Persistent => "persistent",
+            Durability::Temporary => "temporary",
+        })
+    }
+}
+
+/// A decoded storage entry of a contract.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    pub durability: Durability,
+    pub key: Value,
+    pub value: Value,
+    pub live_until_ledger: u32,
+    /// Ledgers left until the entry is archived, or evicted when temporary.
+    pub ttl: i64,
+}
+
+/// The storage of a contract, with its spec to decode keys and values when it can be fetched.
+pub struct Storage {
+    client: rpc::Client,
+    network: network::Network,
+    at_ledger: at_ledger::Args,
+    print: Print,
+    contract: ScAddress,
+    spec: Option<Spec>,
+}
+
+impl Args {
+    pub async fn storage(&self, global_args: &global::Args) -> Result<Storage, Error> {
+        let network = self.config.get_network()?;
+        let contract = self
+            .contract_id
+            .resolve_contract_id(&self.config.locator, &network.network_passphrase)?;
+        let spec = get_remote_contract_spec(
+            &contract.0,
+            &self.config.locator,
+            &self.config.network,
+            Some(global_args),
+            None,
+        )
+        .await
+        .map_err(|e| tracing::debug!("cannot fetch the spec of {contract}: {e}"))
+        .ok()
+        .map(Spec::new);
+        Ok(Storage {
+            client: network.rpc_client()?,
+            network,
+            at_ledger: self.at_ledger.clone(),
+            print: Print::new(global_args.quiet),
+            contract: ScAddress::Contract(Hash(contract.0)),
+            spec,
+        })
+    }
+
+    pub fn keys(&self) -> Result<Vec<ScVal>, Error> {
+        let mut keys = self
+            .key
+            .iter()
+            .map(|key| {
+                soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol).map_err(
+                    |error| Error::CannotParseKey {
+                        key: key.clone(),
+                        error,
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for key in &self.key_xdr {
+            keys.push(ScVal::from_xdr_base64(key, Limits::none())?);
+        }
+        Ok(keys)
+    }
+
+    /// Print the entries returned by `fetch`, and with `--watch` keep polling and print the
+    /// entries that changed.
+    pub async fn print<F, Fut>(&self, fetch: F) -> Result<(), Error>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<Entry>, Error>>,
+    {
+        let mut previous = BTreeMap::new();
+        let book = Book::load(&self.config.locator);
+        loop {
+            let entries = fetch().await?;
+            let current = entries
+                .into_iter()
+                .map(|entry| ((entry.durability, entry.key.to_string()), entry))
+                .collect::<BTreeMap<_, _>>();
+            for (id, entry) in &current {
+                match previous.get(id) {
+                    None => self.print_entry(self.watch.then_some('+'), entry, &book)?,
+                    Some(old) if !same(old, entry) => self.print_entry(Some('~'), entry, &book)?,
+                    Some(_) => {}
+                }
+            }
+            for (id, entry) in &previous {
+                if !current.contains_key(id) {
+                    self.print_entry(Some('-'), entry, &book)?;
+                }
+            }
+            if !self.watch {
+                return Ok(());
+            }
+            previous = current;
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    fn print_entry(&self, change: Option<char>, entry: &Entry, book: &Book) -> Result<(), Error> {
+        match self.output {
+            Output::Json => {
+                let mut json = serde_json::to_value(entry)?;
+                if let Some(change) = change {
+                    json["change"] = Value::String(
+                        match change {
+                            '+' => "added",
+                            '-' => "removed",
+                            _ => "changed",
+                        }
+                        .to_string(),
+                    );
+                }
+                println!("{json}");
+            }
+            Output::Text => println!(
+                "{}{}",
+                change.map(|c| format!("{c} ")).unwrap_or_default(),
+                book.substitute(&entry.line())
+            ),
+        }
+        Ok(())
+    }
+}
+
+/// Whether an entry is unchanged, ignoring its TTL going down as ledgers close.
+fn same(old: &Entry, new: &Entry) -> bool {
+    old.value == new.value && old.live_until_ledger == new.live_until_ledger
+}
+
+impl Entry {
+    fn line(&self) -> String {
+        format!(
+            "{:<10} {} = {}  (live until ledger {}, {} ledgers left)",
+            self.durability, self.key, self.value, self.live_until_ledger, self.ttl
+        )
+    }
+}
+
+impl Storage {
+    /// Keys of the cases without values of the `*Key` unions of the contract, e.g.
+    /// `DataKey::Admin`, the usual way contracts name their storage keys.
+    pub fn spec_keys(&self) -> Vec<ScVal> {
+        let Some(Spec(Some(entries))) = &self.spec else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                ScSpecEntry::UdtUnionV0(union)
+                    if union.name.to_utf8_string_lossy().ends_with("Key") =>
+                {
+                    Some(union.cases.iter())
+                }
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|case| match case {
+                ScSpecUdtUnionCaseV0::VoidV0(void) => Some(ScVal::Vec(Some(
+                    vec![ScVal::Symbol(ScSymbol(
+                        void.name.to_utf8_string_lossy().try_into().ok()?,
+                    ))]
+                    .try_into()
+                    .ok()?,
+                ))),
+                ScSpecUdtUnionCaseV0::TupleV0(_) => None,
+            })
+            .collect()
+    }
+
+    /// Entries of the instance storage when `durabilities` includes it, and of `keys` in the
+    /// other storages of `durabilities`, sorted by durability and key.
+    pub async fn entries(
+        &self,
+        durabilities: &[Durability],
+        keys: &[ScVal],
+    ) -> Result<Vec<Entry>, Error> {
+        let data_key = |durability, key: &ScVal| {
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: self.contract.clone(),
+                key: key.clone(),
+                durability,
+            })
+        };
+        let mut ledger_keys = Vec::new();
+        if durabilities.contains(&Durability::Instance) {
+            ledger_keys.push(data_key(
+                ContractDataDurability::Persistent,
+                &ScVal::LedgerKeyContractInstance,
+            ));
+        }
+        for (durability, xdr_durability) in [
+            (Durability::Persistent, ContractDataDurability::Persistent),
+            (Durability::Temporary, ContractDataDurability::Temporary),
+        ] {
+            if durabilities.contains(&durability) {
+                ledger_keys.extend(keys.iter().map(|key| data_key(xdr_durability, key)));
+            }
+        }
+        if ledger_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = match self
+            .at_ledger
+            .full_ledger_entries(&self.print, &self.network, &ledger_keys)
+            .await?
+        {
+            Some(response) => response,
+            None => self.client.get_full_ledger_entries(&ledger_keys).await?,
+        };
+        let mut entries = Vec::new();
+        for FullLedgerEntry {
+            val,
+            live_until_ledger_seq,
+            ..
+        } in response.entries
+        {
+            let LedgerEntryData::ContractData(ContractDataEntry {
+                key,
+                val,
+                durability,
+                ..
+            }) = val
+            else {
+                continue;
+            };
+            let ttl = i64::from(live_until_ledger_seq) - response.latest_ledger;
+            let entry = |durability, key: &ScVal, value: &ScVal| Entry {
+                durability,
+                key: self.decode(key),
+                value: self.decode(value),
+                live_until_ledger: live_until_ledger_seq,
+                ttl,
+            };
+            match (&key, &val) {
+                (
+                    ScVal::LedgerKeyContractInstance,
+                    ScVal::ContractInstance(ScContractInstance { storage, .. }),
+                ) => {
+                    for item in storage.iter().flat_map(|map| map.iter()) {
+                        entries.push(entry(Durability::Instance, &item.key, &item.val));
+                    }
+                }
+                _ => entries.push(entry(
+                    match durability {
+                        ContractDataDurability::Persistent => Durability::Persistent,
+                        ContractDataDurability::Temporary => Durability::Temporary,
+                    },
+                    &key,
+                    &val,
+                )),
+            }
+        }
+        entries.sort_by(|a, b| {
+            (a.durability, a.key.to_string()).cmp(&(b.durability, b.key.to_string()))
+        });
+        Ok(entries)
+    }
+
+    /// Decode a key or value to JSON, as the union or struct of the spec it matches if any.
+    pub fn decode(&self, val: &ScVal) -> Value {
+        self.spec
+            .as_ref()
+            .and_then(|spec| {
+                let udt = spec_type(spec, val)?;
+                spec.xdr_to_json(val, &ScSpecTypeDef::Udt(ScSpecTypeUdt { name: udt }))
+                    .ok()
+            })
+            .or_else(|| soroban_spec_tools::to_json(val).ok())
+            .unwrap_or_else(|| serde_json::to_value(val).unwrap_or_default())
+    }
+}
+
+/// Name of the union with a case named after the first symbol of a vec, or of the struct with
+/// the fields of a map.
+fn spec_type(spec: &Spec, val: &ScVal) -> Option<xdr::StringM<60>> {
+    let entries = spec.0.as_ref()?;
+    match val {
+        ScVal::Vec(Some(vec)) => {
+            let ScVal::Symbol(case) = vec.first()? else {
+                return None;
+            };
+            entries.iter().find_map(|entry| match entry {
+                ScSpecEntry::UdtUnionV0(union)
+                    if union.cases.iter().any(|c| match c {
+                        ScSpecUdtUnionCaseV0::VoidV0(v) => v.name.as_slice() == case.as_slice(),
+                        ScSpecUdtUnionCaseV0::TupleV0(t) => t.name.as_slice() == case.as_slice(),
+                    }) =>
+                {
+                    Some(union.name.clone())
+                }
+                _ => None,
+            })
+        }
+        ScVal::Map(Some(map)) => {
+            let mut fields = map
+                .iter()
+                .map(|item| match &item.key {
+                    ScVal::Symbol(s) => Some(s.0.to_utf8_string_lossy()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            fields.sort();
+            entries.iter().find_map(|entry| match entry {
+                ScSpecEntry::UdtStructV0(s) => {
+                    let mut names = s
+                        .fields
+                        .iter()
+                        .map(|f| f.name.to_utf8_string_lossy())
+                        .collect::<Vec<_>>();
+                    names.sort();
+                    (names == fields).then(|| s.name.clone())
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        ScMapEntry, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseVoidV0,
+        ScSpecUdtUnionV0,
+    };
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    #[test]
+    fn matches_values_to_spec_types() {
+        let spec = Spec::new(vec![
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                doc: "".try_into().unwrap(),
+                lib: "".try_into().unwrap(),
+                name: "DataKey".try_into().unwrap(),
+                cases: vec![ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "Admin".try_into().unwrap(),
+                })]
+                .try_into()
+                .unwrap(),
+            }),
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                doc: "".try_into().unwrap(),
+                lib: "".try_into().unwrap(),
+                name: "State".try_into().unwrap(),
+                fields: vec![ScSpecUdtStructFieldV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "count".try_into().unwrap(),
+                    type_: ScSpecTypeDef::U32,
+                }]
+                .try_into()
+                .unwrap(),
+            }),
+        ]);
+        let key = ScVal::Vec(Some(vec![symbol("Admin")].try_into().unwrap()));
+        let state = ScVal::Map(Some(
+            vec![ScMapEntry {
+                key: symbol("count"),
+                val: ScVal::U32(1),
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        let name = |val| spec_type(&spec, val).map(|name| name.to_utf8_string_lossy());
+        assert_eq!(name(&key).as_deref(), Some("DataKey"));
+        assert_eq!(name(&state).as_deref(), Some("State"));
+        assert_eq!(name(&symbol("Admin")), None);
+    }
+}
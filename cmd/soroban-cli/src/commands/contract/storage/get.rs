@@ -0,0 +1,43 @@
+use clap::{arg, command, Parser};
+
+use super::entries::{self, Durability, Error};
+use crate::commands::global;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Storage of the entry
+    #[arg(long, value_enum, default_value = "persistent")]
+    pub durability: Durability,
+    #[command(flatten)]
+    pub args: entries::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let keys = self.args.keys()?;
+        if keys.is_empty() {
+            return Err(Error::KeyIsRequired);
+        }
+        let storage = self.args.storage(global_args).await?;
+        let decoded_keys = keys
+            .iter()
+            .map(|key| storage.decode(key))
+            .collect::<Vec<_>>();
+        self.args
+            .print(|| async {
+                let entries = storage
+                    .entries(&[self.durability], &keys)
+                    .await?
+                    .into_iter()
+                    // Instance storage is fetched whole.
+                    .filter(|entry| decoded_keys.contains(&entry.key))
+                    .collect::<Vec<_>>();
+                if entries.is_empty() && !self.args.watch {
+                    return Err(Error::NotFound(self.durability));
+                }
+                Ok(entries)
+            })
+            .await
+    }
+}
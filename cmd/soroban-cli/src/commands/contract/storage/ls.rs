@@ -0,0 +1,38 @@
+use clap::{arg, command, Parser};
+
+use super::entries::{self, Durability, Error};
+use crate::commands::global;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Storages to list, e.g. `--durability instance,persistent`. Lists all of them by default
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub durability: Vec<Durability>,
+    #[command(flatten)]
+    pub args: entries::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let storage = self.args.storage(global_args).await?;
+        let mut keys = self.args.keys()?;
+        for key in storage.spec_keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        let durabilities = if self.durability.is_empty() {
+            vec![
+                Durability::Instance,
+                Durability::Persistent,
+                Durability::Temporary,
+            ]
+        } else {
+            self.durability.clone()
+        };
+        self.args
+            .print(|| storage.entries(&durabilities, &keys))
+            .await
+    }
+}
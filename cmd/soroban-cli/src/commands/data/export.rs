@@ -0,0 +1,25 @@
+use crate::commands::global;
+
+pub mod transactions;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Export the transactions of a range of ledgers, read with `getTransactions`, one row per
+    /// transaction with its fees, operation count, and soroban resources
+    Transactions(transactions::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Transactions(#[from] transactions::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Transactions(cmd) => cmd.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::{arg, command, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::global,
+    config::{
+        self,
+        network::{self, rpc_version},
+    },
+    pagination,
+    parquet::{self, Column},
+    print::Print,
+    utils::envelope_hash,
+    xdr::{
+        self, FeeBumpTransactionInnerTx, Limits, MuxedAccount, ReadXdr, TransactionEnvelope,
+        TransactionExt, TransactionResult,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Pagination(#[from] pagination::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error("creating {path}: {error}")]
+    CannotCreateFile { path: PathBuf, error: io::Error },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First ledger to export the transactions of
+    #[arg(long)]
+    pub start_ledger: u32,
+    #[command(flatten)]
+    pub pagination: pagination::Args,
+    /// Format of the file
+    #[arg(long, value_enum, default_value = "csv")]
+    pub format: Format,
+    /// Write the transactions to this file instead of stdout
+    #[arg(long)]
+    pub out_file: Option<PathBuf>,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Comma separated values with a header row, written as the pages are read
+    Csv,
+    /// Parquet, written once every page is read
+    Parquet,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionInfo {
+    status: String,
+    #[serde(default)]
+    application_order: u32,
+    #[serde(default)]
+    fee_bump: bool,
+    envelope_xdr: String,
+    result_xdr: String,
+    ledger: u32,
+    #[serde(default)]
+    created_at: i64,
+    tx_hash: Option<String>,
+}
+
+/// A transaction with its key fields decoded, one row of the export.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct Row {
+    ledger: u32,
+    created_at: i64,
+    application_order: u32,
+    hash: String,
+    status: String,
+    fee_bump: bool,
+    source_account: String,
+    /// The account paying the fee, the fee source of fee bump transactions.
+    fee_account: String,
+    max_fee: i64,
+    fee_charged: i64,
+    operations: u32,
+    soroban: bool,
+    instructions: u32,
+    read_bytes: u32,
+    write_bytes: u32,
+    resource_fee: i64,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        network
+            .require_rpc(&rpc_version::GET_TRANSACTIONS, global_args.no_cache)
+            .await?;
+        let out: Box<dyn Write> = match &self.out_file {
+            Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|error| {
+                Error::CannotCreateFile {
+                    path: path.clone(),
+                    error,
+                }
+            })?)),
+            None => Box::new(io::stdout()),
+        };
+        let mut sink = match self.format {
+            Format::Csv => Sink::Csv(Box::new(csv::Writer::from_writer(out))),
+            Format::Parquet => Sink::Parquet(out, Vec::new()),
+        };
+        let mut exported = 0;
+        let mut pages = pagination::TransactionPages::new(
+            &network,
+            &self.pagination,
+            self.start_ledger,
+            None,
+            None,
+        );
+        'pages: while let Some(page) = pages.next::<TransactionInfo>().await? {
+            for tx in page {
+                if self.pagination.past_end(tx.ledger) {
+                    break 'pages;
+                }
+                let row = row(tx, &network.network_passphrase)?;
+                match &mut sink {
+                    Sink::Csv(csv) => csv.serialize(&row)?,
+                    Sink::Parquet(_, rows) => rows.push(row),
+                }
+                exported += 1;
+            }
+        }
+        match sink {
+            Sink::Csv(mut csv) => csv.flush()?,
+            Sink::Parquet(mut out, rows) => {
+                parquet::write(&mut out, &columns(&rows))?;
+                out.flush()?;
+            }
+        }
+        print.infoln(format!("Exported {exported} transactions"));
+        Ok(())
+    }
+}
+
+/// Where the rows go, written as they are read to CSV, or kept to write as Parquet columns.
+enum Sink {
+    Csv(Box<csv::Writer<Box<dyn Write>>>),
+    Parquet(Box<dyn Write>, Vec<Row>),
+}
+
+/// Decode the key fields of a transaction of a `getTransactions` page.
+fn row(tx: TransactionInfo, network_passphrase: &str) -> Result<Row, Error> {
+    let envelope = TransactionEnvelope::from_xdr_base64(&tx.envelope_xdr, Limits::none())?;
+    let result = TransactionResult::from_xdr_base64(&tx.result_xdr, Limits::none())?;
+    let hash = match tx.tx_hash {
+        Some(hash) => hash,
+        None => envelope_hash(&envelope, network_passphrase)?
+            .map(hex::encode)
+            .unwrap_or_default(),
+    };
+    let mut row = Row {
+        ledger: tx.ledger,
+        created_at: tx.created_at,
+        application_order: tx.application_order,
+        hash,
+        status: tx.status,
+        fee_bump: tx.fee_bump,
+        fee_charged: result.fee_charged,
+        ..Row::default()
+    };
+    let (ext, operations) = match &envelope {
+        TransactionEnvelope::TxV0(env) => {
+            row.source_account =
+                MuxedAccount::Ed25519(env.tx.source_account_ed25519.clone()).to_string();
+            row.fee_account = row.source_account.clone();
+            row.max_fee = env.tx.fee.into();
+            (None, env.tx.operations.len())
+        }
+        TransactionEnvelope::Tx(env) => {
+            row.source_account = env.tx.source_account.to_string();
+            row.fee_account = row.source_account.clone();
+            row.max_fee = env.tx.fee.into();
+            (Some(&env.tx.ext), env.tx.operations.len())
+        }
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            row.fee_bump = true;
+            row.source_account = inner.tx.source_account.to_string();
+            row.fee_account = env.tx.fee_source.to_string();
+            row.max_fee = env.tx.fee;
+            (Some(&inner.tx.ext), inner.tx.operations.len())
+        }
+    };
+    row.operations = u32::try_from(operations).unwrap_or(u32::MAX);
+    if let Some(TransactionExt::V1(data)) = ext {
+        row.soroban = true;
+        row.instructions = data.resources.instructions;
+        row.read_bytes = data.resources.read_bytes;
+        row.write_bytes = data.resources.write_bytes;
+        row.resource_fee = data.resource_fee;
+    }
+    Ok(row)
+}
+
+/// The rows as the columns of a Parquet file, in the order of the CSV header.
+fn columns(rows: &[Row]) -> Vec<(&'static str, Column)> {
+    let int = |value: fn(&Row) -> i64| Column::Int64(rows.iter().map(value).collect());
+    let bool = |value: fn(&Row) -> bool| Column::Boolean(rows.iter().map(value).collect());
+    let str = |value: fn(&Row) -> &str| {
+        Column::Utf8(rows.iter().map(|row| value(row).to_string()).collect())
+    };
+    vec![
+        ("ledger", int(|r| r.ledger.into())),
+        ("created_at", int(|r| r.created_at)),
+        ("application_order", int(|r| r.application_order.into())),
+        ("hash", str(|r| &r.hash)),
+        ("status", str(|r| &r.status)),
+        ("fee_bump", bool(|r| r.fee_bump)),
+        ("source_account", str(|r| &r.source_account)),
+        ("fee_account", str(|r| &r.fee_account)),
+        ("max_fee", int(|r| r.max_fee)),
+        ("fee_charged", int(|r| r.fee_charged)),
+        ("operations", int(|r| r.operations.into())),
+        ("soroban", bool(|r| r.soroban)),
+        ("instructions", int(|r| r.instructions.into())),
+        ("read_bytes", int(|r| r.read_bytes.into())),
+        ("write_bytes", int(|r| r.write_bytes.into())),
+        ("resource_fee", int(|r| r.resource_fee)),
+    ]
+}
@@ -0,0 +1,156 @@
+use clap::Parser;
+
+use crate::{
+    config::{self, address, asset},
+    horizon, xdr,
+};
+
+use super::{amm, global, tx};
+
+pub mod offer;
+pub mod orderbook;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown, clippy::large_enum_variant)]
+pub enum Cmd {
+    /// Create, cancel, and list the offers of an account on the decentralized exchange
+    /// Learn more about the decentralized exchange:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/sdex/liquidity-on-stellar-sdex-liquidity-pools
+    #[command(subcommand)]
+    Offer(offer::Cmd),
+    /// Show the bids and asks of an asset pair, with prices in the counter asset per unit of the
+    /// base asset, using Horizon
+    Orderbook(orderbook::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("invalid price {0:?}, expected a positive decimal such as `0.25` or a fraction such as `1/4`")]
+    InvalidPrice(String),
+    #[error("a market is between two different assets")]
+    SameAsset,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Offer(cmd) => cmd.run(global_args).await?,
+            Cmd::Orderbook(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+/// Parse a price given as a decimal such as `0.25` or a fraction such as `1/4`. Decimals whose
+/// exact fraction does not fit a price are approximated.
+pub fn parse_price(value: &str) -> Result<xdr::Price, Error> {
+    let invalid = || Error::InvalidPrice(value.to_string());
+    let (n, d) = if let Some((n, d)) = value.split_once('/') {
+        let n: u64 = n.trim().parse().map_err(|_| invalid())?;
+        let d: u64 = d.trim().parse().map_err(|_| invalid())?;
+        (n, d)
+    } else {
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+            || fraction.len() > 18
+        {
+            return Err(invalid());
+        }
+        let n = format!("{whole}{fraction}")
+            .parse()
+            .map_err(|_| invalid())?;
+        (
+            n,
+            10u64.pow(u32::try_from(fraction.len()).unwrap_or_default()),
+        )
+    };
+    if n == 0 || d == 0 {
+        return Err(invalid());
+    }
+    let divisor = gcd(n, d);
+    let (n, d) = (n / divisor, d / divisor);
+    match (i32::try_from(n), i32::try_from(d)) {
+        (Ok(n), Ok(d)) => Ok(xdr::Price { n, d }),
+        #[allow(clippy::cast_precision_loss)]
+        _ => Ok(amm::price(n as f64 / d as f64)),
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Format a price as a decimal with the 7 digits of amounts.
+pub fn format_price(price: &xdr::Price) -> String {
+    let scaled = i128::from(price.n) * 10_000_000 / i128::from(price.d.max(1));
+    horizon::amount(i64::try_from(scaled).unwrap_or(i64::MAX))
+}
+
+/// The `price_r` fraction of a Horizon offer or orderbook level.
+pub fn price_r(record: &serde_json::Value) -> Option<xdr::Price> {
+    Some(xdr::Price {
+        n: i32::try_from(record["price_r"]["n"].as_i64()?).ok()?,
+        d: i32::try_from(record["price_r"]["d"].as_i64()?).ok()?,
+    })
+}
+
+/// The offers created, updated, or deleted by the operations of a transaction result.
+pub fn offer_results(result: &xdr::TransactionResult) -> Vec<xdr::ManageOfferSuccessResult> {
+    let xdr::TransactionResultResult::TxSuccess(results) = &result.result else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .filter_map(|result| match result {
+            xdr::OperationResult::OpInner(
+                xdr::OperationResultTr::ManageSellOffer(xdr::ManageSellOfferResult::Success(s))
+                | xdr::OperationResultTr::CreatePassiveSellOffer(
+                    xdr::ManageSellOfferResult::Success(s),
+                )
+                | xdr::OperationResultTr::ManageBuyOffer(xdr::ManageBuyOfferResult::Success(s)),
+            ) => Some(s.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_prices() {
+        assert_eq!(parse_price("0.25").unwrap(), xdr::Price { n: 1, d: 4 });
+        assert_eq!(parse_price("3/6").unwrap(), xdr::Price { n: 1, d: 2 });
+        assert_eq!(parse_price("12").unwrap(), xdr::Price { n: 12, d: 1 });
+        assert_eq!(parse_price(".5").unwrap(), xdr::Price { n: 1, d: 2 });
+        let approximated = parse_price("0.333333333333").unwrap();
+        assert!(approximated.d < 1_000_000_000);
+        for invalid in ["0", "-1", "1/0", "abc", "", "."] {
+            assert!(parse_price(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(format_price(&xdr::Price { n: 1, d: 3 }), "0.3333333");
+    }
+}
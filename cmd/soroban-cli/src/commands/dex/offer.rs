@@ -0,0 +1,27 @@
+use crate::commands::global;
+
+pub mod cancel;
+pub mod create;
+pub mod ls;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Create an offer to sell or buy an asset for another, or update an existing offer with
+    /// `--offer-id`. Offers crossing the orderbook are filled immediately
+    Create(create::Cmd),
+    /// Cancel an offer of the source account
+    Cancel(cancel::Cmd),
+    /// List the open offers of an account, using Horizon
+    Ls(ls::Cmd),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), super::Error> {
+        match self {
+            Cmd::Create(cmd) => cmd.run(global_args).await?,
+            Cmd::Cancel(cmd) => cmd.run(global_args).await?,
+            Cmd::Ls(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    horizon, xdr,
+};
+
+use super::super::{price_r, Error};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Id of the offer to cancel, as listed by `dex offer ls`
+    #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+    pub offer_id: i64,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let client = self.horizon.client(&self.tx.config.get_network()?)?;
+        // Removing an offer takes the assets and price it was made with.
+        let offer = client
+            .get(&format!("offers/{}", self.offer_id), &[])
+            .await?;
+        let op = xdr::OperationBody::ManageSellOffer(xdr::ManageSellOfferOp {
+            selling: horizon::asset_from(&offer["selling"], "")?,
+            buying: horizon::asset_from(&offer["buying"], "")?,
+            amount: 0,
+            price: price_r(&offer).unwrap_or(xdr::Price { n: 1, d: 1 }),
+            offer_id: self.offer_id,
+        });
+        self.tx.handle_and_print(op, global_args).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx, txn_result::TxnEnvelopeResult},
+    config::asset::Asset,
+    print::Print,
+    xdr::{self, Limits, WriteXdr},
+};
+
+use super::super::{offer_results, parse_price, Error};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset to sell, `native` or `CODE:ISSUER`, where the issuer can be an identity
+    #[arg(long)]
+    pub selling: Asset,
+    /// Asset to buy, `native` or `CODE:ISSUER`, where the issuer can be an identity
+    #[arg(long)]
+    pub buying: Asset,
+    /// Amount in stroops of the selling asset to sell, or with `--buy` of the buying asset to buy.
+    /// Updating an offer with an amount of 0 cancels it
+    #[arg(long, value_parser = clap::value_parser!(i64).range(0..))]
+    pub amount: i64,
+    /// Price of one unit of the selling asset in the buying asset, or with `--buy` of one unit of
+    /// the buying asset in the selling asset, as a decimal such as `0.25` or a fraction such as
+    /// `1/4`
+    #[arg(long)]
+    pub price: String,
+    /// Fix the amount to buy rather than the amount to sell
+    #[arg(long, conflicts_with = "passive")]
+    pub buy: bool,
+    /// Create a passive offer, which does not take offers of the same price
+    #[arg(long, conflicts_with = "offer_id")]
+    pub passive: bool,
+    /// Id of an offer of the source account to update rather than creating one
+    #[arg(long, default_value_t = 0)]
+    pub offer_id: i64,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let tx = self.tx.tx(self.op()?).await?;
+        match self.tx.handle_tx(tx, global_args).await? {
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
+            TxnEnvelopeResult::Res(res) => {
+                let results = res.result.as_ref().map(offer_results).unwrap_or_default();
+                for result in results {
+                    if !result.offers_claimed.is_empty() {
+                        print.infoln(format!(
+                            "Filled against {} offers",
+                            result.offers_claimed.len()
+                        ));
+                    }
+                    match result.offer {
+                        xdr::ManageOfferSuccessResultOffer::Created(offer)
+                        | xdr::ManageOfferSuccessResultOffer::Updated(offer) => {
+                            println!("{}", offer.offer_id);
+                        }
+                        xdr::ManageOfferSuccessResultOffer::Deleted => {
+                            print.checkln("The offer was filled or cancelled, none remains open");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn op(&self) -> Result<xdr::OperationBody, Error> {
+        let config = &self.tx.config;
+        let selling = self.selling.resolve(&config.locator, config.hd_path)?.0;
+        let buying = self.buying.resolve(&config.locator, config.hd_path)?.0;
+        if selling == buying {
+            return Err(Error::SameAsset);
+        }
+        let price = parse_price(&self.price)?;
+        Ok(if self.buy {
+            xdr::OperationBody::ManageBuyOffer(xdr::ManageBuyOfferOp {
+                selling,
+                buying,
+                buy_amount: self.amount,
+                price,
+                offer_id: self.offer_id,
+            })
+        } else if self.passive {
+            xdr::OperationBody::CreatePassiveSellOffer(xdr::CreatePassiveSellOfferOp {
+                selling,
+                buying,
+                amount: self.amount,
+                price,
+            })
+        } else {
+            xdr::OperationBody::ManageSellOffer(xdr::ManageSellOfferOp {
+                selling,
+                buying,
+                amount: self.amount,
+                price,
+                offer_id: self.offer_id,
+            })
+        })
+    }
+}
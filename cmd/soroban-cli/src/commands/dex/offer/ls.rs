@@ -0,0 +1,118 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{self, address::Address},
+    horizon,
+};
+
+use super::super::{format_price, price_r, Error};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per offer
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account to list the offers of, e.g. `GBX...` or an identity, defaults to `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Maximum number of offers to list
+    #[arg(long, default_value = "200")]
+    pub limit: usize,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let account_id = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id();
+        let client = self.horizon.client(&self.config.get_network()?)?;
+        let query = [
+            ("seller", account_id.to_string()),
+            ("limit", self.limit.min(200).to_string()),
+        ];
+        let offers = client
+            .records("offers", &query, Some(self.limit))
+            .await?
+            .iter()
+            .map(offer)
+            .collect::<Result<Vec<_>, _>>()?;
+        match self.output {
+            Output::Json => println!("{}", serde_json::to_string_pretty(&offers)?),
+            Output::Text => {
+                if offers.is_empty() {
+                    println!("No offers");
+                }
+                for o in &offers {
+                    println!(
+                        "{} sell {} {} for {} at {}",
+                        o["id"].as_str().unwrap_or_default(),
+                        o["amount"].as_str().unwrap_or_default(),
+                        o["selling"].as_str().unwrap_or_default(),
+                        o["buying"].as_str().unwrap_or_default(),
+                        o["price"].as_str().unwrap_or_default(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The id, assets, amount, and price of a Horizon offer, with the assets as `CODE:ISSUER`.
+fn offer(record: &Value) -> Result<Value, Error> {
+    let price =
+        price_r(record).map_or_else(|| record["price"].clone(), |p| format_price(&p).into());
+    Ok(json!({
+        "id": record["id"],
+        "selling": horizon::asset_string(&horizon::asset_from(&record["selling"], "")?),
+        "buying": horizon::asset_string(&horizon::asset_from(&record["buying"], "")?),
+        "amount": record["amount"],
+        "price": price,
+        "sponsor": record["sponsor"],
+        "last_modified_ledger": record["last_modified_ledger"],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offer_from_horizon_record() {
+        let record = json!({
+            "id": "1234",
+            "seller": "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            "selling": { "asset_type": "native" },
+            "buying": {
+                "asset_type": "credit_alphanum4",
+                "asset_code": "USDC",
+                "asset_issuer": "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            },
+            "amount": "10.0000000",
+            "price_r": { "n": 1, "d": 8 },
+            "price": "0.1250000",
+        });
+        let offer = offer(&record).unwrap();
+        assert_eq!(offer["selling"], "native");
+        assert_eq!(
+            offer["buying"],
+            "USDC:GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+        );
+        assert_eq!(offer["price"], "0.1250000");
+    }
+}
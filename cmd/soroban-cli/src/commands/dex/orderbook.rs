@@ -0,0 +1,156 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{self, asset::Asset},
+    horizon, xdr,
+};
+
+use super::{format_price, price_r, Error};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Asks from the highest price down to bids from the highest price down
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base asset, whose amounts are shown, `native` or `CODE:ISSUER`, where the issuer can be an
+    /// identity
+    pub base: Asset,
+    /// Counter asset, in which prices are shown
+    pub counter: Asset,
+    /// Maximum number of price levels on each side
+    #[arg(long, default_value = "20", value_parser = clap::value_parser!(u8).range(1..=200))]
+    pub limit: u8,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let base = self.base.resolve(&self.config.locator, None)?.0;
+        let counter = self.counter.resolve(&self.config.locator, None)?.0;
+        if base == counter {
+            return Err(Error::SameAsset);
+        }
+        let client = self.horizon.client(&self.config.get_network()?)?;
+        let mut query = horizon::asset_params("selling_", &base);
+        query.extend(horizon::asset_params("buying_", &counter));
+        query.push(("limit".to_string(), self.limit.to_string()));
+        let query = query
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.clone()))
+            .collect::<Vec<_>>();
+        let book = client.get("order_book", &query).await?;
+        let side = |name: &str, bids: bool| -> Result<Vec<Value>, Error> {
+            book[name]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|level| normalize(level, bids))
+                .collect()
+        };
+        let (bids, asks) = (side("bids", true)?, side("asks", false)?);
+        let (base, counter) = (
+            horizon::asset_string(&base),
+            horizon::asset_string(&counter),
+        );
+        match self.output {
+            Output::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "base": base,
+                    "counter": counter,
+                    "bids": bids,
+                    "asks": asks,
+                }))?
+            ),
+            Output::Text => {
+                println!("{base} priced in {counter}");
+                println!("{:>20} {:>20} {:>20}", "price", "amount", "total");
+                for level in asks.iter().rev() {
+                    print_level("ask", level);
+                }
+                println!("{}", "-".repeat(66));
+                for level in &bids {
+                    print_level("bid", level);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_level(side: &str, level: &Value) {
+    let field = |name| level[name].as_str().unwrap_or_default().to_string();
+    println!(
+        "{:>20} {:>20} {:>20} {side}",
+        field("price"),
+        field("amount"),
+        field("total")
+    );
+}
+
+/// A price level with its price in the counter asset per unit of the base asset, its amount in
+/// the base asset, and its total in the counter asset. Horizon gives the amount of bids in the
+/// counter asset, as it is what they sell.
+fn normalize(level: &Value, bid: bool) -> Result<Value, Error> {
+    let price = price_r(level).ok_or_else(|| Error::InvalidPrice(level["price"].to_string()))?;
+    let amount = horizon::parse_amount(level["amount"].as_str().unwrap_or("0"))?;
+    let (base, counter) = if bid {
+        (
+            convert(
+                amount,
+                &xdr::Price {
+                    n: price.d,
+                    d: price.n,
+                },
+            ),
+            amount,
+        )
+    } else {
+        (amount, convert(amount, &price))
+    };
+    Ok(json!({
+        "price": format_price(&price),
+        "amount": horizon::amount(base),
+        "total": horizon::amount(counter),
+    }))
+}
+
+/// `amount` multiplied by `price`, rounded down.
+fn convert(amount: i64, price: &xdr::Price) -> i64 {
+    let value = i128::from(amount) * i128::from(price.n) / i128::from(price.d.max(1));
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_are_in_base_amounts_and_counter_prices() {
+        let bid =
+            json!({ "price_r": { "n": 1, "d": 4 }, "price": "0.2500000", "amount": "25.0000000" });
+        let bid = normalize(&bid, true).unwrap();
+        assert_eq!(bid["price"], "0.2500000");
+        assert_eq!(bid["amount"], "100.0000000");
+        assert_eq!(bid["total"], "25.0000000");
+
+        let ask =
+            json!({ "price_r": { "n": 1, "d": 2 }, "price": "0.5000000", "amount": "10.0000000" });
+        let ask = normalize(&ask, false).unwrap();
+        assert_eq!(ask["amount"], "10.0000000");
+        assert_eq!(ask["total"], "5.0000000");
+    }
+}
@@ -30,6 +30,10 @@ impl Cmd {
             lines.push(data);
         }
 
+        if let Some(data) = get("STELLAR_FEE_STRATEGY") {
+            lines.push(data);
+        }
+
         if lines.is_empty() {
             print.warnln("No defaults or environment variables set".to_string());
             return Ok(());
@@ -1,14 +1,24 @@
 use clap::{arg, command, Parser};
-use std::io;
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+use soroban_spec_tools::Spec;
 
 use crate::xdr::{self, Limits, ReadXdr};
 
 use super::{global, NetworkRunnable};
 use crate::{
-    config::{self, locator, network},
-    rpc,
+    config::{self, addressbook::Book, locator, network},
+    get_spec::get_remote_contract_spec,
+    pagination, rpc,
 };
 
+/// Largest number of events requested per page.
+const PAGE_LIMIT: usize = 1000;
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
@@ -27,8 +37,14 @@ pub struct Cmd {
     /// Output formatting options for event stream
     #[arg(long, value_enum, default_value = "pretty")]
     output: OutputFormat,
-    /// The maximum number of events to display (defer to the server-defined limit).
-    #[arg(short, long, default_value = "10")]
+    /// The maximum number of events to display, read across as many pages as needed.
+    #[arg(
+        short,
+        long,
+        visible_alias = "limit",
+        default_value = "10",
+        value_parser = pagination::parse_limit
+    )]
     count: usize,
     /// A set of (up to 5) contract IDs to filter events on. This parameter can
     /// be passed multiple times, e.g. `--id C123.. --id C456..`, or passed with
@@ -67,6 +83,12 @@ pub struct Cmd {
         help_heading = "FILTERS"
     )]
     event_type: rpc::EventType,
+    /// Decode events into named fields with the specs of their contracts, printed after each
+    /// event, or added to the JSON output as `decoded`
+    #[arg(long)]
+    decode: bool,
+    #[command(flatten)]
+    pagination: pagination::Args,
     #[command(flatten)]
     locator: locator::Args,
     #[command(flatten)]
@@ -134,7 +156,7 @@ pub enum OutputFormat {
 }
 
 impl Cmd {
-    pub async fn run(&mut self) -> Result<(), Error> {
+    pub async fn run(&mut self, global_args: &global::Args) -> Result<(), Error> {
         // Validate that topics are made up of segments.
         for topic in &self.topic_filters {
             for (i, segment) in topic.split(',').enumerate() {
@@ -158,29 +180,81 @@ impl Cmd {
 
         let response = self.run_against_rpc_server(None, None).await?;
 
+        let mut specs = HashMap::new();
+        let book = Book::load(&self.locator);
         for event in &response.events {
+            let decoded = if self.decode {
+                self.decode(event, &mut specs, global_args).await
+            } else {
+                None
+            };
             match self.output {
                 // Should we pretty-print the JSON like we're doing here or just
                 // dump an event in raw JSON on each line? The latter is easier
                 // to consume programmatically.
                 OutputFormat::Json => {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&event).map_err(|e| {
-                            Error::InvalidJson {
-                                debug: format!("{event:#?}"),
-                                error: e,
-                            }
-                        })?,
-                    );
+                    let mut json = serde_json::to_value(event).map_err(|e| Error::InvalidJson {
+                        debug: format!("{event:#?}"),
+                        error: e,
+                    })?;
+                    if let Some(decoded) = decoded {
+                        json["decoded"] = decoded;
+                    }
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+                OutputFormat::Plain => {
+                    print!("{}", book.substitute(&event.to_string()));
+                    if let Some(decoded) = decoded {
+                        println!("  Decoded:  {}", book.substitute(&decoded.to_string()));
+                    }
+                    println!();
+                }
+                OutputFormat::Pretty => {
+                    pretty_print(event, &book)?;
+                    if let Some(decoded) = decoded {
+                        println!("  Decoded:  {}", book.substitute(&decoded.to_string()));
+                    }
                 }
-                OutputFormat::Plain => println!("{event}"),
-                OutputFormat::Pretty => event.pretty_print()?,
             }
         }
         Ok(())
     }
 
+    /// Decode the topics and value of an event with the spec of its contract, if it can be found.
+    async fn decode(
+        &self,
+        event: &rpc::Event,
+        specs: &mut HashMap<String, Option<Spec>>,
+        global_args: &global::Args,
+    ) -> Option<serde_json::Value> {
+        if !specs.contains_key(&event.contract_id) {
+            let spec = self.contract_spec(&event.contract_id, global_args).await;
+            specs.insert(event.contract_id.clone(), spec);
+        }
+        let spec = specs.get(&event.contract_id)?.as_ref()?;
+        let topics = event
+            .topic
+            .iter()
+            .map(|topic| xdr::ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        let value = xdr::ScVal::from_xdr_base64(&event.value, Limits::none()).ok()?;
+        spec.decode_event(&topics, &value)
+            .map_err(|e| tracing::debug!("cannot decode event {}: {e}", event.id))
+            .ok()
+    }
+
+    /// Spec of a contract, fetched from the network.
+    async fn contract_spec(&self, contract_id: &str, global_args: &global::Args) -> Option<Spec> {
+        let id = stellar_strkey::Contract::from_string(contract_id).ok()?;
+        let entries =
+            get_remote_contract_spec(&id.0, &self.locator, &self.network, Some(global_args), None)
+                .await
+                .map_err(|e| tracing::debug!("cannot fetch spec of {contract_id}: {e}"))
+                .ok()?;
+        Some(Spec::new(entries))
+    }
+
     fn start(&self) -> Result<rpc::EventStart, Error> {
         let start = match (self.start_ledger, self.cursor.clone()) {
             (Some(start), _) => rpc::EventStart::Ledger(start),
@@ -192,6 +266,55 @@ impl Cmd {
     }
 }
 
+/// Print an event as [`rpc::Event::pretty_print`] does, with the labels of the address book
+/// instead of the addresses they label.
+fn pretty_print(event: &rpc::Event, book: &Book) -> Result<(), Error> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    if !stdout.supports_color() {
+        print!("{}", book.substitute(&event.to_string()));
+        return Ok(());
+    }
+    let green = |stdout: &mut StandardStream, text: &str| -> io::Result<()> {
+        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+        write!(stdout, "{text}")?;
+        stdout.reset()
+    };
+    stdout.set_color(ColorSpec::new().set_bold(true))?;
+    write!(stdout, "Event")?;
+    stdout.reset()?;
+    write!(stdout, " ")?;
+    green(&mut stdout, &event.paging_token)?;
+    write!(stdout, " [")?;
+    stdout.set_color(ColorSpec::new().set_bold(true).set_fg(Some(
+        match event.event_type.as_str() {
+            "system" => Color::Yellow,
+            _ => Color::Blue,
+        },
+    )))?;
+    write!(stdout, "{}", event.event_type.to_ascii_uppercase())?;
+    stdout.reset()?;
+    writeln!(stdout, "]:")?;
+    write!(stdout, "  Ledger:   ")?;
+    green(&mut stdout, &event.ledger.to_string())?;
+    write!(stdout, " (closed at ")?;
+    green(&mut stdout, &event.ledger_closed_at)?;
+    writeln!(stdout, ")")?;
+    write!(stdout, "  Contract: ")?;
+    green(&mut stdout, &book.substitute(&event.contract_id))?;
+    writeln!(stdout, "\n  Topics:")?;
+    for topic in &event.topic {
+        let scval = xdr::ScVal::from_xdr_base64(topic, Limits::none())?;
+        write!(stdout, "            ")?;
+        green(&mut stdout, &format!("{scval:?}"))?;
+        writeln!(stdout)?;
+    }
+    let scval = xdr::ScVal::from_xdr_base64(&event.value, Limits::none())?;
+    write!(stdout, "  Value: ")?;
+    green(&mut stdout, &format!("{scval:?}"))?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl NetworkRunnable for Cmd {
     type Error = Error;
@@ -224,15 +347,57 @@ impl NetworkRunnable for Cmd {
             })
             .collect::<Result<Vec<_>, Error>>()?;
 
-        Ok(client
-            .get_events(
-                start,
-                Some(self.event_type),
-                &contract_ids,
-                &self.topic_filters,
-                Some(self.count),
-            )
-            .await
-            .map_err(Error::Rpc)?)
+        let mut limiter = self.pagination.rate_limiter();
+        let mut start = start;
+        let mut response = rpc::GetEventsResponse {
+            events: Vec::new(),
+            latest_ledger: 0,
+        };
+        loop {
+            let remaining = self.count - response.events.len();
+            let page_limit = remaining.min(PAGE_LIMIT);
+            limiter.wait().await;
+            let page = client
+                .get_events(
+                    start,
+                    Some(self.event_type),
+                    &contract_ids,
+                    &self.topic_filters,
+                    Some(page_limit),
+                )
+                .await
+                .map_err(Error::Rpc)?;
+            response.latest_ledger = page.latest_ledger;
+            let page_len = page.events.len();
+            let Some(cursor) = page.events.last().map(|e| e.paging_token.clone()) else {
+                break;
+            };
+            let mut past_end = false;
+            for event in page.events {
+                if self.pagination.past_end(event.ledger) {
+                    past_end = true;
+                    break;
+                }
+                response.events.push(event);
+            }
+            if past_end || page_len < page_limit || response.events.len() >= self.count {
+                break;
+            }
+            start = rpc::EventStart::Cursor(cursor);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_is_at_least_one() {
+        let parse =
+            |count: &str| Cmd::try_parse_from(["events", "--start-ledger", "1", "--count", count]);
+        assert!(parse("0").is_err());
+        assert_eq!(parse("1").unwrap().count, 1);
     }
 }
@@ -0,0 +1,36 @@
+use clap::command;
+
+use crate::{commands::global, config::locator, fee::Strategy, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Set the default fee strategy.
+    #[arg(value_enum)]
+    pub strategy: Strategy,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let printer = Print::new(global_args.quiet);
+
+        self.config_locator
+            .write_default_fee_strategy(&self.strategy.to_string())?;
+
+        printer.infoln(format!(
+            "The default fee strategy is set to `{}`",
+            self.strategy,
+        ));
+
+        Ok(())
+    }
+}
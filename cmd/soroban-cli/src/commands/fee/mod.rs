@@ -0,0 +1,35 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod default;
+pub mod stats;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Set the default fee strategy used by commands that submit transactions when `--fee` is not
+    /// set, e.g. `p90` to price inclusion fees from recent ledgers during surge pricing.
+    /// Overridden by `--fee-strategy` and `STELLAR_FEE_STRATEGY`.
+    Default(default::Cmd),
+    /// Show the inclusion fee percentiles of recent ledgers for classic and soroban transactions
+    /// from `getFeeStats`, and the resource fees from the ledger config settings
+    Stats(stats::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Default(#[from] default::Error),
+    #[error(transparent)]
+    Stats(#[from] stats::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Default(cmd) => cmd.run(global_args)?,
+            Cmd::Stats(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
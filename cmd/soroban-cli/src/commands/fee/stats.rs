@@ -0,0 +1,236 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde::Serialize;
+
+use crate::{
+    config::{self, network},
+    fee::{self, Distribution, Stats},
+    rpc,
+    xdr::{
+        self, ConfigSettingEntry, ConfigSettingId, LedgerEntryData, LedgerKey,
+        LedgerKeyConfigSetting, Limits, ReadXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Fee(#[from] fee::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Tables of the inclusion fee percentiles and the resource fees
+    Text,
+    /// JSON
+    Json,
+}
+
+/// Resource fees of soroban transactions in stroops, from the ledger config settings.
+#[derive(Debug, Clone, Default, Serialize)]
+struct ResourceFees {
+    instructions_10k: i64,
+    read_entry: i64,
+    write_entry: i64,
+    read_1kb: i64,
+    write_1kb_bucket_list_low: i64,
+    write_1kb_bucket_list_high: i64,
+    historical_1kb: i64,
+    events_1kb: i64,
+    tx_size_1kb: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    latest_ledger: u32,
+    inclusion_fee: Distribution,
+    soroban_inclusion_fee: Distribution,
+    resource_fees: ResourceFees,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let network = self.config.get_network()?;
+        let stats = Stats::fetch(&network).await?;
+        let resource_fees = resource_fees(&network.rpc_client()?).await?;
+        let report = Report {
+            latest_ledger: stats.latest_ledger,
+            inclusion_fee: stats.inclusion_fee,
+            soroban_inclusion_fee: stats.soroban_inclusion_fee,
+            resource_fees,
+        };
+        match self.output {
+            Output::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            Output::Text => println!("{}", report.text()),
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    fn text(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Inclusion fees per operation in stroops, as of ledger {}",
+                self.latest_ledger
+            ),
+            format!(
+                "{:<8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                "",
+                "min",
+                "mode",
+                "p10",
+                "p50",
+                "p70",
+                "p90",
+                "p95",
+                "p99",
+                "max",
+                "txs",
+                "ledgers"
+            ),
+        ];
+        for (name, fees) in [
+            ("classic", &self.inclusion_fee),
+            ("soroban", &self.soroban_inclusion_fee),
+        ] {
+            lines.push(format!(
+                "{name:<8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                fees.min,
+                fees.mode,
+                fees.p10,
+                fees.p50,
+                fees.p70,
+                fees.p90,
+                fees.p95,
+                fees.p99,
+                fees.max,
+                fees.transaction_count,
+                fees.ledger_count,
+            ));
+        }
+        let r = &self.resource_fees;
+        lines.push(String::new());
+        lines.push("Resource fees in stroops".to_string());
+        for (name, fee) in [
+            ("per 10,000 instructions", r.instructions_10k),
+            ("per ledger entry read", r.read_entry),
+            ("per ledger entry written", r.write_entry),
+            ("per 1 KB read", r.read_1kb),
+            ("per 1 KB written, low", r.write_1kb_bucket_list_low),
+            ("per 1 KB written, high", r.write_1kb_bucket_list_high),
+            ("per 1 KB of history", r.historical_1kb),
+            ("per 1 KB of events", r.events_1kb),
+            ("per 1 KB of transaction", r.tx_size_1kb),
+        ] {
+            lines.push(format!("  {name:<26}{fee:>10}"));
+        }
+        lines.join("\n")
+    }
+}
+
+async fn resource_fees(client: &rpc::Client) -> Result<ResourceFees, Error> {
+    let keys = [
+        ConfigSettingId::ContractComputeV0,
+        ConfigSettingId::ContractLedgerCostV0,
+        ConfigSettingId::ContractHistoricalDataV0,
+        ConfigSettingId::ContractEventsV0,
+        ConfigSettingId::ContractBandwidthV0,
+    ]
+    .map(|config_setting_id| {
+        LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+    });
+    let mut fees = ResourceFees::default();
+    for entry in client
+        .get_ledger_entries(&keys)
+        .await?
+        .entries
+        .unwrap_or_default()
+    {
+        let LedgerEntryData::ConfigSetting(setting) =
+            LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())?
+        else {
+            continue;
+        };
+        match setting {
+            ConfigSettingEntry::ContractComputeV0(compute) => {
+                fees.instructions_10k = compute.fee_rate_per_instructions_increment;
+            }
+            ConfigSettingEntry::ContractLedgerCostV0(cost) => {
+                fees.read_entry = cost.fee_read_ledger_entry;
+                fees.write_entry = cost.fee_write_ledger_entry;
+                fees.read_1kb = cost.fee_read1_kb;
+                fees.write_1kb_bucket_list_low = cost.write_fee1_kb_bucket_list_low;
+                fees.write_1kb_bucket_list_high = cost.write_fee1_kb_bucket_list_high;
+            }
+            ConfigSettingEntry::ContractHistoricalDataV0(history) => {
+                fees.historical_1kb = history.fee_historical1_kb;
+            }
+            ConfigSettingEntry::ContractEventsV0(events) => {
+                fees.events_1kb = events.fee_contract_events1_kb;
+            }
+            ConfigSettingEntry::ContractBandwidthV0(bandwidth) => {
+                fees.tx_size_1kb = bandwidth.fee_tx_size1_kb;
+            }
+            _ => {}
+        }
+    }
+    Ok(fees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_report() {
+        let fees = |p90: &str| {
+            serde_json::from_value::<Distribution>(serde_json::json!({
+                "max": "500", "min": "100", "mode": "100", "p10": "100", "p20": "100",
+                "p30": "100", "p40": "100", "p50": "100", "p60": "100", "p70": "100",
+                "p80": "100", "p90": p90, "p95": "300", "p99": "400",
+                "transactionCount": "12", "ledgerCount": 50
+            }))
+            .unwrap()
+        };
+        let report = Report {
+            latest_ledger: 42,
+            inclusion_fee: fees("100"),
+            soroban_inclusion_fee: fees("250"),
+            resource_fees: ResourceFees {
+                instructions_10k: 25,
+                ..ResourceFees::default()
+            },
+        };
+        let text = report.text();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Inclusion fees per operation in stroops, as of ledger 42"
+        );
+        assert_eq!(
+            lines[3],
+            "soroban      100     100     100     100     100     250     300     400     500      12      50"
+        );
+        assert_eq!(lines[6], "  per 10,000 instructions           25");
+    }
+}
@@ -2,7 +2,7 @@ use clap::{
     arg,
     builder::styling::{AnsiColor, Effects, Styles},
 };
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use super::{config, HEADING_GLOBAL};
 
@@ -31,21 +31,56 @@ pub struct Args {
     #[arg(long, short = 'q', global = true, help_heading = HEADING_GLOBAL)]
     pub quiet: bool,
 
-    /// Log DEBUG events
-    #[arg(long, short = 'v', global = true, help_heading = HEADING_GLOBAL)]
-    pub verbose: bool,
+    /// Log DEBUG events, or DEBUG and TRACE events with `-vv`
+    #[arg(long, short = 'v', action = clap::ArgAction::Count, global = true, help_heading = HEADING_GLOBAL)]
+    pub verbose: u8,
 
     /// Log DEBUG and TRACE events
     #[arg(long, visible_alias = "vv", global = true, help_heading = HEADING_GLOBAL)]
     pub very_verbose: bool,
 
+    /// Write a structured log of the run in JSON lines to this file, with every RPC request and
+    /// response and secrets redacted. `-vv` writes it to the data directory, for `stellar bug-report`
+    #[arg(long, env = "STELLAR_LOG_FILE", global = true, help_heading = HEADING_GLOBAL)]
+    pub log_file: Option<PathBuf>,
+
+    /// Export the spans of the run, such as simulating, signing, submitting, and polling for
+    /// transactions, to this OpenTelemetry collector over OTLP/HTTP, e.g. `http://localhost:4318`.
+    /// Headers to send to it can be set with `OTEL_EXPORTER_OTLP_HEADERS`
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT", global = true, help_heading = HEADING_GLOBAL)]
+    pub otel_endpoint: Option<String>,
+
     /// List installed plugins. E.g. `stellar-hello`
     #[arg(long)]
     pub list: bool,
 
-    /// Do not cache your simulations and transactions
+    /// Do not cache your simulations and transactions, nor read or write the cache of Wasm,
+    /// contract specs, and network passphrases fetched from networks. Values in the cache that can
+    /// change on the network expire after `STELLAR_CACHE_TTL`, one hour by default
     #[arg(long, env = "STELLAR_NO_CACHE", global = true, help_heading = HEADING_GLOBAL)]
     pub no_cache: bool,
+
+    /// Submit transactions to Mainnet without asking for confirmation
+    #[arg(long, env = "STELLAR_YES", global = true, help_heading = HEADING_GLOBAL)]
+    pub yes: bool,
+
+    /// Never prompt, failing with the flag to pass instead, e.g. in CI. On when stdin or stdout is
+    /// not a terminal
+    #[arg(long, env = "STELLAR_NO_INPUT", global = true, help_heading = HEADING_GLOBAL)]
+    pub no_input: bool,
+
+    /// Abort the command if it has not finished after this many seconds, or a duration such as
+    /// `2m`, including its RPC calls and the wait for transactions to be included. The
+    /// transaction hash is printed if it may still land. `tx wait` waits 30s unless set
+    #[arg(long, env = "STELLAR_TIMEOUT", global = true, help_heading = HEADING_GLOBAL, value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, humantime::DurationError> {
+    s.parse().map_or_else(
+        |_| humantime::parse_duration(s),
+        |secs| Ok(Duration::from_secs(secs)),
+    )
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -67,12 +102,25 @@ impl Args {
     pub fn log_level(&self) -> Option<tracing::Level> {
         if self.quiet {
             None
-        } else if self.very_verbose {
+        } else if self.very_verbose() {
             Some(tracing::Level::TRACE)
-        } else if self.verbose {
+        } else if self.verbose > 0 {
             Some(tracing::Level::DEBUG)
         } else {
             Some(tracing::Level::INFO)
         }
     }
+
+    fn very_verbose(&self) -> bool {
+        self.very_verbose || self.verbose > 1
+    }
+
+    /// File to write the structured log of the run to, if any.
+    pub fn log_file(&self) -> Option<PathBuf> {
+        self.log_file.clone().or_else(|| {
+            self.very_verbose()
+                .then(crate::log::file::default_path)
+                .and_then(Result::ok)
+        })
+    }
 }
@@ -1,7 +1,11 @@
-use crate::commands::config::secret;
+use crate::{
+    commands::config::secret::{self, Secret},
+    passkey,
+    xdr::{self, Limits, WriteXdr},
+};
 
 use super::super::config::locator;
-use clap::arg;
+use clap::{arg, ValueEnum};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -13,6 +17,25 @@ pub enum Error {
 
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
+
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+
+    #[error("{0} is a secp256r1 key, which only has a hex public key")]
+    Secp256r1(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Account address, `G...`
+    #[default]
+    Strkey,
+    /// Raw ed25519 public key as hex
+    Hex,
+    /// Muxed account address of `--muxed-id`, `M...`
+    Muxed,
+    /// Address the account has as an invoker of contracts, the base64 XDR of an `ScVal::Address`
+    ContractAddress,
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -25,16 +48,53 @@ pub struct Cmd {
     #[arg(long)]
     pub hd_path: Option<usize>,
 
+    /// Format of the key to output
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Id of the muxed account address with `--format muxed`
+    #[arg(long, default_value_t = 0)]
+    pub muxed_id: u64,
+
     #[command(flatten)]
     pub locator: locator::Args,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.public_key()?);
+        // secp256r1 keys have no account address, so print the public key a wallet stores instead.
+        if let Ok(secret @ Secret::Secp256r1 { .. }) = self.locator.read_identity(&self.name) {
+            if !matches!(self.format, Format::Strkey | Format::Hex) {
+                return Err(Error::Secp256r1(self.name.clone()));
+            }
+            println!(
+                "{}",
+                hex::encode(passkey::public_key(&secret.secp256r1_key()?))
+            );
+            return Ok(());
+        }
+        println!("{}", self.formatted()?);
         Ok(())
     }
 
+    /// The public key in `--format`.
+    pub fn formatted(&self) -> Result<String, Error> {
+        let key = self.public_key()?;
+        Ok(match self.format {
+            Format::Strkey => key.to_string(),
+            Format::Hex => hex::encode(key.0),
+            Format::Muxed => stellar_strkey::ed25519::MuxedAccount {
+                ed25519: key.0,
+                id: self.muxed_id,
+            }
+            .to_string(),
+            Format::ContractAddress => xdr::ScVal::Address(xdr::ScAddress::Account(
+                xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key.0))),
+            ))
+            .to_xdr_base64(Limits::none())?,
+        })
+    }
+
     pub fn private_key(&self) -> Result<ed25519_dalek::SigningKey, Error> {
         Ok(self
             .locator
@@ -0,0 +1,44 @@
+use clap::{arg, command};
+
+use super::super::config::locator;
+use crate::{commands::global, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("An identity with the name '{0}' already exists, use --overwrite to replace it")]
+    IdentityAlreadyExists(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to copy
+    pub source: String,
+
+    /// Name of the copy
+    pub destination: String,
+
+    /// Overwrite the identity with the name of the copy if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if self.config.identity_exists(&self.destination)? && !self.overwrite {
+            return Err(Error::IdentityAlreadyExists(self.destination.clone()));
+        }
+        self.config.copy_identity(&self.source, &self.destination)?;
+        print.checkln(format!(
+            "Copied identity '{}' to '{}'",
+            self.source, self.destination
+        ));
+        Ok(())
+    }
+}
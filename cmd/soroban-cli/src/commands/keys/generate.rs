@@ -1,10 +1,11 @@
-use clap::{arg, command};
+use clap::{arg, builder::TypedValueParser, command, ValueEnum};
+use serde::Serialize;
 
 use super::super::config::{
     locator, network,
     secret::{self, Secret},
 };
-use crate::{commands::global, print::Print};
+use crate::{commands::global, passkey, print::Print};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -19,6 +20,29 @@ pub enum Error {
 
     #[error("An identity with the name '{0}' already exists")]
     IdentityAlreadyExists(String),
+
+    #[error("secp256r1 keys are not derived from a seed phrase, remove --seed, --default-seed, --as-secret, --hd-path, and --language")]
+    Secp256r1FromSeed,
+
+    #[error("--funded-set creates at most {} accounts, got {0}", TEST_ACCOUNTS.len())]
+    FundedSetTooLarge(usize),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Names of the accounts created with `--funded-set`, in the order of their hd paths.
+pub const TEST_ACCOUNTS: [&str; 10] = [
+    "alice", "bob", "carol", "dave", "eve", "frank", "grace", "heidi", "ivan", "judy",
+];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Algorithm {
+    /// Stellar account key, derived from a seed phrase
+    Ed25519,
+    /// secp256r1 (P-256) key, as used by passkeys to sign for smart wallet contracts.
+    /// It cannot be used as a Stellar account, so is never funded.
+    Secp256r1,
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -26,7 +50,20 @@ pub enum Error {
 #[allow(clippy::struct_excessive_bools)]
 pub struct Cmd {
     /// Name of identity
-    pub name: String,
+    #[arg(required_unless_present = "funded_set")]
+    pub name: Option<String>,
+
+    /// Create the deterministic test accounts alice, bob, carol, and so on, from the default seed
+    /// at hd paths 0, 1, 2, and so on, fund them, and print them as a JSON fixture for test
+    /// suites. Takes the number of accounts, 3 by default
+    #[arg(
+        long,
+        value_name = "COUNT",
+        num_args = 0..=1,
+        default_missing_value = "3",
+        conflicts_with_all = ["name", "seed", "default_seed", "as_secret", "hd_path", "words", "language", "alg"]
+    )]
+    pub funded_set: Option<usize>,
     /// Do not fund address
     #[arg(long)]
     pub no_fund: bool,
@@ -61,18 +98,53 @@ pub struct Cmd {
     /// Overwrite existing identity if it already exists.
     #[arg(long)]
     pub overwrite: bool,
+
+    /// Signature algorithm of the key
+    #[arg(long, value_enum, default_value = "ed25519")]
+    pub alg: Algorithm,
+
+    /// Number of words of the random seed phrase. With `--seed`, the length of the seed sets it
+    #[arg(
+        long,
+        default_value = "12",
+        value_parser = clap::builder::PossibleValuesParser::new(["12", "15", "18", "21", "24"])
+            .map(|words| words.parse::<usize>().unwrap_or(12)),
+        conflicts_with_all = ["seed", "default_seed"]
+    )]
+    pub words: usize,
+
+    /// BIP-39 wordlist of the seed phrase
+    #[arg(long, value_enum, default_value = "english")]
+    pub language: secret::Language,
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         let print = Print::new(global_args.quiet);
+        let Some(name) = &self.name else {
+            return self
+                .funded_set(self.funded_set.unwrap_or_default(), &print)
+                .await;
+        };
 
-        if self.config_locator.read_identity(&self.name).is_ok() {
-            if !self.overwrite {
-                return Err(Error::IdentityAlreadyExists(self.name.clone()));
-            }
+        self.check_overwrite(name, &print)?;
 
-            print.exclaimln(format!("Overwriting identity '{}'", &self.name));
+        if self.alg == Algorithm::Secp256r1 {
+            if self.seed.is_some()
+                || self.default_seed
+                || self.as_secret
+                || self.hd_path.is_some()
+                || self.language != secret::Language::English
+            {
+                return Err(Error::Secp256r1FromSeed);
+            }
+            let secret = Secret::generate_secp256r1();
+            self.config_locator.write_identity(name, &secret)?;
+            print.checkln(format!(
+                "Generated secp256r1 key with public key {}",
+                hex::encode(passkey::public_key(&secret.secp256r1_key()?))
+            ));
+            return Ok(());
         }
 
         if !self.fund {
@@ -84,11 +156,12 @@ impl Cmd {
             );
         }
 
-        let seed_phrase = if self.default_seed {
-            Secret::test_seed_phrase()
+        let seed = if self.default_seed {
+            Some("0000000000000000")
         } else {
-            Secret::from_seed(self.seed.as_deref())
-        }?;
+            self.seed.as_deref()
+        };
+        let seed_phrase = Secret::from_seed(seed, self.words, self.language)?;
 
         let secret = if self.as_secret {
             seed_phrase.private_key(self.hd_path)?.into()
@@ -96,7 +169,7 @@ impl Cmd {
             seed_phrase
         };
 
-        self.config_locator.write_identity(&self.name, &secret)?;
+        self.config_locator.write_identity(name, &secret)?;
 
         if !self.no_fund {
             let addr = secret.public_key(self.hd_path)?;
@@ -112,4 +185,85 @@ impl Cmd {
 
         Ok(())
     }
+
+    fn check_overwrite(&self, name: &str, print: &Print) -> Result<(), Error> {
+        if self.config_locator.read_identity(name).is_ok() {
+            if !self.overwrite {
+                return Err(Error::IdentityAlreadyExists(name.to_string()));
+            }
+
+            print.exclaimln(format!("Overwriting identity '{name}'"));
+        }
+        Ok(())
+    }
+
+    /// Create and fund the first `count` test accounts, and print the fixture of them.
+    async fn funded_set(&self, count: usize, print: &Print) -> Result<(), Error> {
+        let names = TEST_ACCOUNTS
+            .get(..count)
+            .ok_or(Error::FundedSetTooLarge(count))?;
+        for name in names {
+            self.check_overwrite(name, print)?;
+        }
+        let network = if self.no_fund {
+            self.network.get(&self.config_locator).ok()
+        } else {
+            Some(self.network.get(&self.config_locator)?)
+        };
+        let seed_phrase = Secret::test_seed_phrase()?;
+        let mut accounts = Vec::new();
+        for (hd_path, name) in names.iter().enumerate() {
+            let secret: Secret = seed_phrase.private_key(Some(hd_path))?.into();
+            self.config_locator.write_identity(name, &secret)?;
+            let public_key = secret.public_key(None)?;
+            let funded = match network.as_ref().filter(|_| !self.no_fund) {
+                Some(network) => match network.fund_address(&public_key).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        print.warnln(format!("Could not fund '{name}': {e}"));
+                        false
+                    }
+                },
+                None => false,
+            };
+            accounts.push(FixtureAccount {
+                name,
+                hd_path,
+                public_key: public_key.to_string(),
+                secret_key: secret.private_key(None)?.to_string(),
+                funded,
+            });
+        }
+        let Secret::SeedPhrase { seed_phrase } = seed_phrase else {
+            unreachable!("the test seed is a seed phrase");
+        };
+        let fixture = Fixture {
+            network_passphrase: network.as_ref().map(|n| n.network_passphrase.clone()),
+            rpc_url: network.as_ref().map(|n| n.rpc_url.clone()),
+            seed_phrase,
+            accounts,
+        };
+        println!("{}", serde_json::to_string_pretty(&fixture)?);
+        Ok(())
+    }
+}
+
+/// Accounts created with `--funded-set`, in the shape test suites read them.
+#[derive(Serialize)]
+struct Fixture {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network_passphrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_url: Option<String>,
+    seed_phrase: String,
+    accounts: Vec<FixtureAccount>,
+}
+
+#[derive(Serialize)]
+struct FixtureAccount {
+    name: &'static str,
+    hd_path: usize,
+    public_key: String,
+    secret_key: String,
+    funded: bool,
 }
@@ -1,11 +1,22 @@
-use clap::command;
+use clap::{command, ValueEnum};
+use futures::future::join_all;
+use serde_json::json;
 
-use super::super::config::locator;
+use super::super::config::{
+    locator::{self, Location},
+    network,
+    secret::Secret,
+};
+use crate::{horizon, passkey};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -14,14 +25,64 @@ pub struct Cmd {
     #[command(flatten)]
     pub config_locator: locator::Args,
 
+    /// List the identities with their addresses, kinds, and where they are stored
     #[arg(long, short = 'l')]
     pub long: bool,
+
+    /// Also list the XLM balance of each identity on the network, fetched concurrently
+    #[arg(long)]
+    pub balances: bool,
+
+    /// Which hierarchical deterministic path to derive the addresses of seed phrases with
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    /// Format of the output of `--long` and `--balances`
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// A table with a row per identity
+    Text,
+    /// A JSON array with an object per identity
+    Json,
+}
+
+/// An identity as listed by `--long`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub name: String,
+    /// The account address, or the hex public key of secp256r1 keys
+    pub address: Option<String>,
+    pub kind: &'static str,
+    pub hd_path: Option<usize>,
+    pub location: &'static str,
+    /// Balance in stroops, `None` when not fetched or the account is not funded
+    pub balance: Option<i64>,
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        let res = if self.long { self.ls_l() } else { self.ls() }?.join("\n");
-        println!("{res}");
+    pub async fn run(&self) -> Result<(), Error> {
+        if !self.long && !self.balances {
+            println!("{}", self.ls()?.join("\n"));
+            return Ok(());
+        }
+        let mut identities = self.identities()?;
+        if self.balances {
+            self.fetch_balances(&mut identities).await?;
+        }
+        match self.output {
+            Output::Text => println!("{}", table(&identities, self.balances)),
+            Output::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&json(&identities, self.balances))?
+            ),
+        }
         Ok(())
     }
 
@@ -30,12 +91,176 @@ impl Cmd {
         Ok(list)
     }
 
-    pub fn ls_l(&self) -> Result<Vec<String>, Error> {
+    pub fn identities(&self) -> Result<Vec<Identity>, Error> {
         Ok(self
             .config_locator
-            .list_identities_long()?
+            .list_identities_with_secrets()?
             .into_iter()
-            .map(|(name, location)| format!("{location}\nName: {name}\n"))
-            .collect::<Vec<String>>())
+            .map(|(name, secret, location)| {
+                let (kind, hd_path, address) = match &secret {
+                    Secret::SecretKey { .. } => ("secret key", None, secret.public_key(None).ok()),
+                    Secret::SeedPhrase { .. } => (
+                        "seed phrase",
+                        Some(self.hd_path.unwrap_or_default()),
+                        secret.public_key(self.hd_path).ok(),
+                    ),
+                    Secret::Secp256r1 { .. } => {
+                        let address = secret
+                            .secp256r1_key()
+                            .ok()
+                            .map(|key| hex::encode(passkey::public_key(&key)));
+                        return Identity {
+                            name,
+                            address,
+                            kind: "secp256r1",
+                            hd_path: None,
+                            location: location_name(&location),
+                            balance: None,
+                        };
+                    }
+                };
+                Identity {
+                    name,
+                    address: address.map(|a| a.to_string()),
+                    kind,
+                    hd_path,
+                    location: location_name(&location),
+                    balance: None,
+                }
+            })
+            .collect())
+    }
+
+    async fn fetch_balances(&self, identities: &mut [Identity]) -> Result<(), Error> {
+        let client = self.network.get(&self.config_locator)?.rpc_client()?;
+        let balances = join_all(identities.iter().map(|identity| {
+            let client = &client;
+            async move {
+                let address = identity
+                    .address
+                    .as_ref()
+                    .filter(|_| identity.kind != "secp256r1")?;
+                client.get_account(address).await.ok().map(|a| a.balance)
+            }
+        }))
+        .await;
+        for (identity, balance) in identities.iter_mut().zip(balances) {
+            identity.balance = balance;
+        }
+        Ok(())
+    }
+}
+
+fn location_name(location: &Location) -> &'static str {
+    match location {
+        Location::Local(_) => "local",
+        Location::Global(_) => "global",
+    }
+}
+
+fn table(identities: &[Identity], balances: bool) -> String {
+    let mut header = vec!["NAME", "ADDRESS", "KIND", "HD PATH", "LOCATION"];
+    if balances {
+        header.push("BALANCE (XLM)");
+    }
+    let rows = identities.iter().map(|identity| {
+        let mut row = vec![
+            identity.name.clone(),
+            identity.address.clone().unwrap_or_else(|| "-".to_string()),
+            identity.kind.to_string(),
+            identity
+                .hd_path
+                .map_or_else(|| "-".to_string(), |p| p.to_string()),
+            identity.location.to_string(),
+        ];
+        if balances {
+            row.push(
+                identity
+                    .balance
+                    .map_or_else(|| "-".to_string(), horizon::amount),
+            );
+        }
+        row
+    });
+    let rows = std::iter::once(header.into_iter().map(String::from).collect::<Vec<_>>())
+        .chain(rows)
+        .collect::<Vec<_>>();
+    let widths = (0..rows[0].len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn json(identities: &[Identity], balances: bool) -> serde_json::Value {
+    identities
+        .iter()
+        .map(|identity| {
+            let mut value = json!({
+                "name": identity.name,
+                "address": identity.address,
+                "kind": identity.kind,
+                "hd_path": identity.hd_path,
+                "location": identity.location,
+            });
+            if balances {
+                value["balance"] = json!(identity.balance.map(horizon::amount));
+            }
+            value
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_aligns_columns() {
+        let identities = [
+            Identity {
+                name: "alice".to_string(),
+                address: Some("GABC".to_string()),
+                kind: "seed phrase",
+                hd_path: Some(0),
+                location: "global",
+                balance: Some(100_000_000),
+            },
+            Identity {
+                name: "passkey".to_string(),
+                address: None,
+                kind: "secp256r1",
+                hd_path: None,
+                location: "local",
+                balance: None,
+            },
+        ];
+        assert_eq!(
+            table(&identities, true).lines().collect::<Vec<_>>(),
+            [
+                "NAME     ADDRESS  KIND         HD PATH  LOCATION  BALANCE (XLM)",
+                "alice    GABC     seed phrase  0        global    10.0000000",
+                "passkey  -        secp256r1    -        local     -",
+            ]
+        );
+        assert_eq!(
+            json(&identities, false)[1]["address"],
+            serde_json::Value::Null
+        );
     }
 }
@@ -3,36 +3,64 @@ use clap::Parser;
 
 pub mod add;
 pub mod address;
+pub mod cp;
 pub mod default;
 pub mod fund;
 pub mod generate;
 pub mod ls;
+pub mod rename;
 pub mod rm;
+pub mod seq;
 pub mod show;
+pub mod show_secret;
+pub mod sign_message;
+pub mod verify_message;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
     /// Add a new identity (keypair, ledger, macOS keychain)
     Add(add::Cmd),
 
-    /// Given an identity return its address (public key)
+    /// Given an identity return its address (public key), or its public key in another format
+    #[command(visible_alias = "public-key")]
     Address(address::Cmd),
 
+    /// Copy an identity to a new name
+    Cp(cp::Cmd),
+
     /// Fund an identity on a test network
     Fund(fund::Cmd),
 
-    /// Generate a new identity with a seed phrase, currently 12 words
+    /// Generate a new identity with a seed phrase, 12 words of the English wordlist by default
     Generate(generate::Cmd),
 
     /// List identities
     Ls(ls::Cmd),
 
-    /// Remove an identity
+    /// Rename an identity, keeping it the default identity if it is
+    Rename(rename::Cmd),
+
+    /// Remove an identity, asking for confirmation unless `--force` is given
     Rm(rm::Cmd),
 
+    /// Manage the sequence numbers reserved locally with `--seq-strategy pool`
+    #[command(subcommand)]
+    Seq(seq::Cmd),
+
     /// Given an identity return its private key
     Show(show::Cmd),
 
+    /// Reveal the seed phrase or secret key of an identity after confirmation, optionally copying
+    /// it to the clipboard and clearing it after a while
+    ShowSecret(show_secret::Cmd),
+
+    /// Sign a message with an identity as described in SEP-53, e.g. to prove ownership of an
+    /// account off-chain
+    SignMessage(sign_message::Cmd),
+
+    /// Verify the SEP-53 signature of a message by an account
+    VerifyMessage(verify_message::Cmd),
+
     /// Set the default identity that will be used on all commands.
     /// This allows you to skip `--source-account` or setting a environment
     /// variable, while reusing this value in all commands that require it.
@@ -48,21 +76,39 @@ pub enum Error {
     #[error(transparent)]
     Address(#[from] address::Error),
 
+    #[error(transparent)]
+    Cp(#[from] cp::Error),
+
     #[error(transparent)]
     Fund(#[from] fund::Error),
 
     #[error(transparent)]
     Generate(#[from] generate::Error),
 
+    #[error(transparent)]
+    Rename(#[from] rename::Error),
+
     #[error(transparent)]
     Rm(#[from] rm::Error),
 
     #[error(transparent)]
     Ls(#[from] ls::Error),
 
+    #[error(transparent)]
+    Seq(#[from] seq::Error),
+
     #[error(transparent)]
     Show(#[from] show::Error),
 
+    #[error(transparent)]
+    ShowSecret(#[from] show_secret::Error),
+
+    #[error(transparent)]
+    SignMessage(#[from] sign_message::Error),
+
+    #[error(transparent)]
+    VerifyMessage(#[from] verify_message::Error),
+
     #[error(transparent)]
     Default(#[from] default::Error),
 }
@@ -72,11 +118,17 @@ impl Cmd {
         match self {
             Cmd::Add(cmd) => cmd.run()?,
             Cmd::Address(cmd) => cmd.run()?,
+            Cmd::Cp(cmd) => cmd.run(global_args)?,
             Cmd::Fund(cmd) => cmd.run().await?,
             Cmd::Generate(cmd) => cmd.run(global_args).await?,
-            Cmd::Ls(cmd) => cmd.run()?,
-            Cmd::Rm(cmd) => cmd.run()?,
+            Cmd::Ls(cmd) => cmd.run().await?,
+            Cmd::Rename(cmd) => cmd.run(global_args)?,
+            Cmd::Rm(cmd) => cmd.run(global_args)?,
+            Cmd::Seq(cmd) => cmd.run(global_args).await?,
             Cmd::Show(cmd) => cmd.run()?,
+            Cmd::ShowSecret(cmd) => cmd.run(global_args).await?,
+            Cmd::SignMessage(cmd) => cmd.run()?,
+            Cmd::VerifyMessage(cmd) => cmd.run(global_args)?,
             Cmd::Default(cmd) => cmd.run(global_args)?,
         };
         Ok(())
@@ -0,0 +1,49 @@
+use clap::{arg, command};
+
+use super::super::config::{locator, Config};
+use crate::{commands::global, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("An identity with the name '{0}' already exists, use --overwrite to replace it")]
+    IdentityAlreadyExists(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to rename
+    pub old: String,
+
+    /// New name of the identity
+    pub new: String,
+
+    /// Overwrite the identity with the new name if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if self.old == self.new {
+            return Ok(());
+        }
+        if self.config.identity_exists(&self.new)? && !self.overwrite {
+            return Err(Error::IdentityAlreadyExists(self.new.clone()));
+        }
+        self.config.copy_identity(&self.old, &self.new)?;
+        self.config.remove_identity(&self.old)?;
+        if Config::new()?.defaults.identity.as_deref() == Some(self.old.as_str()) {
+            self.config.write_default_identity(&self.new)?;
+            print.infoln(format!("The default identity is now '{}'", self.new));
+        }
+        print.checkln(format!("Renamed identity '{}' to '{}'", self.old, self.new));
+        Ok(())
+    }
+}
@@ -1,11 +1,22 @@
-use clap::command;
+use std::io::{self, BufRead, Write};
+
+use clap::{arg, command};
 
 use super::super::config::locator;
+use crate::{commands::global, input, print::Print};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error("Failed to find identity '{0}'")]
+    NotFound(String),
+    #[error(
+        "Not removing identity '{0}' without confirmation, use --force with --no-input or when not run in a terminal"
+    )]
+    ConfirmationRequired(String),
+    #[error("Failed to read confirmation: {0}")]
+    Confirmation(io::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -14,12 +25,44 @@ pub struct Cmd {
     /// Identity to remove
     pub name: String,
 
+    /// Remove the identity without asking for confirmation
+    #[arg(long)]
+    pub force: bool,
+
     #[command(flatten)]
     pub config: locator::Args,
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        Ok(self.config.remove_identity(&self.name)?)
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if !self.config.identity_exists(&self.name)? {
+            return Err(Error::NotFound(self.name.clone()));
+        }
+        if !self.force && !self.confirm()? {
+            print.infoln(format!("Kept identity '{}'", self.name));
+            return Ok(());
+        }
+        self.config.remove_identity(&self.name)?;
+        print.checkln(format!("Removed identity '{}'", self.name));
+        Ok(())
+    }
+
+    fn confirm(&self) -> Result<bool, Error> {
+        let stdin = io::stdin();
+        if !input::interactive() {
+            return Err(Error::ConfirmationRequired(self.name.clone()));
+        }
+        eprint!(
+            "Remove identity '{}'? Its secret cannot be recovered unless backed up [y/N] ",
+            self.name
+        );
+        io::stderr().flush().map_err(Error::Confirmation)?;
+        let mut answer = String::new();
+        stdin
+            .lock()
+            .read_line(&mut answer)
+            .map_err(Error::Confirmation)?;
+        Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
     }
 }
@@ -0,0 +1,25 @@
+use crate::commands::global;
+
+pub mod reset;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Release the sequence numbers reserved locally for an identity with
+    /// `--seq-strategy pool`, resyncing with its sequence number on the network
+    Reset(reset::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Reset(#[from] reset::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Reset(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use clap::{command, Parser};
+
+use crate::{
+    commands::global,
+    config::{self, address::Address, network, sequence},
+    print::Print,
+    rpc,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Sequence(#[from] sequence::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity or account to reset the sequence numbers of, e.g. `alice` or `GBX...`
+    pub account: Address,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let account = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id()
+            .to_string();
+        let network = self.config.get_network()?;
+        let released = sequence::Pool::new(&network.network_passphrase, &account)?.reset()?;
+        let current = network.rpc_client()?.get_account(&account).await?.seq_num.0;
+        print.checkln(format!(
+            "Released {released} reserved sequence numbers, the next one is {}",
+            current + 1
+        ));
+        Ok(())
+    }
+}
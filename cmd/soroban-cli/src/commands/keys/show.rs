@@ -1,6 +1,9 @@
 use clap::arg;
 
-use crate::config::{locator, secret};
+use crate::config::{
+    locator,
+    secret::{self, Secret},
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -30,6 +33,13 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
+        if let Secret::Secp256r1 {
+            secp256r1_secret_key,
+        } = self.locator.read_identity(&self.name)?
+        {
+            println!("{secp256r1_secret_key}");
+            return Ok(());
+        }
         println!("{}", self.private_key()?.to_string());
         Ok(())
     }
@@ -0,0 +1,192 @@
+use std::{
+    io::{self, BufRead, Write},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use clap::{arg, command};
+
+use crate::{
+    commands::global,
+    config::{
+        locator,
+        secret::{self, Secret},
+    },
+    input,
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(
+        "Not revealing the secret of identity '{0}' without confirmation, use --force with --no-input or when not run in a terminal"
+    )]
+    ConfirmationRequired(String),
+    #[error("Failed to read confirmation: {0}")]
+    Confirmation(io::Error),
+    #[error("No clipboard tool found, install one of {0}")]
+    NoClipboard(String),
+    #[error("Copying to the clipboard with {tool}: {error}")]
+    Clipboard { tool: String, error: io::Error },
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to reveal the secret of
+    pub name: String,
+
+    /// Reveal the secret key derived at this hd path instead of the seed phrase of an identity
+    /// created from one
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    /// Copy the secret to the clipboard instead of printing it
+    #[arg(long)]
+    pub copy: bool,
+
+    /// With `--copy`, clear the clipboard after this long, e.g. `30s` or `2m`. Use `0s` to keep
+    /// the secret in the clipboard
+    #[arg(long, default_value = "30s", value_parser = humantime::parse_duration, requires = "copy")]
+    pub clear_after: Duration,
+
+    /// Reveal the secret without asking for confirmation. Unlike the global `--yes`, it has no
+    /// environment variable, so it is never set for every command
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let secret = self.secret()?;
+        if !self.force && !self.confirm()? {
+            print.infoln("Secret not revealed");
+            return Ok(());
+        }
+        if !self.copy {
+            println!("{secret}");
+            return Ok(());
+        }
+        copy(&secret)?;
+        if self.clear_after.is_zero() {
+            print.checkln(format!(
+                "Copied the secret of '{}' to the clipboard",
+                self.name
+            ));
+            return Ok(());
+        }
+        print.checkln(format!(
+            "Copied the secret of '{}' to the clipboard, clearing it in {}",
+            self.name,
+            humantime::format_duration(self.clear_after)
+        ));
+        // Ctrl-C and `--timeout` cancel the command while it waits, dropping the guard
+        let guard = ClearClipboard;
+        tokio::time::sleep(self.clear_after).await;
+        std::mem::forget(guard);
+        copy("")?;
+        print.checkln("Cleared the clipboard");
+        Ok(())
+    }
+
+    /// The seed phrase or secret key of the identity.
+    fn secret(&self) -> Result<String, Error> {
+        Ok(match self.config.read_identity(&self.name)? {
+            Secret::SeedPhrase { seed_phrase } if self.hd_path.is_none() => seed_phrase,
+            Secret::Secp256r1 {
+                secp256r1_secret_key,
+            } => secp256r1_secret_key,
+            secret => secret.private_key(self.hd_path)?.to_string(),
+        })
+    }
+
+    fn confirm(&self) -> Result<bool, Error> {
+        let stdin = io::stdin();
+        if !input::interactive() {
+            return Err(Error::ConfirmationRequired(self.name.clone()));
+        }
+        eprint!(
+            "Reveal the secret of identity '{}'? Anyone who sees it can spend its funds [y/N] ",
+            self.name
+        );
+        io::stderr().flush().map_err(Error::Confirmation)?;
+        let mut answer = String::new();
+        stdin
+            .lock()
+            .read_line(&mut answer)
+            .map_err(Error::Confirmation)?;
+        Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+    }
+}
+
+/// Clears the clipboard when dropped, for commands cancelled on Ctrl-C or `--timeout` while the
+/// secret is in it.
+struct ClearClipboard;
+
+impl Drop for ClearClipboard {
+    fn drop(&mut self) {
+        if let Err(e) = copy("") {
+            tracing::error!("Clearing the clipboard: {e}");
+        }
+    }
+}
+
+/// Clipboard tools of the platform, tried in order, with the arguments to copy their stdin.
+fn clipboard_tools() -> &'static [(&'static str, &'static [&'static str])] {
+    if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(windows) {
+        &[("clip", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    }
+}
+
+/// Replace the contents of the clipboard with `text`.
+fn copy(text: &str) -> Result<(), Error> {
+    let tools = clipboard_tools();
+    for &(tool, args) in tools {
+        let clipboard = |error| Error::Clipboard {
+            tool: tool.to_string(),
+            error,
+        };
+        let child = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(clipboard(error)),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).map_err(clipboard)?;
+        }
+        let status = child.wait().map_err(clipboard)?;
+        if !status.success() {
+            return Err(clipboard(io::Error::other(format!("exited with {status}"))));
+        }
+        return Ok(());
+    }
+    Err(Error::NoClipboard(
+        tools
+            .iter()
+            .map(|(tool, _)| *tool)
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))
+}
@@ -0,0 +1,102 @@
+use std::{io::Read, path::PathBuf};
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::{arg, ValueEnum};
+
+use crate::{
+    config::{locator, secret},
+    input,
+    sep::sep53,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
+    #[error("reading message from {0}: {1}")]
+    Read(String, std::io::Error),
+
+    #[error("no message on stdin with --no-input or when not run in a terminal, pipe it or pass --message or --file")]
+    MessageRequired,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Standard base64, as in the SEP-53 examples
+    #[default]
+    Base64,
+    /// Lowercase hex
+    Hex,
+}
+
+/// The message to sign or verify, given inline, as a file, or on stdin.
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Message {
+    /// Message as text. Read from stdin when neither `--message` nor `--file` is given
+    #[arg(long, conflicts_with = "file")]
+    pub message: Option<String>,
+
+    /// File containing the message, read as bytes
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+impl Message {
+    pub fn read(&self) -> Result<Vec<u8>, Error> {
+        if let Some(message) = &self.message {
+            return Ok(message.as_bytes().to_vec());
+        }
+        if let Some(file) = &self.file {
+            return std::fs::read(file).map_err(|e| Error::Read(file.display().to_string(), e));
+        }
+        if input::stdin_would_wait() {
+            return Err(Error::MessageRequired);
+        }
+        let mut message = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut message)
+            .map_err(|e| Error::Read("stdin".to_string(), e))?;
+        Ok(message)
+    }
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of the identity to sign with
+    pub name: String,
+
+    #[command(flatten)]
+    pub message: Message,
+
+    /// Encoding of the signature
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// If identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let key = self
+            .locator
+            .read_identity(&self.name)?
+            .key_pair(self.hd_path)?;
+        let signature = sep53::sign(&key, &self.message.read()?).to_bytes();
+        match self.format {
+            Format::Base64 => println!("{}", base64.encode(signature)),
+            Format::Hex => println!("{}", hex::encode(signature)),
+        }
+        Ok(())
+    }
+}
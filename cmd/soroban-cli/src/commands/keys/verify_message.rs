@@ -0,0 +1,79 @@
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::arg;
+
+use crate::{
+    commands::global,
+    config::{
+        address::{self, Address},
+        locator,
+    },
+    print::Print,
+    sep::sep53,
+};
+
+use super::sign_message::{self, Message};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Address(#[from] address::Error),
+
+    #[error(transparent)]
+    Message(#[from] sign_message::Error),
+
+    #[error("invalid public key of {0}")]
+    InvalidKey(String),
+
+    #[error("the signature is not 64 bytes of base64 or hex")]
+    InvalidSignatureEncoding,
+
+    #[error("the signature of the message is not from {0}")]
+    InvalidSignature(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account that signed the message, e.g. `GBX...` or an identity
+    pub address: Address,
+
+    #[command(flatten)]
+    pub message: Message,
+
+    /// Signature to verify, as base64 or hex
+    #[arg(long)]
+    pub signature: String,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let account = self
+            .address
+            .resolve_muxed_account(&self.locator, None)?
+            .account_id()
+            .to_string();
+        let key = stellar_strkey::ed25519::PublicKey::from_string(&account)
+            .ok()
+            .and_then(|key| ed25519_dalek::VerifyingKey::from_bytes(&key.0).ok())
+            .ok_or_else(|| Error::InvalidKey(account.clone()))?;
+        let signature = decode(&self.signature).ok_or(Error::InvalidSignatureEncoding)?;
+        if !sep53::verify(&key, &self.message.read()?, &signature) {
+            return Err(Error::InvalidSignature(account));
+        }
+        Print::new(global_args.quiet).checkln(format!("Signed by {account}"));
+        Ok(())
+    }
+}
+
+/// Decode a signature given as hex or base64.
+fn decode(signature: &str) -> Option<ed25519_dalek::Signature> {
+    let signature = signature.trim();
+    let bytes = hex::decode(signature)
+        .ok()
+        .filter(|bytes| bytes.len() == 64)
+        .or_else(|| base64.decode(signature).ok())?;
+    ed25519_dalek::Signature::from_slice(&bytes).ok()
+}
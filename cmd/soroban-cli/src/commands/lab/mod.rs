@@ -0,0 +1,26 @@
+use crate::commands::global;
+
+pub mod open;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Open Stellar Lab in the browser on the current network, prefilled with a transaction,
+    /// account, or contract
+    #[command(subcommand)]
+    Open(open::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Open(#[from] open::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Open(cmd) => cmd.run(global_args)?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx::xdr},
+    config::{
+        self,
+        address::{self, Address},
+        locator,
+        network::{self, passphrase, Network},
+    },
+    horizon,
+    print::Print,
+    signer::{self, Lab},
+    xdr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr},
+};
+
+const URL: &str = "https://lab.stellar.org";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+    #[error("failed to open {url}: {error}")]
+    Open { url: String, error: std::io::Error },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// View a transaction envelope in the XDR viewer
+    Tx(Tx),
+    /// Sign and submit a transaction envelope in Lab
+    Sign(Tx),
+    /// View an account as returned by Horizon
+    Account(Account),
+    /// Explore the functions and storage of a contract
+    Contract(Contract),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Print the link instead of opening it in the browser
+    #[arg(long)]
+    pub no_open: bool,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Tx {
+    /// Base64 encoded transaction envelope, read from stdin if not given
+    #[arg(long)]
+    pub xdr: Option<String>,
+    #[command(flatten)]
+    pub args: Args,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Account {
+    /// Account to view, e.g. `GBX...` or an identity
+    pub account: Address,
+    #[command(flatten)]
+    pub args: Args,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Contract {
+    /// Contract id or alias of the contract to explore
+    pub contract: config::ContractAddress,
+    #[command(flatten)]
+    pub args: Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let (args, url) = match self {
+            Cmd::Tx(cmd) => {
+                let network = cmd.args.config.get_network()?;
+                let blob = cmd.envelope()?.to_xdr_base64(Limits::none())?;
+                let url = link(
+                    "/xdr/view",
+                    &network,
+                    &cmd.args.horizon,
+                    State::object(
+                        "xdr",
+                        vec![
+                            ("blob", State::value(blob)),
+                            ("type", State::value("TransactionEnvelope")),
+                        ],
+                    ),
+                );
+                (&cmd.args, url)
+            }
+            Cmd::Sign(cmd) => {
+                let network = cmd.args.config.get_network()?;
+                let url = Lab::sign_url(&cmd.envelope()?, &network)?.to_string();
+                (&cmd.args, url)
+            }
+            Cmd::Account(cmd) => {
+                let network = cmd.args.config.get_network()?;
+                let account = cmd
+                    .account
+                    .resolve_muxed_account(&cmd.args.config.locator, None)?
+                    .account_id()
+                    .to_string();
+                let url = link(
+                    "/endpoints/horizon/accounts/single",
+                    &network,
+                    &cmd.args.horizon,
+                    State::object(
+                        "endpoints",
+                        vec![(
+                            "params",
+                            State::Object(vec![("account_id", State::value(account))]),
+                        )],
+                    ),
+                );
+                (&cmd.args, url)
+            }
+            Cmd::Contract(cmd) => {
+                let network = cmd.args.config.get_network()?;
+                let contract = cmd
+                    .contract
+                    .resolve_contract_id(&cmd.args.config.locator, &network.network_passphrase)?
+                    .to_string();
+                let url = link(
+                    "/smart-contracts/contract-explorer",
+                    &network,
+                    &cmd.args.horizon,
+                    State::object(
+                        "smartContracts",
+                        vec![(
+                            "explorer",
+                            State::Object(vec![("contractId", State::value(contract))]),
+                        )],
+                    ),
+                );
+                (&cmd.args, url)
+            }
+        };
+        if args.no_open {
+            println!("{url}");
+        } else {
+            print.globeln(format!("Opening {url}"));
+            open::that(&url).map_err(|error| Error::Open { url, error })?;
+        }
+        Ok(())
+    }
+}
+
+impl Tx {
+    fn envelope(&self) -> Result<TransactionEnvelope, Error> {
+        Ok(match &self.xdr {
+            Some(blob) => TransactionEnvelope::from_xdr_base64(blob.trim(), Limits::none())?,
+            None => xdr::tx_envelope_from_stdin()?,
+        })
+    }
+}
+
+/// Lab keeps its state in the URL, with nested objects started by `$` and ended by `;`.
+#[derive(Debug, Clone)]
+enum State {
+    Value(String),
+    Object(Vec<(&'static str, State)>),
+}
+
+impl State {
+    fn value(value: impl Into<String>) -> Self {
+        State::Value(value.into())
+    }
+
+    fn object(key: &'static str, fields: Vec<(&'static str, State)>) -> (&'static str, Self) {
+        (key, State::Object(fields))
+    }
+
+    fn encode(&self) -> String {
+        match self {
+            State::Value(value) => format!("={}", escape(value)),
+            State::Object(fields) => format!("${};", encode_fields(fields)),
+        }
+    }
+}
+
+fn encode_fields(fields: &[(&'static str, State)]) -> String {
+    fields
+        .iter()
+        .map(|(key, state)| format!("{key}{}", state.encode()))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Link to a page of Lab on `network`, with its state prefilled.
+fn link(
+    path: &str,
+    network: &Network,
+    horizon: &horizon::Args,
+    state: (&'static str, State),
+) -> String {
+    let (id, label) = match network.network_passphrase.as_str() {
+        passphrase::TESTNET => ("testnet", "Testnet"),
+        passphrase::FUTURENET => ("futurenet", "Futurenet"),
+        passphrase::MAINNET => ("mainnet", "Mainnet"),
+        _ => ("custom", "Custom"),
+    };
+    let mut fields = vec![("id", State::value(id)), ("label", State::value(label))];
+    if let Ok(horizon_url) = horizon.url(network) {
+        fields.push(("horizonUrl", State::value(horizon_url)));
+    }
+    fields.push(("rpcUrl", State::value(&network.rpc_url)));
+    fields.push(("passphrase", State::value(&network.network_passphrase)));
+    let root = [("network", State::Object(fields)), state];
+    format!("{URL}{path}?$={};", encode_fields(&root))
+}
+
+/// Escape the characters with a meaning in Lab's state with `/`, and percent-encode the ones
+/// with a meaning in URLs.
+fn escape(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            '/' | ';' | '&' | '$' | '=' => {
+                escaped.push('/');
+                escaped.push(c);
+            }
+            c if c.is_ascii_alphanumeric() || "-_.~:,*@!'()".contains(c) => escaped.push(c),
+            c => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    escaped.push_str(&format!("%{b:02X}"));
+                }
+            }
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_with_network_and_state() {
+        let network = Network {
+            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            rpc_headers: Vec::new(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_signing_secret: None,
+        };
+        let url = link(
+            "/xdr/view",
+            &network,
+            &horizon::Args::default(),
+            State::object("xdr", vec![("blob", State::value("AAA+/w=="))]),
+        );
+        assert_eq!(
+            url,
+            "https://lab.stellar.org/xdr/view?$=network$id=testnet&label=Testnet\
+             &horizonUrl=https:////horizon-testnet.stellar.org\
+             &rpcUrl=https:////soroban-testnet.stellar.org\
+             &passphrase=Test%20SDF%20Network%20/;%20September%202015;\
+             &xdr$blob=AAA%2B//w/=/=;;"
+        );
+    }
+}
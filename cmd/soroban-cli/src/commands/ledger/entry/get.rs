@@ -0,0 +1,232 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::json;
+
+use crate::{
+    commands::{contract::Durability, global, snapshot::at_ledger},
+    config::{self, address::Address, alias, asset, locator, network},
+    print::Print,
+    rpc,
+    xdr::{self, LedgerEntryData, LedgerKey, Limits, ReadXdr, ScVal},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    AtLedger(#[from] at_ledger::Error),
+    #[error("cannot parse storage key {0:?}, expected an `ScVal` as JSON, e.g. '{{\"symbol\":\"Counter\"}}', or a symbol")]
+    CannotParseKey(String),
+    #[error("cannot parse trustline {0:?}, expected ACCOUNT:ASSET, e.g. `alice:USDC:G...`")]
+    CannotParseTrustline(String),
+    #[error("cannot parse wasm hash {0:?}, expected 32 bytes as hex")]
+    CannotParseWasmHash(String),
+    #[error("no ledger keys, use --account, --trustline, --contract-data, --contract-code, or --ledger-key-xdr")]
+    NoKeys,
+    #[error("none of the requested ledger entries exist")]
+    NotFound,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account entry to fetch, e.g. `GBX...` or an identity
+    #[arg(long)]
+    pub account: Vec<Address>,
+    /// Trustline entry to fetch as `ACCOUNT:ASSET`, e.g. `alice:USDC:GBX...`, where the account
+    /// and issuer can be identities
+    #[arg(long)]
+    pub trustline: Vec<String>,
+    /// Contract whose data entries to fetch, the contract instance if no `--key` is given
+    #[arg(long)]
+    pub contract_data: Option<alias::ContractAddress>,
+    /// Storage key of `--contract-data`, an `ScVal` as JSON, e.g. '{"symbol":"Counter"}', or a
+    /// symbol, e.g. `Counter`
+    #[arg(long, requires = "contract_data")]
+    pub key: Vec<String>,
+    /// Durability of the `--contract-data` entries
+    #[arg(long, value_enum, default_value = "persistent")]
+    pub durability: Durability,
+    /// Wasm hash of a contract code entry to fetch
+    #[arg(long)]
+    pub contract_code: Vec<String>,
+    /// Ledger key to fetch as base64-encoded XDR
+    #[arg(long)]
+    pub ledger_key_xdr: Vec<String>,
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "json")]
+    pub output: Output,
+    #[command(flatten)]
+    pub at_ledger: at_ledger::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Keys and entries decoded as JSON, with their last modified and live until ledgers
+    Json,
+    /// One base64-encoded `LedgerEntryData` per line
+    Xdr,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let keys = self.keys(&network.network_passphrase)?;
+        if keys.is_empty() {
+            return Err(Error::NoKeys);
+        }
+        let response = match self
+            .at_ledger
+            .ledger_entries(&print, &network, &keys)
+            .await?
+        {
+            Some(response) => response,
+            None => network.rpc_client()?.get_ledger_entries(&keys).await?,
+        };
+        let found = response.entries.unwrap_or_default();
+        if found.is_empty() {
+            return Err(Error::NotFound);
+        }
+        if found.len() < keys.len() {
+            print.warnln(format!(
+                "{} of {} ledger entries were not found",
+                keys.len() - found.len(),
+                keys.len()
+            ));
+        }
+
+        if self.output == Output::Xdr {
+            for entry in &found {
+                println!("{}", entry.xdr);
+            }
+            return Ok(());
+        }
+        let entries = found
+            .iter()
+            .map(|entry| {
+                let key = LedgerKey::from_xdr_base64(&entry.key, Limits::none())?;
+                let data = LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())?;
+                Ok(json!({
+                    "key": key,
+                    "entry": data,
+                    "last_modified_ledger": entry.last_modified_ledger,
+                    "live_until_ledger": entry.live_until_ledger_seq_ledger_seq,
+                    "live_for_ledgers": entry
+                        .live_until_ledger_seq_ledger_seq
+                        .map(|live_until| i64::from(live_until) - response.latest_ledger),
+                }))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "latest_ledger": response.latest_ledger,
+                "entries": entries,
+            }))?
+        );
+        Ok(())
+    }
+
+    fn keys(&self, network_passphrase: &str) -> Result<Vec<LedgerKey>, Error> {
+        let locator = &self.config.locator;
+        let mut keys = Vec::new();
+        for account in &self.account {
+            keys.push(LedgerKey::Account(xdr::LedgerKeyAccount {
+                account_id: account_id(account, locator)?,
+            }));
+        }
+        for trustline in &self.trustline {
+            let (account, asset) = trustline
+                .split_once(':')
+                .ok_or_else(|| Error::CannotParseTrustline(trustline.clone()))?;
+            let account: Address = account
+                .parse()
+                .map_err(|_| Error::CannotParseTrustline(trustline.clone()))?;
+            let asset = asset.parse::<asset::Asset>()?.resolve(locator, None)?;
+            keys.push(LedgerKey::Trustline(xdr::LedgerKeyTrustLine {
+                account_id: account_id(&account, locator)?,
+                asset: (&asset).into(),
+            }));
+        }
+        if let Some(contract) = &self.contract_data {
+            let contract = contract.resolve_contract_id(locator, network_passphrase)?;
+            let storage_keys = if self.key.is_empty() {
+                vec![ScVal::LedgerKeyContractInstance]
+            } else {
+                self.key
+                    .iter()
+                    .map(|key| parse_key(key))
+                    .collect::<Result<_, _>>()?
+            };
+            for key in storage_keys {
+                keys.push(LedgerKey::ContractData(xdr::LedgerKeyContractData {
+                    contract: xdr::ScAddress::Contract(xdr::Hash(contract.0)),
+                    key,
+                    durability: (&self.durability).into(),
+                }));
+            }
+        }
+        for hash in &self.contract_code {
+            let bytes = hex::decode(hash)
+                .ok()
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .ok_or_else(|| Error::CannotParseWasmHash(hash.clone()))?;
+            keys.push(LedgerKey::ContractCode(xdr::LedgerKeyContractCode {
+                hash: xdr::Hash(bytes),
+            }));
+        }
+        for key in &self.ledger_key_xdr {
+            keys.push(LedgerKey::from_xdr_base64(key, Limits::none())?);
+        }
+        Ok(keys)
+    }
+}
+
+fn account_id(address: &Address, locator: &locator::Args) -> Result<xdr::AccountId, Error> {
+    Ok(address.resolve_muxed_account(locator, None)?.account_id())
+}
+
+/// Parse a storage key given as an `ScVal` in JSON, falling back to a bare symbol.
+fn parse_key(key: &str) -> Result<ScVal, Error> {
+    if let Ok(val) = serde_json::from_str::<ScVal>(key) {
+        return Ok(val);
+    }
+    let cannot_parse = || Error::CannotParseKey(key.to_string());
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(cannot_parse());
+    }
+    xdr::ScSymbol::try_from(key)
+        .map(ScVal::Symbol)
+        .map_err(|()| cannot_parse())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json_and_symbol_keys() {
+        let counter = ScVal::Symbol("Counter".try_into().unwrap());
+        assert_eq!(parse_key(r#"{"symbol":"Counter"}"#).unwrap(), counter);
+        assert_eq!(parse_key("Counter").unwrap(), counter);
+        assert_eq!(parse_key(r#"{"u32":7}"#).unwrap(), ScVal::U32(7));
+        assert!(parse_key("not a symbol").is_err());
+    }
+}
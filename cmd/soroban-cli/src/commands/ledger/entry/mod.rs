@@ -0,0 +1,27 @@
+use clap::Parser;
+
+use crate::commands::global;
+
+pub mod get;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Fetch ledger entries by account, trustline, contract data, or contract code, and print
+    /// them decoded with their TTL
+    Get(Box<get::Cmd>),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Get(#[from] get::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Get(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
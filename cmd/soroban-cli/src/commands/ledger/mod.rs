@@ -0,0 +1,34 @@
+use clap::Parser;
+
+use crate::commands::global;
+
+pub mod entry;
+pub mod watch;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Fetch and decode ledger entries
+    #[command(subcommand)]
+    Entry(entry::Cmd),
+    /// Stream a summary line per closed ledger with its transaction and operation counts and the
+    /// soroban resources it used
+    Watch(watch::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Entry(#[from] entry::Error),
+    #[error(transparent)]
+    Watch(#[from] watch::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Entry(cmd) => cmd.run(global_args).await?,
+            Cmd::Watch(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
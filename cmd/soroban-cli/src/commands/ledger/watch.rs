@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use clap::{arg, command, Parser, ValueEnum};
+use jsonrpsee_core::params::ObjectParams;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::global,
+    config::{
+        self,
+        network::{self, rpc_version},
+    },
+    print::Print,
+    rpc,
+    xdr::{
+        self, ConfigSettingEntry, ConfigSettingId, LedgerEntryData, LedgerKey,
+        LedgerKeyConfigSetting, Limits, ReadXdr, TransactionEnvelope, TransactionExt,
+        TransactionResult,
+    },
+};
+
+/// Page size used when reading the transactions of a ledger.
+const PAGE_LIMIT: u32 = 200;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    JsonRpc(#[from] jsonrpsee_core::Error),
+    #[error("invalid cursor {0:?} in getTransactions response")]
+    InvalidCursor(String),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Ledger to start from, defaults to the next ledger to close
+    #[arg(long)]
+    pub start_ledger: Option<u32>,
+    /// Stop after this many ledgers, runs until interrupted if not set
+    #[arg(long)]
+    pub count: Option<u32>,
+    /// How often to poll for new ledgers, e.g. `1s` or `500ms`
+    #[arg(long, default_value = "1s", value_parser = humantime::parse_duration)]
+    pub interval: Duration,
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One summary line per ledger
+    Text,
+    /// One JSON object per ledger and line
+    Json,
+}
+
+/// Ledger wide limits from the network config settings, used to report utilization.
+#[derive(Debug, Clone, Default)]
+struct LedgerLimits {
+    instructions: i64,
+    read_bytes: u32,
+    write_bytes: u32,
+    tx_count: u32,
+}
+
+/// Summary of a closed ledger.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct Summary {
+    sequence: u32,
+    close_time: Option<i64>,
+    transactions: u32,
+    successful: u32,
+    failed: u32,
+    operations: u32,
+    soroban_transactions: u32,
+    fee_charged: i64,
+    instructions: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    utilization: Option<Utilization>,
+}
+
+/// Declared soroban resources of a ledger as a percentage of the ledger limits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct Utilization {
+    soroban_transactions: f64,
+    instructions: f64,
+    read_bytes: f64,
+    write_bytes: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionsPage {
+    transactions: Vec<TransactionInfo>,
+    latest_ledger: u32,
+    latest_ledger_close_timestamp: i64,
+    cursor: String,
+}
+
+/// A transaction of a `getTransactions` page, keeping the ledger and close time that the RPC
+/// client does not decode.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionInfo {
+    status: String,
+    ledger: u32,
+    created_at: i64,
+    envelope_xdr: String,
+    result_xdr: String,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        network
+            .require_rpc(&rpc_version::GET_TRANSACTIONS, global_args.no_cache)
+            .await?;
+        let client = network.rpc_client()?;
+        let limits = ledger_limits(&client).await;
+        if limits.is_none() {
+            print.warnln("Could not read the ledger limits, utilization is not reported");
+        }
+        let mut next = match self.start_ledger {
+            Some(ledger) => ledger,
+            None => client.get_latest_ledger().await?.sequence + 1,
+        };
+        print.infoln(format!("Watching ledgers from {next}"));
+        let mut remaining = self.count;
+        loop {
+            let latest = client.get_latest_ledger().await?.sequence;
+            while next <= latest {
+                if remaining == Some(0) {
+                    return Ok(());
+                }
+                let summary = summarize(&network, next, limits.as_ref()).await?;
+                match self.output {
+                    Output::Text => println!("{}", summary.line()),
+                    Output::Json => println!("{}", serde_json::to_string(&summary)?),
+                }
+                next += 1;
+                remaining = remaining.map(|n| n - 1);
+            }
+            if remaining == Some(0) {
+                return Ok(());
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+}
+
+/// Read the transactions of `ledger` and summarize them.
+async fn summarize(
+    network: &network::Network,
+    ledger: u32,
+    limits: Option<&LedgerLimits>,
+) -> Result<Summary, Error> {
+    let mut summary = Summary {
+        sequence: ledger,
+        ..Summary::default()
+    };
+    let mut cursor = None;
+    loop {
+        let mut params = ObjectParams::new();
+        let mut pagination = serde_json::json!({ "limit": PAGE_LIMIT });
+        match &cursor {
+            Some(cursor) => pagination["cursor"] = serde_json::json!(cursor),
+            None => params.insert("startLedger", ledger)?,
+        }
+        params.insert("pagination", pagination)?;
+        let page: TransactionsPage = network.rpc_request("getTransactions", params).await?;
+        if page.latest_ledger == ledger {
+            summary.close_time = Some(page.latest_ledger_close_timestamp);
+        }
+        let page_len = page.transactions.len();
+        let mut past_ledger = false;
+        for tx in page.transactions {
+            if tx.ledger != ledger {
+                past_ledger = tx.ledger > ledger;
+                continue;
+            }
+            summary.close_time = Some(tx.created_at);
+            summary.add(&tx)?;
+        }
+        if past_ledger || page_len < PAGE_LIMIT as usize {
+            break;
+        }
+        if page.cursor.parse::<u64>().is_err() {
+            return Err(Error::InvalidCursor(page.cursor));
+        }
+        cursor = Some(page.cursor);
+    }
+    if let Some(limits) = limits {
+        summary.utilization = Some(summary.utilization(limits));
+    }
+    Ok(summary)
+}
+
+impl Summary {
+    fn add(&mut self, tx: &TransactionInfo) -> Result<(), Error> {
+        self.transactions += 1;
+        if tx.status == "SUCCESS" {
+            self.successful += 1;
+        } else {
+            self.failed += 1;
+        }
+        let result = TransactionResult::from_xdr_base64(&tx.result_xdr, Limits::none())?;
+        self.fee_charged += result.fee_charged;
+        let envelope = TransactionEnvelope::from_xdr_base64(&tx.envelope_xdr, Limits::none())?;
+        let (operations, ext) = match &envelope {
+            TransactionEnvelope::TxV0(env) => (env.tx.operations.len(), None),
+            TransactionEnvelope::Tx(env) => (env.tx.operations.len(), Some(&env.tx.ext)),
+            TransactionEnvelope::TxFeeBump(env) => match &env.tx.inner_tx {
+                xdr::FeeBumpTransactionInnerTx::Tx(inner) => {
+                    (inner.tx.operations.len(), Some(&inner.tx.ext))
+                }
+            },
+        };
+        self.operations += u32::try_from(operations).unwrap_or(u32::MAX);
+        if let Some(TransactionExt::V1(data)) = ext {
+            self.soroban_transactions += 1;
+            self.instructions += u64::from(data.resources.instructions);
+            self.read_bytes += u64::from(data.resources.read_bytes);
+            self.write_bytes += u64::from(data.resources.write_bytes);
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn utilization(&self, limits: &LedgerLimits) -> Utilization {
+        let percent = |used: f64, limit: f64| {
+            if limit > 0.0 {
+                (used / limit * 1_000.0).round() / 10.0
+            } else {
+                0.0
+            }
+        };
+        Utilization {
+            soroban_transactions: percent(
+                f64::from(self.soroban_transactions),
+                f64::from(limits.tx_count),
+            ),
+            instructions: percent(self.instructions as f64, limits.instructions as f64),
+            read_bytes: percent(self.read_bytes as f64, f64::from(limits.read_bytes)),
+            write_bytes: percent(self.write_bytes as f64, f64::from(limits.write_bytes)),
+        }
+    }
+
+    fn line(&self) -> String {
+        let closed = self
+            .close_time
+            .and_then(|time| u64::try_from(time).ok())
+            .map_or_else(
+                || "-".to_string(),
+                |time| {
+                    humantime::format_rfc3339_seconds(
+                        std::time::UNIX_EPOCH + Duration::from_secs(time),
+                    )
+                    .to_string()
+                },
+            );
+        let mut line = format!(
+            "ledger {} closed {closed} txs {} ({} ok, {} failed) ops {} soroban {}",
+            self.sequence,
+            self.transactions,
+            self.successful,
+            self.failed,
+            self.operations,
+            self.soroban_transactions,
+        );
+        if let Some(u) = &self.utilization {
+            line.push_str(&format!(
+                " (txs {}%, instructions {}%, read {}%, write {}%)",
+                u.soroban_transactions, u.instructions, u.read_bytes, u.write_bytes
+            ));
+        }
+        line
+    }
+}
+
+/// Read the ledger limits from the network config settings, or `None` if the RPC server does not
+/// return them.
+async fn ledger_limits(client: &rpc::Client) -> Option<LedgerLimits> {
+    let keys = [
+        ConfigSettingId::ContractComputeV0,
+        ConfigSettingId::ContractLedgerCostV0,
+        ConfigSettingId::ContractExecutionLanes,
+    ]
+    .map(|config_setting_id| {
+        LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+    });
+    let entries = client.get_ledger_entries(&keys).await.ok()?.entries?;
+    let mut limits = LedgerLimits::default();
+    for entry in entries {
+        let LedgerEntryData::ConfigSetting(setting) =
+            LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none()).ok()?
+        else {
+            continue;
+        };
+        match setting {
+            ConfigSettingEntry::ContractComputeV0(compute) => {
+                limits.instructions = compute.ledger_max_instructions;
+            }
+            ConfigSettingEntry::ContractLedgerCostV0(cost) => {
+                limits.read_bytes = cost.ledger_max_read_bytes;
+                limits.write_bytes = cost.ledger_max_write_bytes;
+            }
+            ConfigSettingEntry::ContractExecutionLanes(lanes) => {
+                limits.tx_count = lanes.ledger_max_tx_count;
+            }
+            _ => {}
+        }
+    }
+    Some(limits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utilization_and_line() {
+        let summary = Summary {
+            sequence: 100,
+            close_time: Some(86_400),
+            transactions: 3,
+            successful: 2,
+            failed: 1,
+            operations: 5,
+            soroban_transactions: 1,
+            fee_charged: 300,
+            instructions: 25_000_000,
+            read_bytes: 1_000,
+            write_bytes: 0,
+            utilization: None,
+        };
+        let limits = LedgerLimits {
+            instructions: 100_000_000,
+            read_bytes: 3_000,
+            write_bytes: 0,
+            tx_count: 100,
+        };
+        let utilization = summary.utilization(&limits);
+        assert_eq!(
+            utilization,
+            Utilization {
+                soroban_transactions: 1.0,
+                instructions: 25.0,
+                read_bytes: 33.3,
+                write_bytes: 0.0,
+            }
+        );
+        assert_eq!(
+            Summary {
+                utilization: Some(utilization),
+                ..summary
+            }
+            .line(),
+            "ledger 100 closed 1970-01-02T00:00:00Z txs 3 (2 ok, 1 failed) ops 5 soroban 1 (txs 1%, instructions 25%, read 33.3%, write 0%)"
+        );
+    }
+}
@@ -5,19 +5,39 @@ use clap::{command, error::ErrorKind, CommandFactory, FromArgMatches, Parser};
 
 use crate::config;
 
+pub mod account;
+pub mod addressbook;
+pub mod amm;
+pub mod anchor;
+pub mod asset;
+pub mod auth;
+pub mod bug_report;
 pub mod cache;
+pub mod claimable_balance;
 pub mod completion;
 pub mod container;
 pub mod contract;
+pub mod data;
+pub mod dex;
 pub mod env;
 pub mod events;
+pub mod fee;
 pub mod global;
 pub mod keys;
+pub mod lab;
+pub mod ledger;
 pub mod network;
+pub mod payment;
 pub mod plugin;
+pub mod settings;
 pub mod snapshot;
+pub mod sponsorship;
+pub mod state;
+pub mod trust;
 pub mod tx;
 pub mod version;
+pub mod watch;
+pub mod xdr;
 
 pub mod txn_result;
 
@@ -76,28 +96,44 @@ pub struct Root {
 
     #[command(subcommand)]
     pub cmd: Cmd,
+
+    /// Names of the subcommands run, e.g. `["contract", "deploy"]`, kept from the parsed
+    /// arguments for logging.
+    #[arg(skip)]
+    pub command_names: Vec<String>,
 }
 
 impl Root {
     pub fn new() -> Result<Self, Error> {
-        Self::try_parse().map_err(|e| {
-            if std::env::args().any(|s| s == "--list") {
-                let plugins = plugin::list().unwrap_or_default();
-                if plugins.is_empty() {
-                    println!("No Plugins installed. E.g. soroban-hello");
-                } else {
-                    println!("Installed Plugins:\n    {}", plugins.join("\n    "));
+        Self::command()
+            .try_get_matches()
+            .and_then(|mut matches| {
+                let command_names = command_names(&matches);
+                let root = Self::from_arg_matches_mut(&mut matches)
+                    .map_err(|e| e.format(&mut Self::command()))?;
+                Ok(Self {
+                    command_names,
+                    ..root
+                })
+            })
+            .map_err(|e| {
+                if std::env::args().any(|s| s == "--list") {
+                    let plugins = plugin::list().unwrap_or_default();
+                    if plugins.is_empty() {
+                        println!("No Plugins installed. E.g. soroban-hello");
+                    } else {
+                        println!("Installed Plugins:\n    {}", plugins.join("\n    "));
+                    }
+                    std::process::exit(0);
+                }
+                match e.kind() {
+                    ErrorKind::InvalidSubcommand => match plugin::run() {
+                        Ok(()) => Error::Clap(e),
+                        Err(e) => Error::Plugin(e),
+                    },
+                    _ => Error::Clap(e),
                 }
-                std::process::exit(0);
-            }
-            match e.kind() {
-                ErrorKind::InvalidSubcommand => match plugin::run() {
-                    Ok(()) => Error::Clap(e),
-                    Err(e) => Error::Plugin(e),
-                },
-                _ => Error::Clap(e),
-            }
-        })
+            })
     }
 
     pub fn from_arg_matches<I, T>(itr: I) -> Result<Self, clap::Error>
@@ -109,16 +145,35 @@ impl Root {
     }
     pub async fn run(&mut self) -> Result<(), Error> {
         match &mut self.cmd {
+            Cmd::Account(account) => account.run(&self.global_args).await?,
+            Cmd::Anchor(anchor) => anchor.run(&self.global_args).await?,
+            Cmd::Auth(auth) => auth.run(&self.global_args).await?,
             Cmd::Completion(completion) => completion.run(),
             Cmd::Contract(contract) => contract.run(&self.global_args).await?,
-            Cmd::Events(events) => events.run().await?,
-            Cmd::Xdr(xdr) => xdr.run()?,
+            Cmd::Events(events) => events.run(&self.global_args).await?,
+            Cmd::Xdr(xdr) => xdr.run(&self.global_args)?,
             Cmd::Network(network) => network.run(&self.global_args).await?,
             Cmd::Container(container) => container.run(&self.global_args).await?,
             Cmd::Snapshot(snapshot) => snapshot.run(&self.global_args).await?,
-            Cmd::Version(version) => version.run(),
+            Cmd::Version(version) => version.run(&self.global_args).await,
+            Cmd::BugReport(bug_report) => bug_report.run(&self.global_args)?,
             Cmd::Keys(id) => id.run(&self.global_args).await?,
+            Cmd::Addressbook(addressbook) => addressbook.run(&self.global_args)?,
+            Cmd::Lab(lab) => lab.run(&self.global_args)?,
             Cmd::Tx(tx) => tx.run(&self.global_args).await?,
+            Cmd::Trust(trust) => trust.run(&self.global_args).await?,
+            Cmd::Asset(asset) => asset.run(&self.global_args).await?,
+            Cmd::Amm(amm) => amm.run(&self.global_args).await?,
+            Cmd::Dex(dex) => dex.run(&self.global_args).await?,
+            Cmd::Payment(payment) => payment.run(&self.global_args).await?,
+            Cmd::ClaimableBalance(cb) => cb.run(&self.global_args).await?,
+            Cmd::Sponsorship(sponsorship) => sponsorship.run().await?,
+            Cmd::Ledger(ledger) => ledger.run(&self.global_args).await?,
+            Cmd::Watch(watch) => watch.run(&self.global_args).await?,
+            Cmd::State(state) => state.run(&self.global_args).await?,
+            Cmd::Data(data) => data.run(&self.global_args).await?,
+            Cmd::Fee(fee) => fee.run(&self.global_args).await?,
+            Cmd::Config(config) => config.run(&self.global_args).await?,
             Cmd::Cache(cache) => cache.run()?,
             Cmd::Env(env) => env.run(&self.global_args)?,
         };
@@ -134,6 +189,16 @@ impl FromStr for Root {
     }
 }
 
+fn command_names(matches: &clap::ArgMatches) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut matches = matches;
+    while let Some((name, sub)) = matches.subcommand() {
+        names.push(name.to_string());
+        matches = sub;
+    }
+    names
+}
+
 #[derive(Parser, Debug)]
 pub enum Cmd {
     /// Tools for smart contract developers
@@ -152,6 +217,10 @@ pub enum Cmd {
     #[command(subcommand)]
     Keys(keys::Cmd),
 
+    /// Label account and contract addresses to read and use them by name
+    #[command(subcommand)]
+    Addressbook(addressbook::Cmd),
+
     /// Configure connection to networks
     #[command(subcommand)]
     Network(network::Cmd),
@@ -168,8 +237,76 @@ pub enum Cmd {
     #[command(subcommand)]
     Tx(tx::Cmd),
 
-    /// Decode and encode XDR
-    Xdr(stellar_xdr::cli::Root),
+    /// Manage accounts, their signers, thresholds, and options
+    #[command(subcommand)]
+    Account(account::Cmd),
+
+    /// Create, remove, and list trustlines to classic assets
+    #[command(subcommand)]
+    Trust(trust::Cmd),
+
+    /// Issue classic assets, claw them back, and authorize trustlines to them
+    #[command(subcommand)]
+    Asset(asset::Cmd),
+
+    /// Deposit into and withdraw from classic liquidity pools, and show their reserves
+    #[command(subcommand)]
+    Amm(amm::Cmd),
+
+    /// Trade on the decentralized exchange with offers, and show the orderbook of asset pairs
+    #[command(subcommand)]
+    Dex(dex::Cmd),
+
+    /// Send payments, including path payments converting between assets
+    #[command(subcommand)]
+    Payment(payment::Cmd),
+
+    /// Create, claim, and list claimable balances
+    #[command(subcommand)]
+    ClaimableBalance(claimable_balance::Cmd),
+
+    /// Inspect sponsored reserves
+    #[command(subcommand)]
+    Sponsorship(sponsorship::Cmd),
+
+    /// Fetch ledger entries and watch ledgers close
+    #[command(subcommand)]
+    Ledger(ledger::Cmd),
+
+    /// Dashboard of a contract or account: live events, recent transactions, storage, and TTLs,
+    /// refreshed from RPC, with their decoded XDR a key press away
+    Watch(watch::Cmd),
+
+    /// Export the contracts deployed under an alias for infrastructure-as-code tools
+    #[command(subcommand)]
+    State(state::Cmd),
+
+    /// Export transactions to CSV or Parquet files for analytics
+    #[command(subcommand)]
+    Data(data::Cmd),
+
+    /// Show network fees and configure how inclusion fees are priced
+    #[command(subcommand)]
+    Fee(fee::Cmd),
+
+    /// Change settings of the global config file, such as safety interlocks
+    #[command(subcommand)]
+    Config(settings::Cmd),
+
+    /// Authenticate with off-chain services such as anchors
+    #[command(subcommand)]
+    Auth(auth::Cmd),
+
+    /// Test deposit and withdrawal flows against SEP-6 and SEP-24 anchors
+    #[command(subcommand)]
+    Anchor(anchor::Cmd),
+
+    /// Open transactions, accounts, and contracts in Stellar Lab
+    #[command(subcommand)]
+    Lab(lab::Cmd),
+
+    /// Decode, encode, and generate XDR
+    Xdr(xdr::Root),
 
     /// Print shell completion code for the specified shell.
     #[command(long_about = completion::LONG_ABOUT)]
@@ -181,6 +318,10 @@ pub enum Cmd {
 
     /// Print version information
     Version(version::Cmd),
+
+    /// Bundle the log of the last run, written with `-vv` or `--log-file`, with version and config
+    /// information to file an issue
+    BugReport(bug_report::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -196,7 +337,10 @@ pub enum Error {
     Keys(#[from] keys::Error),
 
     #[error(transparent)]
-    Xdr(#[from] stellar_xdr::cli::Error),
+    Addressbook(#[from] addressbook::Error),
+
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
 
     #[error(transparent)]
     Clap(#[from] clap::error::Error),
@@ -221,6 +365,60 @@ pub enum Error {
 
     #[error(transparent)]
     Env(#[from] env::Error),
+
+    #[error(transparent)]
+    Auth(#[from] auth::Error),
+
+    #[error(transparent)]
+    Anchor(#[from] anchor::Error),
+
+    #[error(transparent)]
+    Account(#[from] account::Error),
+
+    #[error(transparent)]
+    Trust(#[from] trust::Error),
+
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+
+    #[error(transparent)]
+    Amm(#[from] amm::Error),
+
+    #[error(transparent)]
+    Dex(#[from] dex::Error),
+
+    #[error(transparent)]
+    Payment(#[from] payment::Error),
+
+    #[error(transparent)]
+    ClaimableBalance(#[from] claimable_balance::Error),
+
+    #[error(transparent)]
+    Sponsorship(#[from] sponsorship::Error),
+
+    #[error(transparent)]
+    Ledger(#[from] ledger::Error),
+
+    #[error(transparent)]
+    Watch(#[from] watch::Error),
+
+    #[error(transparent)]
+    State(#[from] state::Error),
+
+    #[error(transparent)]
+    Data(#[from] data::Error),
+
+    #[error(transparent)]
+    Fee(#[from] fee::Error),
+
+    #[error(transparent)]
+    Lab(#[from] lab::Error),
+
+    #[error(transparent)]
+    Config(#[from] settings::Error),
+
+    #[error(transparent)]
+    BugReport(#[from] bug_report::Error),
 }
 
 #[async_trait]
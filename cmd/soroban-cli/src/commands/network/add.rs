@@ -1,4 +1,12 @@
-use crate::config::{locator, network, secret};
+use crate::{
+    commands::HEADING_RPC,
+    config::{
+        locator,
+        network::{self, presets, Network},
+        secret,
+    },
+    rpc,
+};
 use clap::command;
 
 #[derive(thiserror::Error, Debug)]
@@ -8,6 +16,15 @@ pub enum Error {
 
     #[error(transparent)]
     Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Presets(#[from] presets::Error),
+
+    #[error(transparent)]
+    Network(#[from] network::Error),
+
+    #[error("the RPC server is on another network, use `--network-passphrase {server:?}` if it is the intended one: expected {expected:?}")]
+    PassphraseMismatch { expected: String, server: String },
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -16,17 +33,97 @@ pub struct Cmd {
     /// Name of network
     pub name: String,
 
-    #[command(flatten)]
-    pub network: network::Network,
+    /// Preset to add the network from, one of `network presets ls`, instead of `--rpc-url` and
+    /// `--network-passphrase`. `--rpc-header` overrides the headers of the preset
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// RPC server endpoint
+    #[arg(
+        long = "rpc-url",
+        env = "STELLAR_RPC_URL",
+        help_heading = HEADING_RPC,
+        required_unless_present = "preset",
+    )]
+    pub rpc_url: Option<String>,
+
+    /// Optional header (e.g. API Key) to include in requests to the RPC
+    #[arg(
+        long = "rpc-header",
+        env = "STELLAR_RPC_HEADERS",
+        help_heading = HEADING_RPC,
+        num_args = 1,
+        action = clap::ArgAction::Append,
+        value_delimiter = '\n',
+        value_parser = network::parse_http_header,
+    )]
+    pub rpc_headers: Vec<(String, String)>,
+
+    /// Network passphrase to sign the transaction sent to the rpc server
+    #[arg(
+        long,
+        env = "STELLAR_NETWORK_PASSPHRASE",
+        help_heading = HEADING_RPC,
+        required_unless_present = "preset",
+    )]
+    pub network_passphrase: Option<String>,
+
+    /// Secret to sign transaction submissions to the RPC with, sent as an HMAC-SHA256 of the
+    /// transaction along with an idempotency key, for gateways that deduplicate and attribute
+    /// them. It is stored in plain text in the network's config file. To keep it out of the
+    /// file, set `STELLAR_RPC_SIGNING_SECRET` for the commands that send transactions instead
+    #[arg(long = "rpc-signing-secret", help_heading = HEADING_RPC)]
+    pub rpc_signing_secret: Option<String>,
 
     #[command(flatten)]
     pub config_locator: locator::Args,
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        Ok(self
-            .config_locator
-            .write_network(&self.name, &self.network)?)
+    pub async fn run(&self) -> Result<(), Error> {
+        let network = self.network()?;
+        Self::verify_passphrase(&network).await?;
+        Ok(self.config_locator.write_network(&self.name, &network)?)
+    }
+
+    fn network(&self) -> Result<Network, Error> {
+        // The RPC URL and passphrase of the environment are meant for the commands using a
+        // network, so they don't override the preset.
+        let mut network = match &self.preset {
+            Some(preset) => Network::from(&presets::get(preset)?),
+            None => Network {
+                rpc_url: self.rpc_url.clone().unwrap_or_default(),
+                rpc_headers: Vec::new(),
+                network_passphrase: self.network_passphrase.clone().unwrap_or_default(),
+                rpc_signing_secret: None,
+            },
+        };
+        if !self.rpc_headers.is_empty() {
+            network.rpc_headers.clone_from(&self.rpc_headers);
+        }
+        network
+            .rpc_signing_secret
+            .clone_from(&self.rpc_signing_secret);
+        Ok(network)
+    }
+
+    /// Check that the RPC server is on the network of the passphrase, to catch mismatched
+    /// configs before they are used. An unreachable server is not an error, so that networks
+    /// can be added offline or before starting a local one.
+    async fn verify_passphrase(network: &Network) -> Result<(), Error> {
+        let client = network.rpc_client()?;
+        match client
+            .verify_network_passphrase(Some(&network.network_passphrase))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(rpc::Error::InvalidNetworkPassphrase { expected, server }) => {
+                Err(Error::PassphraseMismatch { expected, server })
+            }
+            Err(e) => {
+                tracing::debug!("could not verify the network passphrase against the RPC: {e}");
+                Ok(())
+            }
+        }
     }
 }
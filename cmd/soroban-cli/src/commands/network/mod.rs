@@ -7,7 +7,9 @@ use super::{config::locator, global};
 pub mod add;
 pub mod default;
 pub mod ls;
+pub mod presets;
 pub mod rm;
+pub mod settings;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
@@ -20,6 +22,14 @@ pub enum Cmd {
     /// List networks
     Ls(ls::Cmd),
 
+    /// List and update the presets to add networks from with `network add --preset`
+    #[command(subcommand)]
+    Presets(presets::Cmd),
+
+    /// Show the settings of the network's ledger: the compute and ledger access limits, the
+    /// contract size limits, the state archival parameters, and the resource fee rates
+    Settings(settings::Cmd),
+
     /// ⚠️ Deprecated: use `stellar container start` instead
     ///
     /// Start network
@@ -65,6 +75,12 @@ pub enum Error {
     #[error(transparent)]
     Ls(#[from] ls::Error),
 
+    #[error(transparent)]
+    Presets(#[from] presets::Error),
+
+    #[error(transparent)]
+    Settings(#[from] settings::Error),
+
     // TODO: remove once `network start` is removed
     #[error(transparent)]
     Start(#[from] crate::commands::container::start::Error),
@@ -97,9 +113,11 @@ impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Default(cmd) => cmd.run(global_args)?,
-            Cmd::Add(cmd) => cmd.run()?,
+            Cmd::Add(cmd) => cmd.run().await?,
             Cmd::Rm(new) => new.run()?,
             Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Presets(cmd) => cmd.run(global_args).await?,
+            Cmd::Settings(cmd) => cmd.run().await?,
             Cmd::Container(cmd) => cmd.run(global_args).await?,
 
             // TODO Remove this once `network start` is removed
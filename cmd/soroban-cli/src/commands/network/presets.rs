@@ -0,0 +1,36 @@
+use crate::commands::global;
+
+pub mod ls;
+pub mod update;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// List the network presets, built-in and from the registry
+    Ls(ls::Cmd),
+
+    /// Replace the registry of network presets with one fetched from a URL or read from a file.
+    ///
+    /// The registry is a TOML file with a `[presets.<name>]` table per preset, with `rpc_url`,
+    /// `network_passphrase`, and optionally `description` and `rpc_headers`. Its presets take
+    /// precedence over the built-in ones of the same name.
+    Update(update::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+
+    #[error(transparent)]
+    Update(#[from] update::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Ls(ls) => ls.run()?,
+            Cmd::Update(update) => update.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
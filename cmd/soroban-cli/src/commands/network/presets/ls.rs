@@ -0,0 +1,87 @@
+use clap::{arg, Parser, ValueEnum};
+use serde::Serialize;
+
+use crate::config::network::presets::{self, Preset, Source};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Presets(#[from] presets::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// A table of the presets' names, sources, RPC URLs, and descriptions
+    Text,
+    /// The presets as JSON
+    Json,
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    name: &'a str,
+    source: Source,
+    #[serde(flatten)]
+    preset: &'a Preset,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let presets = presets::all()?;
+        match self.output {
+            Output::Text => {
+                let rows = presets
+                    .iter()
+                    .map(|(name, (preset, source))| {
+                        [
+                            name.clone(),
+                            match source {
+                                Source::Builtin => "built-in".to_string(),
+                                Source::Registry => "registry".to_string(),
+                            },
+                            preset.rpc_url.clone(),
+                            preset.description.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let widths = (0..3)
+                    .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+                    .collect::<Vec<_>>();
+                for row in rows {
+                    println!(
+                        "{:w0$}  {:w1$}  {:w2$}  {}",
+                        row[0],
+                        row[1],
+                        row[2],
+                        row[3],
+                        w0 = widths[0],
+                        w1 = widths[1],
+                        w2 = widths[2],
+                    );
+                }
+            }
+            Output::Json => {
+                let entries = presets
+                    .iter()
+                    .map(|(name, (preset, source))| Entry {
+                        name,
+                        source: *source,
+                        preset,
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            }
+        }
+        Ok(())
+    }
+}
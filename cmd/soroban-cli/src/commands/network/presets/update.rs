@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::{commands::global, config::network::presets, print::Print, utils::http};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Presets(#[from] presets::Error),
+    #[error("fetching network presets from {url}: {error}")]
+    Fetch { url: String, error: reqwest::Error },
+    #[error("reading network presets from {path:?}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// URL or path of the registry
+    pub source: String,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let contents = if self.source.starts_with("https://") || self.source.starts_with("http://")
+        {
+            let fetch_error = |error| Error::Fetch {
+                url: self.source.clone(),
+                error,
+            };
+            http::client()
+                .get(&self.source)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(fetch_error)?
+                .text()
+                .await
+                .map_err(fetch_error)?
+        } else {
+            std::fs::read_to_string(&self.source).map_err(|error| Error::Read {
+                path: self.source.clone().into(),
+                error,
+            })?
+        };
+        let registry = presets::save(&contents, &self.source)?;
+        print.checkln(format!(
+            "Saved {} network preset(s) to {:?}",
+            registry.presets.len(),
+            presets::path()?
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,366 @@
+use clap::{arg, command, Parser, ValueEnum};
+
+use crate::{
+    config::{self, network},
+    rpc,
+    xdr::{
+        self, ConfigSettingEntry, LedgerEntryData, LedgerKey, LedgerKeyConfigSetting, Limits,
+        ReadXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Tables of the limits, state archival parameters, and fee rates, in human units
+    Text,
+    /// Every config setting entry of the ledger as JSON, including the cost model parameters
+    Json,
+}
+
+/// Average time to close a ledger, used to show lengths in ledgers as durations.
+const LEDGER_SECONDS: u64 = 5;
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let client = self.config.get_network()?.rpc_client()?;
+        let keys = ConfigSettingEntry::VARIANTS.map(|config_setting_id| {
+            LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+        });
+        let mut settings = Vec::new();
+        for entry in client
+            .get_ledger_entries(&keys)
+            .await?
+            .entries
+            .unwrap_or_default()
+        {
+            if let LedgerEntryData::ConfigSetting(setting) =
+                LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())?
+            {
+                settings.push(setting);
+            }
+        }
+        match self.output {
+            Output::Text => println!("{}", text(&settings)),
+            Output::Json => println!("{}", serde_json::to_string_pretty(&settings)?),
+        }
+        Ok(())
+    }
+}
+
+/// Tables of the settings, one per area, with values in human units.
+#[allow(clippy::too_many_lines)]
+fn text(settings: &[ConfigSettingEntry]) -> String {
+    let mut sections: Vec<(&str, Vec<(&str, String)>)> = Vec::new();
+    for setting in settings {
+        match setting {
+            ConfigSettingEntry::ContractMaxSizeBytes(size) => sections.push((
+                "Contract size",
+                vec![("Max Wasm size", bytes(u64::from(*size)))],
+            )),
+            ConfigSettingEntry::ContractDataKeySizeBytes(size) => sections.push((
+                "Contract size",
+                vec![("Max contract data key size", bytes(u64::from(*size)))],
+            )),
+            ConfigSettingEntry::ContractDataEntrySizeBytes(size) => sections.push((
+                "Contract size",
+                vec![("Max contract data entry size", bytes(u64::from(*size)))],
+            )),
+            ConfigSettingEntry::ContractComputeV0(compute) => sections.push((
+                "Compute",
+                vec![
+                    (
+                        "Max instructions per transaction",
+                        count(compute.tx_max_instructions),
+                    ),
+                    (
+                        "Max instructions per ledger",
+                        count(compute.ledger_max_instructions),
+                    ),
+                    (
+                        "Max memory per transaction",
+                        bytes(u64::from(compute.tx_memory_limit)),
+                    ),
+                ],
+            )),
+            ConfigSettingEntry::ContractLedgerCostV0(cost) => {
+                sections.push((
+                    "Ledger access per transaction",
+                    vec![
+                        ("Max entries read", count(cost.tx_max_read_ledger_entries)),
+                        ("Max bytes read", bytes(u64::from(cost.tx_max_read_bytes))),
+                        (
+                            "Max entries written",
+                            count(cost.tx_max_write_ledger_entries),
+                        ),
+                        (
+                            "Max bytes written",
+                            bytes(u64::from(cost.tx_max_write_bytes)),
+                        ),
+                    ],
+                ));
+                sections.push((
+                    "Ledger access per ledger",
+                    vec![
+                        (
+                            "Max entries read",
+                            count(cost.ledger_max_read_ledger_entries),
+                        ),
+                        (
+                            "Max bytes read",
+                            bytes(u64::from(cost.ledger_max_read_bytes)),
+                        ),
+                        (
+                            "Max entries written",
+                            count(cost.ledger_max_write_ledger_entries),
+                        ),
+                        (
+                            "Max bytes written",
+                            bytes(u64::from(cost.ledger_max_write_bytes)),
+                        ),
+                    ],
+                ));
+                sections.push((
+                    "Fees",
+                    vec![
+                        ("Per ledger entry read", stroops(cost.fee_read_ledger_entry)),
+                        (
+                            "Per ledger entry written",
+                            stroops(cost.fee_write_ledger_entry),
+                        ),
+                        ("Per 1 KB read", stroops(cost.fee_read1_kb)),
+                        (
+                            "Per 1 KB written, low",
+                            stroops(cost.write_fee1_kb_bucket_list_low),
+                        ),
+                        (
+                            "Per 1 KB written, high",
+                            stroops(cost.write_fee1_kb_bucket_list_high),
+                        ),
+                    ],
+                ));
+            }
+            ConfigSettingEntry::ContractHistoricalDataV0(history) => sections.push((
+                "Fees",
+                vec![("Per 1 KB of history", stroops(history.fee_historical1_kb))],
+            )),
+            ConfigSettingEntry::ContractEventsV0(events) => {
+                sections.push((
+                    "Transactions",
+                    vec![(
+                        "Max events size",
+                        bytes(u64::from(events.tx_max_contract_events_size_bytes)),
+                    )],
+                ));
+                sections.push((
+                    "Fees",
+                    vec![(
+                        "Per 1 KB of events",
+                        stroops(events.fee_contract_events1_kb),
+                    )],
+                ));
+            }
+            ConfigSettingEntry::ContractBandwidthV0(bandwidth) => {
+                sections.push((
+                    "Transactions",
+                    vec![
+                        (
+                            "Max transaction size",
+                            bytes(u64::from(bandwidth.tx_max_size_bytes)),
+                        ),
+                        (
+                            "Max transactions size per ledger",
+                            bytes(u64::from(bandwidth.ledger_max_txs_size_bytes)),
+                        ),
+                    ],
+                ));
+                sections.push((
+                    "Fees",
+                    vec![(
+                        "Per 1 KB of transaction",
+                        stroops(bandwidth.fee_tx_size1_kb),
+                    )],
+                ));
+            }
+            ConfigSettingEntry::ContractExecutionLanes(lanes) => sections.push((
+                "Transactions",
+                vec![(
+                    "Max transactions per ledger",
+                    count(lanes.ledger_max_tx_count),
+                )],
+            )),
+            ConfigSettingEntry::StateArchival(archival) => sections.push((
+                "State archival",
+                vec![
+                    ("Max entry TTL", ledgers(archival.max_entry_ttl)),
+                    (
+                        "Min persistent entry TTL",
+                        ledgers(archival.min_persistent_ttl),
+                    ),
+                    (
+                        "Min temporary entry TTL",
+                        ledgers(archival.min_temporary_ttl),
+                    ),
+                    (
+                        "Persistent rent rate denominator",
+                        count(archival.persistent_rent_rate_denominator),
+                    ),
+                    (
+                        "Temporary rent rate denominator",
+                        count(archival.temp_rent_rate_denominator),
+                    ),
+                    (
+                        "Max entries to archive",
+                        count(archival.max_entries_to_archive),
+                    ),
+                    (
+                        "Eviction scan size",
+                        bytes(u64::from(archival.eviction_scan_size)),
+                    ),
+                ],
+            )),
+            ConfigSettingEntry::ContractCostParamsCpuInstructions(_)
+            | ConfigSettingEntry::ContractCostParamsMemoryBytes(_)
+            | ConfigSettingEntry::BucketlistSizeWindow(_)
+            | ConfigSettingEntry::EvictionIterator(_) => {}
+        }
+    }
+    if let Some(ConfigSettingEntry::ContractComputeV0(compute)) = settings
+        .iter()
+        .find(|s| matches!(s, ConfigSettingEntry::ContractComputeV0(_)))
+    {
+        sections.push((
+            "Fees",
+            vec![(
+                "Per 10,000 instructions",
+                stroops(compute.fee_rate_per_instructions_increment),
+            )],
+        ));
+    }
+
+    let mut order: Vec<&str> = Vec::new();
+    for (title, _) in &sections {
+        if !order.contains(title) {
+            order.push(title);
+        }
+    }
+    let mut lines = Vec::new();
+    for title in order {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(title.to_string());
+        for (name, value) in sections
+            .iter()
+            .filter(|(t, _)| *t == title)
+            .flat_map(|(_, rows)| rows)
+        {
+            lines.push(format!("  {name:<34}{value}"));
+        }
+    }
+    lines.join("\n")
+}
+
+/// `1234567` to `1,234,567`.
+fn count(n: impl Into<i64>) -> String {
+    let n = n.into();
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    if n < 0 {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+fn bytes(n: u64) -> String {
+    let human = if n >= 1024 * 1024 && n % (1024 * 1024) == 0 {
+        format!(" ({} MiB)", n / (1024 * 1024))
+    } else if n >= 1024 && n % 1024 == 0 {
+        format!(" ({} KiB)", n / 1024)
+    } else {
+        String::new()
+    };
+    format!(
+        "{} bytes{human}",
+        count(i64::try_from(n).unwrap_or(i64::MAX))
+    )
+}
+
+fn stroops(n: i64) -> String {
+    format!("{} stroops", count(n))
+}
+
+/// A number of ledgers with the approximate time they take to close.
+fn ledgers(n: u32) -> String {
+    let seconds = u64::from(n) * LEDGER_SECONDS;
+    let approx = match seconds {
+        s if s >= 24 * 60 * 60 => format!("~{} days", s / (24 * 60 * 60)),
+        s if s >= 60 * 60 => format!("~{} hours", s / (60 * 60)),
+        s => format!("~{} minutes", s / 60),
+    };
+    format!("{} ledgers ({approx})", count(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::ConfigSettingContractComputeV0;
+
+    #[test]
+    fn text_in_human_units() {
+        let text = text(&[
+            ConfigSettingEntry::ContractMaxSizeBytes(65_536),
+            ConfigSettingEntry::ContractComputeV0(ConfigSettingContractComputeV0 {
+                ledger_max_instructions: 500_000_000,
+                tx_max_instructions: 100_000_000,
+                fee_rate_per_instructions_increment: 25,
+                tx_memory_limit: 41_943_040,
+            }),
+        ]);
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            [
+                "Contract size",
+                "  Max Wasm size                     65,536 bytes (64 KiB)",
+                "",
+                "Compute",
+                "  Max instructions per transaction  100,000,000",
+                "  Max instructions per ledger       500,000,000",
+                "  Max memory per transaction        41,943,040 bytes (40 MiB)",
+                "",
+                "Fees",
+                "  Per 10,000 instructions           25 stroops",
+            ]
+        );
+        assert_eq!(ledgers(3_110_400), "3,110,400 ledgers (~180 days)");
+    }
+}
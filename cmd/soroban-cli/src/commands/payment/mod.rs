@@ -0,0 +1,31 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod send;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Send a payment, optionally converting between assets along a path found by Horizon
+    ///
+    /// With `--path-strict-send` the amount sent is fixed and the destination receives at least
+    /// `--dest-min`. With `--path-strict-receive` the amount received is fixed and the source
+    /// sends at most `--send-max`. Candidate paths are printed with their rates before the best
+    /// one, or the one picked with `--path-index`, is submitted.
+    Send(send::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Send(#[from] send::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Send(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
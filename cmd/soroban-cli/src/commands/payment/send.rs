@@ -0,0 +1,294 @@
+use clap::{arg, command, Parser};
+use serde_json::Value;
+
+use crate::{
+    commands::{global, tx},
+    config::{
+        self,
+        address::Address,
+        asset::{self, Asset},
+    },
+    horizon,
+    print::Print,
+    xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("--send-asset is only used with --path-strict-send or --path-strict-receive")]
+    SendAssetWithoutPath,
+    #[error("no path found from {from} to {to}")]
+    NoPath { from: String, to: String },
+    #[error("path index {index} not found, {count} paths were found")]
+    PathNotFound { index: usize, count: usize },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account to send to, e.g. `GBX...`, a federation address, e.g. `bob*example.com`, or an
+    /// address book label
+    #[arg(long)]
+    pub destination: Address,
+    /// Asset received by the destination, `native` or `CODE:ISSUER` where the issuer can be an
+    /// identity
+    #[arg(long, default_value = "native")]
+    pub asset: Asset,
+    /// Amount in stroops, the amount sent with `--path-strict-send` and the amount received
+    /// otherwise
+    #[arg(long)]
+    pub amount: i64,
+    /// Send exactly `--amount` of `--send-asset`, the destination receives at least `--dest-min`
+    /// of `--asset`
+    #[arg(long, requires = "send_asset", conflicts_with = "path_strict_receive")]
+    pub path_strict_send: bool,
+    /// Deliver exactly `--amount` of `--asset`, the source sends at most `--send-max` of
+    /// `--send-asset`
+    #[arg(long, requires = "send_asset")]
+    pub path_strict_receive: bool,
+    /// Asset sent by the source account when converting along a path
+    #[arg(long)]
+    pub send_asset: Option<Asset>,
+    /// Minimum amount in stroops the destination receives with `--path-strict-send`, defaults to
+    /// the amount of the chosen path less `--slippage-bps`
+    #[arg(long, requires = "path_strict_send", value_parser = clap::value_parser!(i64).range(1..))]
+    pub dest_min: Option<i64>,
+    /// Maximum amount in stroops the source sends with `--path-strict-receive`, defaults to the
+    /// amount of the chosen path plus `--slippage-bps`
+    #[arg(long, requires = "path_strict_receive")]
+    pub send_max: Option<i64>,
+    /// Tolerated change of the rate of the chosen path in basis points, used when `--dest-min` or
+    /// `--send-max` are not set
+    #[arg(long, default_value = "100", value_parser = clap::value_parser!(u16).range(0..10_000))]
+    pub slippage_bps: u16,
+    /// Index of the candidate path to use instead of the one with the best rate
+    #[arg(long)]
+    pub path_index: Option<usize>,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+/// A candidate path returned by Horizon path finding.
+#[derive(Debug, Clone)]
+struct Candidate {
+    source_amount: i64,
+    destination_amount: i64,
+    path: Vec<xdr::Asset>,
+}
+
+impl Candidate {
+    fn from_record(record: &Value) -> Result<Self, horizon::Error> {
+        let amount =
+            |field: &str| horizon::parse_amount(record[field].as_str().unwrap_or_default());
+        Ok(Candidate {
+            source_amount: amount("source_amount")?,
+            destination_amount: amount("destination_amount")?,
+            path: record["path"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|asset| horizon::asset_from(asset, ""))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn describe(&self, send_asset: &xdr::Asset, dest_asset: &xdr::Asset) -> String {
+        let via = if self.path.is_empty() {
+            "direct".to_string()
+        } else {
+            self.path
+                .iter()
+                .map(horizon::asset_string)
+                .collect::<Vec<_>>()
+                .join(" > ")
+        };
+        format!(
+            "send {} {} receive {} {} via {via} (rate {:.7})",
+            horizon::amount(self.source_amount),
+            horizon::asset_string(send_asset),
+            horizon::amount(self.destination_amount),
+            horizon::asset_string(dest_asset),
+            self.destination_amount as f64 / self.source_amount as f64,
+        )
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let config = &self.tx.config;
+        let dest_asset: xdr::Asset = self.asset.resolve(&config.locator, config.hd_path)?.into();
+        let (destination, memo) = self.tx.resolve_destination(&self.destination)?;
+        let op = match &self.send_asset {
+            None => xdr::OperationBody::Payment(xdr::PaymentOp {
+                destination,
+                asset: dest_asset,
+                amount: self.amount,
+            }),
+            Some(_) if !self.path_strict_send && !self.path_strict_receive => {
+                return Err(Error::SendAssetWithoutPath)
+            }
+            Some(send_asset) => {
+                let send_asset: xdr::Asset =
+                    send_asset.resolve(&config.locator, config.hd_path)?.into();
+                self.path_payment(send_asset, dest_asset, destination, global_args)
+                    .await?
+            }
+        };
+        self.tx
+            .handle_and_print_with_memo(op, memo, global_args)
+            .await?;
+        Ok(())
+    }
+
+    async fn path_payment(
+        &self,
+        send_asset: xdr::Asset,
+        dest_asset: xdr::Asset,
+        destination: xdr::MuxedAccount,
+        global_args: &global::Args,
+    ) -> Result<xdr::OperationBody, Error> {
+        let print = Print::new(global_args.quiet);
+        let client = self.horizon.client(&self.tx.config.get_network()?)?;
+        let amount = horizon::amount(self.amount);
+        let (endpoint, params) = if self.path_strict_send {
+            let mut params = horizon::asset_params("source_", &send_asset);
+            params.push(("source_amount".to_string(), amount));
+            params.push((
+                "destination_assets".to_string(),
+                horizon::asset_string(&dest_asset),
+            ));
+            ("paths/strict-send", params)
+        } else {
+            let mut params = horizon::asset_params("destination_", &dest_asset);
+            params.push(("destination_amount".to_string(), amount));
+            params.push((
+                "source_assets".to_string(),
+                horizon::asset_string(&send_asset),
+            ));
+            ("paths/strict-receive", params)
+        };
+        let query = params
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.clone()))
+            .collect::<Vec<_>>();
+        let mut paths = client
+            .records(endpoint, &query, None)
+            .await?
+            .iter()
+            .map(Candidate::from_record)
+            .collect::<Result<Vec<_>, _>>()?;
+        if paths.is_empty() {
+            return Err(Error::NoPath {
+                from: horizon::asset_string(&send_asset),
+                to: horizon::asset_string(&dest_asset),
+            });
+        }
+        // Best rate first: most received for a fixed send, least sent for a fixed receive.
+        if self.path_strict_send {
+            paths.sort_by_key(|p| std::cmp::Reverse(p.destination_amount));
+        } else {
+            paths.sort_by_key(|p| p.source_amount);
+        }
+        for (index, path) in paths.iter().enumerate() {
+            print.infoln(format!(
+                "#{index} {}",
+                path.describe(&send_asset, &dest_asset)
+            ));
+        }
+        let index = self.path_index.unwrap_or_default();
+        let count = paths.len();
+        let Candidate {
+            source_amount,
+            destination_amount,
+            path,
+        } = paths
+            .into_iter()
+            .nth(index)
+            .ok_or(Error::PathNotFound { index, count })?;
+        print.checkln(format!("Using path #{index}"));
+
+        let path = path.try_into()?;
+        Ok(if self.path_strict_send {
+            let dest_min = self
+                .dest_min
+                .unwrap_or_else(|| default_dest_min(destination_amount, self.slippage_bps));
+            xdr::OperationBody::PathPaymentStrictSend(xdr::PathPaymentStrictSendOp {
+                send_asset,
+                send_amount: self.amount,
+                destination,
+                dest_asset,
+                dest_min,
+                path,
+            })
+        } else {
+            let send_max = self.send_max.unwrap_or_else(|| {
+                let slippage = i128::from(self.slippage_bps);
+                let max = (i128::from(source_amount) * (10_000 + slippage) + 9_999) / 10_000;
+                i64::try_from(max).unwrap_or(i64::MAX)
+            });
+            xdr::OperationBody::PathPaymentStrictReceive(xdr::PathPaymentStrictReceiveOp {
+                send_asset,
+                send_max,
+                destination,
+                dest_asset,
+                dest_amount: self.amount,
+                path,
+            })
+        })
+    }
+}
+
+/// The amount of the chosen path less `slippage_bps`, at least 1 stroop since core rejects a
+/// `dest_min` of 0.
+fn default_dest_min(destination_amount: i64, slippage_bps: u16) -> i64 {
+    let min = i128::from(destination_amount) * (10_000 - i128::from(slippage_bps)) / 10_000;
+    i64::try_from(min).unwrap_or(destination_amount).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidate_from_path_record() {
+        let issuer = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF";
+        let record = serde_json::json!({
+            "source_asset_type": "native",
+            "source_amount": "10.0000000",
+            "destination_asset_type": "credit_alphanum4",
+            "destination_asset_code": "USDC",
+            "destination_asset_issuer": issuer,
+            "destination_amount": "1.2500000",
+            "path": [{ "asset_type": "credit_alphanum4", "asset_code": "EURC", "asset_issuer": issuer }],
+        });
+        let candidate = Candidate::from_record(&record).unwrap();
+        assert_eq!(candidate.source_amount, 100_000_000);
+        assert_eq!(candidate.destination_amount, 12_500_000);
+        let dest = horizon::asset_from(&record, "destination_").unwrap();
+        assert_eq!(
+            candidate.describe(&xdr::Asset::Native, &dest),
+            format!("send 10.0000000 native receive 1.2500000 USDC:{issuer} via EURC:{issuer} (rate 0.1250000)")
+        );
+    }
+
+    #[test]
+    fn default_dest_min_is_at_least_one_stroop() {
+        assert_eq!(default_dest_min(12_500_000, 100), 12_375_000);
+        assert_eq!(default_dest_min(12_500_000, 9_999), 1_250);
+        assert_eq!(default_dest_min(50, 9_999), 1);
+        assert_eq!(default_dest_min(1, 100), 1);
+    }
+}
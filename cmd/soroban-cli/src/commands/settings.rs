@@ -0,0 +1,43 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod migrate;
+pub mod set;
+pub mod show;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Set a setting of the global config file, e.g. `safety.mainnet-confirm false` to submit
+    /// transactions to Mainnet without asking for confirmation
+    Set(set::Cmd),
+    /// Copy the identities, networks, contract aliases, and settings of the legacy `soroban`
+    /// config directories, global and in the current directory, to the `stellar` ones. Files
+    /// that already exist with other contents are kept and reported
+    Migrate(migrate::Cmd),
+    /// Show the global config file, or with `--resolved` the effective configuration: where
+    /// each value comes from (flag, env, project, or global), the active network and identity,
+    /// the health of the RPC endpoint, and the paths in use
+    Show(show::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Set(#[from] set::Error),
+    #[error(transparent)]
+    Migrate(#[from] migrate::Error),
+    #[error(transparent)]
+    Show(#[from] show::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Set(cmd) => cmd.run(global_args)?,
+            Cmd::Migrate(cmd) => cmd.run(global_args)?,
+            Cmd::Show(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
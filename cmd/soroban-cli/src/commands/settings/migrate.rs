@@ -0,0 +1,304 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{arg, command};
+
+use crate::{
+    commands::global,
+    config::{alias, locator, network::Network, secret::Secret},
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error("failed to migrate {from} to {to}: {error}")]
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Report what would be migrated without copying anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+/// What happens to a file of a legacy config directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Copied, or to be copied with `--dry-run`.
+    Migrated,
+    /// The current directory has the same file.
+    AlreadyMigrated,
+    /// The current directory has a file of the same name with other contents, which is kept.
+    Conflict,
+    /// The file cannot be read as its kind of config.
+    Invalid(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub kind: &'static str,
+    pub name: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// The contents to write, converted to the current format.
+    pub data: String,
+    pub outcome: Outcome,
+}
+
+/// Check a config file and convert it to the current format, returning what to write.
+type Convert = fn(&str) -> Result<String, String>;
+
+/// The directories of the layout and the extension and conversion of their files.
+const KINDS: [(&str, &str, Convert); 3] = [
+    ("identity", "toml", |data| {
+        toml::from_str::<Secret>(data)
+            .map(|_| data.to_string())
+            .map_err(|e| e.to_string())
+    }),
+    ("network", "toml", network),
+    ("contract-ids", "json", |data| {
+        serde_json::from_str::<alias::Data>(data)
+            .map(|_| data.to_string())
+            .map_err(|e| e.to_string())
+    }),
+];
+
+/// Networks written by soroban-cli have no `rpc_headers`, which are now required.
+fn network(data: &str) -> Result<String, String> {
+    let mut value = toml::from_str::<toml::Table>(data).map_err(|e| e.to_string())?;
+    if value.contains_key("rpc_headers") {
+        return toml::from_str::<Network>(data)
+            .map(|_| data.to_string())
+            .map_err(|e| e.to_string());
+    }
+    value.insert("rpc_headers".to_string(), toml::Value::Array(Vec::new()));
+    let network = value.try_into::<Network>().map_err(|e| e.to_string())?;
+    toml::to_string(&network).map_err(|e| e.to_string())
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let home = locator::config_home()?;
+        let pwd = self.config_locator.current_dir()?;
+        let dirs = [
+            (home.join("soroban"), home.join("stellar")),
+            (pwd.join(".soroban"), pwd.join(".stellar")),
+        ];
+        let mut found = false;
+        for (from, to) in dirs.iter().filter(|(from, _)| from.is_dir()) {
+            found = true;
+            let items = plan(from, to)?;
+            for item in &items {
+                report(&print, item, self.dry_run);
+            }
+            if !self.dry_run {
+                apply(&items)?;
+            }
+            if items
+                .iter()
+                .all(|item| matches!(item.outcome, Outcome::Migrated | Outcome::AlreadyMigrated))
+                && !self.dry_run
+            {
+                print.infoln(format!(
+                    "Everything in {} is in {}, the legacy directory can be removed",
+                    from.display(),
+                    to.display()
+                ));
+            }
+        }
+        if !found {
+            print.infoln("No legacy soroban config directory found");
+        }
+        Ok(())
+    }
+}
+
+fn report(print: &Print, item: &Item, dry_run: bool) {
+    let Item {
+        kind,
+        name,
+        from,
+        to,
+        ..
+    } = item;
+    match &item.outcome {
+        Outcome::Migrated if dry_run => {
+            print.infoln(format!("Would migrate {kind} {name} to {}", to.display()));
+        }
+        Outcome::Migrated => print.checkln(format!("Migrated {kind} {name} to {}", to.display())),
+        Outcome::AlreadyMigrated => print.infoln(format!("{kind} {name} is already migrated")),
+        Outcome::Conflict => print.warnln(format!(
+            "Not migrating {kind} {name}, {} exists with other contents than {}",
+            to.display(),
+            from.display()
+        )),
+        Outcome::Invalid(error) => print.warnln(format!(
+            "Not migrating {kind} {name}, {} is invalid: {error}",
+            from.display()
+        )),
+    }
+}
+
+/// Compare the identities, networks, contract aliases, and settings of the legacy directory
+/// `from` with those of `to`.
+pub fn plan(from: &Path, to: &Path) -> Result<Vec<Item>, Error> {
+    let mut items = Vec::new();
+    for (kind, extension, convert) in KINDS {
+        let dir = from.join(kind);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut files = fs::read_dir(&dir)
+            .map_err(locator::Error::from)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == extension))
+            .collect::<Vec<_>>();
+        files.sort();
+        for path in files {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let target = to.join(kind).join(path.file_name().unwrap_or_default());
+            items.push(item(kind, name, path, target, convert)?);
+        }
+    }
+    let settings = from.join("config.toml");
+    if settings.is_file() {
+        items.push(item(
+            "settings",
+            "config.toml".to_string(),
+            settings,
+            to.join("config.toml"),
+            |data| {
+                toml::from_str::<toml::Value>(data)
+                    .map(|_| data.to_string())
+                    .map_err(|e| e.to_string())
+            },
+        )?);
+    }
+    Ok(items)
+}
+
+fn item(
+    kind: &'static str,
+    name: String,
+    from: PathBuf,
+    to: PathBuf,
+    convert: Convert,
+) -> Result<Item, Error> {
+    let data = fs::read_to_string(&from).map_err(locator::Error::from)?;
+    let (data, outcome) = match convert(&data) {
+        Err(error) => (data, Outcome::Invalid(error)),
+        Ok(data) => match fs::read_to_string(&to) {
+            Ok(existing) if existing == data => (data, Outcome::AlreadyMigrated),
+            Ok(_) => (data, Outcome::Conflict),
+            Err(_) => (data, Outcome::Migrated),
+        },
+    };
+    Ok(Item {
+        kind,
+        name,
+        from,
+        to,
+        data,
+        outcome,
+    })
+}
+
+/// Write the files to migrate, keeping their permissions, which matter for secrets.
+pub fn apply(items: &[Item]) -> Result<(), Error> {
+    for Item { from, to, data, .. } in items.iter().filter(|i| i.outcome == Outcome::Migrated) {
+        let copy = |error| Error::Copy {
+            from: from.clone(),
+            to: to.clone(),
+            error,
+        };
+        if let Some(dir) = to.parent() {
+            fs::create_dir_all(dir).map_err(copy)?;
+        }
+        fs::write(to, data).map_err(copy)?;
+        fs::set_permissions(to, fs::metadata(from).map_err(copy)?.permissions()).map_err(copy)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_legacy_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let (from, to) = (dir.path().join(".soroban"), dir.path().join(".stellar"));
+        let write = |path: PathBuf, data: &str| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, data).unwrap();
+        };
+        let secret = "secret_key = \"SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW\"\n";
+        write(from.join("identity/alice.toml"), secret);
+        write(from.join("identity/broken.toml"), "nothing = 1\n");
+        write(
+            from.join("network/local.toml"),
+            "rpc_url = \"http://localhost:8000/rpc\"\nnetwork_passphrase = \"Standalone Network ; February 2017\"\n",
+        );
+        write(
+            from.join("network/testnet.toml"),
+            "rpc_url = \"https://soroban-testnet.stellar.org\"\nnetwork_passphrase = \"Test SDF Network ; September 2015\"\n",
+        );
+        write(from.join("contract-ids/token.json"), r#"{"ids":{}}"#);
+        write(
+            to.join("network/local.toml"),
+            "rpc_url = \"http://localhost:8001/rpc\"\nnetwork_passphrase = \"Standalone Network ; February 2017\"\n",
+        );
+
+        let items = plan(&from, &to).unwrap();
+        let outcome = |name: &str| {
+            items
+                .iter()
+                .find(|item| item.name == name)
+                .unwrap()
+                .outcome
+                .clone()
+        };
+        assert_eq!(items.len(), 5);
+        assert_eq!(outcome("alice"), Outcome::Migrated);
+        assert!(matches!(outcome("broken"), Outcome::Invalid(_)));
+        assert_eq!(outcome("local"), Outcome::Conflict);
+        assert_eq!(outcome("testnet"), Outcome::Migrated);
+        assert_eq!(outcome("token"), Outcome::Migrated);
+
+        apply(&items).unwrap();
+        assert_eq!(
+            fs::read_to_string(to.join("identity/alice.toml")).unwrap(),
+            secret
+        );
+        assert!(to.join("contract-ids/token.json").exists());
+        let testnet = fs::read_to_string(to.join("network/testnet.toml")).unwrap();
+        assert!(toml::from_str::<Network>(&testnet)
+            .unwrap()
+            .rpc_headers
+            .is_empty());
+        assert!(!to.join("identity/broken.toml").exists());
+        assert_eq!(
+            plan(&from, &to).unwrap()[0].outcome,
+            Outcome::AlreadyMigrated
+        );
+    }
+}
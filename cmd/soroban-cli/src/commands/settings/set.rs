@@ -0,0 +1,56 @@
+use clap::{command, ValueEnum};
+
+use crate::{commands::global, config::locator, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error("invalid value {value:?} for {key}, expected `true` or `false`")]
+    InvalidBool { key: String, value: String },
+}
+
+/// Setting of the global config file
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Key {
+    /// Ask for confirmation before submitting transactions to Mainnet, `true` by default
+    #[value(name = "safety.mainnet-confirm")]
+    SafetyMainnetConfirm,
+    /// Check for a new release of the CLI in the background, at most once a day, `true` by
+    /// default. `stellar version --check` checks on demand
+    #[value(name = "upgrade.check")]
+    UpgradeCheck,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Setting to set
+    #[arg(value_enum)]
+    pub key: Key,
+
+    /// Value of the setting
+    pub value: String,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let printer = Print::new(global_args.quiet);
+        let key = self.key.to_possible_value().unwrap();
+        let bool = || {
+            self.value.parse().map_err(|_| Error::InvalidBool {
+                key: key.get_name().to_string(),
+                value: self.value.clone(),
+            })
+        };
+        match self.key {
+            Key::SafetyMainnetConfirm => self.config_locator.write_mainnet_confirm(bool()?)?,
+            Key::UpgradeCheck => self.config_locator.write_upgrade_check(bool()?)?,
+        }
+        printer.infoln(format!("`{}` is set to `{}`", key.get_name(), self.value));
+        Ok(())
+    }
+}
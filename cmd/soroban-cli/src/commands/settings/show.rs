@@ -0,0 +1,375 @@
+use std::{path::Path, time::Instant};
+
+use clap::{command, ValueEnum};
+use jsonrpsee_core::params::ObjectParams;
+use serde::Serialize;
+
+use crate::{
+    commands::global,
+    config::{
+        self,
+        address::Address,
+        data,
+        locator::{self, KeyType, Location},
+        network::{self, Network},
+        secret::Secret,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per setting with its value and where it comes from
+    Pretty,
+    /// A JSON object with the value and source of each setting
+    Json,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Show the effective configuration instead of the global config file: the value of each
+    /// setting and where it comes from, the health of the RPC endpoint, and the paths in use
+    #[arg(long)]
+    pub resolved: bool,
+
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "pretty")]
+    pub output: Output,
+
+    /// Identity or address of the source account, the default identity if not given
+    #[arg(long, visible_alias = "source", env = "STELLAR_ACCOUNT")]
+    pub source_account: Option<String>,
+
+    #[command(flatten)]
+    pub network: network::Args,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+/// Where the value of a setting comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source {
+    /// A command line flag
+    Flag,
+    /// An environment variable
+    Env,
+    /// The config directory of the project
+    Project,
+    /// The global config directory, or the defaults of its config file
+    Global,
+    /// Built into the CLI
+    Default,
+    /// Asked to the RPC server
+    Rpc,
+    /// Not set
+    Unset,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Source::Flag => "flag",
+            Source::Env => "env",
+            Source::Project => "project",
+            Source::Global => "global",
+            Source::Default => "default",
+            Source::Rpc => "rpc",
+            Source::Unset => "unset",
+        })
+    }
+}
+
+impl From<&Location> for Source {
+    fn from(location: &Location) -> Self {
+        match location {
+            Location::Local(_) => Source::Project,
+            Location::Global(_) => Source::Global,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Setting {
+    pub name: &'static str,
+    pub value: Option<String>,
+    pub source: Source,
+}
+
+impl Cmd {
+    pub async fn run(&self, _global_args: &global::Args) -> Result<(), Error> {
+        if !self.resolved {
+            let config = config::Config::new()?;
+            match self.output {
+                Output::Pretty => print!("{}", toml::to_string(&config)?),
+                Output::Json => println!("{}", serde_json::to_string_pretty(&config)?),
+            }
+            return Ok(());
+        }
+
+        let settings = self.resolve().await?;
+        match self.output {
+            Output::Pretty => {
+                let width = settings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+                for Setting {
+                    name,
+                    value,
+                    source,
+                } in &settings
+                {
+                    let value = value.as_deref().unwrap_or("-");
+                    println!("{name:width$}  {value}  # {source}");
+                }
+            }
+            Output::Json => {
+                let json = settings
+                    .into_iter()
+                    .map(
+                        |Setting {
+                             name,
+                             value,
+                             source,
+                         }| {
+                            (
+                                name.to_string(),
+                                serde_json::json!({ "value": value, "source": source }),
+                            )
+                        },
+                    )
+                    .collect::<serde_json::Map<_, _>>();
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn resolve(&self) -> Result<Vec<Setting>, Error> {
+        let locator = &self.config_locator;
+        let locations = locator.local_and_global()?;
+        let config = config::Config::new()?;
+        let mut settings = Vec::new();
+        let mut push = |name, value: Option<String>, source| {
+            settings.push(Setting {
+                name,
+                source: if value.is_some() {
+                    source
+                } else {
+                    Source::Unset
+                },
+                value,
+            });
+        };
+
+        // Network
+        let name = self.network.network.clone();
+        let name_source = arg_source("STELLAR_NETWORK", name.as_deref());
+        push("network", name.clone(), name_source);
+        let network = self.network.get(locator).ok();
+        let network_source = match (&name, &self.network.rpc_url) {
+            (_, Some(rpc_url)) => arg_source("STELLAR_RPC_URL", Some(rpc_url)),
+            (Some(name), None) => find(KeyType::Network, name, &locations)
+                .map_or(Source::Default, |location| (&location).into()),
+            (None, None) => Source::Unset,
+        };
+        push(
+            "network.rpc-url",
+            network.as_ref().map(|n| n.rpc_url.clone()),
+            network_source,
+        );
+        push(
+            "network.passphrase",
+            network.as_ref().map(|n| n.network_passphrase.clone()),
+            network_source,
+        );
+        push(
+            "network.rpc-health",
+            match &network {
+                Some(network) => Some(health(network).await),
+                None => None,
+            },
+            Source::Rpc,
+        );
+
+        // Identity
+        let identity = self.source_account.clone();
+        let identity_source = arg_source("STELLAR_ACCOUNT", identity.as_deref());
+        push(
+            "identity",
+            identity.as_deref().map(display_identity),
+            identity_source,
+        );
+        let address = identity.as_deref().and_then(|identity| {
+            let address = identity
+                .parse::<Address>()
+                .ok()?
+                .resolve_muxed_account(locator, None)
+                .ok()?;
+            let source = find(KeyType::Identity, identity, &locations)
+                .map_or(identity_source, |location| (&location).into());
+            Some((address.to_string(), source))
+        });
+        push(
+            "identity.address",
+            address.as_ref().map(|(address, _)| address.clone()),
+            address.map_or(Source::Unset, |(_, source)| source),
+        );
+
+        // Settings
+        let fee_strategy = std::env::var("STELLAR_FEE_STRATEGY").ok();
+        push(
+            "fee-strategy",
+            fee_strategy.clone(),
+            arg_source("STELLAR_FEE_STRATEGY", fee_strategy.as_deref()),
+        );
+        push(
+            "safety.mainnet-confirm",
+            Some(config.safety.mainnet_confirm().to_string()),
+            if config.safety.mainnet_confirm.is_some() {
+                Source::Global
+            } else {
+                Source::Default
+            },
+        );
+        push(
+            "upgrade.check",
+            Some(config.upgrade.check().to_string()),
+            if config.upgrade.check.is_some() {
+                Source::Global
+            } else {
+                Source::Default
+            },
+        );
+
+        // Paths
+        let [local, global] = &locations;
+        push(
+            "path.project",
+            Some(display(local.as_ref())),
+            Source::Project,
+        );
+        push(
+            "path.global",
+            Some(display(global.as_ref())),
+            Source::Global,
+        );
+        push(
+            "path.config-file",
+            Some(display(&locator::config_file()?)),
+            Source::Global,
+        );
+        push(
+            "path.data",
+            Some(display(&data::data_local_dir()?)),
+            Source::Default,
+        );
+        Ok(settings)
+    }
+}
+
+/// Where the value of an argument that can be set with `env` comes from. Defaults of the global
+/// config file are set as environment variables with a `_SOURCE` of `use`, see `cli.rs`.
+fn arg_source(env: &str, value: Option<&str>) -> Source {
+    let Some(value) = value else {
+        return Source::Unset;
+    };
+    if std::env::var(env).ok().as_deref() != Some(value) {
+        return Source::Flag;
+    }
+    match std::env::var(format!("{env}_SOURCE")).ok().as_deref() {
+        Some("use") => Source::Global,
+        _ => Source::Env,
+    }
+}
+
+/// Where an identity or network is saved, the project config taking precedence.
+/// The identity to show, hiding it when `--source-account` or `STELLAR_ACCOUNT` is a secret key
+/// or seed phrase rather than the name of an identity or an address. Its address is shown as
+/// `identity.address`.
+fn display_identity(identity: &str) -> String {
+    if identity.parse::<Secret>().is_ok() {
+        "<secret>".to_string()
+    } else {
+        identity.to_string()
+    }
+}
+
+fn find(key_type: KeyType, name: &str, locations: &[Location]) -> Option<Location> {
+    key_type
+        .list_paths(locations)
+        .ok()?
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, location)| location)
+}
+
+/// Whether the path exists, for paths that are created on first use.
+fn display(path: &Path) -> String {
+    if path.exists() {
+        path.display().to_string()
+    } else {
+        format!("{} (not created)", path.display())
+    }
+}
+
+/// The health of the RPC server, its latest ledger, and its response time, or why it is
+/// unhealthy.
+async fn health(network: &Network) -> String {
+    let start = Instant::now();
+    let status = match network
+        .rpc_request::<serde_json::Value>("getHealth", ObjectParams::new())
+        .await
+    {
+        Ok(health) => health,
+        Err(e) => return format!("unreachable: {e}"),
+    };
+    let elapsed = start.elapsed().as_millis();
+    let status_str = status["status"].as_str().unwrap_or("unknown");
+    let latest = status["latestLedger"]
+        .as_u64()
+        .map_or_else(String::new, |l| format!(", latest ledger {l}"));
+    let passphrase = match network.rpc_client() {
+        Ok(client) => match client.get_network().await {
+            Ok(rpc) if rpc.passphrase != network.network_passphrase => {
+                format!(", on another network {:?}", rpc.passphrase)
+            }
+            Ok(_) => String::new(),
+            Err(e) => format!(", cannot get its network: {e}"),
+        },
+        Err(e) => format!(", {e}"),
+    };
+    format!("{status_str}{latest}{passphrase} ({elapsed}ms)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_identities_are_hidden() {
+        let secret = stellar_strkey::ed25519::PrivateKey([1; 32]).to_string();
+        assert_eq!(display_identity(&secret), "<secret>");
+        let Secret::SeedPhrase { seed_phrase } = Secret::test_seed_phrase().unwrap() else {
+            panic!("expected a seed phrase");
+        };
+        assert_eq!(display_identity(&seed_phrase), "<secret>");
+        assert_eq!(display_identity("alice"), "alice");
+        assert_eq!(
+            display_identity("GAKSH6AD2IPJQELTHIOWDAPYX74YELUOWJLI2L4RIPIPZH6YQIFNUSDC"),
+            "GAKSH6AD2IPJQELTHIOWDAPYX74YELUOWJLI2L4RIPIPZH6YQIFNUSDC"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::{HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+use soroban_rpc::{
+    FullLedgerEntries, FullLedgerEntry, GetLedgerEntriesResponse, LedgerEntryResult,
+};
+use url::Url;
+
+use crate::{
+    commands::HEADING_RPC,
+    config::network::Network,
+    print::Print,
+    xdr::{
+        self, AccountId, BucketEntry, Frame, Hash, LedgerEntry, LedgerEntryData, LedgerKey,
+        LedgerKeyTrustLine, LedgerKeyTtl, Limited, Limits, ReadXdr, WriteXdr,
+    },
+};
+
+use super::create::{self, cache_bucket, data_into_key, get_history, CHECKPOINT_FREQUENCY};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Archive(#[from] create::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("archive url not configured, use --archive-url to read entries at a ledger")]
+    ArchiveUrlNotConfigured,
+    #[error("ledger {0} is before the first checkpoint of the history archive, ledger {first}", first = CHECKPOINT_FREQUENCY - 1)]
+    BeforeFirstCheckpoint(u32),
+    #[error("the history archive is of network {archive:?}, not {network:?}")]
+    WrongNetwork { archive: String, network: String },
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Read the entries as of a past ledger instead of the latest one, from the history archive
+    /// of the network. Archives hold the state at checkpoints, every 64 ledgers, so entries are
+    /// as of the last checkpoint at or before the ledger
+    #[arg(long)]
+    pub at_ledger: Option<u32>,
+    /// History archive to read entries from with `--at-ledger`, defaults to the archive of
+    /// well-known networks
+    #[arg(long, help_heading = HEADING_RPC, env = "STELLAR_ARCHIVE_URL")]
+    pub archive_url: Option<Url>,
+}
+
+/// The checkpoint ledger at or before `ledger`.
+fn checkpoint(ledger: u32) -> Option<u32> {
+    // In 64 bits, as the ledger after the last one does not fit in 32.
+    let frequency = u64::from(CHECKPOINT_FREQUENCY);
+    let checkpoint = ((u64::from(ledger) + 1) / frequency * frequency).checked_sub(1)?;
+    u32::try_from(checkpoint).ok()
+}
+
+impl Args {
+    /// The entries of `keys` as of `--at-ledger`, from the history archive, as the RPC returns
+    /// them with `getLedgerEntries` and with the checkpoint as the latest ledger. Contract data
+    /// and code whose TTL ran out before the checkpoint were archived and are left out. `None`
+    /// without `--at-ledger`.
+    pub async fn ledger_entries(
+        &self,
+        print: &Print,
+        network: &Network,
+        keys: &[LedgerKey],
+    ) -> Result<Option<GetLedgerEntriesResponse>, Error> {
+        // Soroban entries live until the ledger of their TTL entry.
+        let ttl_keys = keys
+            .iter()
+            .filter(|key| matches!(key, LedgerKey::ContractData(_) | LedgerKey::ContractCode(_)))
+            .map(|key| {
+                Ok((
+                    LedgerKey::Ttl(LedgerKeyTtl {
+                        key_hash: Hash(Sha256::digest(key.to_xdr(Limits::none())?).into()),
+                    }),
+                    key.clone(),
+                ))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()?;
+        let mut wanted = keys.iter().cloned().collect::<HashSet<_>>();
+        wanted.extend(ttl_keys.keys().cloned());
+        let Some((checkpoint, found)) = self
+            .scan(
+                print,
+                network,
+                |key| wanted.contains(key),
+                Some(wanted.len()),
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let live_until = ttl_keys
+            .iter()
+            .filter_map(|(ttl_key, key)| match found.get(ttl_key) {
+                Some(Some(LedgerEntry {
+                    data: LedgerEntryData::Ttl(ttl),
+                    ..
+                })) => Some((key, ttl.live_until_ledger_seq)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let mut entries = Vec::new();
+        for key in keys {
+            let Some(Some(entry)) = found.get(key) else {
+                continue;
+            };
+            let live_until = live_until.get(key).copied();
+            if let Some(live_until) = live_until.filter(|l| *l < checkpoint) {
+                print.warnln(format!(
+                    "Entry {} was archived at ledger {live_until}, before ledger {checkpoint}",
+                    key.to_xdr_base64(Limits::none())?
+                ));
+                continue;
+            }
+            entries.push(LedgerEntryResult {
+                key: key.to_xdr_base64(Limits::none())?,
+                xdr: entry.data.to_xdr_base64(Limits::none())?,
+                last_modified_ledger: entry.last_modified_ledger_seq,
+                live_until_ledger_seq_ledger_seq: live_until,
+            });
+        }
+        Ok(Some(GetLedgerEntriesResponse {
+            entries: Some(entries),
+            latest_ledger: i64::from(checkpoint),
+        }))
+    }
+
+    /// The trustlines of `account` as of `--at-ledger`, from the history archive. `None`
+    /// without `--at-ledger`. Unlike `ledger_entries` this reads every bucket, as the number
+    /// of trustlines isn't known up front.
+    pub async fn trustlines(
+        &self,
+        print: &Print,
+        network: &Network,
+        account: &AccountId,
+    ) -> Result<Option<Vec<LedgerEntry>>, Error> {
+        let wanted = |key: &LedgerKey| matches!(key, LedgerKey::Trustline(LedgerKeyTrustLine { account_id, .. }) if account_id == account);
+        let Some((_, found)) = self.scan(print, network, wanted, None).await? else {
+            return Ok(None);
+        };
+        Ok(Some(found.into_values().flatten().collect()))
+    }
+
+    /// The latest version of the entries of the keys `wanted` as of the checkpoint of
+    /// `--at-ledger`, `None` for deleted ones, stopping once `count` entries are found.
+    async fn scan(
+        &self,
+        print: &Print,
+        network: &Network,
+        wanted: impl Fn(&LedgerKey) -> bool,
+        count: Option<usize>,
+    ) -> Result<Option<(u32, HashMap<LedgerKey, Option<LedgerEntry>>)>, Error> {
+        let Some(ledger) = self.at_ledger else {
+            return Ok(None);
+        };
+        let checkpoint = checkpoint(ledger).ok_or(Error::BeforeFirstCheckpoint(ledger))?;
+        if checkpoint != ledger {
+            print.warnln(format!(
+                "Ledger {ledger} is not a checkpoint, reading entries as of ledger {checkpoint}"
+            ));
+        }
+        let archive_url = self
+            .archive_url
+            .clone()
+            .or_else(|| create::default_archive_url(&network.network_passphrase))
+            .ok_or(Error::ArchiveUrlNotConfigured)?;
+        let history = get_history(print, &archive_url, Some(checkpoint)).await?;
+        if history.network_passphrase != network.network_passphrase {
+            return Err(Error::WrongNetwork {
+                archive: history.network_passphrase,
+                network: network.network_passphrase.clone(),
+            });
+        }
+
+        let buckets = history
+            .current_buckets
+            .iter()
+            .flat_map(|h| [h.curr.clone(), h.snap.clone()])
+            .filter(|b| b != "0000000000000000000000000000000000000000000000000000000000000000")
+            .collect::<Vec<_>>();
+        // Entries appear in several buckets, the version in the first one is the latest.
+        let mut found = HashMap::<LedgerKey, Option<LedgerEntry>>::new();
+        for (i, bucket) in buckets.iter().enumerate() {
+            if count.is_some_and(|count| found.len() == count) {
+                break;
+            }
+            let cache_path = cache_bucket(print, &archive_url, i, bucket).await?;
+            let file =
+                std::fs::File::open(&cache_path).map_err(create::Error::ReadOpeningCachedBucket)?;
+            let limited = &mut Limited::new(file, Limits::none());
+            for entry in Frame::<BucketEntry>::read_xdr_iter(limited) {
+                let Frame(entry) = entry.map_err(create::Error::ReadXdrFrameBucketEntry)?;
+                let (key, val) = match entry {
+                    BucketEntry::Liveentry(l) | BucketEntry::Initentry(l) => {
+                        (data_into_key(&l), Some(l))
+                    }
+                    BucketEntry::Deadentry(k) => (k, None),
+                    BucketEntry::Metaentry(_) => continue,
+                };
+                if wanted(&key) && !found.contains_key(&key) {
+                    found.insert(key, val);
+                }
+            }
+        }
+        Ok(Some((history.current_ledger, found)))
+    }
+
+    /// The entries of `keys` as of `--at-ledger` decoded, as the RPC client returns them with
+    /// `get_full_ledger_entries`. `None` without `--at-ledger`.
+    pub async fn full_ledger_entries(
+        &self,
+        print: &Print,
+        network: &Network,
+        keys: &[LedgerKey],
+    ) -> Result<Option<FullLedgerEntries>, Error> {
+        let Some(response) = self.ledger_entries(print, network, keys).await? else {
+            return Ok(None);
+        };
+        let entries = response
+            .entries
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| {
+                Ok(FullLedgerEntry {
+                    key: LedgerKey::from_xdr_base64(&entry.key, Limits::none())?,
+                    val: LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())?,
+                    last_modified_ledger: entry.last_modified_ledger,
+                    live_until_ledger_seq: entry
+                        .live_until_ledger_seq_ledger_seq
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Some(FullLedgerEntries {
+            entries,
+            latest_ledger: response.latest_ledger,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_down_to_checkpoints() {
+        assert_eq!(checkpoint(62), None);
+        assert_eq!(checkpoint(63), Some(63));
+        assert_eq!(checkpoint(100), Some(63));
+        assert_eq!(checkpoint(127), Some(127));
+        assert_eq!(checkpoint(128), Some(127));
+        assert_eq!(checkpoint(u32::MAX), Some(u32::MAX));
+        assert_eq!(checkpoint(u32::MAX - 1), Some(u32::MAX - 64));
+    }
+}
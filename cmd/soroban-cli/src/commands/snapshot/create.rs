@@ -146,7 +146,7 @@ pub enum Error {
 /// often by 8. There's no way to simply detect what frequency to expect ledgers
 /// at, so it is hardcoded at 64, and this value is used only to help the user
 /// select good ledger numbers when they select one that doesn't exist.
-const CHECKPOINT_FREQUENCY: u32 = 64;
+pub(crate) const CHECKPOINT_FREQUENCY: u32 = 64;
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
@@ -376,20 +376,10 @@ impl Cmd {
         self.archive_url
             .clone()
             .or_else(|| {
-                self.network.get(&self.locator).ok().and_then(|network| {
-                    match network.network_passphrase.as_str() {
-                        passphrase::MAINNET => {
-                            Some("https://history.stellar.org/prd/core-live/core_live_001")
-                        }
-                        passphrase::TESTNET => {
-                            Some("https://history.stellar.org/prd/core-testnet/core_testnet_001")
-                        }
-                        passphrase::FUTURENET => Some("https://history-futurenet.stellar.org"),
-                        passphrase::LOCAL => Some("http://localhost:8000/archive"),
-                        _ => None,
-                    }
-                    .map(|s| Url::from_str(s).expect("archive url valid"))
-                })
+                self.network
+                    .get(&self.locator)
+                    .ok()
+                    .and_then(|network| default_archive_url(&network.network_passphrase))
             })
             .ok_or(Error::ArchiveUrlNotConfigured)
     }
@@ -433,7 +423,21 @@ impl Cmd {
     }
 }
 
-async fn get_history(
+/// The history archive of the well-known network with `network_passphrase`.
+pub fn default_archive_url(network_passphrase: &str) -> Option<Url> {
+    match network_passphrase {
+        passphrase::MAINNET => Some("https://history.stellar.org/prd/core-live/core_live_001"),
+        passphrase::TESTNET => {
+            Some("https://history.stellar.org/prd/core-testnet/core_testnet_001")
+        }
+        passphrase::FUTURENET => Some("https://history-futurenet.stellar.org"),
+        passphrase::LOCAL => Some("http://localhost:8000/archive"),
+        _ => None,
+    }
+    .map(|s| Url::from_str(s).expect("archive url valid"))
+}
+
+pub(crate) async fn get_history(
     print: &print::Print,
     archive_url: &Url,
     ledger: Option<u32>,
@@ -487,7 +491,7 @@ async fn get_history(
     serde_json::from_slice::<History>(&body).map_err(Error::JsonDecodingHistory)
 }
 
-async fn cache_bucket(
+pub(crate) async fn cache_bucket(
     print: &print::Print,
     archive_url: &Url,
     bucket_index: usize,
@@ -551,20 +555,20 @@ async fn cache_bucket(
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct History {
-    current_ledger: u32,
-    current_buckets: Vec<HistoryBucket>,
-    network_passphrase: String,
+pub(crate) struct History {
+    pub current_ledger: u32,
+    pub current_buckets: Vec<HistoryBucket>,
+    pub network_passphrase: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct HistoryBucket {
-    curr: String,
-    snap: String,
+pub(crate) struct HistoryBucket {
+    pub curr: String,
+    pub snap: String,
 }
 
-fn data_into_key(d: &LedgerEntry) -> LedgerKey {
+pub(crate) fn data_into_key(d: &LedgerEntry) -> LedgerKey {
     // TODO: Move this function into stellar-xdr.
     match &d.data {
         LedgerEntryData::Account(e) => LedgerKey::Account(LedgerKeyAccount {
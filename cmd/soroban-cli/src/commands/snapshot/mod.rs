@@ -2,6 +2,7 @@ use clap::Parser;
 
 use super::global;
 
+pub mod at_ledger;
 pub mod create;
 
 /// Create and operate on ledger snapshots.
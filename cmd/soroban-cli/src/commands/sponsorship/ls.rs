@@ -0,0 +1,182 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{
+        self,
+        address::{self, Address},
+    },
+    horizon,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per sponsored account, claimable balance, and offer
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Sponsor to list the sponsorships of, e.g. `GBX...` or an identity, defaults to
+    /// `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Maximum number of entries of each kind to list
+    #[arg(long, default_value = "200")]
+    pub limit: usize,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let sponsor = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id()
+            .to_string();
+        let client = self.horizon.client(&self.config.get_network()?)?;
+        let account = client.account(&sponsor).await?;
+        let query = [
+            ("sponsor", sponsor.clone()),
+            ("limit", self.limit.min(200).to_string()),
+        ];
+        let limit = Some(self.limit);
+        let accounts = client.records("accounts", &query, limit).await?;
+        let balances = client.records("claimable_balances", &query, limit).await?;
+        let offers = client.records("offers", &query, limit).await?;
+
+        let accounts = accounts
+            .iter()
+            .map(|a| sponsored_account(a, &sponsor))
+            .collect::<Vec<_>>();
+        let balances = balances
+            .iter()
+            .map(|b| json!({ "id": b["id"], "asset": b["asset"], "amount": b["amount"] }))
+            .collect::<Vec<_>>();
+        let offers = offers
+            .iter()
+            .map(|o| json!({ "id": o["id"], "seller": o["seller"], "amount": o["amount"] }))
+            .collect::<Vec<_>>();
+        match self.output {
+            Output::Json => {
+                let out = json!({
+                    "num_sponsoring": account["num_sponsoring"],
+                    "num_sponsored": account["num_sponsored"],
+                    "accounts": accounts,
+                    "claimable_balances": balances,
+                    "offers": offers,
+                });
+                println!("{}", serde_json::to_string_pretty(&out)?);
+            }
+            Output::Text => {
+                println!(
+                    "{sponsor} sponsors {} reserves and is sponsored for {}",
+                    account["num_sponsoring"], account["num_sponsored"]
+                );
+                for a in &accounts {
+                    let entries = a["entries"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>();
+                    println!("account {} [{}]", str(&a["account"]), entries.join(", "));
+                }
+                for b in &balances {
+                    println!(
+                        "claimable balance {} {} {}",
+                        str(&b["id"]),
+                        str(&b["amount"]),
+                        str(&b["asset"])
+                    );
+                }
+                for o in &offers {
+                    println!(
+                        "offer {} of {} selling {}",
+                        o["id"],
+                        str(&o["seller"]),
+                        str(&o["amount"])
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn str(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+/// The entries of a Horizon account that are sponsored by `sponsor`.
+fn sponsored_account(account: &Value, sponsor: &str) -> Value {
+    let mut entries = Vec::new();
+    if account["sponsor"] == sponsor {
+        entries.push("account".to_string());
+    }
+    for balance in account["balances"].as_array().into_iter().flatten() {
+        if balance["sponsor"] == sponsor {
+            let asset = match balance["asset_type"].as_str() {
+                Some("liquidity_pool_shares") => {
+                    format!("pool:{}", str(&balance["liquidity_pool_id"]))
+                }
+                _ => format!(
+                    "{}:{}",
+                    str(&balance["asset_code"]),
+                    str(&balance["asset_issuer"])
+                ),
+            };
+            entries.push(format!("trustline {asset}"));
+        }
+    }
+    for signer in account["signers"].as_array().into_iter().flatten() {
+        if signer["sponsor"] == sponsor {
+            entries.push(format!("signer {}", str(&signer["key"])));
+        }
+    }
+    json!({ "account": account["account_id"], "entries": entries })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sponsored_entries_of_account() {
+        let sponsor = "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI";
+        let account = json!({
+            "account_id": "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            "sponsor": sponsor,
+            "balances": [
+                { "asset_type": "native", "balance": "1.0000000" },
+                { "asset_type": "credit_alphanum4", "asset_code": "USDC", "asset_issuer": "GI", "sponsor": sponsor },
+            ],
+            "signers": [{ "key": "GS", "weight": 1, "sponsor": sponsor }],
+        });
+        assert_eq!(
+            sponsored_account(&account, sponsor)["entries"],
+            json!(["account", "trustline USDC:GI", "signer GS"])
+        );
+    }
+}
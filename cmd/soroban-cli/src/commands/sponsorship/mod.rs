@@ -0,0 +1,28 @@
+use clap::Parser;
+
+pub mod ls;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// List the accounts, claimable balances, and offers whose reserves an account sponsors
+    /// Sponsor reserves with `--sponsor` on commands that create entries, e.g. `account create --sponsor treasury`.
+    /// Learn more about sponsored reserves:
+    /// https://developers.stellar.org/docs/learn/encyclopedia/transactions-specialized/sponsored-reserves
+    Ls(ls::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        match self {
+            Cmd::Ls(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use clap::{arg, command, Parser, ValueEnum};
+use serde::Serialize;
+
+use crate::{
+    commands::global,
+    config::{
+        locator,
+        network::{self, Network},
+    },
+    print::Print,
+    rpc,
+    xdr::ContractExecutable,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Format of the export
+    #[arg(long, value_enum, default_value = "json")]
+    pub format: Format,
+    /// Do not look up the Wasm hash of the contracts on their network, only export what is
+    /// recorded locally
+    #[arg(long)]
+    pub offline: bool,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Format {
+    /// JSON object of the networks and their contracts
+    Json,
+    /// Terraform `locals` block, to save as a `.tf` file
+    Terraform,
+}
+
+/// The contracts deployed on a network, keyed by the name of the network, or by its passphrase
+/// when no network with it is configured.
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct State {
+    networks: BTreeMap<String, NetworkState>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct NetworkState {
+    network_passphrase: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_url: Option<String>,
+    contracts: BTreeMap<String, ContractState>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct ContractState {
+    contract_id: String,
+    /// `wasm` or `stellar_asset`, unknown when offline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    executable: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wasm_hash: Option<String>,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let networks = self.locator.list_networks_long()?;
+        let mut state = State::default();
+        for (alias, data) in self.locator.list_contract_aliases()? {
+            for (passphrase, contract_id) in data.ids {
+                let configured = networks
+                    .iter()
+                    .find(|(_, network, _)| network.network_passphrase == passphrase);
+                let name = configured.map_or_else(|| passphrase.clone(), |(name, ..)| name.clone());
+                let mut contract = ContractState {
+                    contract_id,
+                    ..ContractState::default()
+                };
+                if let (false, Some((_, network, _))) = (self.offline, configured) {
+                    if let Err(e) = contract.look_up(network).await {
+                        print.warnln(format!(
+                            "Could not look up the Wasm hash of {alias} on {name}: {e}"
+                        ));
+                    }
+                }
+                let network_state = state.networks.entry(name).or_insert_with(|| NetworkState {
+                    network_passphrase: passphrase,
+                    rpc_url: configured.map(|(_, network, _)| network.rpc_url.clone()),
+                    contracts: BTreeMap::new(),
+                });
+                network_state.contracts.insert(alias.clone(), contract);
+            }
+        }
+        match self.format {
+            Format::Json => println!("{}", serde_json::to_string_pretty(&state)?),
+            Format::Terraform => print!("{}", state.terraform()),
+        }
+        Ok(())
+    }
+}
+
+impl ContractState {
+    /// Fill in the executable of the contract from its instance on `network`.
+    async fn look_up(&mut self, network: &Network) -> Result<(), LookUpError> {
+        let id = stellar_strkey::Contract::from_string(&self.contract_id)?;
+        let instance = network.rpc_client()?.get_contract_instance(&id.0).await?;
+        match instance.executable {
+            ContractExecutable::Wasm(hash) => {
+                self.executable = Some("wasm");
+                self.wasm_hash = Some(hex::encode(hash.0));
+            }
+            ContractExecutable::StellarAsset => self.executable = Some("stellar_asset"),
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum LookUpError {
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+}
+
+impl State {
+    /// The state as a Terraform `locals` block, read as `local.stellar.networks`.
+    fn terraform(&self) -> String {
+        let mut out = String::from("# Generated by `stellar state export --format terraform`\n");
+        out.push_str("locals {\n  stellar = {\n    networks = {\n");
+        for (name, network) in &self.networks {
+            let _ = writeln!(out, "      {} = {{", quote(name));
+            let _ = writeln!(
+                out,
+                "        network_passphrase = {}",
+                quote(&network.network_passphrase)
+            );
+            if let Some(rpc_url) = &network.rpc_url {
+                let _ = writeln!(out, "        rpc_url = {}", quote(rpc_url));
+            }
+            out.push_str("        contracts = {\n");
+            for (alias, contract) in &network.contracts {
+                let _ = writeln!(out, "          {} = {{", quote(alias));
+                let _ = writeln!(
+                    out,
+                    "            contract_id = {}",
+                    quote(&contract.contract_id)
+                );
+                if let Some(executable) = contract.executable {
+                    let _ = writeln!(out, "            executable = {}", quote(executable));
+                }
+                if let Some(wasm_hash) = &contract.wasm_hash {
+                    let _ = writeln!(out, "            wasm_hash = {}", quote(wasm_hash));
+                }
+                out.push_str("          }\n");
+            }
+            out.push_str("        }\n      }\n");
+        }
+        out.push_str("    }\n  }\n}\n");
+        out
+    }
+}
+
+/// A Terraform string literal, escaping what would otherwise start an interpolation.
+fn quote(s: &str) -> String {
+    let escaped = serde_json::to_string(s).unwrap_or_default();
+    escaped.replace("${", "$${").replace("%{", "%%{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terraform() {
+        let state = State {
+            networks: [(
+                "testnet".to_string(),
+                NetworkState {
+                    network_passphrase: "Test SDF Network ; September 2015".to_string(),
+                    rpc_url: Some("https://soroban-testnet.stellar.org".to_string()),
+                    contracts: [(
+                        "hello".to_string(),
+                        ContractState {
+                            contract_id: "CA".to_string(),
+                            executable: Some("wasm"),
+                            wasm_hash: Some("ab".to_string()),
+                        },
+                    )]
+                    .into(),
+                },
+            )]
+            .into(),
+        };
+        assert_eq!(
+            state.terraform(),
+            r#"# Generated by `stellar state export --format terraform`
+locals {
+  stellar = {
+    networks = {
+      "testnet" = {
+        network_passphrase = "Test SDF Network ; September 2015"
+        rpc_url = "https://soroban-testnet.stellar.org"
+        contracts = {
+          "hello" = {
+            contract_id = "CA"
+            executable = "wasm"
+            wasm_hash = "ab"
+          }
+        }
+      }
+    }
+  }
+}
+"#
+        );
+        assert_eq!(quote("a${b}"), r#""a$${b}""#);
+    }
+}
@@ -0,0 +1,27 @@
+use clap::Parser;
+
+use crate::commands::global;
+
+pub mod export;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Export the contracts deployed under an alias, with their Wasm hashes and networks, for
+    /// infrastructure-as-code tools such as Terraform or Pulumi
+    Export(export::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Export(#[from] export::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Export(cmd) => cmd.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::asset::{self, Asset},
+    xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+    #[error("the native asset does not use trustlines")]
+    Native,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset to trust as `CODE:ISSUER`, where the issuer can be a public key or an identity
+    pub asset: Asset,
+    /// Maximum balance of the asset the account can hold, defaults to the maximum possible
+    #[arg(long, default_value = i64::MAX.to_string(), value_parser = clap::value_parser!(i64).range(1..))]
+    pub limit: i64,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let op = change_trust(&self.asset, self.limit, &self.tx)?;
+        self.tx.handle_and_print(op, global_args).await?;
+        Ok(())
+    }
+}
+
+/// A `ChangeTrust` operation for `asset`, resolving an issuer alias with the config of `tx`.
+pub fn change_trust(asset: &Asset, limit: i64, tx: &tx::Args) -> Result<xdr::OperationBody, Error> {
+    let asset = asset.resolve(&tx.config.locator, tx.config.hd_path)?;
+    if matches!(asset.0, xdr::Asset::Native) {
+        return Err(Error::Native);
+    }
+    Ok(xdr::OperationBody::ChangeTrust(xdr::ChangeTrustOp {
+        line: (&asset).into(),
+        limit,
+    }))
+}
@@ -0,0 +1,219 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    commands::{global, snapshot::at_ledger},
+    config::{self, address::Address},
+    horizon,
+    print::Print,
+    xdr::{LedgerEntry, LedgerEntryData, LedgerEntryExt, TrustLineAsset, TrustLineFlags},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    AtLedger(#[from] at_ledger::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per trustline
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account to list the trustlines of, e.g. `GBX...` or an identity, defaults to `STELLAR_ACCOUNT`
+    #[arg(env = "STELLAR_ACCOUNT")]
+    pub account: Address,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+    #[command(flatten)]
+    pub at_ledger: at_ledger::Args,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let account_id = self
+            .account
+            .resolve_muxed_account(&self.config.locator, None)?
+            .account_id();
+        let network = self.config.get_network()?;
+        let trustlines = if let Some(entries) = self
+            .at_ledger
+            .trustlines(&print, &network, &account_id)
+            .await?
+        {
+            let mut trustlines = entries
+                .iter()
+                .filter_map(trustline_entry)
+                .collect::<Vec<_>>();
+            trustlines.sort_by_key(|line| line["asset"].to_string());
+            trustlines
+        } else {
+            let client = self.horizon.client(&network)?;
+            let account = client.account(&account_id.to_string()).await?;
+            account["balances"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|balance| balance["asset_type"] != "native")
+                .map(trustline)
+                .collect::<Vec<_>>()
+        };
+        match self.output {
+            Output::Json => println!("{}", serde_json::to_string_pretty(&trustlines)?),
+            Output::Text => {
+                if trustlines.is_empty() {
+                    println!("No trustlines");
+                }
+                for line in &trustlines {
+                    let flags = [
+                        "authorized",
+                        "authorized_to_maintain_liabilities",
+                        "clawback_enabled",
+                    ]
+                    .into_iter()
+                    .filter(|flag| line[flag] == true)
+                    .collect::<Vec<_>>();
+                    println!(
+                        "{} balance {} limit {} [{}]",
+                        str(&line["asset"]),
+                        str(&line["balance"]),
+                        str(&line["limit"]),
+                        flags.join(", ")
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn str(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+/// Normalize a Horizon balance to the asset, amounts, and trustline flags.
+fn trustline(balance: &Value) -> Value {
+    let asset = if balance["asset_type"] == "liquidity_pool_shares" {
+        format!("pool:{}", str(&balance["liquidity_pool_id"]))
+    } else {
+        format!(
+            "{}:{}",
+            str(&balance["asset_code"]),
+            str(&balance["asset_issuer"])
+        )
+    };
+    json!({
+        "asset": asset,
+        "balance": balance["balance"],
+        "limit": balance["limit"],
+        "authorized": balance["is_authorized"],
+        "authorized_to_maintain_liabilities": balance["is_authorized_to_maintain_liabilities"],
+        "clawback_enabled": balance["is_clawback_enabled"],
+        "sponsor": balance["sponsor"],
+    })
+}
+
+/// Normalize a trustline ledger entry, as read from the history archive, like `trustline`.
+fn trustline_entry(entry: &LedgerEntry) -> Option<Value> {
+    let LedgerEntryData::Trustline(line) = &entry.data else {
+        return None;
+    };
+    let asset = match &line.asset {
+        TrustLineAsset::Native => return None,
+        TrustLineAsset::CreditAlphanum4(a) => format!("{}:{}", a.asset_code, a.issuer),
+        TrustLineAsset::CreditAlphanum12(a) => format!("{}:{}", a.asset_code, a.issuer),
+        TrustLineAsset::PoolShare(id) => format!("pool:{}", hex::encode(id.0 .0)),
+    };
+    let flag = |flag: TrustLineFlags| line.flags & flag as u32 != 0;
+    let sponsor = match &entry.ext {
+        LedgerEntryExt::V1(v1) => v1.sponsoring_id.0.as_ref().map(ToString::to_string),
+        LedgerEntryExt::V0 => None,
+    };
+    Some(json!({
+        "asset": asset,
+        "balance": horizon::amount(line.balance),
+        "limit": horizon::amount(line.limit),
+        "authorized": flag(TrustLineFlags::AuthorizedFlag),
+        "authorized_to_maintain_liabilities": flag(TrustLineFlags::AuthorizedToMaintainLiabilitiesFlag),
+        "clawback_enabled": flag(TrustLineFlags::TrustlineClawbackEnabledFlag),
+        "sponsor": sponsor,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trustline_from_horizon_balance() {
+        let balance = json!({
+            "balance": "10.0000000",
+            "limit": "922337203685.4775807",
+            "asset_type": "credit_alphanum4",
+            "asset_code": "USDC",
+            "asset_issuer": "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            "is_authorized": true,
+            "is_authorized_to_maintain_liabilities": true,
+            "is_clawback_enabled": false,
+        });
+        let line = trustline(&balance);
+        assert_eq!(
+            line["asset"],
+            "USDC:GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+        );
+        assert_eq!(line["authorized"], true);
+        assert_eq!(line["clawback_enabled"], false);
+    }
+
+    #[test]
+    fn trustline_from_archived_entry() {
+        use crate::xdr::{
+            AccountId, AlphaNum4, AssetCode4, PublicKey, TrustLineEntry, TrustLineEntryExt, Uint256,
+        };
+        let account = |b| AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([b; 32])));
+        let entry = LedgerEntry {
+            last_modified_ledger_seq: 63,
+            data: LedgerEntryData::Trustline(TrustLineEntry {
+                account_id: account(1),
+                asset: TrustLineAsset::CreditAlphanum4(AlphaNum4 {
+                    asset_code: AssetCode4(*b"USDC"),
+                    issuer: account(2),
+                }),
+                balance: 100_000_000,
+                limit: i64::MAX,
+                flags: TrustLineFlags::AuthorizedFlag as u32
+                    | TrustLineFlags::TrustlineClawbackEnabledFlag as u32,
+                ext: TrustLineEntryExt::V0,
+            }),
+            ext: LedgerEntryExt::V0,
+        };
+        let line = trustline_entry(&entry).unwrap();
+        assert_eq!(line["asset"], format!("USDC:{}", account(2)));
+        assert_eq!(line["balance"], "10.0000000");
+        assert_eq!(line["limit"], "922337203685.4775807");
+        assert_eq!(line["authorized"], true);
+        assert_eq!(line["authorized_to_maintain_liabilities"], false);
+        assert_eq!(line["clawback_enabled"], true);
+        assert_eq!(line["sponsor"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+
+use super::global;
+
+pub mod add;
+pub mod ls;
+pub mod remove;
+
+#[derive(Debug, Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    /// Create a trustline from the source account to a classic asset, or update its limit
+    /// Learn more about trustlines:
+    /// https://developers.stellar.org/docs/learn/fundamentals/stellar-data-structures/accounts#trustlines
+    Add(add::Cmd),
+    /// List the trustlines of an account with their balance, limit, and authorization flags
+    Ls(ls::Cmd),
+    /// Remove a trustline from the source account, the balance of the asset must be zero
+    Remove(remove::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Add(#[from] add::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Add(cmd) => cmd.run(global_args).await?,
+            Cmd::Ls(cmd) => cmd.run(global_args).await?,
+            Cmd::Remove(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use clap::{command, Parser};
+
+use crate::{
+    commands::{global, tx},
+    config::asset::Asset,
+};
+
+use super::add::{change_trust, Error};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset of the trustline as `CODE:ISSUER`, where the issuer can be a public key or an identity
+    pub asset: Asset,
+    #[command(flatten)]
+    pub tx: tx::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let op = change_trust(&self.asset, 0, &self.tx)?;
+        self.tx.handle_and_print(op, global_args).await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,19 @@
 use crate::{
-    commands::{global, txn_result::TxnEnvelopeResult},
-    config::{self, data, network, secret},
+    commands::{
+        global,
+        tx::{memo, preconditions},
+        txn_result::TxnEnvelopeResult,
+    },
+    config::{
+        self,
+        address::{self, Address},
+        data, network, secret, Submitted,
+    },
     fee,
+    print::Print,
     rpc::{self, Client, GetTransactionResponse},
-    tx::builder::{self, TxExt},
+    signer::{self, LocalKey, Signer, SignerKind},
+    tx::builder::{self, sponsorship, TxExt},
     xdr::{self, Limits, WriteXdr},
 };
 
@@ -14,6 +24,15 @@ pub struct Args {
     pub fee: fee::Args,
     #[clap(flatten)]
     pub config: config::Args,
+    /// Account that pays the reserves of the entries created by the transaction, e.g. an
+    /// identity. The operation is wrapped in begin and end sponsoring future reserves
+    /// operations, and the sponsor signs the transaction too.
+    #[arg(long)]
+    pub sponsor: Option<Address>,
+    #[clap(flatten)]
+    pub memo: memo::Args,
+    #[clap(flatten)]
+    pub preconditions: preconditions::Args,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -32,26 +51,80 @@ pub enum Error {
     Data(#[from] data::Error),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Fee(#[from] fee::Error),
+    #[error(transparent)]
+    Preconditions(#[from] preconditions::Error),
+    #[error(transparent)]
+    Memo(#[from] memo::Error),
 }
 
 impl Args {
     pub async fn tx(&self, body: impl Into<xdr::OperationBody>) -> Result<xdr::Transaction, Error> {
+        self.sponsored_tx(body, None, None).await
+    }
+
+    /// Build a transaction for `body`, sponsored by `--sponsor` when it is set. The sponsored
+    /// account defaults to the source account, but can differ, e.g. when creating an account.
+    /// `required_memo` is the memo required by the destination of the operation, see
+    /// `resolve_destination`.
+    pub async fn sponsored_tx(
+        &self,
+        body: impl Into<xdr::OperationBody>,
+        sponsored: Option<xdr::AccountId>,
+        required_memo: Option<xdr::Memo>,
+    ) -> Result<xdr::Transaction, Error> {
         let source_account = self.source_account()?;
+        let account_id = source_account.clone().account_id();
         let seq_num = self
             .config
-            .next_sequence_number(source_account.clone().account_id())
+            .next_sequence_number(account_id, self.fee.seq_strategy)
             .await?;
         // Once we have a way to add operations this will be updated to allow for a different source account
         let operation = xdr::Operation {
             source_account: None,
             body: body.into(),
         };
-        Ok(xdr::Transaction::new_tx(
-            source_account,
-            self.fee.fee,
-            seq_num,
-            operation,
-        ))
+        let fee = self
+            .fee
+            .inclusion_fee(&self.config.get_network()?, false)
+            .await?;
+        // The sponsor only pays reserves, so a memo its federation record requires for deposits
+        // does not apply.
+        let sponsor = self
+            .sponsor
+            .as_ref()
+            .map(|sponsor| self.resolve_muxed_account(sponsor))
+            .transpose()?;
+        let tx = xdr::Transaction::new_tx(source_account.clone(), fee, seq_num, operation)
+            .add_cond(self.preconditions.preconditions()?)
+            .add_memo(self.memo.memo(required_memo)?);
+        let Some(sponsor) = sponsor else {
+            return Ok(tx);
+        };
+        let sponsor = sponsor.account_id();
+        let sponsored = sponsored.unwrap_or_else(|| source_account.account_id());
+        Ok(sponsorship::wrap(tx, sponsor, sponsored)?)
+    }
+
+    /// Resolve an address argument of the command, e.g. the destination of a payment, with the
+    /// config of the command.
+    pub fn resolve_muxed_account(&self, address: &Address) -> Result<xdr::MuxedAccount, Error> {
+        Ok(address.resolve_muxed_account(&self.config.locator, self.config.hd_path)?)
+    }
+
+    /// Resolve the destination of the operation, e.g. of a payment, with the config of the
+    /// command, returning the memo its federation record requires to pass on to
+    /// `sponsored_tx` or `handle_and_print_with_memo`.
+    pub fn resolve_destination(
+        &self,
+        address: &Address,
+    ) -> Result<(xdr::MuxedAccount, Option<xdr::Memo>), Error> {
+        Ok(address.resolve_muxed_account_and_memo(&self.config.locator, self.config.hd_path)?)
     }
 
     pub fn client(&self) -> Result<Client, Error> {
@@ -64,17 +137,42 @@ impl Args {
         op: impl Into<xdr::OperationBody>,
         global_args: &global::Args,
     ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
-        let tx = self.tx(op.into()).await?;
+        self.handle_with_memo(op, None, global_args).await
+    }
+
+    /// Like `handle`, with the memo required by the destination of the operation.
+    pub async fn handle_with_memo(
+        &self,
+        op: impl Into<xdr::OperationBody>,
+        required_memo: Option<xdr::Memo>,
+        global_args: &global::Args,
+    ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
+        let tx = self.sponsored_tx(op.into(), None, required_memo).await?;
         self.handle_tx(tx, global_args).await
     }
+
     pub async fn handle_and_print(
         &self,
         op: impl Into<xdr::OperationBody>,
         global_args: &global::Args,
     ) -> Result<(), Error> {
-        let res = self.handle(op, global_args).await?;
-        if let TxnEnvelopeResult::TxnEnvelope(tx) = res {
-            println!("{}", tx.to_xdr_base64(Limits::none())?);
+        self.handle_and_print_with_memo(op, None, global_args).await
+    }
+
+    /// Like `handle_and_print`, with the memo required by the destination of the operation.
+    pub async fn handle_and_print_with_memo(
+        &self,
+        op: impl Into<xdr::OperationBody>,
+        required_memo: Option<xdr::Memo>,
+        global_args: &global::Args,
+    ) -> Result<(), Error> {
+        match self
+            .handle_with_memo(op, required_memo, global_args)
+            .await?
+        {
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::Submitted(hash) => println!("{hash}"),
+            TxnEnvelopeResult::Res(_) => {}
         };
         Ok(())
     }
@@ -83,6 +181,16 @@ impl Args {
         &self,
         tx: xdr::Transaction,
         args: &global::Args,
+    ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
+        self.handle_tx_with_signers(tx, Vec::new(), args).await
+    }
+
+    /// Like `handle_tx`, also signing with `signers` and the sponsor after the source account.
+    pub async fn handle_tx_with_signers(
+        &self,
+        mut tx: xdr::Transaction,
+        mut signers: Vec<ed25519_dalek::SigningKey>,
+        args: &global::Args,
     ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
         let network = self.config.get_network()?;
         let client = Client::new(&network.rpc_url)?;
@@ -90,9 +198,42 @@ impl Args {
             return Ok(TxnEnvelopeResult::TxnEnvelope(Box::new(tx.into())));
         }
 
-        let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(tx).await?)
-            .await?;
+        let reservation = self.config.reserve_sequence_number(&mut tx, &self.fee)?;
+        let mut tx_env = self.config.sign_with_local_key(tx).await?;
+        if let Some(sponsor) = &self.sponsor {
+            signers.push(
+                sponsor
+                    .resolve_secret(&self.config.locator)?
+                    .key_pair(self.config.hd_path)?,
+            );
+        }
+        let source_key = self.config.key_pair()?.verifying_key();
+        signers.retain(|key| key.verifying_key() != source_key);
+        signers.dedup_by_key(|key| key.verifying_key());
+        for key in signers {
+            let signer = Signer {
+                kind: SignerKind::Local(LocalKey { key }),
+                print: Print::new(args.quiet),
+            };
+            tx_env = signer.sign_tx_env(&tx_env, &network)?;
+        }
+
+        let txn_resp = match self
+            .config
+            .submit(
+                &network,
+                &client,
+                tx_env,
+                reservation,
+                &self.fee,
+                Some(args),
+            )
+            .await?
+        {
+            Submitted::DryRun(tx_env) => return Ok(TxnEnvelopeResult::TxnEnvelope(tx_env)),
+            Submitted::Sent(hash) => return Ok(TxnEnvelopeResult::Submitted(hash)),
+            Submitted::Done(res) => *res,
+        };
 
         if !args.no_cache {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
@@ -0,0 +1,142 @@
+use clap::{arg, ValueEnum};
+use itertools::Itertools;
+
+use crate::{
+    commands::tx::xdr::{tx_envelope_from_stdin, unwrap_envelope_v1},
+    config::{addressbook::Book, locator},
+    xdr::{
+        ContractExecutable, CreateContractArgs, CreateContractArgsV2, InvokeContractArgs, ScVal,
+        SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanCredentials,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] crate::commands::tx::xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("transaction does not have a single invoke host function operation")]
+    NotInvokeHostFunction,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Tree of invocations authorized by each entry
+    Tree,
+    /// Json
+    Json,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "tree")]
+    pub output: Output,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let tx = unwrap_envelope_v1(tx_envelope_from_stdin()?)?;
+        let entries = super::entries(&tx).ok_or(Error::NotInvokeHostFunction)?;
+        match self.output {
+            Output::Json => println!("{}", serde_json::to_string_pretty(entries)?),
+            Output::Tree => {
+                if entries.is_empty() {
+                    println!("No authorization entries");
+                }
+                let book = Book::load(&self.locator);
+                for (index, entry) in entries.iter().enumerate() {
+                    print!("{}", book.substitute(&tree(index, entry)?));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Render an authorization entry and its invocations, one line per invocation.
+pub fn tree(index: usize, entry: &SorobanAuthorizationEntry) -> Result<String, Error> {
+    let mut out = format!("#{index} {}\n", credentials(&entry.credentials));
+    invocation(&mut out, &entry.root_invocation, 1)?;
+    Ok(out)
+}
+
+fn credentials(credentials: &SorobanCredentials) -> String {
+    match credentials {
+        SorobanCredentials::SourceAccount => {
+            "source account (authorized by the transaction signature)".to_string()
+        }
+        SorobanCredentials::Address(SorobanAddressCredentials {
+            address,
+            nonce,
+            signature_expiration_ledger,
+            signature,
+        }) => {
+            let status = if *signature == ScVal::Void {
+                "unsigned".to_string()
+            } else {
+                format!("signed, expires after ledger {signature_expiration_ledger}")
+            };
+            format!("{address} (nonce {nonce}, {status})")
+        }
+    }
+}
+
+fn invocation(
+    out: &mut String,
+    SorobanAuthorizedInvocation {
+        function,
+        sub_invocations,
+    }: &SorobanAuthorizedInvocation,
+    depth: usize,
+) -> Result<(), Error> {
+    let description = match function {
+        SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address,
+            function_name,
+            args,
+        }) => format!(
+            "{contract_address}.{}({})",
+            function_name.0.to_utf8_string_lossy(),
+            values(args)?
+        ),
+        SorobanAuthorizedFunction::CreateContractHostFn(CreateContractArgs {
+            executable, ..
+        }) => format!("create contract from {}", executable_name(executable)),
+        SorobanAuthorizedFunction::CreateContractV2HostFn(CreateContractArgsV2 {
+            executable,
+            constructor_args,
+            ..
+        }) => format!(
+            "create contract from {} with constructor({})",
+            executable_name(executable),
+            values(constructor_args)?
+        ),
+    };
+    out.push_str(&format!("{}└─ {description}\n", "   ".repeat(depth - 1)));
+    for sub in sub_invocations.iter() {
+        invocation(out, sub, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn executable_name(executable: &ContractExecutable) -> String {
+    match executable {
+        ContractExecutable::Wasm(hash) => format!("wasm {}", hex::encode(hash.0)),
+        ContractExecutable::StellarAsset => "stellar asset".to_string(),
+    }
+}
+
+fn values(args: &[ScVal]) -> Result<String, Error> {
+    Ok(args
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .join(", "))
+}
@@ -0,0 +1,51 @@
+use crate::xdr::{
+    InvokeHostFunctionOp, Operation, OperationBody, SorobanAuthorizationEntry, Transaction, VecM,
+};
+
+use super::global;
+
+pub mod inspect;
+pub mod sign;
+
+#[derive(Debug, clap::Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Cmd {
+    /// Print the authorization entries of a transaction envelope from stdin as a tree of invocations
+    Inspect(inspect::Cmd),
+    /// Sign a single authorization entry of a transaction envelope from stdin
+    ///
+    /// The entry is signed with `--source`, which can differ from the transaction's source
+    /// account, allowing several parties to authorize one invocation before it is submitted.
+    /// When `--source` is a secp256r1 identity the entry of a smart wallet contract is signed
+    /// with a `WebAuthn` assertion, as a passkey would.
+    Sign(sign::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Inspect(#[from] inspect::Error),
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Inspect(cmd) => cmd.run()?,
+            Cmd::Sign(cmd) => cmd.run(global_args).await?,
+        };
+        Ok(())
+    }
+}
+
+/// The authorization entries of the transaction's `InvokeHostFunction` operation, if it has one.
+pub fn entries(tx: &Transaction) -> Option<&VecM<SorobanAuthorizationEntry>> {
+    match tx.operations.as_slice() {
+        [Operation {
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }),
+            ..
+        }] => Some(auth),
+        _ => None,
+    }
+}
@@ -0,0 +1,196 @@
+use clap::{arg, command};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    commands::{
+        global,
+        tx::xdr::{tx_envelope_from_stdin, unwrap_envelope_v1},
+    },
+    config::{
+        self, address, network,
+        secret::{self, Secret},
+    },
+    passkey,
+    print::Print,
+    rpc, signer,
+    xdr::{
+        self, AccountId, Hash, Limits, OperationBody, PublicKey, ScAddress,
+        SorobanAddressCredentials, SorobanCredentials, TransactionEnvelope, TransactionV1Envelope,
+        Uint256, VecM, WriteXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] crate::commands::tx::xdr::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Passkey(#[from] passkey::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("transaction does not have a single invoke host function operation")]
+    NotInvokeHostFunction,
+    #[error("authorization entry #{index} not found, transaction has {count} entries")]
+    EntryNotFound { index: usize, count: usize },
+    #[error("authorization entry #{0} uses the source account credentials and is authorized by the transaction signature")]
+    SourceAccountCredentials(usize),
+    #[error("authorization entry #{index} is for {address}, secp256r1 keys can only sign for smart wallet contracts")]
+    PasskeyRequiresContract { index: usize, address: String },
+    #[error("authorization entry #{index} must be signed by {address}, not {signer}")]
+    WrongSigner {
+        index: usize,
+        address: String,
+        signer: String,
+    },
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Index of the authorization entry to sign, as printed by `tx auth inspect`
+    #[arg(long)]
+    pub entry: usize,
+    /// Ledger after which the signature is no longer valid. Defaults to 60 ledgers (~5 minutes)
+    /// after the latest ledger.
+    #[arg(long)]
+    pub signature_expiration_ledger: Option<u32>,
+    #[command(flatten)]
+    pub passkey: PasskeyArgs,
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+/// Options for signing with a secp256r1 identity, which produces a `WebAuthn` assertion for a
+/// smart wallet contract instead of an ed25519 signature.
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct PasskeyArgs {
+    /// Relying party id the passkey is registered with
+    #[arg(
+        long = "webauthn-rp-id",
+        default_value = "localhost",
+        help_heading = "Passkey"
+    )]
+    pub rp_id: String,
+    /// Origin reported in the client data of the assertion
+    #[arg(
+        long = "webauthn-origin",
+        default_value = "https://localhost",
+        help_heading = "Passkey"
+    )]
+    pub origin: String,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let tx_env = tx_envelope_from_stdin()?;
+        let had_signatures =
+            matches!(&tx_env, TransactionEnvelope::Tx(env) if !env.signatures.is_empty());
+        let mut tx = unwrap_envelope_v1(tx_env)?;
+        let mut auth = super::entries(&tx)
+            .ok_or(Error::NotInvokeHostFunction)?
+            .to_vec();
+        let count = auth.len();
+        let entry = auth.get_mut(self.entry).ok_or(Error::EntryNotFound {
+            index: self.entry,
+            count,
+        })?;
+
+        let secret = self
+            .config
+            .source_account
+            .resolve_secret(&self.config.locator)?;
+        let SorobanCredentials::Address(SorobanAddressCredentials { address, nonce, .. }) =
+            &entry.credentials
+        else {
+            return Err(Error::SourceAccountCredentials(self.entry));
+        };
+        let (address, nonce) = (address.clone(), *nonce);
+        let key = if matches!(secret, Secret::Secp256r1 { .. }) {
+            if !matches!(address, ScAddress::Contract(_)) {
+                return Err(Error::PasskeyRequiresContract {
+                    index: self.entry,
+                    address: address.to_string(),
+                });
+            }
+            None
+        } else {
+            let key = secret.key_pair(self.config.hd_path)?;
+            let signer_key = key.verifying_key().to_bytes();
+            if !matches!(
+                &address,
+                ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(a)))) if *a == signer_key
+            ) {
+                return Err(Error::WrongSigner {
+                    index: self.entry,
+                    address: address.to_string(),
+                    signer: stellar_strkey::ed25519::PublicKey(signer_key).to_string(),
+                });
+            }
+            Some(key)
+        };
+
+        let network = self.config.get_network()?;
+        let expiration = match self.signature_expiration_ledger {
+            Some(ledger) => ledger,
+            None => network.rpc_client()?.get_latest_ledger().await?.sequence + 60,
+        };
+        let network_id = Hash(Sha256::digest(network.network_passphrase.as_bytes()).into());
+        if let Some(key) = key {
+            *entry =
+                signer::sign_soroban_authorization_entry(entry, &key, expiration, &network_id)?;
+        } else {
+            let payload = signer::authorization_payload(
+                &entry.root_invocation,
+                nonce,
+                expiration,
+                &network_id,
+            )?;
+            let assertion = passkey::sign(
+                &secret.secp256r1_key()?,
+                &payload,
+                &self.passkey.rp_id,
+                &self.passkey.origin,
+            )?;
+            if let SorobanCredentials::Address(credentials) = &mut entry.credentials {
+                credentials.signature = assertion.to_scval()?;
+                credentials.signature_expiration_ledger = expiration;
+            }
+        }
+
+        let mut operations = tx.operations.to_vec();
+        if let Some(OperationBody::InvokeHostFunction(body)) =
+            operations.first_mut().map(|op| &mut op.body)
+        {
+            body.auth = auth.try_into()?;
+        }
+        tx.operations = operations.try_into()?;
+        print.checkln(format!(
+            "Signed authorization entry #{} for {address}",
+            self.entry
+        ));
+        if had_signatures {
+            print.warnln("Removed the transaction signatures as they no longer match, sign the transaction again with `stellar tx sign`");
+        }
+
+        let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: VecM::default(),
+        });
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
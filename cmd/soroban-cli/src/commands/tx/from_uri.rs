@@ -0,0 +1,82 @@
+use clap::arg;
+
+use crate::{
+    commands::global,
+    print::Print,
+    sep::{
+        sep7::{self, Operation, Uri},
+        stellar_toml, StellarToml,
+    },
+    xdr::{self, Limits, ReadXdr, TransactionEnvelope, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Sep7(#[from] sep7::Error),
+    #[error(transparent)]
+    StellarToml(#[from] stellar_toml::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error(transparent)]
+    Ed25519(#[from] ed25519_dalek::SignatureError),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// SEP-7 URI, e.g. `web+stellar:tx?xdr=...`
+    pub uri: Uri,
+    /// Verify the URI signature against the `URI_REQUEST_SIGNING_KEY` of its origin domain
+    #[arg(long)]
+    pub verify: bool,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let uri = &self.uri;
+        if let Some(msg) = uri.get("msg") {
+            print.infoln(format!("Message: {msg}"));
+        }
+        if let Some(callback) = uri.get("callback") {
+            print.linkln(format!("Callback: {callback}"));
+        }
+        if let Some(passphrase) = uri.get("network_passphrase") {
+            print.globeln(format!("Network passphrase: {passphrase}"));
+        }
+        if self.verify {
+            let domain = uri.require("origin_domain")?;
+            let toml = StellarToml::fetch(domain).await?;
+            let key: stellar_strkey::ed25519::PublicKey =
+                toml.uri_request_signing_key(domain)?.parse()?;
+            uri.verify(&ed25519_dalek::VerifyingKey::from_bytes(&key.0)?)?;
+            print.checkln(format!("URI is signed by {domain}"));
+        } else if let Some(domain) = uri.get("origin_domain") {
+            print.warnln(format!(
+                "Origin domain {domain} has not been verified, use --verify to check the signature"
+            ));
+        }
+
+        match uri.operation {
+            Operation::Tx => {
+                let tx_env =
+                    TransactionEnvelope::from_xdr_base64(uri.require("xdr")?, Limits::none())?;
+                println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+            }
+            Operation::Pay => {
+                let params = uri
+                    .params
+                    .iter()
+                    .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                    .collect::<serde_json::Map<_, _>>();
+                println!("{}", serde_json::to_string_pretty(&params)?);
+            }
+        }
+        Ok(())
+    }
+}
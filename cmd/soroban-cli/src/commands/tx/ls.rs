@@ -0,0 +1,114 @@
+use clap::{arg, command, ValueEnum};
+use serde_json::Value;
+
+use crate::{
+    commands::global,
+    config::{
+        self,
+        network::{self, rpc_version},
+    },
+    pagination,
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Pagination(#[from] pagination::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First ledger to list the transactions of
+    #[arg(long, conflicts_with = "cursor", required_unless_present = "cursor")]
+    pub start_ledger: Option<u32>,
+    /// Cursor to continue listing from, as printed at the end of a previous listing
+    #[arg(long)]
+    pub cursor: Option<String>,
+    /// Maximum number of transactions to list, read across as many pages as needed
+    #[arg(long, default_value = "10", value_parser = pagination::parse_limit)]
+    pub limit: usize,
+    #[command(flatten)]
+    pub pagination: pagination::Args,
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One line per transaction with its ledger, hash, and status
+    Text,
+    /// One JSON object per transaction and line, as returned by RPC
+    Json,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        network
+            .require_rpc(&rpc_version::GET_TRANSACTIONS, global_args.no_cache)
+            .await?;
+        let mut pages = pagination::TransactionPages::new(
+            &network,
+            &self.pagination,
+            self.start_ledger.unwrap_or_default(),
+            self.cursor.clone(),
+            Some(self.limit),
+        );
+        let mut listed = 0;
+        while let Some(page) = pages.next::<Value>().await? {
+            for tx in page {
+                let ledger = tx["ledger"].as_u64().unwrap_or_default();
+                if self
+                    .pagination
+                    .past_end(u32::try_from(ledger).unwrap_or(u32::MAX))
+                {
+                    return Ok(());
+                }
+                match self.output {
+                    Output::Text => println!(
+                        "{ledger} {} {}",
+                        tx["txHash"].as_str().unwrap_or_default(),
+                        tx["status"].as_str().unwrap_or_default()
+                    ),
+                    Output::Json => println!("{}", serde_json::to_string(&tx)?),
+                }
+                listed += 1;
+            }
+        }
+        if listed >= self.limit {
+            if let Some(cursor) = pages.cursor() {
+                print.infoln(format!(
+                    "Listed {listed} transactions, continue with --cursor {cursor}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn limit_is_at_least_one() {
+        let parse =
+            |limit: &str| Cmd::try_parse_from(["ls", "--start-ledger", "1", "--limit", limit]);
+        assert!(parse("0").is_err());
+        assert_eq!(parse("1").unwrap().limit, 1);
+    }
+}
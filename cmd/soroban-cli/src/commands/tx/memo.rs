@@ -0,0 +1,143 @@
+use clap::{arg, ValueEnum};
+
+use crate::xdr::{self, Memo};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid {memo_type} memo {memo:?}, {expected}")]
+    Invalid {
+        memo_type: &'static str,
+        memo: String,
+        expected: &'static str,
+    },
+    #[error("the memo {memo} conflicts with the memo {required} required by the destination")]
+    Conflict { memo: String, required: String },
+    #[error("the accounts of the transaction require different memos, {0} and {1}")]
+    Conflicting(String, String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Type {
+    /// Text of up to 28 bytes
+    #[default]
+    Text,
+    /// Unsigned 64-bit integer
+    Id,
+    /// Hex encoded 32-byte hash
+    Hash,
+    /// Hex encoded 32-byte hash of the transaction being refunded
+    Return,
+}
+
+/// Memo of the transaction. Destinations resolved from a federation address may require one,
+/// e.g. the deposit memo of an exchange, which is then attached without `--memo`.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Memo of the transaction, e.g. the deposit memo of an exchange account
+    #[arg(long)]
+    pub memo: Option<String>,
+    /// Type of `--memo`
+    #[arg(long, value_enum, default_value_t, requires = "memo")]
+    pub memo_type: Type,
+}
+
+impl Args {
+    /// The memo of the transaction, `required` being the one required by the destination. Fails
+    /// if `--memo` is a different one, as the destination could not credit the transaction.
+    pub fn memo(&self, required: Option<Memo>) -> Result<Memo, Error> {
+        let memo = self
+            .memo
+            .as_deref()
+            .map(|memo| parse(self.memo_type, memo))
+            .transpose()?;
+        match (memo, required) {
+            (Some(memo), Some(required)) if memo != required => Err(Error::Conflict {
+                memo: describe(&memo),
+                required: describe(&required),
+            }),
+            (Some(memo), _) | (None, Some(memo)) => Ok(memo),
+            (None, None) => Ok(Memo::None),
+        }
+    }
+}
+
+/// Combine the memos required by the accounts of a transaction, which can only carry one.
+pub fn require(a: Option<Memo>, b: Option<Memo>) -> Result<Option<Memo>, Error> {
+    match (a, b) {
+        (Some(a), Some(b)) if a != b => Err(Error::Conflicting(describe(&a), describe(&b))),
+        (a, b) => Ok(a.or(b)),
+    }
+}
+
+fn parse(memo_type: Type, memo: &str) -> Result<Memo, Error> {
+    let invalid = |memo_type, expected| Error::Invalid {
+        memo_type,
+        memo: memo.to_string(),
+        expected,
+    };
+    let hash = |memo_type| {
+        hex::decode(memo)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(xdr::Hash)
+            .ok_or_else(|| invalid(memo_type, "expected 32 hex encoded bytes"))
+    };
+    Ok(match memo_type {
+        Type::Text => Memo::Text(
+            memo.try_into()
+                .map_err(|_| invalid("text", "expected at most 28 bytes"))?,
+        ),
+        Type::Id => Memo::Id(
+            memo.parse()
+                .map_err(|_| invalid("id", "expected an unsigned 64-bit integer"))?,
+        ),
+        Type::Hash => Memo::Hash(hash("hash")?),
+        Type::Return => Memo::Return(hash("return")?),
+    })
+}
+
+fn describe(memo: &Memo) -> String {
+    match memo {
+        Memo::None => "none".to_string(),
+        Memo::Text(text) => format!("text {:?}", text.to_utf8_string_lossy()),
+        Memo::Id(id) => format!("id {id}"),
+        Memo::Hash(hash) => format!("hash {}", hex::encode(hash.0)),
+        Memo::Return(hash) => format!("return {}", hex::encode(hash.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_must_match_the_required_one() {
+        let args = |memo: Option<&str>, memo_type| Args {
+            memo: memo.map(str::to_string),
+            memo_type,
+        };
+        assert_eq!(args(None, Type::Text).memo(None).unwrap(), Memo::None);
+        assert_eq!(
+            args(None, Type::Text).memo(Some(Memo::Id(7))).unwrap(),
+            Memo::Id(7)
+        );
+        assert_eq!(
+            args(Some("7"), Type::Id).memo(Some(Memo::Id(7))).unwrap(),
+            Memo::Id(7)
+        );
+        assert!(matches!(
+            args(Some("7"), Type::Text).memo(Some(Memo::Id(7))),
+            Err(Error::Conflict { .. })
+        ));
+        assert!(matches!(
+            args(Some("abc"), Type::Hash).memo(None),
+            Err(Error::Invalid { .. })
+        ));
+        assert!(matches!(
+            require(Some(Memo::Id(1)), Some(Memo::Id(2))),
+            Err(Error::Conflicting(..))
+        ));
+        assert_eq!(require(None, Some(Memo::Id(2))).unwrap(), Some(Memo::Id(2)));
+    }
+}
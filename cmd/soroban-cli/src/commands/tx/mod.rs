@@ -1,18 +1,27 @@
 use super::global;
 
 pub mod args;
+pub mod auth;
+pub mod from_uri;
 pub mod hash;
+pub mod ls;
+pub mod memo;
 pub mod new;
+pub mod preconditions;
 pub mod send;
 pub mod sign;
+pub mod signers;
 pub mod simulate;
+pub mod to_uri;
+pub mod wait;
 pub mod xdr;
 
 pub use args::Args;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
-    /// Simulate a transaction envelope from stdin
+    /// Simulate a transaction envelope, and print it assembled with the resources, fees, and auth
+    /// from the simulation
     Simulate(simulate::Cmd),
     /// Calculate the hash of a transaction envelope from stdin
     Hash(hash::Cmd),
@@ -20,9 +29,24 @@ pub enum Cmd {
     Sign(sign::Cmd),
     /// Send a transaction envelope to the network
     Send(send::Cmd),
+    /// Wait for a transaction sent with `--no-wait` to be included in a ledger, and print its
+    /// return value and events
+    Wait(wait::Cmd),
+    /// List the transactions of a range of ledgers, paging through them
+    Ls(ls::Cmd),
     /// Create a new transaction
     #[command(subcommand)]
     New(new::Cmd),
+    /// Inspect and sign the Soroban authorization entries of a transaction
+    #[command(subcommand)]
+    Auth(auth::Cmd),
+    /// Analyze which signatures a transaction envelope needs from the signers of its accounts
+    #[command(subcommand)]
+    Signers(signers::Cmd),
+    /// Encode a transaction envelope from stdin, or a payment, as a SEP-7 `web+stellar:` URI
+    ToUri(to_uri::Cmd),
+    /// Decode a SEP-7 `web+stellar:` URI, printing the transaction envelope of `tx` URIs
+    FromUri(from_uri::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -30,6 +54,8 @@ pub enum Error {
     #[error(transparent)]
     Hash(#[from] hash::Error),
     #[error(transparent)]
+    Ls(#[from] ls::Error),
+    #[error(transparent)]
     New(#[from] new::Error),
     #[error(transparent)]
     Simulate(#[from] simulate::Error),
@@ -37,6 +63,16 @@ pub enum Error {
     Sign(#[from] sign::Error),
     #[error(transparent)]
     Send(#[from] send::Error),
+    #[error(transparent)]
+    Wait(#[from] wait::Error),
+    #[error(transparent)]
+    Auth(#[from] auth::Error),
+    #[error(transparent)]
+    Signers(#[from] signers::Error),
+    #[error(transparent)]
+    ToUri(#[from] to_uri::Error),
+    #[error(transparent)]
+    FromUri(#[from] from_uri::Error),
 }
 
 impl Cmd {
@@ -44,9 +80,15 @@ impl Cmd {
         match self {
             Cmd::Simulate(cmd) => cmd.run(global_args).await?,
             Cmd::Hash(cmd) => cmd.run(global_args)?,
+            Cmd::Ls(cmd) => cmd.run(global_args).await?,
             Cmd::New(cmd) => cmd.run(global_args).await?,
             Cmd::Sign(cmd) => cmd.run(global_args).await?,
             Cmd::Send(cmd) => cmd.run(global_args).await?,
+            Cmd::Wait(cmd) => cmd.run(global_args).await?,
+            Cmd::Auth(cmd) => cmd.run(global_args).await?,
+            Cmd::Signers(cmd) => cmd.run().await?,
+            Cmd::ToUri(cmd) => cmd.run(global_args).await?,
+            Cmd::FromUri(cmd) => cmd.run(global_args).await?,
         };
         Ok(())
     }
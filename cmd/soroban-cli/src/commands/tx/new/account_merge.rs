@@ -1,19 +1,26 @@
 use clap::{command, Parser};
 
-use crate::{commands::tx, xdr};
+use crate::{
+    commands::{global, tx},
+    config::address::Address,
+    xdr,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     pub tx: tx::Args,
-    /// Muxed Account to merge with, e.g. `GBX...`, 'MBX...'
+    /// Muxed Account to merge with, e.g. `GBX...`, 'MBX...', a federation address, or an address book label
     #[arg(long)]
-    pub account: xdr::MuxedAccount,
+    pub account: Address,
 }
 
-impl From<&Cmd> for xdr::OperationBody {
-    fn from(cmd: &Cmd) -> Self {
-        xdr::OperationBody::AccountMerge(cmd.account.clone())
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), tx::args::Error> {
+        let (into, memo) = self.tx.resolve_destination(&self.account)?;
+        self.tx
+            .handle_and_print_with_memo(xdr::OperationBody::AccountMerge(into), memo, global_args)
+            .await
     }
 }
@@ -16,13 +16,8 @@ pub struct Cmd {
 
 impl From<&Cmd> for xdr::OperationBody {
     fn from(cmd: &Cmd) -> Self {
-        let line = match cmd.line.0.clone() {
-            xdr::Asset::CreditAlphanum4(asset) => xdr::ChangeTrustAsset::CreditAlphanum4(asset),
-            xdr::Asset::CreditAlphanum12(asset) => xdr::ChangeTrustAsset::CreditAlphanum12(asset),
-            xdr::Asset::Native => xdr::ChangeTrustAsset::Native,
-        };
         xdr::OperationBody::ChangeTrust(xdr::ChangeTrustOp {
-            line,
+            line: (&cmd.line).into(),
             limit: cmd.limit,
         })
     }
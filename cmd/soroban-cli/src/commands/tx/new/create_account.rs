@@ -1,25 +1,33 @@
 use clap::{command, Parser};
 
-use crate::{commands::tx, xdr};
+use crate::{
+    commands::{global, tx},
+    config::address::Address,
+    xdr,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     pub tx: tx::Args,
-    /// Account Id to create, e.g. `GBX...`
+    /// Account Id to create, e.g. `GBX...`, a federation address, or an address book label
     #[arg(long)]
-    pub destination: xdr::AccountId,
+    pub destination: Address,
     /// Initial balance in stroops of the account, default 1 XLM
     #[arg(long, default_value = "10000000")]
     pub starting_balance: i64,
 }
 
-impl From<&Cmd> for xdr::OperationBody {
-    fn from(cmd: &Cmd) -> Self {
-        xdr::OperationBody::CreateAccount(xdr::CreateAccountOp {
-            destination: cmd.destination.clone(),
-            starting_balance: cmd.starting_balance,
-        })
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), tx::args::Error> {
+        let (destination, memo) = self.tx.resolve_destination(&self.destination)?;
+        let op = xdr::OperationBody::CreateAccount(xdr::CreateAccountOp {
+            destination: destination.account_id(),
+            starting_balance: self.starting_balance,
+        });
+        self.tx
+            .handle_and_print_with_memo(op, memo, global_args)
+            .await
     }
 }
@@ -8,7 +8,7 @@ mod change_trust;
 mod create_account;
 mod manage_data;
 mod payment;
-mod set_options;
+pub mod set_options;
 mod set_trustline_flags;
 
 #[derive(Debug, Parser)]
@@ -55,12 +55,12 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
-            Cmd::AccountMerge(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
+            Cmd::AccountMerge(cmd) => cmd.run(global_args).await,
             Cmd::BumpSequence(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
             Cmd::ChangeTrust(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
-            Cmd::CreateAccount(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
+            Cmd::CreateAccount(cmd) => cmd.run(global_args).await,
             Cmd::ManageData(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
-            Cmd::Payment(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
+            Cmd::Payment(cmd) => cmd.run(global_args).await,
             Cmd::SetOptions(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
             Cmd::SetTrustlineFlags(cmd) => cmd.tx.handle_and_print(cmd, global_args).await,
         }?;
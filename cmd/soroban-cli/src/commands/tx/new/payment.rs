@@ -1,15 +1,21 @@
 use clap::{command, Parser};
 
-use crate::{commands::tx, tx::builder, xdr};
+use crate::{
+    commands::{global, tx},
+    config::address::Address,
+    tx::builder,
+    xdr,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     pub tx: tx::Args,
-    /// Account to send to, e.g. `GBX...`
+    /// Account to send to, e.g. `GBX...`, a federation address, e.g. `bob*example.com`, or an
+    /// address book label
     #[arg(long)]
-    pub destination: xdr::MuxedAccount,
+    pub destination: Address,
     /// Asset to send, default native, e.i. XLM
     #[arg(long, default_value = "native")]
     pub asset: builder::Asset,
@@ -18,12 +24,16 @@ pub struct Cmd {
     pub amount: i64,
 }
 
-impl From<&Cmd> for xdr::OperationBody {
-    fn from(cmd: &Cmd) -> Self {
-        xdr::OperationBody::Payment(xdr::PaymentOp {
-            destination: cmd.destination.clone(),
-            asset: cmd.asset.clone().into(),
-            amount: cmd.amount,
-        })
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), tx::args::Error> {
+        let (destination, memo) = self.tx.resolve_destination(&self.destination)?;
+        let op = xdr::OperationBody::Payment(xdr::PaymentOp {
+            destination,
+            asset: self.asset.clone().into(),
+            amount: self.amount,
+        });
+        self.tx
+            .handle_and_print_with_memo(op, memo, global_args)
+            .await
     }
 }
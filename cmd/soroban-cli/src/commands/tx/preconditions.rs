@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use clap::arg;
+
+use crate::xdr::{self, LedgerBounds, Preconditions, PreconditionsV2, SignerKey, TimeBounds};
+
+pub const HEADING_PRECONDITIONS: &str = "Options (Preconditions)";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("a transaction can have at most 2 extra signers, got {0}")]
+    TooManyExtraSigners(usize),
+}
+
+/// Preconditions of the transaction, checked by the network before it is applied. The
+/// transaction is invalid outside of the bounds, so it is neither charged a fee nor consumes
+/// its sequence number.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Time bounds of the transaction in unix seconds, as `MIN:MAX`. Either side can be left
+    /// empty, e.g. `:1735689600` for a transaction valid until that time
+    #[arg(long, value_parser = parse_bounds::<u64>, help_heading = HEADING_PRECONDITIONS)]
+    pub time_bounds: Option<(u64, u64)>,
+    /// Ledger bounds of the transaction, as `MIN:MAX`. Either side can be left empty, e.g.
+    /// `:1000000` for a transaction valid until that ledger
+    #[arg(long, value_parser = parse_bounds::<u32>, help_heading = HEADING_PRECONDITIONS)]
+    pub ledger_bounds: Option<(u32, u32)>,
+    /// Only valid if the sequence number of the source account is at least this number, instead
+    /// of exactly the one before the transaction's. Bumps the account's sequence number to the
+    /// transaction's
+    #[arg(long, help_heading = HEADING_PRECONDITIONS)]
+    pub min_seq_num: Option<i64>,
+    /// Only valid once the sequence number of the source account is at least this old, e.g.
+    /// `1h`
+    #[arg(long, value_parser = humantime::parse_duration, help_heading = HEADING_PRECONDITIONS)]
+    pub min_seq_age: Option<Duration>,
+    /// Only valid once this many ledgers have closed since the sequence number of the source
+    /// account was bumped
+    #[arg(long, help_heading = HEADING_PRECONDITIONS)]
+    pub min_seq_ledger_gap: Option<u32>,
+    /// Signer that must also sign the transaction, up to 2, e.g. `GBX...`, a pre-authorized
+    /// transaction hash `T...`, a hash-x `X...`, or a signed payload `P...`
+    #[arg(long, help_heading = HEADING_PRECONDITIONS)]
+    pub extra_signer: Vec<SignerKey>,
+}
+
+impl Args {
+    /// The preconditions, only using the v2 form when a precondition other than the time bounds
+    /// is set.
+    pub fn preconditions(&self) -> Result<Preconditions, Error> {
+        let time_bounds = self.time_bounds.map(|(min_time, max_time)| TimeBounds {
+            min_time: min_time.into(),
+            max_time: max_time.into(),
+        });
+        let is_v2 = self.ledger_bounds.is_some()
+            || self.min_seq_num.is_some()
+            || self.min_seq_age.is_some()
+            || self.min_seq_ledger_gap.is_some()
+            || !self.extra_signer.is_empty();
+        Ok(match time_bounds {
+            _ if is_v2 => Preconditions::V2(PreconditionsV2 {
+                time_bounds,
+                ledger_bounds: self
+                    .ledger_bounds
+                    .map(|(min_ledger, max_ledger)| LedgerBounds {
+                        min_ledger,
+                        max_ledger,
+                    }),
+                min_seq_num: self.min_seq_num.map(xdr::SequenceNumber),
+                min_seq_age: self.min_seq_age.map_or(0, |age| age.as_secs()).into(),
+                min_seq_ledger_gap: self.min_seq_ledger_gap.unwrap_or_default(),
+                extra_signers: self
+                    .extra_signer
+                    .clone()
+                    .try_into()
+                    .map_err(|_| Error::TooManyExtraSigners(self.extra_signer.len()))?,
+            }),
+            Some(time_bounds) => Preconditions::Time(time_bounds),
+            None => Preconditions::None,
+        })
+    }
+}
+
+/// Parse `MIN:MAX`, where an empty side is 0, meaning no bound.
+fn parse_bounds<T>(s: &str) -> Result<(T, T), String>
+where
+    T: std::str::FromStr + Default + PartialOrd + Copy,
+    T::Err: std::fmt::Display,
+{
+    let (min, max) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected `MIN:MAX`, got {s:?}"))?;
+    let parse = |s: &str| -> Result<T, String> {
+        if s.is_empty() {
+            Ok(T::default())
+        } else {
+            s.parse().map_err(|e| format!("invalid bound {s:?}: {e}"))
+        }
+    };
+    let (min, max) = (parse(min)?, parse(max)?);
+    if max != T::default() && min > max {
+        return Err(format!("the minimum of {s:?} is after the maximum"));
+    }
+    Ok((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preconditions() {
+        assert_eq!(parse_bounds::<u32>(":100"), Ok((0, 100)));
+        assert_eq!(parse_bounds::<u64>("5:"), Ok((5, 0)));
+        assert!(parse_bounds::<u32>("10:5").is_err());
+        assert!(parse_bounds::<u32>("10").is_err());
+
+        assert_eq!(
+            Args::default().preconditions().unwrap(),
+            Preconditions::None
+        );
+        let time = Args {
+            time_bounds: Some((0, 100)),
+            ..Args::default()
+        };
+        assert!(matches!(
+            time.preconditions().unwrap(),
+            Preconditions::Time(TimeBounds { .. })
+        ));
+        let v2 = Args {
+            min_seq_age: Some(Duration::from_secs(3600)),
+            ..time
+        };
+        let Preconditions::V2(v2) = v2.preconditions().unwrap() else {
+            panic!("expected v2 preconditions");
+        };
+        assert_eq!(u64::from(v2.min_seq_age), 3600);
+        assert!(v2.time_bounds.is_some());
+    }
+}
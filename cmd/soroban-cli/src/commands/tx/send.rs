@@ -1,10 +1,9 @@
-use crate::{print::Print, utils::transaction_hash};
+use crate::{fee, print::Print, utils::transaction_hash};
 use async_trait::async_trait;
-use soroban_rpc::GetTransactionResponse;
 
 use crate::{
     commands::{global, NetworkRunnable},
-    config::{self, locator, network},
+    config::{self, locator, network, Submitted},
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -26,6 +25,10 @@ pub enum Error {
 /// Command to send a transaction envelope to the network
 /// e.g. `cat file.txt | soroban tx send`
 pub struct Cmd {
+    /// Return as soon as the transaction is sent and print its hash, instead of waiting for it to
+    /// be included in a ledger. Wait for it later with `stellar tx wait`
+    #[arg(long)]
+    pub no_wait: bool,
     #[clap(flatten)]
     pub network: network::Args,
     #[clap(flatten)]
@@ -34,8 +37,11 @@ pub struct Cmd {
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let response = self.run_against_rpc_server(Some(global_args), None).await?;
-        println!("{}", serde_json::to_string_pretty(&response)?);
+        match self.run_against_rpc_server(Some(global_args), None).await? {
+            Submitted::Sent(hash) => println!("{hash}"),
+            Submitted::Done(response) => println!("{}", serde_json::to_string_pretty(&response)?),
+            Submitted::DryRun(_) => {}
+        }
         Ok(())
     }
 }
@@ -44,27 +50,36 @@ impl Cmd {
 impl NetworkRunnable for Cmd {
     type Error = Error;
 
-    type Result = GetTransactionResponse;
+    type Result = Submitted;
     async fn run_against_rpc_server(
         &self,
         globals: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<Self::Result, Self::Error> {
-        let network = if let Some(config) = config {
-            config.get_network()?
-        } else {
-            self.network.get(&self.locator)?
-        };
+        let config = config.cloned().unwrap_or_else(|| config::Args {
+            network: self.network.clone(),
+            locator: self.locator.clone(),
+            ..Default::default()
+        });
+        let network = config.get_network()?;
         let client = network.rpc_client()?;
         let tx_env = super::xdr::tx_envelope_from_stdin()?;
 
-        if let Ok(Ok(hash)) = super::xdr::unwrap_envelope_v1(tx_env.clone())
-            .map(|tx| transaction_hash(&tx, &network.network_passphrase))
-        {
-            let print = Print::new(globals.map_or(false, |g| g.quiet));
-            print.infoln(format!("Transaction Hash: {}", hex::encode(hash)));
+        if !self.no_wait {
+            if let Ok(Ok(hash)) = super::xdr::unwrap_envelope_v1(tx_env.clone())
+                .map(|tx| transaction_hash(&tx, &network.network_passphrase))
+            {
+                let print = Print::new(globals.map_or(false, |g| g.quiet));
+                print.infoln(format!("Transaction Hash: {}", hex::encode(hash)));
+            }
         }
 
-        Ok(client.send_transaction_polling(&tx_env).await?)
+        let fee = fee::Args {
+            no_wait: self.no_wait,
+            ..Default::default()
+        };
+        Ok(config
+            .submit(&network, &client, tx_env, None, &fee, globals)
+            .await?)
     }
 }
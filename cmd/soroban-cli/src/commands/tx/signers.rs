@@ -0,0 +1,25 @@
+pub mod required;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Report which signatures a transaction envelope still needs, comparing its signatures
+    /// and those of its authorization entries with the signers and thresholds of every account
+    /// that must sign it. For a fee bump, the fee source must meet its low threshold, and the
+    /// inner transaction is checked as well
+    Required(required::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Required(#[from] required::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        match self {
+            Cmd::Required(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,569 @@
+use std::collections::BTreeMap;
+
+use clap::{arg, ValueEnum};
+use ed25519_dalek::Verifier;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    commands::tx::xdr::{tx_envelope_from_input, unwrap_envelope},
+    config::{self, addressbook::Book, network},
+    rpc,
+    utils::{envelope_hash, transaction_hash},
+    xdr::{
+        self, AccountEntry, AccountId, DecoratedSignature, FeeBumpTransactionEnvelope,
+        FeeBumpTransactionInnerTx, OperationBody, PublicKey, ScAddress, ScVal, SignerKey,
+        SorobanCredentials, TransactionEnvelope, Uint256,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] crate::commands::tx::xdr::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// One block per account, with its signers and what is missing
+    Text,
+    /// Json
+    Json,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base64 encoded transaction envelope, or a file containing one. Read from stdin if not
+    /// given or `-`
+    pub tx_xdr: Option<String>,
+    /// Type of output to display
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+/// The thresholds of operations, from the account thresholds `low`, `med`, and `high`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Low,
+    Medium,
+    High,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Low => "low",
+            Level::Medium => "medium",
+            Level::High => "high",
+        }
+    }
+
+    fn threshold(self, account: &AccountEntry) -> u8 {
+        let [_, low, med, high] = account.thresholds.0;
+        match self {
+            Level::Low => low,
+            Level::Medium => med,
+            Level::High => high,
+        }
+    }
+}
+
+/// The threshold the source account of an operation must meet, as stellar-core assigns them.
+pub fn level(body: &OperationBody) -> Level {
+    match body {
+        OperationBody::AllowTrust(_)
+        | OperationBody::SetTrustLineFlags(_)
+        | OperationBody::BumpSequence(_)
+        | OperationBody::ClaimClaimableBalance(_)
+        | OperationBody::Inflation
+        | OperationBody::ExtendFootprintTtl(_)
+        | OperationBody::RestoreFootprint(_) => Level::Low,
+        OperationBody::AccountMerge(_) => Level::High,
+        OperationBody::SetOptions(op)
+            if op.master_weight.is_some()
+                || op.low_threshold.is_some()
+                || op.med_threshold.is_some()
+                || op.high_threshold.is_some()
+                || op.signer.is_some() =>
+        {
+            Level::High
+        }
+        _ => Level::Medium,
+    }
+}
+
+/// Whether an account that must sign has, and which signatures are missing.
+#[derive(Debug, Serialize)]
+struct Report {
+    account: String,
+    reasons: Vec<String>,
+    satisfied: bool,
+    #[serde(flatten)]
+    check: Check,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Check {
+    /// Signatures weighed against a threshold of the account.
+    Account {
+        level: Level,
+        threshold: u8,
+        signed_weight: u32,
+        signers: Vec<Signer>,
+        /// The combinations of unsigned signers that would meet the threshold.
+        missing: Vec<Vec<String>>,
+        authorization_signed_keys: Option<Vec<String>>,
+    },
+    /// Authorization checked by the contract's `__check_auth`.
+    Contract { contract: bool },
+}
+
+#[derive(Debug, Serialize)]
+struct Signer {
+    key: String,
+    weight: u32,
+    signed: bool,
+}
+
+/// An account that must sign, with the highest threshold it must meet and why.
+struct Need {
+    level: Level,
+    reasons: Vec<String>,
+    /// Public keys that signed, for authorization entries whose signatures are not checked here.
+    signed_keys: Option<Vec<[u8; 32]>>,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let tx_env = tx_envelope_from_input(self.tx_xdr.as_deref())?;
+        let network = self.config.get_network()?;
+        let client = network.rpc_client()?;
+        let mut reports = Vec::new();
+        // The fee source signs the fee bump, the accounts of the inner transaction sign it.
+        let tx_env = match tx_env {
+            TransactionEnvelope::TxFeeBump(env) => {
+                reports.push(fee_bump_report(&client, &env, &network.network_passphrase).await?);
+                let FeeBumpTransactionInnerTx::Tx(inner) = env.tx.inner_tx;
+                TransactionEnvelope::Tx(inner)
+            }
+            tx_env => tx_env,
+        };
+        let signatures = match &tx_env {
+            TransactionEnvelope::Tx(env) => env.signatures.to_vec(),
+            TransactionEnvelope::TxV0(env) => env.signatures.to_vec(),
+            TransactionEnvelope::TxFeeBump(_) => unreachable!("fee bumps are unwrapped above"),
+        };
+        let tx = unwrap_envelope(tx_env)?;
+        let hash = transaction_hash(&tx, &network.network_passphrase)?;
+
+        let mut needs = BTreeMap::<String, (AccountId, Need)>::new();
+        let mut need = |account: AccountId, level: Level, reason: String| {
+            let (_, need) = needs.entry(account.to_string()).or_insert_with(|| {
+                (
+                    account,
+                    Need {
+                        level,
+                        reasons: Vec::new(),
+                        signed_keys: None,
+                    },
+                )
+            });
+            need.level = need.level.max(level);
+            need.reasons.push(reason);
+        };
+        let source = tx.source_account.clone().account_id();
+        need(source.clone(), Level::Low, "transaction source".to_string());
+        for (index, op) in tx.operations.iter().enumerate() {
+            let account = op
+                .source_account
+                .clone()
+                .map_or_else(|| source.clone(), xdr::MuxedAccount::account_id);
+            need(
+                account,
+                level(&op.body),
+                format!("{} operation {index}", op.body.name()),
+            );
+        }
+
+        for (account_id, need) in needs.values() {
+            let account = client.get_account(&account_id.to_string()).await?;
+            reports.push(report(&account, need, &|key| {
+                signed(key, &hash, &signatures)
+            }));
+        }
+        reports.extend(auth_reports(&client, &tx).await?);
+        self.print(&reports)
+    }
+
+    fn print(&self, reports: &[Report]) -> Result<(), Error> {
+        if self.output == Output::Json {
+            println!("{}", serde_json::to_string_pretty(reports)?);
+            return Ok(());
+        }
+        let book = Book::load(&self.config.locator);
+        for report in reports {
+            let reasons = report
+                .reasons
+                .iter()
+                .map(|reason| book.substitute(reason))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("{} ({reasons})", book.substitute(&report.account));
+            let Check::Account {
+                level,
+                threshold,
+                signed_weight,
+                signers,
+                missing,
+                ..
+            } = &report.check
+            else {
+                let status = if report.satisfied {
+                    "signed"
+                } else {
+                    "unsigned"
+                };
+                println!("  {status}, checked by the contract's `__check_auth`");
+                continue;
+            };
+            println!(
+                "  {} threshold {threshold}, signed weight {signed_weight}{}",
+                level.name(),
+                if report.satisfied { ", satisfied" } else { "" }
+            );
+            for signer in signers {
+                let status = if signer.signed { "signed" } else { "missing" };
+                println!(
+                    "  {} weight {} {status}",
+                    book.substitute(&signer.key),
+                    signer.weight
+                );
+            }
+            for combination in missing {
+                let keys = combination
+                    .iter()
+                    .map(|key| book.substitute(key))
+                    .collect::<Vec<_>>();
+                println!("  needs {}", keys.join(" + "));
+            }
+            if !report.satisfied && missing.is_empty() {
+                println!("  cannot be satisfied, the signers do not have enough weight");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The report of the fee source of the fee bump `env`, which signs it with the low threshold.
+async fn fee_bump_report(
+    client: &rpc::Client,
+    env: &FeeBumpTransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<Report, Error> {
+    let hash = envelope_hash(
+        &TransactionEnvelope::TxFeeBump(env.clone()),
+        network_passphrase,
+    )?
+    .expect("fee bumps have a hash");
+    let account = client
+        .get_account(&env.tx.fee_source.clone().account_id().to_string())
+        .await?;
+    let need = Need {
+        level: Level::Low,
+        reasons: vec!["fee bump source".to_string()],
+        signed_keys: None,
+    };
+    Ok(report(&account, &need, &|key| {
+        signed(key, &hash, &env.signatures)
+    }))
+}
+
+/// The reports of the addresses of the authorization entries of `tx`. Accounts are checked
+/// against the keys that signed their entry, contracts only for whether their entry is signed.
+async fn auth_reports(client: &rpc::Client, tx: &xdr::Transaction) -> Result<Vec<Report>, Error> {
+    let auth = super::super::auth::entries(tx)
+        .map(xdr::VecM::to_vec)
+        .unwrap_or_default();
+    let mut reports = Vec::new();
+    for (index, entry) in auth.iter().enumerate() {
+        let SorobanCredentials::Address(credentials) = &entry.credentials else {
+            continue;
+        };
+        let reason = format!("authorization entry {index}");
+        match &credentials.address {
+            ScAddress::Account(account_id) => {
+                let account = client.get_account(&account_id.to_string()).await?;
+                let keys = auth_keys(&credentials.signature);
+                let need = Need {
+                    level: Level::Medium,
+                    reasons: vec![reason],
+                    signed_keys: Some(keys.clone()),
+                };
+                reports.push(report(&account, &need, &|key| match key {
+                    SignerKey::Ed25519(Uint256(key)) => keys.contains(key),
+                    _ => false,
+                }));
+            }
+            address @ ScAddress::Contract(_) => reports.push(Report {
+                account: address.to_string(),
+                reasons: vec![reason],
+                satisfied: credentials.signature != ScVal::Void,
+                check: Check::Contract { contract: true },
+            }),
+        }
+    }
+    Ok(reports)
+}
+
+/// Compare the signers of `account` and which of them `signed` with the threshold of `need`.
+fn report(account: &AccountEntry, need: &Need, signed: &dyn Fn(&SignerKey) -> bool) -> Report {
+    let PublicKey::PublicKeyTypeEd25519(master) = &account.account_id.0;
+    let mut signers = vec![(
+        SignerKey::Ed25519(master.clone()),
+        account.thresholds.0[0].into(),
+    )];
+    signers.extend(account.signers.iter().map(|s| (s.key.clone(), s.weight)));
+    signers.retain(|(_, weight)| *weight > 0);
+    // A threshold of 0 still takes a signature of some weight.
+    let threshold = need.level.threshold(account).max(1);
+    let signed_weight: u32 = signers
+        .iter()
+        .filter(|(key, _)| signed(key))
+        .map(|(_, weight)| (*weight).min(255))
+        .sum();
+    let unsigned = signers
+        .iter()
+        .filter(|(key, _)| !signed(key))
+        .map(|(key, weight)| (key.to_string(), (*weight).min(255)))
+        .collect::<Vec<_>>();
+    let missing = combinations(
+        &unsigned,
+        u32::from(threshold).saturating_sub(signed_weight),
+    );
+    Report {
+        account: account.account_id.to_string(),
+        reasons: need.reasons.clone(),
+        satisfied: signed_weight >= u32::from(threshold),
+        check: Check::Account {
+            level: need.level,
+            threshold,
+            signed_weight,
+            signers: signers
+                .iter()
+                .map(|(key, weight)| Signer {
+                    key: key.to_string(),
+                    weight: *weight,
+                    signed: signed(key),
+                })
+                .collect(),
+            missing,
+            authorization_signed_keys: need.signed_keys.as_ref().map(|keys| {
+                keys.iter()
+                    .map(|key| stellar_strkey::ed25519::PublicKey(*key).to_string())
+                    .collect()
+            }),
+        },
+    }
+}
+
+/// The most combinations of signers that are listed for an account.
+const MAX_COMBINATIONS: usize = 10;
+
+/// The smallest sets of `signers` whose weights add up to `needed`, none of which has a signer
+/// it could do without, fewest signers first.
+pub fn combinations(signers: &[(String, u32)], needed: u32) -> Vec<Vec<String>> {
+    if needed == 0 {
+        return Vec::new();
+    }
+    // Accounts have at most 20 signers and the master key.
+    let signers = &signers[..signers.len().min(21)];
+    let mut found = Vec::new();
+    for mask in 1u32..(1 << signers.len()) {
+        let chosen = signers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, signer)| signer)
+            .collect::<Vec<_>>();
+        let total: u32 = chosen.iter().map(|(_, weight)| weight).sum();
+        let lightest = chosen.iter().map(|(_, weight)| *weight).min().unwrap_or(0);
+        if total >= needed && total - lightest < needed {
+            found.push(chosen);
+        }
+    }
+    found.sort_by_key(|chosen| {
+        (
+            chosen.len(),
+            u32::MAX - chosen.iter().map(|(_, weight)| weight).sum::<u32>(),
+        )
+    });
+    found
+        .into_iter()
+        .take(MAX_COMBINATIONS)
+        .map(|chosen| chosen.into_iter().map(|(key, _)| key.clone()).collect())
+        .collect()
+}
+
+/// Whether one of `signatures` of the transaction with `hash` is from `key`.
+fn signed(key: &SignerKey, hash: &[u8; 32], signatures: &[DecoratedSignature]) -> bool {
+    let verifies = |public_key: &[u8; 32], message: &[u8], signature: &[u8]| {
+        let Ok(public_key) = ed25519_dalek::VerifyingKey::from_bytes(public_key) else {
+            return false;
+        };
+        let Ok(signature) = ed25519_dalek::Signature::from_slice(signature) else {
+            return false;
+        };
+        public_key.verify(message, &signature).is_ok()
+    };
+    match key {
+        // The transaction is authorized by its hash being a signer.
+        SignerKey::PreAuthTx(Uint256(key)) => key == hash,
+        SignerKey::Ed25519(Uint256(key)) => signatures
+            .iter()
+            .any(|s| s.hint.0 == key[28..] && verifies(key, hash, s.signature.0.as_slice())),
+        SignerKey::HashX(Uint256(key)) => signatures
+            .iter()
+            .any(|s| Sha256::digest(s.signature.0.as_slice()).as_slice() == key),
+        SignerKey::Ed25519SignedPayload(payload) => signatures.iter().any(|s| {
+            verifies(
+                &payload.ed25519.0,
+                &payload.payload,
+                s.signature.0.as_slice(),
+            )
+        }),
+    }
+}
+
+/// The public keys of the signatures of an authorization entry of an account, a vector of maps
+/// with a `public_key` and a `signature`.
+fn auth_keys(signature: &ScVal) -> Vec<[u8; 32]> {
+    let ScVal::Vec(Some(signatures)) = signature else {
+        return Vec::new();
+    };
+    signatures
+        .iter()
+        .filter_map(|signature| {
+            let ScVal::Map(Some(map)) = signature else {
+                return None;
+            };
+            map.iter().find_map(|entry| match (&entry.key, &entry.val) {
+                (ScVal::Symbol(name), ScVal::Bytes(key)) if name.0.as_slice() == b"public_key" => {
+                    key.0.as_slice().try_into().ok()
+                }
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operation_thresholds() {
+        assert_eq!(
+            level(&OperationBody::BumpSequence(xdr::BumpSequenceOp {
+                bump_to: xdr::SequenceNumber(1)
+            })),
+            Level::Low
+        );
+        assert_eq!(level(&OperationBody::Inflation), Level::Low);
+        let set_options = xdr::SetOptionsOp {
+            inflation_dest: None,
+            clear_flags: None,
+            set_flags: None,
+            master_weight: None,
+            low_threshold: None,
+            med_threshold: None,
+            high_threshold: None,
+            home_domain: None,
+            signer: None,
+        };
+        assert_eq!(
+            level(&OperationBody::SetOptions(set_options.clone())),
+            Level::Medium
+        );
+        assert_eq!(
+            level(&OperationBody::SetOptions(xdr::SetOptionsOp {
+                master_weight: Some(0),
+                ..set_options
+            })),
+            Level::High
+        );
+    }
+
+    #[test]
+    fn minimal_combinations() {
+        let signers = [
+            ("a".to_string(), 2),
+            ("b".to_string(), 1),
+            ("c".to_string(), 1),
+        ];
+        assert_eq!(
+            combinations(&signers, 2),
+            vec![
+                vec!["a".to_string()],
+                vec!["b".to_string(), "c".to_string()]
+            ]
+        );
+        assert_eq!(combinations(&signers, 4).len(), 1);
+        assert!(combinations(&signers, 5).is_empty());
+        assert!(combinations(&signers, 0).is_empty());
+    }
+
+    #[test]
+    fn report_of_an_account() {
+        let account_id: AccountId = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        let account = AccountEntry {
+            account_id: account_id.clone(),
+            balance: 0,
+            seq_num: xdr::SequenceNumber(1),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: xdr::String32::default(),
+            thresholds: xdr::Thresholds([1, 0, 2, 2]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        };
+        let need = Need {
+            level: Level::Medium,
+            reasons: vec!["transaction source".to_string()],
+            signed_keys: None,
+        };
+        let report = report(&account, &need, &|_| false);
+        assert!(!report.satisfied);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "account": account_id.to_string(),
+                "reasons": ["transaction source"],
+                "satisfied": false,
+                "level": "medium",
+                "threshold": 2,
+                "signed_weight": 0,
+                "signers": [{ "key": account_id.to_string(), "weight": 1, "signed": false }],
+                "missing": [],
+                "authorization_signed_keys": null,
+            })
+        );
+    }
+}
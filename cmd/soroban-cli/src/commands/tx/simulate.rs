@@ -1,6 +1,7 @@
 use crate::{
-    assembled::{simulate_and_assemble_transaction, Assembled},
-    xdr::{self, TransactionEnvelope, WriteXdr},
+    assembled::Assembled,
+    print::Print,
+    xdr::{self, TransactionEnvelope, TransactionV1Envelope, VecM, WriteXdr},
 };
 use async_trait::async_trait;
 
@@ -18,6 +19,8 @@ pub enum Error {
     Xdr(#[from] xdr::Error),
     #[error(transparent)]
     Network(#[from] config::network::Error),
+    #[error("transaction simulation failed: {0}")]
+    SimulationFailed(String),
 }
 
 /// Command to simulate a transaction envelope via rpc
@@ -25,6 +28,10 @@ pub enum Error {
 #[derive(Debug, clap::Parser, Clone, Default)]
 #[group(skip)]
 pub struct Cmd {
+    /// Base64 encoded transaction envelope, or a file containing one. Read from stdin if not
+    /// given or `-`. Envelopes built by SDKs and other tools are supported, v0 envelopes are
+    /// upgraded to v1, and the signatures are dropped as assembling changes the transaction
+    pub tx_xdr: Option<String>,
     #[clap(flatten)]
     pub config: super::super::config::Args,
 }
@@ -47,14 +54,35 @@ impl NetworkRunnable for Cmd {
     type Result = Assembled;
     async fn run_against_rpc_server(
         &self,
-        _: Option<&global::Args>,
+        global_args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<Self::Result, Self::Error> {
+        let print = Print::new(global_args.is_some_and(|a| a.quiet));
         let config = config.unwrap_or(&self.config);
         let network = config.get_network()?;
         let client = network.rpc_client()?;
-        let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_stdin()?)?;
-        let tx = simulate_and_assemble_transaction(&client, &tx).await?;
-        Ok(tx)
+        let tx = super::xdr::unwrap_envelope(super::xdr::tx_envelope_from_input(
+            self.tx_xdr.as_deref(),
+        )?)?;
+        let sim_res = client
+            .simulate_transaction_envelope(&TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: tx.clone(),
+                signatures: VecM::default(),
+            }))
+            .await?;
+        if let Some(error) = sim_res.error.clone() {
+            let events = sim_res.events()?;
+            if !events.is_empty() {
+                print.errorln("Diagnostic events:");
+            }
+            for (i, event) in events.iter().enumerate() {
+                print.println(format!(
+                    "  {i}: {}",
+                    serde_json::to_string(event).unwrap_or_else(|e| e.to_string())
+                ));
+            }
+            return Err(Error::SimulationFailed(error));
+        }
+        Ok(Assembled::new(&tx, sim_res)?)
     }
 }
@@ -0,0 +1,128 @@
+use clap::{arg, command};
+
+use crate::{
+    commands::global,
+    config::{locator, network, secret},
+    print::Print,
+    sep::sep7::{Operation, Uri},
+    xdr::{self, Limits, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("--destination is required for pay URIs")]
+    MissingDestination,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Kind of request to encode. `tx` reads a transaction envelope from stdin.
+    #[arg(long, value_enum, default_value = "tx")]
+    pub operation: Operation,
+    /// URL to post the signed transaction to instead of submitting it to the network
+    #[arg(long)]
+    pub callback: Option<String>,
+    /// Message to show to the user, at most 300 characters
+    #[arg(long)]
+    pub msg: Option<String>,
+    /// Domain of the application requesting the signature. Required when signing the URI.
+    #[arg(long)]
+    pub origin_domain: Option<String>,
+    /// Identity or secret key to sign the URI with. Its public key must be published as
+    /// `URI_REQUEST_SIGNING_KEY` in the stellar.toml of `--origin-domain`.
+    #[arg(long, requires = "origin_domain")]
+    pub uri_signer: Option<String>,
+    /// Public key of the account that should sign the transaction
+    #[arg(long, help_heading = "Tx")]
+    pub pubkey: Option<String>,
+    /// Account to pay
+    #[arg(long, help_heading = "Pay")]
+    pub destination: Option<String>,
+    /// Amount to pay. If omitted the wallet asks the user for an amount.
+    #[arg(long, help_heading = "Pay")]
+    pub amount: Option<String>,
+    /// Code of the asset to pay, omitted for XLM
+    #[arg(long, help_heading = "Pay", requires = "asset_issuer")]
+    pub asset_code: Option<String>,
+    /// Issuer of the asset to pay
+    #[arg(long, help_heading = "Pay", requires = "asset_code")]
+    pub asset_issuer: Option<String>,
+    /// Memo to attach to the payment
+    #[arg(long, help_heading = "Pay")]
+    pub memo: Option<String>,
+    /// Type of `--memo`, e.g. `MEMO_TEXT`, `MEMO_ID`, `MEMO_HASH`, or `MEMO_RETURN`
+    #[arg(long, help_heading = "Pay", requires = "memo")]
+    pub memo_type: Option<String>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    #[allow(clippy::unused_async)]
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let uri = match self.operation {
+            Operation::Tx => {
+                let tx_env = super::xdr::tx_envelope_from_stdin()?;
+                Uri::new(Operation::Tx)
+                    .param("xdr", Some(tx_env.to_xdr_base64(Limits::none())?))
+                    .param("pubkey", self.pubkey.clone())
+            }
+            Operation::Pay => Uri::new(Operation::Pay)
+                .param(
+                    "destination",
+                    Some(self.destination.clone().ok_or(Error::MissingDestination)?),
+                )
+                .param("amount", self.amount.clone())
+                .param("asset_code", self.asset_code.clone())
+                .param("asset_issuer", self.asset_issuer.clone())
+                .param("memo", self.memo.clone())
+                .param("memo_type", self.memo_type.clone()),
+        };
+        let mut uri = uri
+            .param(
+                "callback",
+                self.callback.as_ref().map(|c| format!("url:{c}")),
+            )
+            .param("msg", self.msg.clone())
+            .param("network_passphrase", self.network_passphrase()?)
+            .param("origin_domain", self.origin_domain.clone());
+        if let Some(signer) = &self.uri_signer {
+            let key = self.locator.key(signer)?.key_pair(None)?;
+            uri.sign(&key);
+            print.checkln(format!(
+                "Signed URI with {}",
+                stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes())
+            ));
+        }
+        println!("{uri}");
+        Ok(())
+    }
+
+    /// The passphrase to include in the URI, omitted for the public network as per SEP-7.
+    fn network_passphrase(&self) -> Result<Option<String>, Error> {
+        let passphrase = if let Some(passphrase) = &self.network.network_passphrase {
+            passphrase.clone()
+        } else {
+            match self.network.get(&self.locator) {
+                Ok(network) => network.network_passphrase,
+                Err(network::Error::Network) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+        };
+        Ok((passphrase != network::passphrase::MAINNET).then_some(passphrase))
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+use clap::{arg, command, ValueEnum};
+use serde_json::json;
+
+use crate::{
+    commands::global,
+    config::{self, network},
+    interrupt,
+    print::Print,
+    rpc::{self, GetTransactionResponse},
+    xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("invalid transaction hash {0:?}, expected 32 bytes as hex")]
+    InvalidHash(String),
+    #[error("transaction {0} was not included within {1}")]
+    Timeout(xdr::Hash, humantime::Duration),
+    #[error("transaction {hash} failed: {result}")]
+    Failed { hash: xdr::Hash, result: String },
+    #[error("transaction {hash} has unexpected status {status}")]
+    UnexpectedStatus { hash: xdr::Hash, status: String },
+}
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Hash of the transaction, as printed by commands run with `--no-wait`
+    #[arg(value_parser = parse_hash)]
+    pub hash: xdr::Hash,
+    /// Format of the output
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: Output,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// The return value on stdout, the status and events on stderr
+    Text,
+    /// The status, result, return value, and events as JSON
+    Json,
+}
+
+fn parse_hash(hash: &str) -> Result<xdr::Hash, Error> {
+    hash.parse()
+        .map_err(|_| Error::InvalidHash(hash.to_string()))
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let client = self.config.get_network()?.rpc_client()?;
+        // How long to wait for the transaction to be included, set with the global `--timeout`.
+        let timeout = global_args.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let start = Instant::now();
+        print.infoln(format!("Waiting for transaction {}…", self.hash));
+        interrupt::sent(self.hash.clone());
+        let response = loop {
+            let response = client.get_transaction(&self.hash).await?;
+            if response.status != "NOT_FOUND" {
+                break response;
+            }
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(self.hash.clone(), timeout.into()));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        };
+        interrupt::done();
+        self.output(&print, &response)?;
+        match response.status.as_str() {
+            "SUCCESS" => Ok(()),
+            "FAILED" => Err(Error::Failed {
+                hash: self.hash.clone(),
+                result: response
+                    .result
+                    .map(|r| r.result.name().to_string())
+                    .unwrap_or_default(),
+            }),
+            _ => Err(Error::UnexpectedStatus {
+                hash: self.hash.clone(),
+                status: response.status,
+            }),
+        }
+    }
+
+    fn output(&self, print: &Print, response: &GetTransactionResponse) -> Result<(), Error> {
+        let return_value = response.return_value().ok();
+        let events = response.contract_events().unwrap_or_default();
+        match self.output {
+            Output::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "hash": self.hash,
+                    "status": response.status,
+                    "fee_charged": response.result.as_ref().map(|r| r.fee_charged),
+                    "result": response.result,
+                    "return_value": return_value,
+                    "events": events,
+                }))?
+            ),
+            Output::Text => {
+                if response.status == "SUCCESS" {
+                    print.checkln(format!("Transaction {} succeeded", self.hash));
+                }
+                for event in &events {
+                    print.infoln(format!("Event: {}", serde_json::to_string(&event.event)?));
+                }
+                if let Some(value) = return_value {
+                    println!("{}", serde_json::to_string(&value)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,11 @@ use std::{
     path::PathBuf,
 };
 
-use crate::xdr::{Limits, ReadXdr, Transaction, TransactionEnvelope, TransactionV1Envelope};
+use crate::input;
+use crate::xdr::{
+    Limits, MuxedAccount, Preconditions, ReadXdr, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV0, TransactionV0Envelope, TransactionV1Envelope,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -13,16 +17,23 @@ pub enum Error {
     FileDecode(PathBuf),
     #[error("failed to decode XDR from stdin")]
     StdinDecode,
+    #[error("no transaction on stdin with --no-input or when not run in a terminal, pipe it or pass it as an argument")]
+    StdinRequired,
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error("only transaction v1 is supported")]
     OnlyTransactionV1Supported,
+    #[error("fee bump transactions are not supported, use the inner transaction instead")]
+    FeeBumpNotSupported,
 }
 
 pub fn tx_envelope_from_stdin() -> Result<TransactionEnvelope, Error> {
     from_stdin()
 }
 pub fn from_stdin<T: ReadXdr>() -> Result<T, Error> {
+    if input::stdin_would_wait() {
+        return Err(Error::StdinRequired);
+    }
     let mut buf = String::new();
     let _ = stdin()
         .read_to_string(&mut buf)
@@ -30,9 +41,95 @@ pub fn from_stdin<T: ReadXdr>() -> Result<T, Error> {
     T::from_xdr_base64(buf.trim(), Limits::none()).map_err(|_| Error::StdinDecode)
 }
 
+/// Read a transaction envelope from `input`, which is either base64 XDR or the path of a file
+/// containing it. Reads from stdin when `input` is `None` or `-`.
+pub fn tx_envelope_from_input(input: Option<&str>) -> Result<TransactionEnvelope, Error> {
+    let input = match input {
+        None | Some("-") => return tx_envelope_from_stdin(),
+        Some(input) => input,
+    };
+    let path = PathBuf::from(input);
+    if path.is_file() {
+        let contents = std::fs::read_to_string(&path)?;
+        return TransactionEnvelope::from_xdr_base64(contents.trim(), Limits::none())
+            .map_err(|_| Error::FileDecode(path));
+    }
+    TransactionEnvelope::from_xdr_base64(input.trim(), Limits::none())
+        .map_err(|_| Error::Base64Decode)
+}
+
+/// The transaction of a v0 or v1 envelope, upgrading a v0 transaction to v1.
+pub fn unwrap_envelope(tx_env: TransactionEnvelope) -> Result<Transaction, Error> {
+    match tx_env {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => Ok(tx),
+        TransactionEnvelope::TxV0(TransactionV0Envelope {
+            tx:
+                TransactionV0 {
+                    source_account_ed25519,
+                    fee,
+                    seq_num,
+                    time_bounds,
+                    memo,
+                    operations,
+                    ..
+                },
+            ..
+        }) => Ok(Transaction {
+            source_account: MuxedAccount::Ed25519(source_account_ed25519),
+            fee,
+            seq_num,
+            cond: time_bounds.map_or(Preconditions::None, Preconditions::Time),
+            memo,
+            operations,
+            ext: TransactionExt::V0,
+        }),
+        TransactionEnvelope::TxFeeBump(_) => Err(Error::FeeBumpNotSupported),
+    }
+}
+
 pub fn unwrap_envelope_v1(tx_env: TransactionEnvelope) -> Result<Transaction, Error> {
     let TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) = tx_env else {
         return Err(Error::OnlyTransactionV1Supported);
     };
     Ok(tx)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{Memo, SequenceNumber, TimeBounds, Uint256, VecM, WriteXdr};
+
+    #[test]
+    fn reads_and_upgrades_v0_envelope() {
+        let time_bounds = TimeBounds {
+            min_time: 0.into(),
+            max_time: 100.into(),
+        };
+        let tx_env = TransactionEnvelope::TxV0(TransactionV0Envelope {
+            tx: TransactionV0 {
+                source_account_ed25519: Uint256([1; 32]),
+                fee: 100,
+                seq_num: SequenceNumber(2),
+                time_bounds: Some(time_bounds.clone()),
+                memo: Memo::None,
+                operations: VecM::default(),
+                ext: crate::xdr::TransactionV0Ext::V0,
+            },
+            signatures: VecM::default(),
+        });
+        let blob = tx_env.to_xdr_base64(Limits::none()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("tx.xdr");
+        std::fs::write(&file, format!("{blob}\n")).unwrap();
+
+        for input in [blob.as_str(), file.to_str().unwrap()] {
+            let tx = unwrap_envelope(tx_envelope_from_input(Some(input)).unwrap()).unwrap();
+            assert_eq!(tx.source_account, MuxedAccount::Ed25519(Uint256([1; 32])));
+            assert_eq!(tx.cond, Preconditions::Time(time_bounds.clone()));
+        }
+        assert!(matches!(
+            tx_envelope_from_input(Some("not xdr")),
+            Err(Error::Base64Decode)
+        ));
+    }
+}
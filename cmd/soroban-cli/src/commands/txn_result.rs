@@ -1,16 +1,20 @@
-use crate::xdr::{Transaction, TransactionEnvelope, TransactionV1Envelope, VecM};
+use crate::xdr::{Hash, Transaction, TransactionEnvelope, TransactionV1Envelope, VecM};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TxnResult<R> {
     Txn(Box<Transaction>),
     Res(R),
+    /// The transaction was sent with `--no-wait`, and its result is not known yet.
+    Submitted(Hash),
+    /// The signed transaction, built with `--dry-run` and not sent.
+    TxnEnvelope(Box<TransactionEnvelope>),
 }
 
 impl<R> TxnResult<R> {
     pub fn into_result(self) -> Option<R> {
         match self {
             TxnResult::Res(res) => Some(res),
-            TxnResult::Txn(_) => None,
+            TxnResult::Txn(_) | TxnResult::Submitted(_) | TxnResult::TxnEnvelope(_) => None,
         }
     }
 
@@ -23,6 +27,8 @@ impl<R> TxnResult<R> {
                 }),
             )),
             TxnResult::Res(res) => TxnEnvelopeResult::Res(res),
+            TxnResult::Submitted(hash) => TxnEnvelopeResult::Submitted(hash),
+            TxnResult::TxnEnvelope(tx_env) => TxnEnvelopeResult::TxnEnvelope(tx_env),
         }
     }
 }
@@ -31,4 +37,6 @@ impl<R> TxnResult<R> {
 pub enum TxnEnvelopeResult<R> {
     TxnEnvelope(Box<TransactionEnvelope>),
     Res(R),
+    /// The transaction was sent with `--no-wait`, and its result is not known yet.
+    Submitted(Hash),
 }
@@ -1,14 +1,62 @@
 use clap::Parser;
+use semver::Version;
 use std::fmt::Debug;
 
+use crate::{
+    commands::global,
+    config::{locator, network},
+    print::Print,
+    upgrade_check,
+};
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
-pub struct Cmd;
+pub struct Cmd {
+    /// Check for a new release of the CLI, and whether the network is on a protocol newer than
+    /// the CLI supports
+    #[arg(long)]
+    pub check: bool,
+
+    #[command(flatten)]
+    pub network: network::Args,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
 
 impl Cmd {
-    #[allow(clippy::unused_self)]
-    pub fn run(&self) {
+    pub async fn run(&self, global_args: &global::Args) {
         println!("stellar {}", long());
+        if !self.check {
+            return;
+        }
+        let print = Print::new(global_args.quiet);
+        match upgrade_check::latest_version().await {
+            Ok(latest) if Version::parse(pkg()).is_ok_and(|current| latest > current) => {
+                print.warnln(format!(
+                    "A new release of stellar-cli is available: {} -> {latest}",
+                    pkg()
+                ));
+            }
+            Ok(latest) => print.checkln(format!(
+                "stellar-cli is up to date, the latest release is {latest}"
+            )),
+            Err(e) => print.warnln(format!("Could not check for a new release: {e}")),
+        }
+        let Ok(network) = self.network.get(&self.locator) else {
+            print.infoln("No network selected, pass --network to check its protocol");
+            return;
+        };
+        match network.rpc_version(global_args.no_cache).await {
+            Ok(info) => match upgrade_check::protocol_warning(info.protocol_version) {
+                Some(warning) => print.warnln(warning),
+                None => print.checkln(format!(
+                    "The network is on protocol {}, which this version supports",
+                    info.protocol_version
+                )),
+            },
+            Err(e) => print.warnln(format!("Could not get the protocol of the network: {e}")),
+        }
     }
 }
 
@@ -16,6 +64,16 @@ pub fn pkg() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// The latest protocol the CLI supports, the major version of its XDR.
+pub fn protocol() -> u32 {
+    stellar_xdr::VERSION
+        .pkg
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or_default()
+}
+
 pub fn git() -> &'static str {
     env!("GIT_REVISION")
 }
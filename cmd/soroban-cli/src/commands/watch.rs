@@ -0,0 +1,650 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    io::{self, BufRead, IsTerminal},
+    time::Duration,
+};
+
+use clap::{arg, command, Parser};
+use jsonrpsee_core::params::ObjectParams;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::{
+    commands::global,
+    config::{self, address::Address, addressbook::Book, locator, network},
+    horizon, input,
+    pagination::{Event, EventsPage},
+    rpc,
+    xdr::{
+        self, ContractDataDurability, ContractExecutable, Hash, LedgerEntryData, LedgerKey,
+        LedgerKeyContractCode, LedgerKeyContractData, Limits, ReadXdr, ScAddress, ScVal,
+        TransactionEnvelope, TransactionResult, WriteXdr,
+    },
+};
+
+/// Ledgers to look back for events when the dashboard starts, about an hour.
+const EVENTS_LOOKBACK: u32 = 720;
+
+/// Average time between ledgers, used to estimate when entries expire.
+const LEDGER_TIME: Duration = Duration::from_secs(5);
+
+/// Longest summary of an item shown on the dashboard.
+const SUMMARY_WIDTH: usize = 100;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Horizon(#[from] horizon::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    JsonRpc(#[from] jsonrpsee_core::Error),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract to watch, e.g. `CBX...` or an alias
+    #[arg(
+        long = "id",
+        conflicts_with = "account",
+        required_unless_present = "account"
+    )]
+    pub contract_id: Option<config::ContractAddress>,
+    /// Account to watch, e.g. `GBX...` or an identity
+    #[arg(long)]
+    pub account: Option<Address>,
+    /// How often to poll for updates, e.g. `5s` or `1m`
+    #[arg(long, default_value = "5s", value_parser = humantime::parse_duration)]
+    pub interval: Duration,
+    /// Number of events and transactions to show
+    #[arg(long, default_value = "10")]
+    pub limit: usize,
+    /// Stop after this many updates, runs until `q` is entered if not set
+    #[arg(long)]
+    pub count: Option<u32>,
+    #[command(flatten)]
+    pub config: config::ArgsLocatorAndNetwork,
+    #[command(flatten)]
+    pub horizon: horizon::Args,
+}
+
+/// What is being watched.
+#[derive(Debug, Clone)]
+enum Target {
+    Contract(stellar_strkey::Contract),
+    Account(xdr::AccountId),
+}
+
+/// An entry of the dashboard that can be selected to show its decoded XDR.
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    summary: String,
+    detail: Detail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Detail {
+    Json(Value),
+    /// A transaction fetched from RPC when selected.
+    Transaction(String),
+}
+
+/// An entry that expires, with the ledger it is live until.
+#[derive(Debug, Clone, PartialEq)]
+struct Ttl {
+    name: String,
+    live_until: u32,
+}
+
+#[derive(Debug, Default)]
+struct Dashboard {
+    title: String,
+    latest_ledger: u32,
+    events: VecDeque<Item>,
+    transactions: Vec<Item>,
+    storage: Vec<Item>,
+    ttls: Vec<Ttl>,
+    /// Cursor of the last event read, the next poll continues from it.
+    cursor: Option<String>,
+    status: Option<String>,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network = self.config.get_network()?;
+        let client = network.rpc_client()?;
+        let target = match (&self.contract_id, &self.account) {
+            (Some(contract), _) => Target::Contract(
+                contract.resolve_contract_id(&self.config.locator, &network.network_passphrase)?,
+            ),
+            (None, Some(account)) => Target::Account(
+                account
+                    .resolve_muxed_account(&self.config.locator, None)?
+                    .account_id(),
+            ),
+            (None, None) => unreachable!("clap requires --id or --account"),
+        };
+        let mut dashboard = Dashboard {
+            title: match &target {
+                Target::Contract(id) => format!("contract {id}"),
+                Target::Account(id) => format!("account {id}"),
+            },
+            ..Dashboard::default()
+        };
+        let clear = io::stdout().is_terminal() && !global_args.quiet;
+        let book = Book::load(&self.config.locator);
+        let show = |screen: &str| draw(&book.substitute(screen), clear);
+        let mut input = keys();
+        // Whether a selected item is shown instead of the dashboard
+        let mut detail = false;
+        let mut remaining = self.count;
+        loop {
+            dashboard.status = self
+                .refresh(&mut dashboard, &target, &network, &client)
+                .await
+                .err()
+                .map(|e| e.to_string());
+            remaining = remaining.map(|n| n.saturating_sub(1));
+            if !detail {
+                show(&dashboard.render(self.interval));
+            }
+            if remaining == Some(0) {
+                return Ok(());
+            }
+            let tick = tokio::time::sleep(self.interval);
+            tokio::pin!(tick);
+            loop {
+                tokio::select! {
+                    () = &mut tick => break,
+                    key = input.recv() => {
+                        let Some(key) = key else {
+                            // Stdin closed, keep refreshing until interrupted
+                            tick.as_mut().await;
+                            break;
+                        };
+                        match key.trim() {
+                            "q" => return Ok(()),
+                            "r" => break,
+                            "b" | "" => {
+                                detail = false;
+                                show(&dashboard.render(self.interval));
+                            }
+                            key => match key.parse::<usize>() {
+                                Ok(n) if (1..=dashboard.items().len()).contains(&n) => {
+                                    detail = true;
+                                    show(&self.detail(&dashboard, n, &client).await);
+                                }
+                                _ => show(&dashboard.render(self.interval)),
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn refresh(
+        &self,
+        dashboard: &mut Dashboard,
+        target: &Target,
+        network: &network::Network,
+        client: &rpc::Client,
+    ) -> Result<(), Error> {
+        dashboard.latest_ledger = client.get_latest_ledger().await?.sequence;
+        self.poll_events(dashboard, target, network).await?;
+        match target {
+            Target::Contract(id) => {
+                // Contracts have no transaction index, list those that emitted the events shown
+                let mut hashes = Vec::new();
+                for item in dashboard.events.iter().rev() {
+                    if let Detail::Json(event) = &item.detail {
+                        if let Some(hash) = event["tx_hash"].as_str() {
+                            if !hashes.iter().any(|(h, _)| h == hash) {
+                                hashes.push((hash.to_string(), event["ledger"].clone()));
+                            }
+                        }
+                    }
+                }
+                dashboard.transactions = hashes
+                    .into_iter()
+                    .take(self.limit)
+                    .map(|(hash, ledger)| Item {
+                        summary: format!("{hash} ledger {ledger}"),
+                        detail: Detail::Transaction(hash),
+                    })
+                    .collect();
+                (dashboard.storage, dashboard.ttls) = contract_storage(client, id).await?;
+            }
+            Target::Account(id) => {
+                let id = id.to_string();
+                let horizon = self.horizon.client(network)?;
+                dashboard.transactions = account_transactions(&horizon, &id, self.limit).await?;
+                dashboard.storage = account_storage(client, &horizon, &id).await?;
+                dashboard.ttls = Vec::new();
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the events since the last poll, keeping the last `limit`.
+    async fn poll_events(
+        &self,
+        dashboard: &mut Dashboard,
+        target: &Target,
+        network: &network::Network,
+    ) -> Result<(), Error> {
+        let mut filter = json!({ "type": "contract" });
+        match target {
+            Target::Contract(id) => filter["contractIds"] = json!([id.to_string()]),
+            Target::Account(id) => {
+                // Token events have the address they move funds from or to as second or third
+                // topic, e.g. `transfer`, `mint`, and `burn`
+                let address =
+                    ScVal::Address(ScAddress::Account(id.clone())).to_xdr_base64(Limits::none())?;
+                filter["topics"] = json!([
+                    ["*", address, "*", "*"],
+                    ["*", "*", address, "*"],
+                    ["*", address, "*"],
+                ]);
+            }
+        }
+        loop {
+            let mut params = ObjectParams::new();
+            let mut pagination = json!({ "limit": 100 });
+            match &dashboard.cursor {
+                Some(cursor) => pagination["cursor"] = json!(cursor),
+                None => params.insert(
+                    "startLedger",
+                    dashboard
+                        .latest_ledger
+                        .saturating_sub(EVENTS_LOOKBACK)
+                        .max(1),
+                )?,
+            }
+            params.insert("filters", [&filter])?;
+            params.insert("pagination", pagination)?;
+            let page: EventsPage = network.rpc_request("getEvents", params).await?;
+            let len = page.events.len();
+            let cursor = page
+                .cursor
+                .or_else(|| page.events.last().map(|e| e.id.clone()));
+            for event in page.events {
+                dashboard.events.push_back(event_item(event)?);
+                if dashboard.events.len() > self.limit {
+                    dashboard.events.pop_front();
+                }
+            }
+            dashboard.latest_ledger = dashboard.latest_ledger.max(page.latest_ledger);
+            if cursor.is_some() {
+                dashboard.cursor = cursor;
+            }
+            if len < 100 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The decoded XDR of the `n`th item of the dashboard.
+    async fn detail(&self, dashboard: &Dashboard, n: usize, client: &rpc::Client) -> String {
+        let (section, item) = &dashboard.items()[n - 1];
+        let json = match &item.detail {
+            Detail::Json(json) => Ok(json.clone()),
+            Detail::Transaction(hash) => transaction(client, hash).await,
+        };
+        let body = match json.and_then(|json| Ok(serde_json::to_string_pretty(&json)?)) {
+            Ok(body) => body,
+            Err(e) => format!("error: {e}"),
+        };
+        format!(
+            "{section} [{n}] {}\n\n{body}\n\nb back, r refresh, q quit (then Enter)\n",
+            item.summary
+        )
+    }
+}
+
+fn event_item(event: Event) -> Result<Item, Error> {
+    let (topics, value) = event.decode()?;
+    let summary = format!(
+        "ledger {} {} = {}",
+        event.ledger,
+        topics.iter().map(short).collect::<Vec<_>>().join(" "),
+        short(&value)
+    );
+    Ok(Item {
+        summary,
+        detail: Detail::Json(json!({
+            "id": event.id,
+            "ledger": event.ledger,
+            "contract_id": event.contract_id,
+            "tx_hash": event.tx_hash,
+            "topics": topics,
+            "value": value,
+        })),
+    })
+}
+
+impl Dashboard {
+    /// The items that can be selected, numbered from 1 in the order they are shown.
+    fn items(&self) -> Vec<(&'static str, &Item)> {
+        self.events
+            .iter()
+            .rev()
+            .map(|item| ("Events", item))
+            .chain(self.transactions.iter().map(|item| ("Transactions", item)))
+            .chain(self.storage.iter().map(|item| ("Storage", item)))
+            .collect()
+    }
+
+    fn render(&self, interval: Duration) -> String {
+        let mut out = format!(
+            "Watching {} at ledger {} (every {})\n",
+            self.title,
+            self.latest_ledger,
+            humantime::format_duration(interval)
+        );
+        if let Some(status) = &self.status {
+            let _ = writeln!(out, "error: {status}");
+        }
+        let items = self.items();
+        for section in ["Events", "Transactions", "Storage"] {
+            let _ = write!(out, "\n{section}\n");
+            let mut none = true;
+            for (n, (_, item)) in items.iter().enumerate().filter(|(_, (s, _))| *s == section) {
+                let _ = writeln!(out, "  [{}] {}", n + 1, truncate(&item.summary));
+                none = false;
+            }
+            if none {
+                out.push_str("  none\n");
+            }
+        }
+        out.push_str("\nTTL\n");
+        if self.ttls.is_empty() {
+            out.push_str("  none\n");
+        }
+        for ttl in &self.ttls {
+            let left = ttl.live_until.saturating_sub(self.latest_ledger);
+            let _ = writeln!(
+                out,
+                "  {} live until ledger {} ({left} ledgers, ~{})",
+                ttl.name,
+                ttl.live_until,
+                humantime::format_duration(LEDGER_TIME * left)
+            );
+        }
+        out.push_str("\nEnter a number to show its decoded XDR, r to refresh, q to quit\n");
+        out
+    }
+}
+
+/// The instance storage of a contract, and the TTLs of its instance and code.
+async fn contract_storage(
+    client: &rpc::Client,
+    id: &stellar_strkey::Contract,
+) -> Result<(Vec<Item>, Vec<Ttl>), Error> {
+    let instance_key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::Contract(Hash(id.0)),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+    });
+    let entries = client.get_full_ledger_entries(&[instance_key]).await?;
+    let mut storage = Vec::new();
+    let mut ttls = Vec::new();
+    for entry in entries.entries {
+        let LedgerEntryData::ContractData(data) = &entry.val else {
+            continue;
+        };
+        ttls.push(Ttl {
+            name: "instance".to_string(),
+            live_until: entry.live_until_ledger_seq,
+        });
+        let ScVal::ContractInstance(instance) = &data.val else {
+            continue;
+        };
+        if let ContractExecutable::Wasm(hash) = &instance.executable {
+            let code_key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+            if let Some(code) = client
+                .get_full_ledger_entries(&[code_key])
+                .await?
+                .entries
+                .into_iter()
+                .next()
+            {
+                ttls.push(Ttl {
+                    name: format!("wasm {}", hex::encode(hash.0)),
+                    live_until: code.live_until_ledger_seq,
+                });
+            }
+        }
+        for entry in instance.storage.iter().flat_map(|storage| storage.iter()) {
+            storage.push(Item {
+                summary: format!("{} = {}", short(&entry.key), short(&entry.val)),
+                detail: Detail::Json(json!({ "key": entry.key, "val": entry.val })),
+            });
+        }
+    }
+    Ok((storage, ttls))
+}
+
+async fn transaction(client: &rpc::Client, hash: &str) -> Result<Value, Error> {
+    let hash = hash.parse::<Hash>()?;
+    Ok(serde_json::to_value(client.get_transaction(&hash).await?)?)
+}
+
+/// The last transactions of an account, newest first.
+async fn account_transactions(
+    horizon: &horizon::Client,
+    id: &str,
+    limit: usize,
+) -> Result<Vec<Item>, Error> {
+    let records = horizon
+        .records(
+            &format!("accounts/{id}/transactions"),
+            &[("order", "desc".to_string()), ("limit", limit.to_string())],
+            Some(limit),
+        )
+        .await?;
+    records
+        .iter()
+        .map(|record| {
+            let envelope = record["envelope_xdr"]
+                .as_str()
+                .map(|e| TransactionEnvelope::from_xdr_base64(e, Limits::none()))
+                .transpose()?;
+            let result = record["result_xdr"]
+                .as_str()
+                .map(|r| TransactionResult::from_xdr_base64(r, Limits::none()))
+                .transpose()?;
+            let status = if record["successful"].as_bool().unwrap_or_default() {
+                "SUCCESS"
+            } else {
+                "FAILED"
+            };
+            Ok(Item {
+                summary: format!(
+                    "{} ledger {} {status} {}",
+                    record["hash"].as_str().unwrap_or_default(),
+                    record["ledger"],
+                    record["created_at"].as_str().unwrap_or_default(),
+                ),
+                detail: Detail::Json(json!({
+                    "hash": record["hash"],
+                    "ledger": record["ledger"],
+                    "status": status,
+                    "envelope": envelope,
+                    "result": result,
+                })),
+            })
+        })
+        .collect()
+}
+
+/// The account entry of an account and its balances.
+async fn account_storage(
+    client: &rpc::Client,
+    horizon: &horizon::Client,
+    id: &str,
+) -> Result<Vec<Item>, Error> {
+    let entry = client.get_account(id).await?;
+    let mut storage = vec![Item {
+        summary: format!(
+            "account seq {} balance {} XLM",
+            entry.seq_num.0,
+            horizon::amount(entry.balance)
+        ),
+        detail: Detail::Json(serde_json::to_value(&entry)?),
+    }];
+    let account = horizon.account(id).await?;
+    for balance in account["balances"].as_array().into_iter().flatten() {
+        if balance["asset_type"] == "native" {
+            continue;
+        }
+        let asset = match balance["asset_code"].as_str() {
+            Some(code) => format!(
+                "{code}:{}",
+                balance["asset_issuer"].as_str().unwrap_or_default()
+            ),
+            None => balance["liquidity_pool_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        };
+        storage.push(Item {
+            summary: format!(
+                "trustline {asset} balance {}",
+                balance["balance"].as_str().unwrap_or_default()
+            ),
+            detail: Detail::Json(balance.clone()),
+        });
+    }
+    Ok(storage)
+}
+
+/// Lines entered on stdin, each a key pressed. None when prompts cannot be answered, e.g. with
+/// `--no-input`, leaving stdin to whatever is piped to it.
+fn keys() -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    if !input::interactive() {
+        return rx;
+    }
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn draw(screen: &str, clear: bool) {
+    if clear {
+        // Clear the screen and move the cursor to its top left corner
+        print!("\x1b[2J\x1b[H");
+    }
+    print!("{screen}");
+}
+
+/// A compact rendering of a value, its string form for symbols, strings, numbers, and
+/// addresses, and JSON otherwise.
+fn short(val: &ScVal) -> String {
+    match val {
+        ScVal::Symbol(s) => s.to_utf8_string_lossy(),
+        ScVal::String(s) => format!("{:?}", s.to_utf8_string_lossy()),
+        ScVal::U32(n) => n.to_string(),
+        ScVal::I32(n) => n.to_string(),
+        ScVal::U64(n) => n.to_string(),
+        ScVal::I64(n) => n.to_string(),
+        ScVal::U128(n) => (u128::from(n.hi) << 64 | u128::from(n.lo)).to_string(),
+        ScVal::I128(n) => ((i128::from(n.hi) << 64) | i128::from(n.lo)).to_string(),
+        ScVal::Bool(b) => b.to_string(),
+        ScVal::Address(ScAddress::Contract(Hash(id))) => stellar_strkey::Contract(*id).to_string(),
+        ScVal::Address(ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key)),
+        ))) => stellar_strkey::ed25519::PublicKey(*key).to_string(),
+        val => serde_json::to_string(val).unwrap_or_default(),
+    }
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= SUMMARY_WIDTH {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(SUMMARY_WIDTH - 1).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_sections_and_ttls() {
+        let item = |summary: &str| Item {
+            summary: summary.to_string(),
+            detail: Detail::Json(Value::Null),
+        };
+        let dashboard = Dashboard {
+            title: "contract C1".to_string(),
+            latest_ledger: 1_000,
+            events: [item("first"), item("second")].into(),
+            storage: vec![item("COUNTER = 3")],
+            ttls: vec![Ttl {
+                name: "instance".to_string(),
+                live_until: 1_720,
+            }],
+            ..Dashboard::default()
+        };
+        assert_eq!(dashboard.items()[0].1.summary, "second");
+        assert_eq!(
+            dashboard.render(Duration::from_secs(5)),
+            "Watching contract C1 at ledger 1000 (every 5s)
+
+Events
+  [1] second
+  [2] first
+
+Transactions
+  none
+
+Storage
+  [3] COUNTER = 3
+
+TTL
+  instance live until ledger 1720 (720 ledgers, ~1h)
+
+Enter a number to show its decoded XDR, r to refresh, q to quit
+"
+        );
+    }
+
+    #[test]
+    fn short_values() {
+        assert_eq!(
+            short(&ScVal::Symbol("transfer".try_into().unwrap())),
+            "transfer"
+        );
+        assert_eq!(
+            short(&ScVal::I128(xdr::Int128Parts {
+                hi: -1,
+                lo: u64::MAX
+            })),
+            "-1"
+        );
+        assert_eq!(truncate(&"a".repeat(120)).chars().count(), SUMMARY_WIDTH);
+    }
+}
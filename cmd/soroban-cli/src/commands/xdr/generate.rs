@@ -0,0 +1,418 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng, SeedableRng,
+};
+use schemars::gen::SchemaSettings;
+use serde_json::{Map, Value};
+use stellar_strkey::{ed25519, Contract, HashX, PreAuthTx, Strkey};
+use stellar_xdr::cli::Channel;
+
+use crate::{commands::global, print::Print};
+
+/// How many values are generated for each one printed before giving up, for the rare values the
+/// schema allows but the type does not.
+const ATTEMPTS: usize = 100;
+
+/// How deep definitions can nest at all, to stop on types that cannot be cut short.
+const MAX_NESTING: usize = 256;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("could not generate a valid {0} in {ATTEMPTS} attempts: {1}")]
+    Invalid(String, String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// XDR encoded with base64
+    #[default]
+    Base64,
+    /// The JSON of the XDR value, as `xdr decode --output json` prints it
+    Json,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// XDR type to generate values of, e.g. `ScVal` or `TransactionEnvelope`
+    #[arg(long)]
+    pub r#type: String,
+
+    /// Seed of the random values, to generate the same values again with the same version of the
+    /// CLI. A random seed is used and printed to stderr if not given
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Number of values to generate, printed one per line
+    #[arg(long, default_value = "1")]
+    pub count: usize,
+
+    /// How deep recursive types nest, such as the vecs and maps of an `ScVal` or the
+    /// sub-invocations of an auth entry
+    #[arg(long, default_value = "3")]
+    pub max_depth: usize,
+
+    /// Maximum number of items of arrays, and of bytes and characters of variable length opaques
+    /// and strings
+    #[arg(long, default_value = "4")]
+    pub max_len: usize,
+
+    /// Format of the output
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+macro_rules! generate_x {
+    ($f:ident, $m:ident) => {
+        fn $f(&self, rng: &mut StdRng) -> Result<Vec<String>, Error> {
+            use stellar_xdr::$m::{Limits, Type, TypeVariant, WriteXdr};
+            let variant = TypeVariant::from_str(&self.r#type)
+                .map_err(|_| Error::UnknownType(self.r#type.clone(), &TypeVariant::VARIANTS_STR))?;
+            let schema = serde_json::to_value(
+                variant.json_schema(SchemaSettings::draft2019_09().into_generator()),
+            )?;
+            let empty = Map::new();
+            let mut generator = Generator {
+                definitions: schema
+                    .get("definitions")
+                    .and_then(Value::as_object)
+                    .unwrap_or(&empty),
+                rng,
+                max_depth: self.max_depth,
+                max_len: self.max_len,
+                path: Vec::new(),
+            };
+            (0..self.count)
+                .map(|_| {
+                    let mut last_error = "too deeply nested".to_string();
+                    for _ in 0..ATTEMPTS {
+                        let Some(json) = generator.named(variant.name(), &schema, false) else {
+                            continue;
+                        };
+                        let value = match Type::from_json(variant, json.to_string().as_bytes()) {
+                            Ok(value) => value,
+                            Err(e) => {
+                                last_error = e.to_string();
+                                continue;
+                            }
+                        };
+                        let encoded = match value.to_xdr_base64(Limits::none()) {
+                            Ok(encoded) => encoded,
+                            Err(e) => {
+                                last_error = e.to_string();
+                                continue;
+                            }
+                        };
+                        match Type::from_xdr_base64(variant, &encoded, Limits::none()) {
+                            Ok(decoded) if decoded == value => {}
+                            Ok(_) => {
+                                last_error = "value changes when encoded".to_string();
+                                continue;
+                            }
+                            Err(e) => {
+                                last_error = e.to_string();
+                                continue;
+                            }
+                        }
+                        return Ok(match self.output {
+                            OutputFormat::Base64 => encoded,
+                            OutputFormat::Json => serde_json::to_string(&value)?,
+                        });
+                    }
+                    Err(Error::Invalid(self.r#type.clone(), last_error))
+                })
+                .collect()
+        }
+    };
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let seed = self.seed.unwrap_or_else(|| {
+            let seed = rand::random();
+            print.infoln(format!(
+                "Using seed {seed}, pass --seed {seed} to generate the same values"
+            ));
+            seed
+        });
+        let mut rng = StdRng::seed_from_u64(seed);
+        let values = match channel {
+            Channel::Curr => self.generate_curr(&mut rng)?,
+            Channel::Next => self.generate_next(&mut rng)?,
+        };
+        for value in values {
+            println!("{value}");
+        }
+        Ok(())
+    }
+
+    generate_x!(generate_curr, curr);
+    generate_x!(generate_next, next);
+}
+
+/// Generates JSON values that follow the JSON schema of an XDR type.
+struct Generator<'a> {
+    definitions: &'a Map<String, Value>,
+    rng: &'a mut StdRng,
+    max_depth: usize,
+    max_len: usize,
+    /// Names of the definitions being generated, outermost first.
+    path: Vec<&'a str>,
+}
+
+impl<'a> Generator<'a> {
+    /// A value of the definition `name`. `None` if it nests too deeply.
+    ///
+    /// Once a definition nests in itself `max_depth` times, the value is `limited`: unions pick
+    /// the arms that do not refer to other definitions, options are null, and arrays are as short
+    /// as they can be.
+    fn named(&mut self, name: &'a str, schema: &'a Value, limited: bool) -> Option<Value> {
+        if let Some(s) = self.string(name) {
+            return Some(Value::String(s));
+        }
+        if self.path.len() >= MAX_NESTING {
+            return None;
+        }
+        let limited = limited || self.path.iter().filter(|n| **n == name).count() >= self.max_depth;
+        self.path.push(name);
+        let value = self.value(schema, limited);
+        self.path.pop();
+        value
+    }
+
+    fn value(&mut self, schema: &'a Value, limited: bool) -> Option<Value> {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix("#/definitions/"))
+        {
+            let definition = self.definitions.get(name)?;
+            return self.named(name, definition, limited);
+        }
+        if let Some(arms) = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+        {
+            let leaves = arms.iter().filter(|a| !has_ref(a)).collect::<Vec<_>>();
+            let arm = if limited && !leaves.is_empty() {
+                *leaves.choose(self.rng)?
+            } else {
+                arms.choose(self.rng)?
+            };
+            return self.value(arm, limited);
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return values.choose(self.rng).cloned();
+        }
+        let ty = match schema.get("type") {
+            Some(Value::Array(types)) if limited && types.contains(&"null".into()) => "null",
+            Some(Value::Array(types)) => types.choose(self.rng)?.as_str()?,
+            Some(Value::String(ty)) => ty,
+            _ => "null",
+        };
+        Some(match ty {
+            "boolean" => Value::Bool(self.rng.gen()),
+            "integer" => self.integer(schema.get("format").and_then(Value::as_str)),
+            "string" => self.unnamed_string(schema),
+            "array" => {
+                let (min, max) = self.len(schema, "minItems", "maxItems", 1);
+                let len = if limited {
+                    min
+                } else {
+                    self.rng.gen_range(min..=max)
+                };
+                let items = schema.get("items")?;
+                Value::Array(
+                    (0..len)
+                        .map(|_| self.value(items, limited))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            "object" => {
+                let mut object = Map::new();
+                if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                    for (name, property) in properties {
+                        object.insert(name.clone(), self.value(property, limited)?);
+                    }
+                }
+                Value::Object(object)
+            }
+            _ => Value::Null,
+        })
+    }
+
+    /// Integers of the format, with the edge values of the format a quarter of the time.
+    fn integer(&mut self, format: Option<&str>) -> Value {
+        let (min, max) = match format {
+            Some("uint8") => (0, i128::from(u8::MAX)),
+            Some("int32") => (i128::from(i32::MIN), i128::from(i32::MAX)),
+            Some("uint32") => (0, i128::from(u32::MAX)),
+            Some("int64") => (i128::from(i64::MIN), i128::from(i64::MAX)),
+            _ => (0, i128::from(u64::MAX)),
+        };
+        let n = if self.rng.gen_bool(0.25) {
+            let edges = [min, max, 0, 1, -1]
+                .into_iter()
+                .filter(|n| (min..=max).contains(n))
+                .collect::<Vec<_>>();
+            edges.choose(self.rng).copied().unwrap_or_default()
+        } else {
+            self.rng.gen_range(min..=max)
+        };
+        i64::try_from(n).map_or_else(|_| Value::from(u64::try_from(n).unwrap_or(0)), Value::from)
+    }
+
+    /// Hex for opaques, and letters, digits, and underscores for strings, which are valid symbols.
+    fn unnamed_string(&mut self, schema: &Value) -> Value {
+        if schema.get("contentEncoding").and_then(Value::as_str) == Some("hex") {
+            let (min, max) = self.len(schema, "minLength", "maxLength", 2);
+            let len = self.rng.gen_range(min..=max);
+            let bytes = (0..len).map(|_| self.rng.gen()).collect::<Vec<u8>>();
+            return Value::String(hex::encode(bytes));
+        }
+        let (min, max) = self.len(schema, "minLength", "maxLength", 1);
+        let len = self.rng.gen_range(min..=max);
+        Value::String(
+            (0..len)
+                .map(|_| {
+                    if self.rng.gen_ratio(1, 16) {
+                        '_'
+                    } else {
+                        char::from(self.rng.sample(Alphanumeric))
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// The bounds of the length of an array or string, with variable lengths capped at
+    /// `max_len`. `unit` is the number of characters per item, 2 for hex.
+    fn len(&self, schema: &Value, min: &str, max: &str, unit: u64) -> (usize, usize) {
+        let bound = |key: &str| {
+            schema
+                .get(key)
+                .and_then(Value::as_u64)
+                .map(|n| usize::try_from(n / unit).unwrap_or(usize::MAX))
+        };
+        let min = bound(min).unwrap_or(0);
+        let max = bound(max).unwrap_or(usize::MAX);
+        if min == max {
+            (min, max)
+        } else {
+            (min, max.min(min.max(self.max_len)))
+        }
+    }
+
+    /// Definitions that are strings in a format the schema does not describe, such as strkeys.
+    fn string(&mut self, name: &str) -> Option<String> {
+        let rng = &mut *self.rng;
+        let g = |rng: &mut StdRng| Strkey::PublicKeyEd25519(ed25519::PublicKey(rng.gen()));
+        let m = |rng: &mut StdRng| {
+            Strkey::MuxedAccountEd25519(ed25519::MuxedAccount {
+                ed25519: rng.gen(),
+                id: rng.gen(),
+            })
+        };
+        let p = |rng: &mut StdRng| {
+            let len = rng.gen_range(0..=64);
+            Strkey::SignedPayloadEd25519(ed25519::SignedPayload {
+                ed25519: rng.gen(),
+                payload: (0..len).map(|_| rng.gen()).collect(),
+            })
+        };
+        let strkey = match name {
+            "AccountId" | "NodeId" | "PublicKey" => g(rng),
+            "MuxedAccount" | "ScAddress" if rng.gen() => g(rng),
+            "MuxedAccount" | "MuxedAccountMed25519" => m(rng),
+            "ScAddress" => Strkey::Contract(Contract(rng.gen())),
+            "SignerKey" => match rng.gen_range(0..4) {
+                0 => g(rng),
+                1 => Strkey::PreAuthTx(PreAuthTx(rng.gen())),
+                2 => Strkey::HashX(HashX(rng.gen())),
+                _ => p(rng),
+            },
+            "SignerKeyEd25519SignedPayload" => p(rng),
+            "ClaimableBalanceId" => {
+                return Some(format!("00000000{}", hex::encode(rng.gen::<[u8; 32]>())))
+            }
+            "AssetCode4" => return Some(asset_code(rng, 1..=4)),
+            "AssetCode12" => return Some(asset_code(rng, 5..=12)),
+            "AssetCode" => return Some(asset_code(rng, 1..=12)),
+            _ => return None,
+        };
+        Some(strkey.to_string())
+    }
+}
+
+fn asset_code(rng: &mut StdRng, len: std::ops::RangeInclusive<usize>) -> String {
+    let len = rng.gen_range(len);
+    Alphanumeric.sample_string(rng, len)
+}
+
+/// Whether the schema refers to a definition, which may nest without end.
+fn has_ref(schema: &Value) -> bool {
+    match schema {
+        Value::Object(object) => object.contains_key("$ref") || object.values().any(has_ref),
+        Value::Array(values) => values.iter().any(has_ref),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cmd(r#type: &str, output: OutputFormat) -> Cmd {
+        Cmd {
+            r#type: r#type.to_string(),
+            seed: None,
+            count: 20,
+            max_depth: 3,
+            max_len: 4,
+            output,
+        }
+    }
+
+    #[test]
+    fn generates_the_same_valid_values_for_a_seed() {
+        use stellar_xdr::curr::{Limits, ReadXdr, ScVal, TransactionEnvelope};
+        for output in [OutputFormat::Base64, OutputFormat::Json] {
+            for ty in ["ScVal", "TransactionEnvelope", "SorobanAuthorizationEntry"] {
+                let cmd = cmd(ty, output);
+                let values = cmd.generate_curr(&mut StdRng::seed_from_u64(1)).unwrap();
+                assert_eq!(values.len(), 20);
+                assert_eq!(
+                    values,
+                    cmd.generate_curr(&mut StdRng::seed_from_u64(1)).unwrap()
+                );
+                assert_ne!(
+                    values,
+                    cmd.generate_curr(&mut StdRng::seed_from_u64(2)).unwrap()
+                );
+            }
+        }
+        let values = cmd("ScVal", OutputFormat::Base64)
+            .generate_curr(&mut StdRng::seed_from_u64(1))
+            .unwrap();
+        for value in values {
+            ScVal::from_xdr_base64(value, Limits::none()).unwrap();
+        }
+        let values = cmd("TransactionEnvelope", OutputFormat::Json)
+            .generate_curr(&mut StdRng::seed_from_u64(1))
+            .unwrap();
+        for value in values {
+            serde_json::from_str::<TransactionEnvelope>(&value).unwrap();
+        }
+    }
+}
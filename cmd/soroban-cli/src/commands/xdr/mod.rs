@@ -0,0 +1,57 @@
+use clap::Parser;
+use stellar_xdr::cli::{self as xdr, Channel};
+
+use crate::commands::global;
+
+pub mod generate;
+
+#[derive(Debug, Parser)]
+#[command(disable_help_subcommand = true, infer_subcommands = true)]
+pub struct Root {
+    /// Channel of XDR to operate on
+    #[arg(value_enum, default_value_t)]
+    channel: Channel,
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    #[command(flatten)]
+    Xdr(xdr::Cmd),
+    /// Generate random but valid XDR values of a type, e.g. as a corpus to test SDKs and
+    /// decoders with
+    Generate(generate::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Generate(#[from] generate::Error),
+}
+
+impl Root {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let channel = &self.channel;
+        match &self.cmd {
+            Cmd::Xdr(cmd) => match cmd {
+                xdr::Cmd::Types(c) => c.run(channel).map_err(xdr::Error::from)?,
+                xdr::Cmd::Guess(c) => c.run(channel).map_err(xdr::Error::from)?,
+                xdr::Cmd::Decode(c) => c.run(channel).map_err(xdr::Error::from)?,
+                xdr::Cmd::Encode(c) => c.run(channel).map_err(xdr::Error::from)?,
+                xdr::Cmd::Compare(c) => c.run(channel).map_err(xdr::Error::from)?,
+                xdr::Cmd::Version => {
+                    let v = stellar_xdr::VERSION;
+                    println!(
+                        "stellar-xdr {} ({})\nxdr (+curr): {}\nxdr (+next): {}",
+                        v.pkg, v.rev, v.xdr_curr, v.xdr_next
+                    );
+                }
+            },
+            Cmd::Generate(c) => c.run(channel, global_args)?,
+        }
+        Ok(())
+    }
+}
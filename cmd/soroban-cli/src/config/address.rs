@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
-use crate::xdr;
+use crate::{sep::federation, xdr};
 
 use super::{locator, secret};
 
-/// Address can be either a public key or eventually an alias of a address.
+/// Address can be either a public key, a federation address (`name*domain.com`), or an alias or
+/// address book label of an address.
 #[derive(Clone, Debug)]
 pub enum Address {
     MuxedAccount(xdr::MuxedAccount),
@@ -23,6 +24,8 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error(transparent)]
     Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Federation(#[from] federation::Error),
     #[error("Address cannot be used to sign {0}")]
     CannotSign(xdr::MuxedAccount),
 }
@@ -44,13 +47,37 @@ impl Address {
         locator: &locator::Args,
         hd_path: Option<usize>,
     ) -> Result<xdr::MuxedAccount, Error> {
+        Ok(self.resolve_muxed_account_and_memo(locator, hd_path)?.0)
+    }
+
+    /// Like `resolve_muxed_account`, also returning the memo that transactions to the account
+    /// must carry, required by the federation record of a federation address, e.g. the deposit
+    /// memo of an exchange.
+    pub fn resolve_muxed_account_and_memo(
+        &self,
+        locator: &locator::Args,
+        hd_path: Option<usize>,
+    ) -> Result<(xdr::MuxedAccount, Option<xdr::Memo>), Error> {
         match self {
-            Address::MuxedAccount(muxed_account) => Ok(muxed_account.clone()),
-            Address::AliasOrSecret(alias) => alias.parse().or_else(|_| {
-                Ok(xdr::MuxedAccount::Ed25519(
-                    locator.read_identity(alias)?.public_key(hd_path)?.0.into(),
-                ))
-            }),
+            Address::MuxedAccount(muxed_account) => Ok((muxed_account.clone(), None)),
+            Address::AliasOrSecret(alias) if federation::is_federation_address(alias) => {
+                let record = federation::resolve_blocking(alias)?;
+                Ok((record.muxed_account()?, record.memo()?))
+            }
+            Address::AliasOrSecret(alias) => Ok((
+                alias.parse().or_else(|_| -> Result<_, Error> {
+                    match locator.read_identity(alias) {
+                        Ok(secret) => Ok(xdr::MuxedAccount::Ed25519(
+                            secret.public_key(hd_path)?.0.into(),
+                        )),
+                        Err(e) => locator
+                            .read_label(alias)?
+                            .and_then(|address| address.parse().ok())
+                            .ok_or(e.into()),
+                    }
+                })?,
+                None,
+            )),
         }
     }
 
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::locator;
+
+/// A labelled address of the address book, `addressbook/<label>.toml` in the config directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub address: String,
+}
+
+/// The labels of the address book, shown instead of the addresses in human-readable output.
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    /// Label of each address.
+    labels: HashMap<String, String>,
+}
+
+impl Book {
+    /// The local and global address book, local labels first. Output is only less readable
+    /// without it, so it is empty if it cannot be read.
+    pub fn load(locator: &locator::Args) -> Self {
+        let mut labels = HashMap::new();
+        match locator.list_labels() {
+            Ok(entries) => {
+                for (label, Entry { address }) in entries {
+                    labels.entry(address).or_insert(label);
+                }
+            }
+            Err(e) => tracing::debug!("cannot read the address book: {e}"),
+        }
+        Book { labels }
+    }
+
+    pub fn label(&self, address: &str) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// `text` with the addresses of the book replaced by their labels.
+    pub fn substitute(&self, text: &str) -> String {
+        if self.labels.is_empty() {
+            return text.to_string();
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        // Addresses are strkeys, runs of uppercase base32 characters.
+        while let Some(start) = rest.find(is_strkey_char) {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest.find(|c| !is_strkey_char(c)).unwrap_or(rest.len());
+            let run = &rest[..end];
+            out.push_str(self.label(run).unwrap_or(run));
+            rest = &rest[end..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+fn is_strkey_char(c: char) -> bool {
+    c.is_ascii_uppercase() || ('2'..='7').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_whole_addresses() {
+        let alice = "GBKZTN6VP5HGEDUQEOFSW76DWQDPX5SVJIJR2J5I6EM3C7LDTHALAMY7";
+        let token = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+        let book = Book {
+            labels: HashMap::from([
+                (alice.to_string(), "alice".to_string()),
+                (token.to_string(), "usdc".to_string()),
+            ]),
+        };
+        assert_eq!(
+            book.substitute(&format!("{{\"from\":\"{alice}\",\"to\":\"{token}\"}} 2 OK")),
+            "{\"from\":\"alice\",\"to\":\"usdc\"} 2 OK"
+        );
+        // A longer strkey starting with a known one is another address
+        let muxed = format!("{alice}AAAAAAAAAAAAA");
+        assert_eq!(book.substitute(&muxed), muxed);
+        assert_eq!(Book::default().substitute(alice), alice);
+    }
+}
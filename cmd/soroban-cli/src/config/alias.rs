@@ -9,7 +9,8 @@ pub struct Data {
     pub ids: HashMap<String, String>,
 }
 
-/// Address can be either a contract address, C.. or eventually an alias of a contract address.
+/// Address can be either a contract address, C.., an alias of a contract address, or an address
+/// book label of one.
 #[derive(Clone, Debug)]
 pub enum ContractAddress {
     ContractId(stellar_strkey::Contract),
@@ -41,9 +42,15 @@ impl ContractAddress {
     ) -> Result<stellar_strkey::Contract, locator::Error> {
         match self {
             ContractAddress::ContractId(muxed_account) => Ok(*muxed_account),
-            ContractAddress::Alias(alias) => locator
-                .get_contract_id(alias, network_passphrase)?
-                .ok_or_else(|| locator::Error::ContractNotFound(alias.to_owned())),
+            ContractAddress::Alias(alias) => {
+                match locator.get_contract_id(alias, network_passphrase)? {
+                    Some(contract) => Ok(contract),
+                    None => locator
+                        .read_label(alias)?
+                        .and_then(|address| address.parse().ok())
+                        .ok_or_else(|| locator::Error::ContractNotFound(alias.to_owned())),
+                }
+            }
         }
     }
 }
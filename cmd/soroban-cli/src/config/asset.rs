@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use crate::{
+    tx::builder,
+    xdr::{self, AlphaNum12, AlphaNum4, AssetCode},
+};
+
+use super::{address, locator};
+
+/// Asset can be `native` or `CODE:ISSUER`, where the issuer is a public key or the alias of an
+/// identity, e.g. `USDC:issuer`.
+#[derive(Clone, Debug)]
+pub enum Asset {
+    Asset(builder::Asset),
+    IssuerAlias {
+        code: String,
+        issuer: address::Address,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Builder(#[from] builder::asset::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+impl FromStr for Asset {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match builder::Asset::from_str(value) {
+            Ok(asset) => Ok(Asset::Asset(asset)),
+            Err(e) => {
+                let Some((code, issuer)) = value.split_once(':') else {
+                    return Err(e.into());
+                };
+                Ok(Asset::IssuerAlias {
+                    code: code.to_string(),
+                    issuer: issuer.parse()?,
+                })
+            }
+        }
+    }
+}
+
+impl Asset {
+    pub fn resolve(
+        &self,
+        locator: &locator::Args,
+        hd_path: Option<usize>,
+    ) -> Result<builder::Asset, Error> {
+        let (code, issuer) = match self {
+            Asset::Asset(asset) => return Ok(asset.clone()),
+            Asset::IssuerAlias { code, issuer } => (code, issuer),
+        };
+        let issuer = match issuer.resolve_muxed_account(locator, hd_path)? {
+            xdr::MuxedAccount::Ed25519(key) => key,
+            xdr::MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519,
+        };
+        let issuer = xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(issuer));
+        Ok(builder::Asset(match code.parse()? {
+            AssetCode::CreditAlphanum4(asset_code) => {
+                xdr::Asset::CreditAlphanum4(AlphaNum4 { asset_code, issuer })
+            }
+            AssetCode::CreditAlphanum12(asset_code) => {
+                xdr::Asset::CreditAlphanum12(AlphaNum12 { asset_code, issuer })
+            }
+        }))
+    }
+}
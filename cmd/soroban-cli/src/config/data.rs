@@ -1,10 +1,17 @@
 use crate::rpc::{GetTransactionResponse, GetTransactionResponseRaw, SimulateTransactionResponse};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use sha2::{Digest, Sha256};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 use url::Url;
 
-use crate::xdr::{self, WriteXdr};
+use crate::xdr::{self, ReadXdr, WriteXdr};
+
+use super::locator;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -20,10 +27,19 @@ pub enum Error {
     Ulid(#[from] ulid::DecodeError),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error("invalid {STELLAR_CACHE_TTL} {0:?}: {1}")]
+    InvalidCacheTtl(String, humantime::DurationError),
 }
 
 pub const XDG_DATA_HOME: &str = "XDG_DATA_HOME";
 
+pub const STELLAR_CACHE_TTL: &str = "STELLAR_CACHE_TTL";
+
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Results of reads are only reused within a ledger, so older ones are removed.
+const INVOKE_RESULT_TTL: Duration = Duration::from_secs(60);
+
 pub fn project_dir() -> Result<directories::ProjectDirs, Error> {
     std::env::var(XDG_DATA_HOME)
         .map_or_else(
@@ -56,6 +72,154 @@ pub fn bucket_dir() -> Result<std::path::PathBuf, Error> {
     Ok(dir)
 }
 
+/// Wasm fetched from networks, by hash.
+pub fn wasm_dir() -> Result<PathBuf, Error> {
+    let dir = data_local_dir()?.join("wasm");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Passphrases of the networks of RPC servers, by RPC URL, expiring after the [`cache_ttl`].
+pub fn network_passphrase_dir() -> Result<PathBuf, Error> {
+    let dir = data_local_dir()?.join("network-passphrase");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Versions of RPC servers, by RPC URL, expiring after the [`cache_ttl`].
+pub fn rpc_version_dir() -> Result<PathBuf, Error> {
+    let dir = data_local_dir()?.join("rpc-version");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Return values of read-only invocations, by network, contract, function, arguments, and
+/// ledger.
+pub fn invoke_result_dir() -> Result<PathBuf, Error> {
+    let dir = data_local_dir()?.join("invoke-result");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Directories of everything fetched from networks, as opposed to the actions taken.
+pub fn fetched_dirs() -> Result<Vec<PathBuf>, Error> {
+    let dir = data_local_dir()?;
+    Ok([
+        "spec",
+        "wasm",
+        "network-passphrase",
+        "rpc-version",
+        "invoke-result",
+    ]
+    .into_iter()
+    .map(|name| dir.join(name))
+    .collect())
+}
+
+/// How long fetched values that can change on the network are cached for, set with
+/// `STELLAR_CACHE_TTL`, e.g. `10m`. One hour by default.
+pub fn cache_ttl() -> Result<Duration, Error> {
+    std::env::var(STELLAR_CACHE_TTL).map_or(Ok(DEFAULT_CACHE_TTL), |ttl| {
+        humantime::parse_duration(&ttl).map_err(|e| Error::InvalidCacheTtl(ttl, e))
+    })
+}
+
+pub fn write_wasm(hash: &str, wasm: &[u8]) -> Result<(), Error> {
+    locator::write_atomic(&wasm_dir()?.join(hash), wasm)?;
+    Ok(())
+}
+
+/// The Wasm with `hash`, if fetched before and intact.
+pub fn read_wasm(hash: &str) -> Result<Option<Vec<u8>>, Error> {
+    let file = wasm_dir()?.join(hash);
+    if !file.exists() {
+        return Ok(None);
+    }
+    let wasm = std::fs::read(file)?;
+    Ok((hex::encode(Sha256::digest(&wasm)) == hash).then_some(wasm))
+}
+
+pub fn write_network_passphrase(rpc_url: &str, network_passphrase: &str) -> Result<(), Error> {
+    locator::write_atomic(
+        &network_passphrase_dir()?.join(short_hash(rpc_url)),
+        network_passphrase,
+    )?;
+    Ok(())
+}
+
+pub fn read_network_passphrase(rpc_url: &str) -> Result<Option<String>, Error> {
+    read_fresh(&network_passphrase_dir()?.join(short_hash(rpc_url)))
+}
+
+pub fn write_rpc_version(rpc_url: &str, version_info: &str) -> Result<(), Error> {
+    locator::write_atomic(&rpc_version_dir()?.join(short_hash(rpc_url)), version_info)?;
+    Ok(())
+}
+
+pub fn read_rpc_version(rpc_url: &str) -> Result<Option<String>, Error> {
+    read_fresh(&rpc_version_dir()?.join(short_hash(rpc_url)))
+}
+
+/// The contents of `file` if it was written within the [`cache_ttl`].
+fn read_fresh(file: &Path) -> Result<Option<String>, Error> {
+    let Ok(modified) = std::fs::metadata(file).and_then(|m| m.modified()) else {
+        return Ok(None);
+    };
+    let ttl = cache_ttl()?;
+    if modified.elapsed().map_or(true, |age| age > ttl) {
+        return Ok(None);
+    }
+    Ok(Some(std::fs::read_to_string(file)?))
+}
+
+/// The key of the result of invoking a contract with `args` on the network of
+/// `network_passphrase` at `ledger`.
+pub fn invoke_result_key(
+    network_passphrase: &str,
+    args: &xdr::InvokeContractArgs,
+    ledger: u32,
+) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    hasher.update(network_passphrase);
+    hasher.update(args.to_xdr(xdr::Limits::none())?);
+    hasher.update(ledger.to_be_bytes());
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Save the return value of a read, removing the results of reads of past ledgers.
+pub fn write_invoke_result(key: &str, value: &xdr::ScVal) -> Result<(), Error> {
+    let dir = invoke_result_dir()?;
+    for entry in std::fs::read_dir(&dir)?.filter_map(Result::ok) {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > INVOKE_RESULT_TTL);
+        if expired {
+            // Another command may be removing the same result.
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    locator::write_atomic(&dir.join(key), to_xdr(value)?)?;
+    Ok(())
+}
+
+pub fn read_invoke_result(key: &str) -> Result<Option<xdr::ScVal>, Error> {
+    let file = invoke_result_dir()?.join(key);
+    if !file.exists() {
+        return Ok(None);
+    }
+    Ok(Some(xdr::ScVal::from_xdr_base64(
+        std::fs::read_to_string(file)?,
+        xdr::Limits::none(),
+    )?))
+}
+
+fn short_hash(s: &str) -> String {
+    hex::encode(&Sha256::digest(s)[..8])
+}
+
 pub fn write(action: Action, rpc_url: &Url) -> Result<ulid::Ulid, Error> {
     let data = Data {
         action,
@@ -63,7 +227,7 @@ pub fn write(action: Action, rpc_url: &Url) -> Result<ulid::Ulid, Error> {
     };
     let id = ulid::Ulid::new();
     let file = actions_dir()?.join(id.to_string()).with_extension("json");
-    std::fs::write(file, serde_json::to_string(&data)?)?;
+    locator::write_atomic(&file, serde_json::to_string(&data)?)?;
     Ok(id)
 }
 
@@ -80,7 +244,7 @@ pub fn write_spec(hash: &str, spec_entries: &[xdr::ScSpecEntry]) -> Result<(), E
     for entry in spec_entries {
         contents.extend(entry.to_xdr(xdr::Limits::none())?);
     }
-    std::fs::write(file, contents)?;
+    locator::write_atomic(&file, contents)?;
     Ok(())
 }
 
@@ -214,4 +378,40 @@ mod test {
             _ => panic!("Action mismatch"),
         }
     }
+
+    #[test]
+    fn invoke_results_are_keyed_by_ledger() {
+        let args = xdr::InvokeContractArgs {
+            contract_address: xdr::ScAddress::Contract(xdr::Hash([1; 32])),
+            function_name: "balance".try_into().unwrap(),
+            args: xdr::VecM::default(),
+        };
+        let passphrase = "Test SDF Network ; September 2015";
+        let key = invoke_result_key(passphrase, &args, 10).unwrap();
+        assert_eq!(key, invoke_result_key(passphrase, &args, 10).unwrap());
+        assert_ne!(key, invoke_result_key(passphrase, &args, 11).unwrap());
+        assert_ne!(
+            key,
+            invoke_result_key("Public Global Stellar Network ; September 2015", &args, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn fetched_values_expire() {
+        let t = assert_fs::TempDir::new().unwrap();
+        let file = t.path().join("passphrase");
+        std::fs::write(&file, "Test SDF Network ; September 2015").unwrap();
+        assert_eq!(
+            read_fresh(&file).unwrap().as_deref(),
+            Some("Test SDF Network ; September 2015")
+        );
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - 2 * DEFAULT_CACHE_TTL)
+            .unwrap();
+        assert_eq!(read_fresh(&file).unwrap(), None);
+        assert_eq!(read_fresh(&t.path().join("missing")).unwrap(), None);
+    }
 }
@@ -1,21 +1,24 @@
 use clap::arg;
 use directories::UserDirs;
+use fs2::FileExt;
 use itertools::Itertools;
 use serde::de::DeserializeOwned;
 use std::{
     ffi::OsStr,
     fmt::Display,
-    fs::{self, create_dir_all, OpenOptions},
-    io::{self, Write},
+    fs::{self, create_dir_all, File, OpenOptions},
+    io,
     path::{Path, PathBuf},
     str::FromStr,
+    thread,
+    time::{Duration, Instant},
 };
 use stellar_strkey::{Contract, DecodeError};
 
 use crate::{commands::HEADING_GLOBAL, utils::find_config_dir, Pwd};
 
 use super::{
-    alias,
+    addressbook, alias,
     network::{self, Network},
     secret::Secret,
     Config,
@@ -83,8 +86,15 @@ pub enum Error {
     UpgradeCheckReadFailed { path: PathBuf, error: io::Error },
     #[error("Failed to write upgrade check file: {path}: {error}")]
     UpgradeCheckWriteFailed { path: PathBuf, error: io::Error },
+    #[error("Timed out after {timeout:?} waiting for another command to finish writing {path}")]
+    LockTimeout { path: PathBuf, timeout: Duration },
+    #[error("Failed to lock {path}: {error}")]
+    Lock { path: PathBuf, error: io::Error },
 }
 
+/// How long to wait for another command to finish writing a config file by default.
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, clap::Args, Default, Clone)]
 #[group(skip)]
 pub struct Args {
@@ -95,6 +105,17 @@ pub struct Args {
     /// Location of config directory, default is "."
     #[arg(long, global = true, help_heading = HEADING_GLOBAL)]
     pub config_dir: Option<PathBuf>,
+
+    /// How long to wait for another command to finish writing a config file, e.g. `30s`,
+    /// default is 10s
+    #[arg(
+        long,
+        global = true,
+        help_heading = HEADING_GLOBAL,
+        env = "STELLAR_CONFIG_LOCK_TIMEOUT",
+        value_parser = humantime::parse_duration,
+    )]
+    pub config_lock_timeout: Option<Duration>,
 }
 
 pub enum Location {
@@ -162,20 +183,52 @@ impl Args {
         )
     }
 
+    pub fn lock_timeout(&self) -> Duration {
+        self.config_lock_timeout.unwrap_or(DEFAULT_LOCK_TIMEOUT)
+    }
+
     pub fn write_identity(&self, name: &str, secret: &Secret) -> Result<(), Error> {
-        KeyType::Identity.write(name, secret, &self.config_dir()?)
+        KeyType::Identity.write(name, secret, &self.config_dir()?, self.lock_timeout())
     }
 
     pub fn write_network(&self, name: &str, network: &Network) -> Result<(), Error> {
-        KeyType::Network.write(name, network, &self.config_dir()?)
+        KeyType::Network.write(name, network, &self.config_dir()?, self.lock_timeout())
     }
 
     pub fn write_default_network(&self, name: &str) -> Result<(), Error> {
-        Config::new()?.set_network(name).save()
+        self.update_config(|config| config.set_network(name))
     }
 
     pub fn write_default_identity(&self, name: &str) -> Result<(), Error> {
-        Config::new()?.set_identity(name).save()
+        self.update_config(|config| config.set_identity(name))
+    }
+
+    pub fn write_default_fee_strategy(&self, strategy: &str) -> Result<(), Error> {
+        self.update_config(|config| config.set_fee_strategy(strategy))
+    }
+
+    pub fn write_mainnet_confirm(&self, confirm: bool) -> Result<(), Error> {
+        self.update_config(|config| config.set_mainnet_confirm(confirm))
+    }
+
+    pub fn write_upgrade_check(&self, check: bool) -> Result<(), Error> {
+        self.update_config(|config| config.set_upgrade_check(check))
+    }
+
+    /// The global config file with the settings of the local config directory's `config.toml`
+    /// taking precedence, unless `--global`.
+    pub fn read_config(&self) -> Result<Config, Error> {
+        let config = Config::new()?;
+        if self.global {
+            return Ok(config);
+        }
+        Ok(config.overlay(Config::read(self.local_config()?.join("config.toml"))?))
+    }
+
+    /// Read, change, and save the config file, without another command saving it in between.
+    fn update_config(&self, update: impl FnOnce(Config) -> Config) -> Result<(), Error> {
+        let _lock = FileLock::acquire(&config_file()?, self.lock_timeout())?;
+        update(Config::new()?).save()
     }
 
     pub fn list_identities(&self) -> Result<Vec<String>, Error> {
@@ -200,6 +253,18 @@ impl Args {
             .collect())
     }
 
+    /// Identities with their secrets and whether they are local or global, local ones first.
+    pub fn list_identities_with_secrets(&self) -> Result<Vec<(String, Secret, Location)>, Error> {
+        Ok(KeyType::Identity
+            .list_paths(&self.local_and_global()?)?
+            .into_iter()
+            .filter_map(|(name, location)| {
+                let secret = KeyType::read_from_path(location.as_ref()).ok()?;
+                Some((name, secret, location))
+            })
+            .collect())
+    }
+
     pub fn list_networks(&self) -> Result<Vec<String>, Error> {
         let saved_networks = KeyType::Network
             .list_paths(&self.local_and_global()?)
@@ -257,10 +322,53 @@ impl Args {
         KeyType::Identity.remove(name, &self.config_dir()?)
     }
 
+    /// Whether the identity `name` is in the config directory, as opposed to only in the global
+    /// one when the config directory is local.
+    pub fn identity_exists(&self, name: &str) -> Result<bool, Error> {
+        Ok(KeyType::Identity.path(&self.config_dir()?, name).exists())
+    }
+
+    /// Copy the identity `from` to `to` in the config directory.
+    pub fn copy_identity(&self, from: &str, to: &str) -> Result<(), Error> {
+        let dir = self.config_dir()?;
+        let secret: Secret = KeyType::Identity
+            .read(from, &dir)
+            .map_err(|_| Error::ConfigMissing(KeyType::Identity.to_string(), from.to_string()))?;
+        KeyType::Identity.write(to, &secret, &dir, self.lock_timeout())
+    }
+
     pub fn remove_network(&self, name: &str) -> Result<(), Error> {
         KeyType::Network.remove(name, &self.config_dir()?)
     }
 
+    pub fn write_label(&self, label: &str, entry: &addressbook::Entry) -> Result<(), Error> {
+        KeyType::Label.write(label, entry, &self.config_dir()?, self.lock_timeout())
+    }
+
+    /// The address of `label` in the local or global address book, if it has one.
+    pub fn read_label(&self, label: &str) -> Result<Option<String>, Error> {
+        match KeyType::Label.read_with_global::<addressbook::Entry>(label, &self.local_config()?) {
+            Ok(entry) => Ok(Some(entry.address)),
+            Err(Error::ConfigMissing(_, _)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The labels of the local and global address book, local ones first.
+    pub fn list_labels(&self) -> Result<Vec<(String, addressbook::Entry)>, Error> {
+        Ok(KeyType::Label
+            .list_paths(&self.local_and_global()?)?
+            .into_iter()
+            .filter_map(|(label, location)| {
+                Some((label, KeyType::read_from_path(location.as_ref()).ok()?))
+            })
+            .collect())
+    }
+
+    pub fn remove_label(&self, label: &str) -> Result<(), Error> {
+        KeyType::Label.remove(label, &self.config_dir()?)
+    }
+
     fn load_contract_from_alias(&self, alias: &str) -> Result<Option<alias::Data>, Error> {
         let path = self.alias_path(alias)?;
 
@@ -274,6 +382,29 @@ impl Args {
         Ok(Some(data))
     }
 
+    /// The contract aliases of the config directory, sorted by name, with their ID on each
+    /// network.
+    pub fn list_contract_aliases(&self) -> Result<Vec<(String, alias::Data)>, Error> {
+        let dir = self.config_dir()?.join("contract-ids");
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut aliases = Vec::new();
+        for entry in fs::read_dir(dir)?.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.extension().and_then(OsStr::to_str) != Some("json") {
+                continue;
+            }
+            let Some(name) = path.file_stem() else {
+                continue;
+            };
+            let data = serde_json::from_str(&fs::read_to_string(&path)?).unwrap_or_default();
+            aliases.push((name.to_string_lossy().into_owned(), data));
+        }
+        aliases.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(aliases)
+    }
+
     fn alias_path(&self, alias: &str) -> Result<PathBuf, Error> {
         let file_name = format!("{alias}.json");
         let config_dir = self.config_dir()?;
@@ -291,21 +422,16 @@ impl Args {
 
         create_dir_all(dir).map_err(|_| Error::CannotAccessConfigDir)?;
 
+        let _lock = FileLock::acquire(&path, self.lock_timeout())?;
         let content = fs::read_to_string(&path).unwrap_or_default();
         let mut data: alias::Data = serde_json::from_str(&content).unwrap_or_default();
 
-        let mut to_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(path)?;
-
         data.ids
             .insert(network_passphrase.into(), contract_id.to_string());
 
         let content = serde_json::to_string(&data)?;
 
-        Ok(to_file.write_all(content.as_bytes())?)
+        Ok(write_atomic(&path, content)?)
     }
 
     pub fn remove_contract_id(&self, network_passphrase: &str, alias: &str) -> Result<(), Error> {
@@ -315,20 +441,15 @@ impl Args {
             return Err(Error::CannotAccessAliasConfigFile);
         }
 
+        let _lock = FileLock::acquire(&path, self.lock_timeout())?;
         let content = fs::read_to_string(&path).unwrap_or_default();
         let mut data: alias::Data = serde_json::from_str(&content).unwrap_or_default();
 
-        let mut to_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(path)?;
-
         data.ids.remove::<str>(network_passphrase);
 
         let content = serde_json::to_string(&data)?;
 
-        Ok(to_file.write_all(content.as_bytes())?)
+        Ok(write_atomic(&path, content)?)
     }
 
     pub fn get_contract_id(
@@ -354,6 +475,12 @@ impl Args {
         network_passphrase: &str,
     ) -> Result<Contract, Error> {
         let Some(contract) = self.get_contract_id(alias_or_contract_id, network_passphrase)? else {
+            if let Some(contract) = self
+                .read_label(alias_or_contract_id)?
+                .and_then(|address| address.parse().ok())
+            {
+                return Ok(contract);
+            }
             return alias_or_contract_id
                 .parse()
                 .map_err(|e| Error::CannotParseContractId(alias_or_contract_id.to_owned(), e));
@@ -374,6 +501,72 @@ pub fn ensure_directory(dir: PathBuf) -> Result<PathBuf, Error> {
     Ok(dir)
 }
 
+/// An advisory lock on a config file, so that concurrent commands, e.g. parallel CI jobs
+/// sharing a config directory, take turns writing it. Released when dropped.
+///
+/// The lock is an OS file lock on a file next to the locked one, so it is released by the OS
+/// when the command holding it exits, even if it crashes or is killed. The lock file is left in
+/// place, as removing it would let another command lock a new file while one still holds the
+/// old one.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Lock `file`, waiting up to `timeout` for the command holding the lock to release it.
+    pub fn acquire(file: &Path, timeout: Duration) -> Result<Self, Error> {
+        let lock_error = |error| Error::Lock {
+            path: file.to_path_buf(),
+            error,
+        };
+        let mut path = file.as_os_str().to_owned();
+        path.push(".lock");
+        let path = ensure_directory(PathBuf::from(path))?;
+        let lock_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(lock_error)?;
+        let start = Instant::now();
+        loop {
+            match lock_file.try_lock_exclusive() {
+                Ok(()) => return Ok(Self { _file: lock_file }),
+                Err(e) if e.kind() == fs2::lock_contended_error().kind() => {
+                    if start.elapsed() >= timeout {
+                        return Err(Error::LockTimeout {
+                            path: file.to_path_buf(),
+                            timeout,
+                        });
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(error) => return Err(lock_error(error)),
+            }
+        }
+    }
+}
+
+/// Write `data` to a temporary file next to `path` and rename it over `path`, so that readers
+/// see either the previous or the new contents, never a truncated file. Keeps the permissions
+/// of the file being replaced.
+pub fn write_atomic(path: &Path, data: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    let result = fs::write(&tmp, data)
+        .and_then(|()| match fs::metadata(path) {
+            Ok(metadata) => fs::set_permissions(&tmp, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
 fn dir_creation_failed(p: &Path) -> Error {
     Error::DirCreationFailed {
         path: p.to_path_buf(),
@@ -398,6 +591,7 @@ fn read_dir(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
 pub enum KeyType {
     Identity,
     Network,
+    Label,
 }
 
 impl Display for KeyType {
@@ -408,6 +602,7 @@ impl Display for KeyType {
             match self {
                 KeyType::Identity => "identity",
                 KeyType::Network => "network",
+                KeyType::Label => "addressbook",
             }
         )
     }
@@ -441,10 +636,12 @@ impl KeyType {
         key: &str,
         value: &T,
         pwd: &Path,
+        lock_timeout: Duration,
     ) -> Result<(), Error> {
         let filepath = ensure_directory(self.path(pwd, key))?;
         let data = toml::to_string(value).map_err(|_| Error::ConfigSerialization)?;
-        std::fs::write(&filepath, data).map_err(|error| Error::IdCreationFailed { filepath, error })
+        let _lock = FileLock::acquire(&filepath, lock_timeout)?;
+        write_atomic(&filepath, data).map_err(|error| Error::IdCreationFailed { filepath, error })
     }
 
     fn root(&self, pwd: &Path) -> PathBuf {
@@ -490,15 +687,21 @@ impl KeyType {
     }
 }
 
-pub fn global_config_path() -> Result<PathBuf, Error> {
-    let config_dir = if let Ok(config_home) = std::env::var("XDG_CONFIG_HOME") {
-        PathBuf::from_str(&config_home).map_err(|_| Error::XdgConfigHome(config_home))?
+/// The directory holding the global `stellar` and legacy `soroban` config directories,
+/// `$XDG_CONFIG_HOME` or `~/.config`.
+pub fn config_home() -> Result<PathBuf, Error> {
+    if let Ok(config_home) = std::env::var("XDG_CONFIG_HOME") {
+        PathBuf::from_str(&config_home).map_err(|_| Error::XdgConfigHome(config_home))
     } else {
-        UserDirs::new()
+        Ok(UserDirs::new()
             .ok_or(Error::HomeDirNotFound)?
             .home_dir()
-            .join(".config")
-    };
+            .join(".config"))
+    }
+}
+
+pub fn global_config_path() -> Result<PathBuf, Error> {
+    let config_dir = config_home()?;
 
     let soroban_dir = config_dir.join("soroban");
     let stellar_dir = config_dir.join("stellar");
@@ -506,7 +709,7 @@ pub fn global_config_path() -> Result<PathBuf, Error> {
     let stellar_exists = stellar_dir.exists();
 
     if stellar_exists && soroban_exists {
-        tracing::warn!("the .stellar and .soroban config directories exist at path {config_dir:?}, using the .stellar");
+        tracing::warn!("the .stellar and .soroban config directories exist at path {config_dir:?}, using the .stellar, see `stellar config migrate`");
     }
 
     if stellar_exists {
@@ -523,3 +726,73 @@ pub fn global_config_path() -> Result<PathBuf, Error> {
 pub fn config_file() -> Result<PathBuf, Error> {
     Ok(global_config_path()?.join("config.toml"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_alias_writes_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let locator = Args {
+            global: false,
+            config_dir: Some(dir.path().to_path_buf()),
+            config_lock_timeout: None,
+        };
+        let contract_id = Contract([1; 32]);
+        thread::scope(|s| {
+            for i in 0..8 {
+                let locator = &locator;
+                s.spawn(move || {
+                    locator
+                        .save_contract_id(&format!("network {i}"), &contract_id, "token")
+                        .unwrap();
+                });
+            }
+        });
+        let (_, data) = locator.list_contract_aliases().unwrap().remove(0);
+        assert_eq!(data.ids.len(), 8);
+        // No temporary files are left behind, only the lock file of the aliases.
+        let files = fs::read_dir(dir.path().join(".stellar/contract-ids"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(files, ["token.json", "token.json.lock"]);
+    }
+
+    #[test]
+    fn lock_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("identity/alice.toml");
+        let lock = FileLock::acquire(&file, DEFAULT_LOCK_TIMEOUT).unwrap();
+        assert!(matches!(
+            FileLock::acquire(&file, Duration::from_millis(50)),
+            Err(Error::LockTimeout { .. })
+        ));
+        drop(lock);
+        FileLock::acquire(&file, Duration::ZERO).unwrap();
+    }
+
+    #[test]
+    fn local_config_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".stellar")).unwrap();
+        fs::write(
+            dir.path().join(".stellar/config.toml"),
+            "[safety]\nmainnet-confirm = false\n",
+        )
+        .unwrap();
+        let mut locator = Args {
+            global: false,
+            config_dir: Some(dir.path().to_path_buf()),
+            config_lock_timeout: None,
+        };
+        assert!(!locator.read_config().unwrap().safety.mainnet_confirm());
+        locator.global = true;
+        assert_eq!(
+            locator.read_config().unwrap().safety.mainnet_confirm,
+            Config::new().unwrap().safety.mainnet_confirm
+        );
+    }
+}
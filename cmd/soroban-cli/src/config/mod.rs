@@ -1,25 +1,28 @@
 use address::Address;
 use clap::{arg, command};
 use serde::{Deserialize, Serialize};
-use std::{
-    fs::{self, File},
-    io::Write,
-};
+use std::{fs, path::PathBuf};
 
 use crate::{
+    commands::global,
+    diagnostic, fee,
     print::Print,
+    rpc::{Client, GetTransactionResponse},
     signer::{self, LocalKey, Signer, SignerKind},
-    xdr::{self, SequenceNumber, Transaction, TransactionEnvelope},
+    xdr::{self, Hash, SequenceNumber, Transaction, TransactionEnvelope},
     Pwd,
 };
 use network::Network;
 
 pub mod address;
+pub mod addressbook;
 pub mod alias;
+pub mod asset;
 pub mod data;
 pub mod locator;
 pub mod network;
 pub mod secret;
+pub mod sequence;
 pub mod sign_with;
 pub mod upgrade_check;
 
@@ -41,6 +44,28 @@ pub enum Error {
     StellarStrkey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
     Address(#[from] address::Error),
+    #[error(transparent)]
+    Sequence(#[from] sequence::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Diagnostic(Box<diagnostic::Error>),
+}
+
+impl From<diagnostic::Error> for Error {
+    fn from(error: diagnostic::Error) -> Self {
+        Error::Diagnostic(Box::new(error))
+    }
+}
+
+/// Outcome of `Args::submit`.
+pub enum Submitted {
+    /// The signed transaction, built with `--dry-run` and not sent.
+    DryRun(Box<TransactionEnvelope>),
+    /// The transaction was sent with `--no-wait`, and its result is not known yet.
+    Sent(Hash),
+    /// The transaction was included in a ledger.
+    Done(Box<GetTransactionResponse>),
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -94,6 +119,7 @@ impl Args {
         Ok(signer.sign_tx(tx, network)?)
     }
 
+    #[tracing::instrument(name = "sign_auth", skip_all, err(level = "debug"))]
     pub async fn sign_soroban_authorizations(
         &self,
         tx: &Transaction,
@@ -117,19 +143,106 @@ impl Args {
         Ok(self.network.get(&self.locator)?)
     }
 
+    /// The sequence number of the next transaction of `account`, picked with `strategy`.
     pub async fn next_sequence_number(
         &self,
         account: impl Into<xdr::AccountId>,
+        strategy: sequence::Strategy,
     ) -> Result<SequenceNumber, Error> {
         let network = self.get_network()?;
-        let client = network.rpc_client()?;
-        Ok((client
-            .get_account(&account.into().to_string())
+        let account = account.into();
+        let current = network
+            .rpc_client()?
+            .get_account(&account.to_string())
             .await?
             .seq_num
-            .0
-            + 1)
-        .into())
+            .0;
+        Ok(self
+            .sequence_number_after(&account, current, strategy)?
+            .into())
+    }
+
+    /// The sequence number after `current`, the sequence number of `account` on the network.
+    /// With the `pool` strategy, skips the numbers reserved by other commands in the account's
+    /// local sequence pool, without reserving it: that is left to `reserve_sequence_number` once
+    /// the transaction is about to be sent.
+    pub fn sequence_number_after(
+        &self,
+        account: &xdr::AccountId,
+        current: i64,
+        strategy: sequence::Strategy,
+    ) -> Result<i64, Error> {
+        Ok(match strategy {
+            sequence::Strategy::Network => current + 1,
+            sequence::Strategy::Pool => self.sequence_pool(account)?.next(current)?,
+        })
+    }
+
+    /// With the `pool` strategy, reserve the sequence number of `tx` before it is signed, moving
+    /// it to the next free one if another command reserved it since `tx` was built. Nothing is
+    /// reserved for transactions that are not sent, i.e. with `--build-only`, `--sim-only` or
+    /// `--dry-run`.
+    pub fn reserve_sequence_number(
+        &self,
+        tx: &mut Transaction,
+        fee: &fee::Args,
+    ) -> Result<Option<sequence::Reservation>, Error> {
+        if fee.seq_strategy != sequence::Strategy::Pool
+            || fee.build_only
+            || fee.sim_only
+            || fee.dry_run
+        {
+            return Ok(None);
+        }
+        let account = tx.source_account.clone().account_id();
+        let reservation = self.sequence_pool(&account)?.reserve(tx.seq_num.0)?;
+        tx.seq_num = reservation.seq_num().into();
+        Ok(Some(reservation))
+    }
+
+    /// Send the signed `tx_env` to `network`, or only print it with `--dry-run`. Once it is about
+    /// to be sent the `reservation` of its sequence number is kept, as the transaction may reach
+    /// the network and consume it even if sending or polling fails. With `--no-wait` returns as
+    /// soon as it is sent, otherwise waits for its result, keeping the diagnostic events of a
+    /// failed transaction in the error.
+    pub async fn submit(
+        &self,
+        network: &Network,
+        client: &Client,
+        tx_env: TransactionEnvelope,
+        reservation: Option<sequence::Reservation>,
+        fee: &fee::Args,
+        global_args: Option<&global::Args>,
+    ) -> Result<Submitted, Error> {
+        if fee.dry_run {
+            Print::new(global_args.map_or(false, |a| a.quiet)).log_dry_run(&tx_env, network)?;
+            return Ok(Submitted::DryRun(Box::new(tx_env)));
+        }
+        let sender = network
+            .guard_submission(client, &tx_env, global_args, &self.locator)
+            .await?;
+        if let Some(reservation) = reservation {
+            reservation.keep();
+        }
+        if fee.no_wait {
+            return Ok(Submitted::Sent(diagnostic::submit(&sender, &tx_env).await?));
+        }
+        let res = diagnostic::send_transaction_polling(
+            &sender,
+            client,
+            &tx_env,
+            &network.network_passphrase,
+        )
+        .await?;
+        Ok(Submitted::Done(Box::new(res)))
+    }
+
+    fn sequence_pool(&self, account: &xdr::AccountId) -> Result<sequence::Pool, Error> {
+        let network = self.get_network()?;
+        Ok(sequence::Pool::new(
+            &network.network_passphrase,
+            &account.to_string(),
+        )?)
     }
 }
 
@@ -157,19 +270,54 @@ impl ArgsLocatorAndNetwork {
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Config {
+    #[serde(default)]
     pub defaults: Defaults,
+    #[serde(default)]
+    pub safety: Safety,
+    #[serde(default)]
+    pub upgrade: Upgrade,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Defaults {
     pub network: Option<String>,
     pub identity: Option<String>,
+    pub fee_strategy: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Safety {
+    /// Ask for confirmation before submitting transactions to Mainnet, true by default
+    pub mainnet_confirm: Option<bool>,
+}
+
+impl Safety {
+    pub fn mainnet_confirm(&self) -> bool {
+        self.mainnet_confirm.unwrap_or(true)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Upgrade {
+    /// Check for a new release of the CLI in the background, at most once a day, true by default
+    pub check: Option<bool>,
+}
+
+impl Upgrade {
+    pub fn check(&self) -> bool {
+        self.check.unwrap_or(true)
+    }
 }
 
 impl Config {
     pub fn new() -> Result<Config, locator::Error> {
-        let path = locator::config_file()?;
+        Self::read(locator::config_file()?)
+    }
 
+    /// The config file at `path`, or the default config if there is none.
+    pub fn read(path: PathBuf) -> Result<Config, locator::Error> {
         if path.exists() {
             let data = fs::read_to_string(&path).map_err(|_| locator::Error::FileRead { path })?;
             Ok(toml::from_str(&data)?)
@@ -178,6 +326,24 @@ impl Config {
         }
     }
 
+    /// This config with the settings that `other` sets taking precedence.
+    #[must_use]
+    pub fn overlay(self, other: Config) -> Self {
+        Config {
+            defaults: Defaults {
+                network: other.defaults.network.or(self.defaults.network),
+                identity: other.defaults.identity.or(self.defaults.identity),
+                fee_strategy: other.defaults.fee_strategy.or(self.defaults.fee_strategy),
+            },
+            safety: Safety {
+                mainnet_confirm: other.safety.mainnet_confirm.or(self.safety.mainnet_confirm),
+            },
+            upgrade: Upgrade {
+                check: other.upgrade.check.or(self.upgrade.check),
+            },
+        }
+    }
+
     #[must_use]
     pub fn set_network(mut self, s: &str) -> Self {
         self.defaults.network = Some(s.to_string());
@@ -190,10 +356,30 @@ impl Config {
         self
     }
 
+    #[must_use]
+    pub fn set_fee_strategy(mut self, s: &str) -> Self {
+        self.defaults.fee_strategy = Some(s.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn set_mainnet_confirm(mut self, confirm: bool) -> Self {
+        self.safety.mainnet_confirm = Some(confirm);
+        self
+    }
+
+    #[must_use]
+    pub fn set_upgrade_check(mut self, check: bool) -> Self {
+        self.upgrade.check = Some(check);
+        self
+    }
+
     pub fn save(&self) -> Result<(), locator::Error> {
         let toml_string = toml::to_string(&self)?;
-        let mut file = File::create(locator::config_file()?)?;
-        file.write_all(toml_string.as_bytes())?;
+        locator::write_atomic(
+            &locator::ensure_directory(locator::config_file()?)?,
+            toml_string,
+        )?;
 
         Ok(())
     }
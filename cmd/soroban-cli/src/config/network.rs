@@ -1,22 +1,33 @@
 use clap::arg;
+use hmac::{Hmac, Mac};
 use itertools::Itertools;
-use jsonrpsee_http_client::HeaderMap;
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
+use jsonrpsee_http_client::{HeaderMap, HttpClientBuilder};
 use phf::phf_map;
 use reqwest::header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use stellar_strkey::ed25519::PublicKey;
 use url::Url;
 
-use super::locator;
-use crate::utils::http;
+use super::{data, locator};
+use crate::utils::{envelope_hash, http};
 use crate::{
-    commands::HEADING_RPC,
+    commands::{global, HEADING_RPC},
+    input, interrupt,
+    print::Print,
     rpc::{self, Client},
+    upgrade_check,
+    xdr::{self, Hash, Limits, TransactionEnvelope, WriteXdr},
 };
 pub mod passphrase;
+pub mod presets;
+pub mod rpc_version;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -55,6 +66,31 @@ STELLAR_NETWORK, STELLAR_RPC_URL and STELLAR_NETWORK_PASSPHRASE"#
     InvalidHeaderValue(#[from] InvalidHeaderValue),
     #[error("invalid HTTP header: must be in the form 'key:value'")]
     InvalidHeader,
+    #[error(transparent)]
+    JsonRpc(#[from] jsonrpsee_core::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error("Not submitting the transaction to Mainnet without confirmation, use --yes with --no-input or when not run in a terminal, or turn the confirmation off with `stellar config set safety.mainnet-confirm false`")]
+    MainnetConfirmationRequired,
+    #[error("Transaction not submitted to Mainnet")]
+    MainnetNotConfirmed,
+    #[error("Failed to read confirmation: {0}")]
+    Confirmation(std::io::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("{feature} requires stellar-rpc >= {required}, but the RPC server at {rpc_url} runs {found}")]
+    RpcTooOld {
+        feature: String,
+        required: String,
+        found: String,
+        rpc_url: String,
+    },
+    #[error("The RPC server at {rpc_url} does not support `{method}`{hint}")]
+    RpcMethodNotFound {
+        method: String,
+        rpc_url: String,
+        hint: String,
+    },
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -85,6 +121,15 @@ pub struct Args {
         help_heading = HEADING_RPC,
     )]
     pub network_passphrase: Option<String>,
+    /// Secret to sign transaction submissions to the RPC with, for gateways that deduplicate and
+    /// attribute them. Overrides the secret of `--network`
+    #[arg(
+        long = "rpc-signing-secret",
+        env = "STELLAR_RPC_SIGNING_SECRET",
+        hide_env_values = true,
+        help_heading = HEADING_RPC,
+    )]
+    pub rpc_signing_secret: Option<String>,
     /// Name of network to use from config
     #[arg(
         long,
@@ -104,17 +149,26 @@ impl Args {
             (None, None, None) => Err(Error::Network),
             (_, Some(_), None) => Err(Error::MissingNetworkPassphrase),
             (_, None, Some(_)) => Err(Error::MissingRpcUrl),
-            (Some(network), None, None) => Ok(locator.read_network(network)?),
+            (Some(network), None, None) => {
+                let mut network = locator.read_network(network)?;
+                if self.rpc_signing_secret.is_some() {
+                    network
+                        .rpc_signing_secret
+                        .clone_from(&self.rpc_signing_secret);
+                }
+                Ok(network)
+            }
             (_, Some(rpc_url), Some(network_passphrase)) => Ok(Network {
                 rpc_url,
                 rpc_headers: self.rpc_headers.clone(),
                 network_passphrase,
+                rpc_signing_secret: self.rpc_signing_secret.clone(),
             }),
         }
     }
 }
 
-#[derive(Debug, clap::Args, Serialize, Deserialize, Clone)]
+#[derive(clap::Args, Serialize, Deserialize, Clone)]
 #[group(skip)]
 pub struct Network {
     /// RPC server endpoint
@@ -142,9 +196,34 @@ pub struct Network {
             help_heading = HEADING_RPC,
         )]
     pub network_passphrase: String,
+    /// Secret to sign transaction submissions to the RPC with, sent as an HMAC-SHA256 of the
+    /// transaction along with an idempotency key. Saved networks store it in plain text
+    #[arg(
+        long = "rpc-signing-secret",
+        env = "STELLAR_RPC_SIGNING_SECRET",
+        hide_env_values = true,
+        help_heading = HEADING_RPC,
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_signing_secret: Option<String>,
+}
+
+// The signing secret is left out, as networks are printed by `network ls --long` and traced.
+impl std::fmt::Debug for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Network")
+            .field("rpc_url", &self.rpc_url)
+            .field("rpc_headers", &self.rpc_headers)
+            .field("network_passphrase", &self.network_passphrase)
+            .field(
+                "rpc_signing_secret",
+                &self.rpc_signing_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
 }
 
-fn parse_http_header(header: &str) -> Result<(String, String), Error> {
+pub(crate) fn parse_http_header(header: &str) -> Result<(String, String), Error> {
     let header_components = header.splitn(2, ':');
 
     let (key, value) = header_components
@@ -184,6 +263,128 @@ impl Network {
         }
     }
 
+    /// Check that the RPC server is on this network, trusting a check made within the cache TTL
+    /// unless `no_cache`.
+    pub async fn verify_rpc_passphrase(
+        &self,
+        client: &Client,
+        no_cache: bool,
+    ) -> Result<(), Error> {
+        if !no_cache
+            && data::read_network_passphrase(&self.rpc_url)?.as_deref()
+                == Some(self.network_passphrase.as_str())
+        {
+            return Ok(());
+        }
+        let passphrase = client
+            .verify_network_passphrase(Some(&self.network_passphrase))
+            .await?;
+        if !no_cache {
+            data::write_network_passphrase(&self.rpc_url, &passphrase)?;
+        }
+        Ok(())
+    }
+
+    /// Check before submitting a transaction that the RPC server is on this network and, on
+    /// Mainnet, that the user means to, unless `--yes` or `safety.mainnet-confirm` is off in the
+    /// config of `locator`. Warns
+    /// when the network is on a protocol newer than the CLI supports. Returns the client to send
+    /// `tx_env` with.
+    pub async fn guard_submission(
+        &self,
+        client: &Client,
+        tx_env: &TransactionEnvelope,
+        global_args: Option<&global::Args>,
+        locator: &locator::Args,
+    ) -> Result<Client, Error> {
+        let global::Args {
+            no_cache,
+            yes,
+            quiet,
+            ..
+        } = global_args.cloned().unwrap_or_default();
+        self.verify_rpc_passphrase(client, no_cache).await?;
+        if let Ok(info) = self.rpc_version(no_cache).await {
+            if let Some(warning) = upgrade_check::protocol_warning(info.protocol_version) {
+                Print::new(quiet).warnln(warning);
+            }
+        }
+        if !yes
+            && self.network_passphrase == passphrase::MAINNET
+            && locator.read_config()?.safety.mainnet_confirm()
+        {
+            confirm_mainnet()?;
+        }
+        if let Ok(Some(hash)) = envelope_hash(tx_env, &self.network_passphrase) {
+            interrupt::sending(Hash(hash));
+        }
+        self.sender(client, tx_env)
+    }
+
+    /// The client to send `tx_env` with. With a signing secret, its requests carry the SHA-256 of
+    /// the envelope as an `Idempotency-Key`, the same on every retry of the transaction, and an
+    /// HMAC-SHA256 of it in `X-Stellar-Signature`. Polling for the result is left to `client`, so
+    /// that gateways don't answer it with the response to the submission.
+    fn sender(&self, client: &Client, tx_env: &TransactionEnvelope) -> Result<Client, Error> {
+        let Some(secret) = &self.rpc_signing_secret else {
+            return Ok(client.clone());
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let headers = self.header_map(&submission_headers(secret, tx_env, timestamp)?)?;
+        Ok(rpc::Client::new_with_headers(&self.rpc_url, headers)?)
+    }
+
+    /// The version of the RPC server, queried with `getVersionInfo` on first use and trusted
+    /// within the cache TTL unless `no_cache`.
+    pub async fn rpc_version(&self, no_cache: bool) -> Result<rpc_version::VersionInfo, Error> {
+        if !no_cache {
+            if let Some(cached) = data::read_rpc_version(&self.rpc_url)? {
+                if let Ok(info) = serde_json::from_str(&cached) {
+                    return Ok(info);
+                }
+            }
+        }
+        let info: rpc_version::VersionInfo = self
+            .rpc_request("getVersionInfo", ObjectParams::new())
+            .await?;
+        tracing::debug!("RPC server {} runs {info:?}", self.rpc_url);
+        if !no_cache {
+            if let Ok(json) = serde_json::to_string(&info) {
+                data::write_rpc_version(&self.rpc_url, &json)?;
+            }
+        }
+        Ok(info)
+    }
+
+    /// Check that the RPC server is recent enough for `requirement`, failing with the version it
+    /// needs rather than with whatever the older server responds.
+    pub async fn require_rpc(
+        &self,
+        requirement: &rpc_version::Requirement,
+        no_cache: bool,
+    ) -> Result<(), Error> {
+        let too_old = |found: String| Error::RpcTooOld {
+            feature: requirement.feature.to_string(),
+            required: rpc_version::format(requirement.version),
+            found,
+            rpc_url: self.rpc_url.clone(),
+        };
+        match self.rpc_version(no_cache).await {
+            Ok(info) if info.at_least(requirement.version) => Ok(()),
+            Ok(info) => Err(too_old(info.version)),
+            Err(Error::RpcMethodNotFound { .. }) => {
+                Err(too_old("a version without `getVersionInfo`".to_string()))
+            }
+            Err(Error::JsonRpc(jsonrpsee_core::Error::ParseError(e))) => {
+                tracing::debug!("Unrecognised getVersionInfo response, not checking it: {e}");
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     #[allow(clippy::similar_names)]
     pub async fn fund_address(&self, addr: &PublicKey) -> Result<(), Error> {
         let uri = self.helper_url(&addr.to_string()).await?;
@@ -218,17 +419,93 @@ impl Network {
     }
 
     pub fn rpc_client(&self) -> Result<Client, Error> {
+        Ok(rpc::Client::new_with_headers(
+            &self.rpc_url,
+            self.rpc_header_map()?,
+        )?)
+    }
+
+    /// Call an RPC method that the RPC client does not expose, or whose response it does not
+    /// fully decode.
+    pub async fn rpc_request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ObjectParams,
+    ) -> Result<R, Error> {
+        let client = HttpClientBuilder::default()
+            .set_headers(self.rpc_header_map()?)
+            .build(&self.rpc_url)?;
+        match client.request(method, params).await {
+            Err(jsonrpsee_core::Error::Call(e))
+                if e.code() == rpc_version::METHOD_NOT_FOUND_CODE =>
+            {
+                Err(Error::RpcMethodNotFound {
+                    method: method.to_string(),
+                    rpc_url: self.rpc_url.clone(),
+                    hint: rpc_version::method_version(method).map_or_else(String::new, |v| {
+                        format!(", it requires stellar-rpc >= {}", rpc_version::format(v))
+                    }),
+                })
+            }
+            res => Ok(res?),
+        }
+    }
+
+    fn rpc_header_map(&self) -> Result<HeaderMap, Error> {
+        self.header_map(&[])
+    }
+
+    fn header_map(&self, extra: &[(String, String)]) -> Result<HeaderMap, Error> {
         let mut header_hash_map = HashMap::new();
-        for (header_name, header_value) in &self.rpc_headers {
+        for (header_name, header_value) in self.rpc_headers.iter().chain(extra) {
             header_hash_map.insert(header_name.to_string(), header_value.to_string());
         }
 
-        let header_map: HeaderMap = (&header_hash_map)
+        (&header_hash_map)
             .try_into()
-            .map_err(|_| Error::InvalidHeader)?;
+            .map_err(|_| Error::InvalidHeader)
+    }
+}
 
-        Ok(rpc::Client::new_with_headers(&self.rpc_url, header_map)?)
+fn confirm_mainnet() -> Result<(), Error> {
+    let stdin = io::stdin();
+    if !input::interactive() {
+        return Err(Error::MainnetConfirmationRequired);
     }
+    eprint!("Submit the transaction to Mainnet? [y/N] ");
+    io::stderr().flush().map_err(Error::Confirmation)?;
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .map_err(Error::Confirmation)?;
+    if matches!(answer.trim(), "y" | "Y" | "yes") {
+        Ok(())
+    } else {
+        Err(Error::MainnetNotConfirmed)
+    }
+}
+
+/// The idempotency key and signature headers of submitting `tx_env` at `timestamp`. The signature
+/// is `t=<timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<idempotency key>.<envelope base64>">`.
+fn submission_headers(
+    secret: &str,
+    tx_env: &TransactionEnvelope,
+    timestamp: u64,
+) -> Result<[(String, String); 2], Error> {
+    let envelope = tx_env.to_xdr_base64(Limits::none())?;
+    let key = hex::encode(Sha256::digest(tx_env.to_xdr(Limits::none())?));
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.{key}.{envelope}").as_bytes());
+    let signature = hex::encode(mac.finalize().into_bytes());
+    Ok([
+        ("Idempotency-Key".to_string(), key),
+        (
+            "X-Stellar-Signature".to_string(),
+            format!("t={timestamp},v1={signature}"),
+        ),
+    ])
 }
 
 pub static DEFAULTS: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
@@ -257,6 +534,7 @@ impl From<&(&str, &str)> for Network {
             rpc_url: n.0.to_string(),
             rpc_headers: Vec::new(),
             network_passphrase: n.1.to_string(),
+            rpc_signing_secret: None,
         }
     }
 }
@@ -276,6 +554,7 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: Vec::new(),
+            rpc_signing_secret: None,
         };
 
         let result = network
@@ -314,6 +593,7 @@ mod tests {
             rpc_url: server.url(),
             network_passphrase: passphrase::TESTNET.to_string(),
             rpc_headers: Vec::new(),
+            rpc_signing_secret: None,
         };
         let url = network
             .helper_url("GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI")
@@ -345,6 +625,7 @@ mod tests {
             rpc_url: server.url(),
             network_passphrase: passphrase::TESTNET.to_string(),
             rpc_headers: Vec::new(),
+            rpc_signing_secret: None,
         };
         let url = network
             .helper_url("GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI")
@@ -390,6 +671,7 @@ mod tests {
             rpc_url: "http://localhost:1234".to_string(),
             network_passphrase: "Network passphrase".to_string(),
             rpc_headers: [].to_vec(),
+            rpc_signing_secret: None,
         };
 
         let result = network.rpc_client();
@@ -402,6 +684,7 @@ mod tests {
             rpc_url: "http://localhost:1234".to_string(),
             network_passphrase: "Network passphrase".to_string(),
             rpc_headers: [("Authorization".to_string(), "Bearer 1234".to_string())].to_vec(),
+            rpc_signing_secret: None,
         };
 
         let result = network.rpc_client();
@@ -418,6 +701,7 @@ mod tests {
                 ("api-key".to_string(), "5678".to_string()),
             ]
             .to_vec(),
+            rpc_signing_secret: None,
         };
 
         let result = network.rpc_client();
@@ -430,6 +714,7 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: [(INVALID_HEADER_NAME.to_string(), "Bearer".to_string())].to_vec(),
+            rpc_signing_secret: None,
         };
 
         let result = network.rpc_client();
@@ -446,6 +731,7 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: [("api-key".to_string(), INVALID_HEADER_VALUE.to_string())].to_vec(),
+            rpc_signing_secret: None,
         };
 
         let result = network.rpc_client();
@@ -455,4 +741,42 @@ mod tests {
             format!("invalid HTTP header: must be in the form 'key:value'")
         );
     }
+
+    #[test]
+    fn test_submission_headers_are_stable_across_retries() {
+        let tx_env = TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: xdr::Transaction {
+                source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([0; 32])),
+                fee: 100,
+                seq_num: xdr::SequenceNumber(1),
+                cond: xdr::Preconditions::None,
+                memo: xdr::Memo::None,
+                operations: xdr::VecM::default(),
+                ext: xdr::TransactionExt::V0,
+            },
+            signatures: xdr::VecM::default(),
+        });
+        let [(_, key), (_, signature)] = submission_headers("secret", &tx_env, 1).unwrap();
+        let [(_, retry_key), (_, retry_signature)] =
+            submission_headers("secret", &tx_env, 2).unwrap();
+        let [_, (_, other_signature)] = submission_headers("other", &tx_env, 1).unwrap();
+        assert_eq!(key, retry_key);
+        assert_eq!(key.len(), 64);
+        assert!(signature.starts_with("t=1,v1="));
+        assert!(retry_signature.starts_with("t=2,v1="));
+        assert_ne!(signature, other_signature);
+    }
+
+    #[test]
+    fn test_debug_hides_the_signing_secret() {
+        let network = Network {
+            rpc_url: "https://rpc.example".to_string(),
+            rpc_headers: Vec::new(),
+            network_passphrase: "passphrase".to_string(),
+            rpc_signing_secret: Some("hunter2".to_string()),
+        };
+        let debug = format!("{network:#?}");
+        assert!(debug.contains("https://rpc.example"));
+        assert!(!debug.contains("hunter2"));
+    }
 }
@@ -0,0 +1,161 @@
+//! Presets of networks to add with `network add --preset`: the built-in ones, and the ones of
+//! the registry `presets.toml` in the global config directory, which `network presets update`
+//! replaces with a registry fetched from a URL or read from a file.
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{locator, Network};
+
+const BUILTIN: &str = include_str!("presets.toml");
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("parsing the network presets of {source_name}: {error}")]
+    Parse {
+        source_name: String,
+        error: toml::de::Error,
+    },
+    #[error("reading the network presets registry {path:?}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("writing the network presets registry {path:?}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("no network preset named {name}, the presets are: {names}")]
+    NotFound { name: String, names: String },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Registry {
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    pub description: Option<String>,
+    pub rpc_url: String,
+    pub network_passphrase: String,
+    /// Headers to send to the RPC, e.g. `[["Authorization", "Bearer ..."]]`
+    #[serde(default)]
+    pub rpc_headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Builtin,
+    Registry,
+}
+
+impl From<&Preset> for Network {
+    fn from(preset: &Preset) -> Self {
+        Network {
+            rpc_url: preset.rpc_url.clone(),
+            rpc_headers: preset.rpc_headers.clone(),
+            network_passphrase: preset.network_passphrase.clone(),
+            rpc_signing_secret: None,
+        }
+    }
+}
+
+impl Registry {
+    pub fn parse(contents: &str, source_name: &str) -> Result<Self, Error> {
+        toml::from_str(contents).map_err(|error| Error::Parse {
+            source_name: source_name.to_string(),
+            error,
+        })
+    }
+
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN, "built-in presets").expect("built-in presets are valid")
+    }
+
+    /// The registry in the global config directory, empty if there is none.
+    pub fn read() -> Result<Self, Error> {
+        let path = path()?;
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents, &path.to_string_lossy()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(Error::Read { path, error }),
+        }
+    }
+}
+
+/// Path of the registry, `presets.toml` in the global config directory.
+pub fn path() -> Result<PathBuf, Error> {
+    Ok(locator::global_config_path()?.join("presets.toml"))
+}
+
+/// Replace the registry with `contents`, after checking that they parse.
+pub fn save(contents: &str, source_name: &str) -> Result<Registry, Error> {
+    let registry = Registry::parse(contents, source_name)?;
+    let path = path()?;
+    let write_error = |error| Error::Write {
+        path: path.clone(),
+        error,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(write_error)?;
+    }
+    fs::write(&path, contents).map_err(write_error)?;
+    Ok(registry)
+}
+
+/// The built-in presets and the ones of the registry, which take precedence.
+pub fn all() -> Result<BTreeMap<String, (Preset, Source)>, Error> {
+    let mut presets = Registry::builtin()
+        .presets
+        .into_iter()
+        .map(|(name, preset)| (name, (preset, Source::Builtin)))
+        .collect::<BTreeMap<_, _>>();
+    for (name, preset) in Registry::read()?.presets {
+        presets.insert(name, (preset, Source::Registry));
+    }
+    Ok(presets)
+}
+
+pub fn get(name: &str) -> Result<Preset, Error> {
+    let mut presets = all()?;
+    presets
+        .remove(name)
+        .map(|(preset, _)| preset)
+        .ok_or_else(|| Error::NotFound {
+            name: name.to_string(),
+            names: presets.keys().cloned().collect::<Vec<_>>().join(", "),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::network::passphrase;
+
+    #[test]
+    fn builtin_presets_match_the_default_networks() {
+        let builtin = Registry::builtin();
+        for name in ["local", "testnet", "futurenet"] {
+            let preset = &builtin.presets[name];
+            let (rpc_url, network_passphrase) = super::super::DEFAULTS[name];
+            assert_eq!(preset.rpc_url, rpc_url);
+            assert_eq!(preset.network_passphrase, network_passphrase);
+        }
+        assert_eq!(
+            builtin.presets["mainnet-gateway"].network_passphrase,
+            passphrase::MAINNET
+        );
+        assert!(matches!(
+            Registry::parse("[presets.x]\nrpc = \"http://x\"", "test"),
+            Err(Error::Parse { .. })
+        ));
+    }
+}
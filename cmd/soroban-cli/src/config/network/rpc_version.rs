@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// The response of `getVersionInfo`, whose fields were snake case before stellar-rpc 22.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub version: String,
+    #[serde(alias = "protocol_version")]
+    pub protocol_version: u32,
+}
+
+impl VersionInfo {
+    /// Whether the server is at least `version`. Servers whose version cannot be parsed, such as
+    /// local builds, are assumed to be recent enough.
+    pub fn at_least(&self, version: (u64, u64, u64)) -> bool {
+        parse(&self.version).map_or(true, |v| v >= version)
+    }
+}
+
+/// The JSON-RPC error code of calls to methods the server does not have.
+pub const METHOD_NOT_FOUND_CODE: i32 = -32601;
+
+/// A feature of stellar-rpc that the CLI relies on.
+#[derive(Debug, Clone, Copy)]
+pub struct Requirement {
+    pub feature: &'static str,
+    pub version: (u64, u64, u64),
+}
+
+/// Listing transactions with `getTransactions`.
+pub const GET_TRANSACTIONS: Requirement = Requirement {
+    feature: "Reading transactions with `getTransactions`",
+    version: (21, 0, 0),
+};
+
+/// The stellar-rpc version at which `method` became available, if it was added after the
+/// methods every supported server has.
+pub fn method_version(method: &str) -> Option<(u64, u64, u64)> {
+    match method {
+        "getTransactions" | "getVersionInfo" | "getFeeStats" => Some((21, 0, 0)),
+        _ => None,
+    }
+}
+
+pub fn format(version: (u64, u64, u64)) -> String {
+    format!("{}.{}.{}", version.0, version.1, version.2)
+}
+
+/// The leading `major.minor.patch` of a version such as `v21.4.1-4e7be4bc`, with missing
+/// components read as zero.
+pub fn parse(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let mut parts = version[..end].split('.').filter(|p| !p.is_empty());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Ok(0), str::parse).ok()?;
+    let patch = parts.next().map_or(Ok(0), str::parse).ok()?;
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(parse("21.4.1-4e7be4bc"), Some((21, 4, 1)));
+        assert_eq!(parse("v22.1.0"), Some((22, 1, 0)));
+        assert_eq!(parse("22"), Some((22, 0, 0)));
+        assert_eq!(parse("dev"), None);
+    }
+
+    #[test]
+    fn deserializes_either_case() {
+        let old: VersionInfo = serde_json::from_str(
+            r#"{"version":"21.4.1","commit_hash":"abc","protocol_version":21}"#,
+        )
+        .unwrap();
+        let new: VersionInfo =
+            serde_json::from_str(r#"{"version":"22.1.0","protocolVersion":22}"#).unwrap();
+        assert_eq!(old.protocol_version, 21);
+        assert!(!old.at_least((22, 0, 0)));
+        assert!(new.at_least((22, 0, 0)));
+        assert!(VersionInfo {
+            version: "dev".to_string(),
+            protocol_version: 22
+        }
+        .at_least((22, 0, 0)));
+    }
+}
@@ -1,9 +1,10 @@
-use clap::arg;
+use clap::{arg, ValueEnum};
 use serde::{Deserialize, Serialize};
 use std::{io::Write, str::FromStr};
 use stellar_strkey::ed25519::{PrivateKey, PublicKey};
 
 use crate::{
+    input, passkey,
     print::Print,
     signer::{self, LocalKey, Signer, SignerKind},
     utils,
@@ -17,6 +18,11 @@ pub enum Error {
     // InvalidSeedPhrase { len: usize },
     #[error("secret input error")]
     PasswordRead,
+    #[error("cannot prompt for the {what} with --no-input or when not run in a terminal, set it in {env} instead")]
+    NoInput {
+        what: &'static str,
+        env: &'static str,
+    },
     #[error(transparent)]
     Secret(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
@@ -27,6 +33,14 @@ pub enum Error {
     InvalidAddress(String),
     #[error(transparent)]
     Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Passkey(#[from] passkey::Error),
+    #[error("secp256r1 keys cannot be used as a Stellar account, only to sign for smart wallets")]
+    Secp256r1NotAccount,
+    #[error("expected a secp256r1 key")]
+    NotSecp256r1,
+    #[error("invalid seed phrase, it is not a valid BIP-39 mnemonic in any supported language")]
+    InvalidSeedPhrase,
 }
 
 #[derive(Debug, clap::Args, Clone)]
@@ -36,7 +50,9 @@ pub struct Args {
     /// Can provide with `SOROBAN_SECRET_KEY`
     #[arg(long, conflicts_with = "seed_phrase")]
     pub secret_key: bool,
-    /// Add using 12 word seed phrase to generate `secret_key`
+    /// Add using a BIP-39 seed phrase to generate `secret_key`, of 12 to 24 words in any of
+    /// the languages of `keys generate --language`
+    /// Can provide with `SOROBAN_SEED_PHRASE`
     #[arg(long, conflicts_with = "secret_key")]
     pub seed_phrase: bool,
 }
@@ -46,6 +62,12 @@ impl Args {
         if let Ok(secret_key) = std::env::var("SOROBAN_SECRET_KEY") {
             Ok(Secret::SecretKey { secret_key })
         } else if self.secret_key {
+            if !input::interactive() {
+                return Err(Error::NoInput {
+                    what: "secret key",
+                    env: "SOROBAN_SECRET_KEY",
+                });
+            }
             println!("Type a secret key: ");
             let secret_key = read_password()?;
             let secret_key = PrivateKey::from_string(&secret_key)
@@ -53,19 +75,21 @@ impl Args {
                 .to_string();
             Ok(Secret::SecretKey { secret_key })
         } else if self.seed_phrase {
-            println!("Type a 12 word seed phrase: ");
-            let seed_phrase = read_password()?;
-            let seed_phrase: Vec<&str> = seed_phrase.split_whitespace().collect();
-            // if seed_phrase.len() != 12 {
-            //     let len = seed_phrase.len();
-            //     return Err(Error::InvalidSeedPhrase { len });
-            // }
+            let seed_phrase = if let Ok(seed_phrase) = std::env::var("SOROBAN_SEED_PHRASE") {
+                seed_phrase
+            } else {
+                if !input::interactive() {
+                    return Err(Error::NoInput {
+                        what: "seed phrase",
+                        env: "SOROBAN_SEED_PHRASE",
+                    });
+                }
+                println!("Type a seed phrase: ");
+                read_password()?
+            };
+            let seed_phrase = parse_seed_phrase(&seed_phrase)?;
             Ok(Secret::SeedPhrase {
-                seed_phrase: seed_phrase
-                    .into_iter()
-                    .map(ToString::to_string)
-                    .collect::<Vec<_>>()
-                    .join(" "),
+                seed_phrase: seed_phrase.phrase().to_string(),
             })
         } else {
             Err(Error::PasswordRead {})
@@ -78,6 +102,7 @@ impl Args {
 pub enum Secret {
     SecretKey { secret_key: String },
     SeedPhrase { seed_phrase: String },
+    Secp256r1 { secp256r1_secret_key: String },
 }
 
 impl FromStr for Secret {
@@ -88,7 +113,7 @@ impl FromStr for Secret {
             Ok(Secret::SecretKey {
                 secret_key: s.to_string(),
             })
-        } else if sep5::SeedPhrase::from_str(s).is_ok() {
+        } else if parse_seed_phrase(s).is_ok() {
             Ok(Secret::SeedPhrase {
                 seed_phrase: s.to_string(),
             })
@@ -111,11 +136,12 @@ impl Secret {
         Ok(match self {
             Secret::SecretKey { secret_key } => PrivateKey::from_string(secret_key)?,
             Secret::SeedPhrase { seed_phrase } => PrivateKey::from_payload(
-                &sep5::SeedPhrase::from_str(seed_phrase)?
+                &parse_seed_phrase(seed_phrase)?
                     .from_path_index(index.unwrap_or_default(), None)?
                     .private()
                     .0,
             )?,
+            Secret::Secp256r1 { .. } => return Err(Error::Secp256r1NotAccount),
         })
     }
 
@@ -132,6 +158,7 @@ impl Secret {
                 let key = self.key_pair(index)?;
                 SignerKind::Local(LocalKey { key })
             }
+            Secret::Secp256r1 { .. } => return Err(Error::Secp256r1NotAccount),
         };
         Ok(Signer { kind, print })
     }
@@ -140,23 +167,125 @@ impl Secret {
         Ok(utils::into_signing_key(&self.private_key(index)?))
     }
 
-    pub fn from_seed(seed: Option<&str>) -> Result<Self, Error> {
-        let seed_phrase = if let Some(seed) = seed.map(str::as_bytes) {
-            sep5::SeedPhrase::from_entropy(seed)
+    pub fn secp256r1_key(&self) -> Result<p256::ecdsa::SigningKey, Error> {
+        match self {
+            Secret::Secp256r1 {
+                secp256r1_secret_key,
+            } => Ok(passkey::from_hex(secp256r1_secret_key)?),
+            _ => Err(Error::NotSecp256r1),
+        }
+    }
+
+    pub fn generate_secp256r1() -> Self {
+        Secret::Secp256r1 {
+            secp256r1_secret_key: passkey::to_hex(&passkey::generate()),
+        }
+    }
+
+    /// Generate a seed phrase in `language` from `seed` as entropy, whose length sets the number
+    /// of words, or a random one of `words` words.
+    pub fn from_seed(seed: Option<&str>, words: usize, language: Language) -> Result<Self, Error> {
+        let mnemonic = if let Some(seed) = seed.map(str::as_bytes) {
+            bip39::Mnemonic::from_entropy(seed, language.into()).map_err(|e| {
+                e.downcast::<bip39::ErrorKind>()
+                    .map_or(sep5::error::Error::Unknown, Into::into)
+            })?
         } else {
-            sep5::SeedPhrase::random(sep5::MnemonicType::Words12)
-        }?
-        .seed_phrase
-        .into_phrase();
-        Ok(Secret::SeedPhrase { seed_phrase })
+            let mtype = bip39::MnemonicType::for_word_count(words).map_err(|e| {
+                e.downcast::<bip39::ErrorKind>()
+                    .map_or(sep5::error::Error::Unknown, Into::into)
+            })?;
+            bip39::Mnemonic::new(mtype, language.into())
+        };
+        Ok(Secret::SeedPhrase {
+            seed_phrase: mnemonic.into_phrase(),
+        })
     }
 
     pub fn test_seed_phrase() -> Result<Self, Error> {
-        Self::from_seed(Some("0000000000000000"))
+        Self::from_seed(Some("0000000000000000"), 12, Language::English)
     }
 }
 
+/// Wordlist of a BIP-39 seed phrase.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    ChineseSimplified,
+    ChineseTraditional,
+}
+
+impl From<Language> for bip39::Language {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::English => bip39::Language::English,
+            Language::Spanish => bip39::Language::Spanish,
+            Language::French => bip39::Language::French,
+            Language::Italian => bip39::Language::Italian,
+            Language::Japanese => bip39::Language::Japanese,
+            Language::Korean => bip39::Language::Korean,
+            Language::ChineseSimplified => bip39::Language::ChineseSimplified,
+            Language::ChineseTraditional => bip39::Language::ChineseTraditional,
+        }
+    }
+}
+
+/// Parse a seed phrase in any of the supported languages, English first. The words can be
+/// separated by any whitespace, e.g. the ideographic spaces of Japanese phrases.
+pub fn parse_seed_phrase(seed_phrase: &str) -> Result<sep5::SeedPhrase, Error> {
+    let seed_phrase = seed_phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    Language::value_variants()
+        .iter()
+        .find_map(|language| bip39::Mnemonic::from_phrase(&seed_phrase, (*language).into()).ok())
+        .map(sep5::SeedPhrase::new_ed25519)
+        .ok_or(Error::InvalidSeedPhrase)
+}
+
 fn read_password() -> Result<String, Error> {
     std::io::stdout().flush().map_err(|_| Error::PasswordRead)?;
     rpassword::read_password().map_err(|_| Error::PasswordRead)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_phrases_in_other_languages_and_lengths() {
+        let Secret::SeedPhrase { seed_phrase } =
+            Secret::from_seed(None, 24, Language::Spanish).unwrap()
+        else {
+            panic!("expected a seed phrase");
+        };
+        assert_eq!(seed_phrase.split_whitespace().count(), 24);
+        let parsed = parse_seed_phrase(&seed_phrase).unwrap();
+        assert_eq!(parsed.seed_phrase.language(), bip39::Language::Spanish);
+        assert!(matches!(
+            Secret::from_str(&seed_phrase).unwrap(),
+            Secret::SeedPhrase { .. }
+        ));
+
+        let japanese = Secret::from_seed(Some("0000000000000000"), 12, Language::Japanese).unwrap();
+        let Secret::SeedPhrase { seed_phrase } = &japanese else {
+            panic!("expected a seed phrase");
+        };
+        let ideographic = seed_phrase.replace(' ', "\u{3000}");
+        assert_eq!(
+            Secret::from_str(&ideographic)
+                .unwrap()
+                .public_key(None)
+                .unwrap(),
+            japanese.public_key(None).unwrap()
+        );
+        assert!(matches!(
+            parse_seed_phrase("not a seed phrase"),
+            Err(Error::InvalidSeedPhrase)
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fs, io, path::PathBuf};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::data;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error("failed to reserve a sequence number in {path}: {error}")]
+    Reserve { path: PathBuf, error: io::Error },
+    #[error("failed to reset the sequence numbers reserved in {path}: {error}")]
+    Reset { path: PathBuf, error: io::Error },
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Use the sequence number after the account's current one on the network
+    #[default]
+    Network,
+    /// Reserve sequence numbers locally, so that concurrent commands with the same source
+    /// account each use a different one instead of racing for the next one
+    Pool,
+}
+
+/// Sequence numbers reserved for an account on a network, one empty file per number.
+///
+/// Creating a file is atomic, so concurrent processes never reserve the same number.
+/// Reservations at or below the account's sequence number on the network have been used, and
+/// are pruned when picking the next one.
+pub struct Pool {
+    dir: PathBuf,
+}
+
+impl Pool {
+    pub fn new(network_passphrase: &str, account: &str) -> Result<Self, Error> {
+        let network = hex::encode(&Sha256::digest(network_passphrase)[..8]);
+        Ok(Self {
+            dir: data::data_local_dir()?
+                .join("sequence")
+                .join(network)
+                .join(account),
+        })
+    }
+
+    /// The lowest sequence number after `current`, the account's sequence number on the network,
+    /// that no other command has reserved, without reserving it.
+    pub fn next(&self, current: i64) -> Result<i64, Error> {
+        let reserve_error = |error| Error::Reserve {
+            path: self.dir.clone(),
+            error,
+        };
+        if !self.dir.exists() {
+            return Ok(current + 1);
+        }
+        let mut next = current + 1;
+        for reserved in self.reserved().map_err(reserve_error)? {
+            if reserved <= current {
+                // Another command may be pruning the same reservation.
+                let _ = fs::remove_file(self.dir.join(reserved.to_string()));
+            } else if reserved == next {
+                next += 1;
+            }
+        }
+        Ok(next)
+    }
+
+    /// Reserve the lowest sequence number from `next` on that no other command has reserved,
+    /// e.g. the one returned by [`Pool::next`] once the transaction is about to be signed and
+    /// sent.
+    pub fn reserve(&self, mut next: i64) -> Result<Reservation, Error> {
+        let reserve_error = |error| Error::Reserve {
+            path: self.dir.clone(),
+            error,
+        };
+        fs::create_dir_all(&self.dir).map_err(reserve_error)?;
+        loop {
+            let path = self.dir.join(next.to_string());
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => {
+                    return Ok(Reservation {
+                        path,
+                        seq_num: next,
+                        keep: false,
+                    })
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => next += 1,
+                Err(e) => return Err(reserve_error(e)),
+            }
+        }
+    }
+
+    /// Release every reservation, e.g. after a transaction with a reserved sequence number was
+    /// rejected or never landed, leaving a gap that makes later transactions fail with
+    /// `txBAD_SEQ`.
+    /// Returns the number of reservations released.
+    pub fn reset(&self) -> Result<usize, Error> {
+        let reset_error = |error| Error::Reset {
+            path: self.dir.clone(),
+            error,
+        };
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+        let released = self.reserved().map_err(reset_error)?.len();
+        fs::remove_dir_all(&self.dir).map_err(reset_error)?;
+        Ok(released)
+    }
+
+    fn reserved(&self) -> io::Result<Vec<i64>> {
+        let mut reserved = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            if let Some(n) = entry?.file_name().to_str().and_then(|n| n.parse().ok()) {
+                reserved.push(n);
+            }
+        }
+        reserved.sort_unstable();
+        Ok(reserved)
+    }
+}
+
+/// A sequence number reserved in a [`Pool`], released when dropped unless [`Reservation::keep`]
+/// is called, so that a command that fails or is aborted before its transaction is sent leaves
+/// no gap. Releasing the number of a transaction that landed is harmless, as it is at or below
+/// the account's sequence number on the network from then on.
+#[derive(Debug)]
+pub struct Reservation {
+    path: PathBuf,
+    seq_num: i64,
+    keep: bool,
+}
+
+impl Reservation {
+    pub fn seq_num(&self) -> i64 {
+        self.seq_num
+    }
+
+    /// Keep the reservation once the transaction is about to be sent. From then on it may land
+    /// even if sending or waiting for it fails, so other commands must not reuse its sequence
+    /// number.
+    pub fn keep(mut self) {
+        self.keep = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserves_unique_numbers_and_prunes_used_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = Pool {
+            dir: dir.path().join("pool"),
+        };
+        let first = pool.reserve(pool.next(10).unwrap()).unwrap();
+        assert_eq!(first.seq_num(), 11);
+        let second = pool.reserve(pool.next(10).unwrap()).unwrap();
+        assert_eq!(second.seq_num(), 12);
+        first.keep();
+        second.keep();
+        // 11 was used on the network, 12 is still reserved.
+        assert_eq!(pool.next(11).unwrap(), 13);
+        assert_eq!(pool.reserved().unwrap(), [12]);
+        // Another command reserved 13 after this one picked it.
+        pool.reserve(13).unwrap().keep();
+        assert_eq!(pool.reserve(13).unwrap().seq_num(), 14);
+        assert_eq!(pool.reset().unwrap(), 2);
+        assert_eq!(pool.next(11).unwrap(), 12);
+    }
+
+    #[test]
+    fn dry_runs_and_dropped_reservations_leave_no_gap() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = Pool {
+            dir: dir.path().join("pool"),
+        };
+        // A dry run picks a number without reserving it.
+        assert_eq!(pool.next(10).unwrap(), 11);
+        assert_eq!(pool.next(10).unwrap(), 11);
+        // A command that fails or is aborted before sending drops its reservation.
+        drop(pool.reserve(11).unwrap());
+        assert!(pool.reserved().unwrap().is_empty());
+        let reservation = pool.reserve(pool.next(10).unwrap()).unwrap();
+        assert_eq!(reservation.seq_num(), 11);
+        assert_eq!(pool.next(10).unwrap(), 12);
+    }
+}
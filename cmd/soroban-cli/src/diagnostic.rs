@@ -0,0 +1,575 @@
+//! Diagnostic events of failed invocations, rendered as a call stack with the contract errors
+//! named after the cases of the contracts' error enums, and the resources they used.
+use std::collections::HashMap;
+
+use soroban_spec_tools::Spec;
+
+use crate::{
+    assembled::Assembled,
+    interrupt,
+    log::extract_events,
+    print::Print,
+    rpc::{self, Client, GetTransactionResponse},
+    utils::transaction_hash,
+    xdr::{
+        self, ConfigSettingEntry, ConfigSettingId, ContractEventBody, ContractEventV0,
+        DiagnosticEvent, Hash, LedgerEntryData, LedgerKey, LedgerKeyConfigSetting, Limits, ReadXdr,
+        ScError, ScSpecEntry, ScVal, Transaction, TransactionEnvelope, TransactionV1Envelope, VecM,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("{error}")]
+    Failed {
+        error: rpc::Error,
+        events: Vec<DiagnosticEvent>,
+        /// Cost of the failed simulation, `None` for failed transactions.
+        cost: Option<rpc::Cost>,
+    },
+}
+
+impl Error {
+    /// Diagnostic events of the failed simulation or transaction, if any.
+    pub fn events(&self) -> &[DiagnosticEvent] {
+        match self {
+            Error::Failed { events, .. } => events,
+            Error::Rpc(_) | Error::Xdr(_) => &[],
+        }
+    }
+
+    /// Cost of the failed simulation, if the error is of one.
+    pub fn cost(&self) -> Option<&rpc::Cost> {
+        match self {
+            Error::Failed { cost, .. } => cost.as_ref(),
+            Error::Rpc(_) | Error::Xdr(_) => None,
+        }
+    }
+}
+
+/// Like `assembled::simulate_and_assemble_transaction`, keeping the diagnostic events of a
+/// failed simulation in the error.
+#[tracing::instrument(name = "simulate", skip_all, err(level = "debug"))]
+pub async fn simulate_and_assemble_transaction(
+    client: &Client,
+    tx: &Transaction,
+) -> Result<Assembled, Error> {
+    let sim_res = client
+        .simulate_transaction_envelope(&TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: VecM::default(),
+        }))
+        .await?;
+    if let Some(error) = &sim_res.error {
+        return Err(Error::Failed {
+            error: rpc::Error::TransactionSimulationFailed(error.clone()),
+            events: sim_res.events()?,
+            cost: Some(sim_res.cost.clone()),
+        });
+    }
+    Ok(Assembled::new(tx, sim_res)?)
+}
+
+/// Like `Client::send_transaction_polling`, fetching the diagnostic events of a failed
+/// transaction from its meta. The events are only recorded when the RPC's captive core has
+/// diagnostic events enabled.
+pub async fn send_transaction_polling(
+    sender: &Client,
+    client: &Client,
+    tx_env: &TransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<GetTransactionResponse, Error> {
+    match submit_and_poll(sender, client, tx_env).await {
+        Ok(res) => Ok(res),
+        Err(error @ rpc::Error::TransactionSubmissionFailed(_)) => {
+            let events = match tx_env {
+                TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
+                    let hash = Hash(transaction_hash(tx, network_passphrase)?);
+                    client
+                        .get_transaction(&hash)
+                        .await
+                        .ok()
+                        .and_then(|res| res.result_meta)
+                        .map(|meta| extract_events(&meta))
+                        .unwrap_or_default()
+                }
+                _ => Vec::new(),
+            };
+            Err(Error::Failed {
+                error,
+                events,
+                cost: None,
+            })
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Like `Client::send_transaction`, in a `submit` span.
+#[tracing::instrument(name = "submit", skip_all, fields(hash), err(level = "debug"))]
+pub async fn submit(client: &Client, tx_env: &TransactionEnvelope) -> Result<Hash, rpc::Error> {
+    let hash = client.send_transaction(tx_env).await?;
+    tracing::Span::current().record("hash", hash.to_string());
+    interrupt::sent(hash.clone());
+    Ok(hash)
+}
+
+/// Like `Client::send_transaction_polling`, in a `submit` span for sending the transaction with
+/// `sender` and a `poll` span for waiting for its result with `client`.
+pub async fn submit_and_poll(
+    sender: &Client,
+    client: &Client,
+    tx_env: &TransactionEnvelope,
+) -> Result<GetTransactionResponse, rpc::Error> {
+    let hash = submit(sender, tx_env).await?;
+    poll(client, &hash).await
+}
+
+#[tracing::instrument(skip(client), fields(hash = %hash), err(level = "debug"))]
+async fn poll(client: &Client, hash: &Hash) -> Result<GetTransactionResponse, rpc::Error> {
+    let res = client
+        .get_transaction_polling(hash, interrupt::remaining())
+        .await;
+    if !matches!(res, Err(rpc::Error::TransactionSubmissionTimeout)) {
+        interrupt::done();
+    }
+    res
+}
+
+/// Code of the contract error the invocation of `events` failed with, the last one raised as
+/// errors can be raised again by the calling contracts.
+pub fn contract_error(events: &[DiagnosticEvent]) -> Option<u32> {
+    events.iter().rev().find_map(|event| match topics(event) {
+        [ScVal::Symbol(topic), ScVal::Error(ScError::Contract(code)), ..]
+            if topic.as_slice() == b"error" =>
+        {
+            Some(*code)
+        }
+        _ => None,
+    })
+}
+
+/// Contract ids that raised an error in `events`, to fetch the specs of to name the errors.
+pub fn erroring_contracts(events: &[DiagnosticEvent]) -> Vec<Hash> {
+    let mut contracts = Vec::new();
+    for event in events {
+        if let (Some(contract), Some(ScVal::Symbol(topic))) =
+            (&event.event.contract_id, topics(event).first())
+        {
+            if topic.as_slice() == b"error" && !contracts.contains(contract) {
+                contracts.push(contract.clone());
+            }
+        }
+    }
+    contracts
+}
+
+/// Print the call stack of `events` to stderr, naming contract errors with `specs`.
+pub(crate) fn print_stack(print: &Print, events: &[DiagnosticEvent], specs: &HashMap<Hash, Spec>) {
+    let lines = stack(events, specs);
+    if lines.is_empty() {
+        return;
+    }
+    print.errorln("Call stack:");
+    for line in lines {
+        print.println(format!("  {line}"));
+    }
+}
+
+/// The `fn_call` and `fn_return` frames of `events`, indented by depth, with the errors and logs
+/// raised in them.
+pub(crate) fn stack(events: &[DiagnosticEvent], specs: &HashMap<Hash, Spec>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut calls: Vec<Option<Hash>> = Vec::new();
+    for event in events {
+        let ContractEventBody::V0(ContractEventV0 { data, .. }) = &event.event.body;
+        let topics = topics(event);
+        let Some(ScVal::Symbol(kind)) = topics.first() else {
+            continue;
+        };
+        let indent = "  ".repeat(calls.len());
+        match (kind.as_slice(), &topics[1..]) {
+            (b"fn_call", [ScVal::Bytes(contract), ScVal::Symbol(function), ..]) => {
+                let contract = <[u8; 32]>::try_from(contract.as_slice()).ok().map(Hash);
+                let name = contract.as_ref().map_or_else(
+                    || "?".to_string(),
+                    |c| stellar_strkey::Contract(c.0).to_string(),
+                );
+                lines.push(format!(
+                    "{indent}→ {name}.{}({})",
+                    function.to_utf8_string_lossy(),
+                    args(data)
+                ));
+                calls.push(contract);
+            }
+            (b"fn_return", [ScVal::Symbol(function), ..]) => {
+                calls.pop();
+                let indent = "  ".repeat(calls.len());
+                let returned = match data {
+                    ScVal::Void => String::new(),
+                    value => format!(" {}", value_string(value)),
+                };
+                lines.push(format!(
+                    "{indent}← {}{returned}",
+                    function.to_utf8_string_lossy()
+                ));
+            }
+            (b"error", [ScVal::Error(error), ..]) => {
+                let contract = event
+                    .event
+                    .contract_id
+                    .clone()
+                    .or_else(|| calls.last().cloned().flatten());
+                let name = match error {
+                    ScError::Contract(code) => contract
+                        .and_then(|c| specs.get(&c))
+                        .and_then(|spec| error_name(spec, *code))
+                        .map(|name| format!(" {name}"))
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                };
+                let message = match data {
+                    ScVal::Void => String::new(),
+                    data => format!(": {}", message(data)),
+                };
+                lines.push(format!("{indent}✗ {}{name}{message}", error_string(error)));
+            }
+            (b"log", _) => lines.push(format!("{indent}log: {}", message(data))),
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// The resources used by the invocation of `events`, of its `core_metrics` diagnostic events,
+/// e.g. `cpu_insn` and `read_entry`, in the order they were emitted.
+pub fn core_metrics(events: &[DiagnosticEvent]) -> Vec<(String, u64)> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let ContractEventBody::V0(ContractEventV0 { data, .. }) = &event.event.body;
+            match (topics(event), data) {
+                ([ScVal::Symbol(kind), ScVal::Symbol(name), ..], ScVal::U64(value))
+                    if kind.as_slice() == b"core_metrics" =>
+                {
+                    Some((name.to_utf8_string_lossy(), *value))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The resources a transaction can use, of the `ContractComputeV0` config setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub instructions: u64,
+    pub memory: u64,
+}
+
+impl Budget {
+    pub async fn fetch(client: &Client) -> Result<Option<Self>, Error> {
+        let key = LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+            config_setting_id: ConfigSettingId::ContractComputeV0,
+        });
+        for entry in client
+            .get_ledger_entries(&[key])
+            .await?
+            .entries
+            .unwrap_or_default()
+        {
+            if let LedgerEntryData::ConfigSetting(ConfigSettingEntry::ContractComputeV0(compute)) =
+                LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())?
+            {
+                return Ok(Some(Budget {
+                    instructions: u64::try_from(compute.tx_max_instructions).unwrap_or_default(),
+                    memory: compute.tx_memory_limit.into(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// What a failed invocation used of `budget`, one line per metric of `metrics`, see
+/// `core_metrics`. The CPU instructions and memory fall back to the `cost` of its simulation
+/// when it didn't emit them, e.g. when the RPC has diagnostic metrics disabled.
+pub fn budget_lines(
+    metrics: &[(String, u64)],
+    cost: Option<&rpc::Cost>,
+    budget: Option<Budget>,
+) -> Vec<String> {
+    let mut metrics = metrics.to_vec();
+    if let Some(cost) = cost {
+        for (name, value) in [("cpu_insn", cost.cpu_insns), ("mem_byte", cost.mem_bytes)] {
+            if value > 0 && !metrics.iter().any(|(n, _)| n == name) {
+                metrics.push((name.to_string(), value));
+            }
+        }
+    }
+    metrics
+        .iter()
+        .map(|(name, value)| {
+            let limit = match name.as_str() {
+                "cpu_insn" => budget.map(|b| b.instructions),
+                "mem_byte" => budget.map(|b| b.memory),
+                _ => None,
+            };
+            match limit.filter(|limit| *limit > 0) {
+                Some(limit) => format!(
+                    "{name}: {value} of {limit} ({}%)",
+                    value.saturating_mul(100) / limit
+                ),
+                None => format!("{name}: {value}"),
+            }
+        })
+        .collect()
+}
+
+/// Name of the case with `code` in the error enums of `spec`.
+pub fn error_name(spec: &Spec, code: u32) -> Option<String> {
+    spec.0.as_ref()?.iter().find_map(|entry| match entry {
+        ScSpecEntry::UdtErrorEnumV0(error_enum) => error_enum
+            .cases
+            .iter()
+            .find(|case| case.value == code)
+            .map(|case| case.name.to_utf8_string_lossy()),
+        _ => None,
+    })
+}
+
+/// `Error(Contract, #7)`, as the host formats errors.
+fn error_string(error: &ScError) -> String {
+    match error {
+        ScError::Contract(code) => format!("Error(Contract, #{code})"),
+        ScError::WasmVm(code)
+        | ScError::Context(code)
+        | ScError::Storage(code)
+        | ScError::Object(code)
+        | ScError::Crypto(code)
+        | ScError::Events(code)
+        | ScError::Budget(code)
+        | ScError::Value(code)
+        | ScError::Auth(code) => format!("Error({}, {})", error.name(), code.name()),
+    }
+}
+
+fn topics(event: &DiagnosticEvent) -> &[ScVal] {
+    let ContractEventBody::V0(ContractEventV0 { topics, .. }) = &event.event.body;
+    topics
+}
+
+/// Arguments of a call, given as a vec when there are several, the value when there is one.
+fn args(data: &ScVal) -> String {
+    match data {
+        ScVal::Void => String::new(),
+        ScVal::Vec(Some(args)) => args.iter().map(value_string).collect::<Vec<_>>().join(", "),
+        value => value_string(value),
+    }
+}
+
+/// Message of an error or log, given as a string, or a vec of the string and its arguments.
+fn message(data: &ScVal) -> String {
+    match data {
+        ScVal::String(message) => message.to_utf8_string_lossy(),
+        ScVal::Vec(Some(values)) => match values.split_first() {
+            Some((ScVal::String(message), args)) if !args.is_empty() => format!(
+                "{}, {}",
+                message.to_utf8_string_lossy(),
+                args.iter().map(value_string).collect::<Vec<_>>().join(", ")
+            ),
+            Some((ScVal::String(message), _)) => message.to_utf8_string_lossy(),
+            _ => value_string(data),
+        },
+        value => value_string(value),
+    }
+}
+
+fn value_string(value: &ScVal) -> String {
+    match value {
+        ScVal::Error(error) => error_string(error),
+        value => soroban_spec_tools::to_string(value).unwrap_or_else(|_| format!("{value:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        ContractEvent, ContractEventType, ExtensionPoint, ScErrorCode, ScSpecUdtErrorEnumCaseV0,
+        ScSpecUdtErrorEnumV0, ScString, ScSymbol,
+    };
+
+    fn event(contract: Option<Hash>, topics: Vec<ScVal>, data: ScVal) -> DiagnosticEvent {
+        DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: contract,
+                type_: ContractEventType::Diagnostic,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: topics.try_into().unwrap(),
+                    data,
+                }),
+            },
+        }
+    }
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn string(s: &str) -> ScVal {
+        ScVal::String(ScString(s.try_into().unwrap()))
+    }
+
+    #[test]
+    fn stack_with_named_contract_error() {
+        let contract = Hash([1; 32]);
+        let events = [
+            event(
+                None,
+                vec![
+                    symbol("fn_call"),
+                    ScVal::Bytes(contract.0.to_vec().try_into().unwrap()),
+                    symbol("withdraw"),
+                ],
+                ScVal::Vec(Some(vec![ScVal::U32(5), ScVal::U32(7)].try_into().unwrap())),
+            ),
+            event(
+                Some(contract.clone()),
+                vec![symbol("log")],
+                string("checking balance"),
+            ),
+            event(
+                Some(contract.clone()),
+                vec![symbol("error"), ScVal::Error(ScError::Contract(7))],
+                string("escalating error to panic"),
+            ),
+            event(
+                None,
+                vec![
+                    symbol("error"),
+                    ScVal::Error(ScError::Budget(ScErrorCode::ExceededLimit)),
+                ],
+                ScVal::Void,
+            ),
+        ];
+        let spec = Spec::new(vec![ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Error".try_into().unwrap(),
+            cases: vec![ScSpecUdtErrorEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: "InsufficientBalance".try_into().unwrap(),
+                value: 7,
+            }]
+            .try_into()
+            .unwrap(),
+        })]);
+        let specs = HashMap::from([(contract.clone(), spec)]);
+        assert_eq!(erroring_contracts(&events), [contract.clone()]);
+        assert_eq!(
+            stack(&events, &specs),
+            [
+                format!("→ {}.withdraw(5, 7)", stellar_strkey::Contract(contract.0)),
+                "  log: checking balance".to_string(),
+                "  ✗ Error(Contract, #7) InsufficientBalance: escalating error to panic"
+                    .to_string(),
+                "  ✗ Error(Budget, ExceededLimit)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_contract_error() {
+        let error = |error: ScError| {
+            event(
+                Some(Hash([1; 32])),
+                vec![symbol("error"), ScVal::Error(error)],
+                ScVal::Void,
+            )
+        };
+        assert_eq!(contract_error(&[]), None);
+        assert_eq!(
+            contract_error(&[error(ScError::Auth(ScErrorCode::InvalidAction))]),
+            None
+        );
+        assert_eq!(
+            contract_error(&[
+                error(ScError::Contract(3)),
+                error(ScError::Contract(12)),
+                error(ScError::Context(ScErrorCode::InvalidAction)),
+                event(
+                    None,
+                    vec![symbol("log")],
+                    ScVal::Error(ScError::Contract(1))
+                ),
+            ]),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn budget_of_core_metrics() {
+        let metric = |name: &str, value: u64| {
+            event(
+                None,
+                vec![symbol("core_metrics"), symbol(name)],
+                ScVal::U64(value),
+            )
+        };
+        let events = [
+            event(
+                None,
+                vec![
+                    symbol("error"),
+                    ScVal::Error(ScError::Budget(xdr::ScErrorCode::ExceededLimit)),
+                ],
+                ScVal::Void,
+            ),
+            metric("read_entry", 3),
+            metric("cpu_insn", 1200),
+            metric("mem_byte", 50),
+        ];
+        let metrics = core_metrics(&events);
+        assert_eq!(
+            metrics,
+            [
+                ("read_entry".to_string(), 3),
+                ("cpu_insn".to_string(), 1200),
+                ("mem_byte".to_string(), 50),
+            ]
+        );
+        let budget = Some(Budget {
+            instructions: 1000,
+            memory: 100,
+        });
+        let cost = rpc::Cost {
+            cpu_insns: 1,
+            mem_bytes: 1,
+        };
+        assert_eq!(
+            budget_lines(&metrics, Some(&cost), budget),
+            [
+                "read_entry: 3",
+                "cpu_insn: 1200 of 1000 (120%)",
+                "mem_byte: 50 of 100 (50%)",
+            ]
+        );
+        // Without the metrics, the cost of the simulation is reported.
+        let cost = rpc::Cost {
+            cpu_insns: 900,
+            mem_bytes: 0,
+        };
+        assert_eq!(
+            budget_lines(&[], Some(&cost), budget),
+            ["cpu_insn: 900 of 1000 (90%)"]
+        );
+        assert_eq!(budget_lines(&[], None, None), Vec::<String>::new());
+    }
+}
@@ -1,20 +1,43 @@
-use clap::arg;
+use clap::{arg, ValueEnum};
+use jsonrpsee_core::params::ObjectParams;
+use serde::{Deserialize, Serialize};
+use serde_aux::prelude::deserialize_number_from_string;
 
 use crate::assembled::Assembled;
+use crate::config::{
+    network::{self, Network},
+    sequence,
+};
 use crate::xdr;
 
 use crate::{commands::HEADING_RPC, deprecated_arg};
 
+/// Inclusion fee used when neither `--fee` nor a fee strategy is set, the network minimum.
+pub const DEFAULT_FEE: u32 = 100;
+
 const DEPRECATION_MESSAGE: &str = "--sim-only is deprecated and will be removed \
 in the future versions of CLI. The same functionality is offered by `tx simulate` command. To \
 replicate the behaviour, run `stellar <command> --build only | stellar tx simulate`";
 
 #[derive(Debug, clap::Args, Clone)]
 #[group(skip)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args {
-    /// fee amount for transaction, in stroops. 1 stroop = 0.0000001 xlm
-    #[arg(long, default_value = "100", env = "STELLAR_FEE", help_heading = HEADING_RPC)]
-    pub fee: u32,
+    /// Inclusion fee of the transaction in stroops, 1 stroop = 0.0000001 xlm. Takes precedence
+    /// over `--fee-strategy` [default: 100]
+    #[arg(long, visible_alias = "inclusion-fee", env = "STELLAR_FEE", help_heading = HEADING_RPC)]
+    pub fee: Option<u32>,
+    /// How to price the inclusion fee when `--fee` is not set. `p50` and `p90` use the percentiles
+    /// of the fees of recent ledgers from `getFeeStats`, which helps transactions get included
+    /// during surge pricing. Set a default with `stellar fee default`
+    #[arg(
+        long,
+        value_enum,
+        default_value = "fixed",
+        env = "STELLAR_FEE_STRATEGY",
+        help_heading = HEADING_RPC
+    )]
+    pub fee_strategy: Strategy,
     /// Output the cost execution to stderr
     #[arg(long = "cost", help_heading = HEADING_RPC)]
     pub cost: bool,
@@ -33,9 +56,131 @@ pub struct Args {
         value_parser = deprecated_arg!(bool, DEPRECATION_MESSAGE))
     ]
     pub sim_only: bool,
+    /// Return as soon as the transaction is sent and print its hash, instead of waiting for it to
+    /// be included in a ledger. Wait for it later with `stellar tx wait`
+    #[arg(long, help_heading = HEADING_RPC, overrides_with = "wait")]
+    pub no_wait: bool,
+    /// Wait for the transaction to be included in a ledger, the default
+    #[arg(long, help_heading = HEADING_RPC, overrides_with = "no_wait")]
+    pub wait: bool,
+    /// Build, simulate, and sign the transaction, then write the base64 xdr of the envelope to
+    /// stdout without sending it. The RPC endpoint, hash, and fees are logged to stderr
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with_all = ["build_only", "sim_only", "no_wait"])]
+    pub dry_run: bool,
+    /// How to pick the sequence number of the transaction. Use `pool` when commands with the
+    /// same source account run concurrently, and `stellar keys seq reset` if a transaction with
+    /// a reserved sequence number was rejected or never landed
+    #[arg(
+        long,
+        value_enum,
+        default_value = "network",
+        env = "STELLAR_SEQ_STRATEGY",
+        help_heading = HEADING_RPC
+    )]
+    pub seq_strategy: sequence::Strategy,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Network(#[from] network::Error),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Pay `--fee`, or the minimum fee of 100 stroops
+    #[default]
+    Fixed,
+    /// Pay the median inclusion fee of recent ledgers
+    P50,
+    /// Pay the 90th percentile inclusion fee of recent ledgers
+    P90,
+}
+
+impl std::fmt::Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Strategy::Fixed => "fixed",
+            Strategy::P50 => "p50",
+            Strategy::P90 => "p90",
+        })
+    }
+}
+
+/// Response of the `getFeeStats` RPC method.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub soroban_inclusion_fee: Distribution,
+    pub inclusion_fee: Distribution,
+    pub latest_ledger: u32,
+}
+
+/// Distribution of the inclusion fees per operation charged in recent ledgers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub min: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub mode: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p10: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p20: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p30: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p40: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p50: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p60: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p70: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p80: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p90: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p95: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p99: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub transaction_count: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub ledger_count: u32,
+}
+
+impl Stats {
+    pub async fn fetch(network: &Network) -> Result<Self, Error> {
+        Ok(network
+            .rpc_request("getFeeStats", ObjectParams::new())
+            .await?)
+    }
 }
 
 impl Args {
+    /// The inclusion fee to pay, `--fee` if it is set and otherwise priced by the fee strategy
+    /// from the fees of soroban or classic transactions.
+    pub async fn inclusion_fee(&self, network: &Network, soroban: bool) -> Result<u32, Error> {
+        if let Some(fee) = self.fee {
+            return Ok(fee);
+        }
+        if self.fee_strategy == Strategy::Fixed {
+            return Ok(DEFAULT_FEE);
+        }
+        let stats = Stats::fetch(network).await?;
+        Ok(self.fee_strategy.price(if soroban {
+            &stats.soroban_inclusion_fee
+        } else {
+            &stats.inclusion_fee
+        }))
+    }
+
     pub fn apply_to_assembled_txn(&self, txn: Assembled) -> Assembled {
         if let Some(instructions) = self.instructions {
             txn.set_max_instructions(instructions)
@@ -58,14 +203,62 @@ pub fn add_padding_to_instructions(txn: Assembled) -> Assembled {
     txn.set_max_instructions(instructions)
 }
 
+impl Strategy {
+    /// The fee of this strategy for a distribution, never less than the minimum fee.
+    pub fn price(self, fees: &Distribution) -> u32 {
+        let fee = match self {
+            Strategy::Fixed => return DEFAULT_FEE,
+            Strategy::P50 => fees.p50,
+            Strategy::P90 => fees.p90,
+        };
+        u32::try_from(fee).unwrap_or(u32::MAX).max(DEFAULT_FEE)
+    }
+}
+
 impl Default for Args {
     fn default() -> Self {
         Self {
-            fee: 100,
+            fee: None,
+            fee_strategy: Strategy::Fixed,
             cost: false,
             instructions: None,
             build_only: false,
             sim_only: false,
+            no_wait: false,
+            wait: false,
+            dry_run: false,
+            seq_strategy: sequence::Strategy::Network,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strategy_prices_from_fee_stats() {
+        let stats: Stats = serde_json::from_value(serde_json::json!({
+            "sorobanInclusionFee": {
+                "max": "210", "min": "100", "mode": "100", "p10": "100", "p20": "100",
+                "p30": "100", "p40": "100", "p50": "100", "p60": "100", "p70": "100",
+                "p80": "100", "p90": "120", "p95": "190", "p99": "200",
+                "transactionCount": "10", "ledgerCount": 50
+            },
+            "inclusionFee": {
+                "max": "100", "min": "100", "mode": "100", "p10": "100", "p20": "100",
+                "p30": "100", "p40": "100", "p50": "10", "p60": "100", "p70": "100",
+                "p80": "100", "p90": "100", "p95": "100", "p99": "100",
+                "transactionCount": "7", "ledgerCount": 10
+            },
+            "latestLedger": 4_519_945
+        }))
+        .unwrap();
+        assert_eq!(Strategy::P90.price(&stats.soroban_inclusion_fee), 120);
+        assert_eq!(Strategy::P50.price(&stats.inclusion_fee), DEFAULT_FEE);
+        assert_eq!(
+            Strategy::Fixed.price(&stats.soroban_inclusion_fee),
+            DEFAULT_FEE
+        );
+    }
+}
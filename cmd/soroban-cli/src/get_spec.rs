@@ -8,7 +8,7 @@ pub use soroban_spec_tools::contract as contract_spec;
 use crate::commands::global;
 use crate::config::{self, data, locator, network};
 use crate::rpc;
-use crate::utils::rpc::get_remote_wasm_from_hash;
+use crate::wasm;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -28,8 +28,12 @@ pub enum Error {
     Config(#[from] config::Error),
     #[error(transparent)]
     ContractSpec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
 }
 
+/// The spec of a deployed contract, from the cache of specs by Wasm hash, which includes the
+/// specs fetched from a registry with `contract spec fetch`, or read from its Wasm.
 ///
 /// # Errors
 pub async fn get_remote_contract_spec(
@@ -44,33 +48,23 @@ pub async fn get_remote_contract_spec(
         |c| c.get_network().map_err(Error::from),
     )?;
     tracing::trace!(?network);
+    let no_cache = global_args.is_some_and(|a| a.no_cache);
     let client = rpc::Client::new(&network.rpc_url)?;
-    // Get contract data
-    let r = client.get_contract_data(contract_id).await?;
-    tracing::trace!("{r:?}");
-
-    let ContractDataEntry {
-        val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
-        ..
-    } = r
-    else {
-        return Err(Error::MissingResult);
-    };
-
     // Get the contract spec entries based on the executable type
-    Ok(match executable {
+    Ok(match contract_executable(&client, contract_id).await? {
         ContractExecutable::Wasm(hash) => {
             let hash_str = hash.to_string();
-            if let Ok(entries) = data::read_spec(&hash_str) {
-                entries
-            } else {
-                let raw_wasm = get_remote_wasm_from_hash(&client, &hash).await?;
-                let res = contract_spec::Spec::new(&raw_wasm)?;
-                let res = res.spec;
-                if global_args.map_or(true, |a| !a.no_cache) {
-                    data::write_spec(&hash_str, &res)?;
+            match data::read_spec(&hash_str) {
+                Ok(entries) if !no_cache => entries,
+                _ => {
+                    let raw_wasm = wasm::fetch_from_hash(&client, &hash, no_cache).await?;
+                    let res = contract_spec::Spec::new(&raw_wasm)?;
+                    let res = res.spec;
+                    if !no_cache {
+                        data::write_spec(&hash_str, &res)?;
+                    }
+                    res
                 }
-                res
             }
         }
         ContractExecutable::StellarAsset => {
@@ -78,3 +72,22 @@ pub async fn get_remote_contract_spec(
         }
     })
 }
+
+/// The executable of the instance of a contract. It is always fetched rather than cached, as
+/// contracts can be upgraded to other Wasm, and specs are cached by the hash of their Wasm.
+///
+/// # Errors
+pub async fn contract_executable(
+    client: &rpc::Client,
+    contract_id: &[u8; 32],
+) -> Result<ContractExecutable, Error> {
+    let r = client.get_contract_data(contract_id).await?;
+    tracing::trace!("{r:?}");
+    match r {
+        ContractDataEntry {
+            val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
+            ..
+        } => Ok(executable),
+        _ => Err(Error::MissingResult),
+    }
+}
@@ -0,0 +1,299 @@
+//! A small Horizon client for the classic data that Stellar RPC does not index, such as the
+//! balances of an account, path finding, and claimable balances.
+use clap::arg;
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    commands::HEADING_RPC,
+    config::network::{passphrase, Network},
+    tx::builder,
+    utils::http,
+    xdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("no Horizon server is known for network {0:?}, use `--horizon-url` or `STELLAR_HORIZON_URL`")]
+    MissingUrl(String),
+    #[error("invalid Horizon URL {0}")]
+    InvalidUrl(String),
+    #[error("failed to fetch {url}: {error}")]
+    Fetch { url: Url, error: reqwest::Error },
+    #[error("invalid amount {0:?}")]
+    InvalidAmount(String),
+    #[error("invalid asset in Horizon response: {0}")]
+    InvalidAsset(String),
+    #[error("{url} responded with {status}: {detail}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+        detail: String,
+    },
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Horizon server endpoint, defaults to the SDF Horizon of testnet, futurenet, and mainnet,
+    /// and to the quickstart Horizon of a local network
+    #[arg(long, env = "STELLAR_HORIZON_URL", help_heading = HEADING_RPC)]
+    pub horizon_url: Option<String>,
+}
+
+impl Args {
+    pub fn client(&self, network: &Network) -> Result<Client, Error> {
+        Client::new(&self.url(network)?)
+    }
+
+    /// The Horizon URL given, or the default one of the network.
+    pub fn url(&self, network: &Network) -> Result<String, Error> {
+        match &self.horizon_url {
+            Some(url) => Ok(url.clone()),
+            None => default_url(network),
+        }
+    }
+}
+
+fn default_url(network: &Network) -> Result<String, Error> {
+    Ok(match network.network_passphrase.as_str() {
+        passphrase::TESTNET => "https://horizon-testnet.stellar.org".to_string(),
+        passphrase::FUTURENET => "https://horizon-futurenet.stellar.org".to_string(),
+        passphrase::MAINNET => "https://horizon.stellar.org".to_string(),
+        // Quickstart serves Horizon at the root of the same port as RPC.
+        passphrase::LOCAL => {
+            let mut url = Url::parse(&network.rpc_url)
+                .map_err(|_| Error::InvalidUrl(network.rpc_url.clone()))?;
+            url.set_path("/");
+            url.to_string()
+        }
+        other => return Err(Error::MissingUrl(other.to_string())),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    url: Url,
+}
+
+impl Client {
+    pub fn new(url: &str) -> Result<Self, Error> {
+        let mut url = Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_string()))?;
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self { url })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// GET a resource relative to the server URL, e.g. `accounts/G...`.
+    pub async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<Value, Error> {
+        let mut url = self
+            .url
+            .join(path)
+            .map_err(|_| Error::InvalidUrl(path.to_string()))?;
+        if !query.is_empty() {
+            url.query_pairs_mut()
+                .extend_pairs(query.iter().map(|(k, v)| (*k, v.as_str())));
+        }
+        self.get_url(url).await
+    }
+
+    async fn get_url(&self, url: Url) -> Result<Value, Error> {
+        tracing::debug!("GET {url}");
+        let fetch = |error| Error::Fetch {
+            url: url.clone(),
+            error,
+        };
+        let response = http::client()
+            .get(url.clone())
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(fetch)?;
+        let status = response.status();
+        let body: Value = response.json().await.map_err(fetch)?;
+        tracing::trace!("{body:#?}");
+        if !status.is_success() {
+            let detail = body
+                .get("detail")
+                .or_else(|| body.get("title"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            return Err(Error::Status {
+                url,
+                status,
+                detail,
+            });
+        }
+        Ok(body)
+    }
+
+    /// GET a collection and follow its `next` links until it is exhausted or `limit` records have
+    /// been read.
+    pub async fn records(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        limit: Option<usize>,
+    ) -> Result<Vec<Value>, Error> {
+        let mut records = Vec::new();
+        let mut page = self.get(path, query).await?;
+        loop {
+            let batch = page["_embedded"]["records"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if batch.is_empty() {
+                break;
+            }
+            records.extend(batch);
+            if limit.is_some_and(|limit| records.len() >= limit) {
+                records.truncate(limit.unwrap_or_default());
+                break;
+            }
+            let Some(next) = page["_links"]["next"]["href"]
+                .as_str()
+                .and_then(|href| Url::parse(href).ok())
+            else {
+                break;
+            };
+            page = self.get_url(next).await?;
+        }
+        Ok(records)
+    }
+
+    /// The account resource, including its balances and trustline flags.
+    pub async fn account(&self, account_id: &str) -> Result<Value, Error> {
+        self.get(&format!("accounts/{account_id}"), &[]).await
+    }
+}
+
+/// Format an amount in stroops as the 7 decimal string used by Horizon.
+pub fn amount(stroops: i64) -> String {
+    let sign = if stroops < 0 { "-" } else { "" };
+    let stroops = stroops.unsigned_abs();
+    format!("{sign}{}.{:07}", stroops / 10_000_000, stroops % 10_000_000)
+}
+
+/// Parse a 7 decimal amount returned by Horizon into stroops.
+pub fn parse_amount(value: &str) -> Result<i64, Error> {
+    let invalid = || Error::InvalidAmount(value.to_string());
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 7 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let whole: i64 = whole.parse().map_err(|_| invalid())?;
+    let fraction: i64 = format!("{fraction:0<7}").parse().map_err(|_| invalid())?;
+    whole
+        .checked_mul(10_000_000)
+        .and_then(|w| w.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Format an asset as Horizon does in list parameters, `native` or `CODE:ISSUER`.
+pub fn asset_string(asset: &xdr::Asset) -> String {
+    match asset {
+        xdr::Asset::Native => "native".to_string(),
+        xdr::Asset::CreditAlphanum4(a) => format!("{}:{}", a.asset_code, a.issuer),
+        xdr::Asset::CreditAlphanum12(a) => format!("{}:{}", a.asset_code, a.issuer),
+    }
+}
+
+/// Query parameters describing `asset`, e.g. `source_asset_type`, `source_asset_code`, and
+/// `source_asset_issuer` for the prefix `source_`.
+pub fn asset_params(prefix: &str, asset: &xdr::Asset) -> Vec<(String, String)> {
+    let (asset_type, code_issuer) = match asset {
+        xdr::Asset::Native => ("native", None),
+        xdr::Asset::CreditAlphanum4(a) => (
+            "credit_alphanum4",
+            Some((a.asset_code.to_string(), a.issuer.to_string())),
+        ),
+        xdr::Asset::CreditAlphanum12(a) => (
+            "credit_alphanum12",
+            Some((a.asset_code.to_string(), a.issuer.to_string())),
+        ),
+    };
+    let mut params = vec![(format!("{prefix}asset_type"), asset_type.to_string())];
+    if let Some((code, issuer)) = code_issuer {
+        params.push((format!("{prefix}asset_code"), code));
+        params.push((format!("{prefix}asset_issuer"), issuer));
+    }
+    params
+}
+
+/// Read the asset described by the `{prefix}asset_type`, `{prefix}asset_code`, and
+/// `{prefix}asset_issuer` fields of a Horizon record.
+pub fn asset_from(record: &Value, prefix: &str) -> Result<xdr::Asset, Error> {
+    let field = |name: &str| {
+        record[format!("{prefix}{name}")]
+            .as_str()
+            .unwrap_or_default()
+    };
+    if field("asset_type") == "native" {
+        return Ok(xdr::Asset::Native);
+    }
+    let asset = format!("{}:{}", field("asset_code"), field("asset_issuer"));
+    asset
+        .parse::<builder::Asset>()
+        .map(Into::into)
+        .map_err(|_| Error::InvalidAsset(asset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_round_trip() {
+        assert_eq!(amount(12_345_678), "1.2345678");
+        assert_eq!(amount(5), "0.0000005");
+        assert_eq!(parse_amount("1.2345678").unwrap(), 12_345_678);
+        assert_eq!(parse_amount("10").unwrap(), 100_000_000);
+        assert_eq!(parse_amount("0.5").unwrap(), 5_000_000);
+        assert!(parse_amount("0.12345678").is_err());
+    }
+
+    #[test]
+    fn asset_from_record() {
+        let record = serde_json::json!({
+            "source_asset_type": "credit_alphanum4",
+            "source_asset_code": "USDC",
+            "source_asset_issuer": "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF",
+            "destination_asset_type": "native",
+        });
+        let asset = asset_from(&record, "source_").unwrap();
+        assert_eq!(
+            asset_string(&asset),
+            "USDC:GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+        );
+        assert_eq!(
+            asset_from(&record, "destination_").unwrap(),
+            xdr::Asset::Native
+        );
+    }
+
+    #[test]
+    fn default_url_for_local_network_uses_rpc_host() {
+        let network = Network {
+            rpc_url: "http://localhost:8000/rpc".to_string(),
+            rpc_headers: Vec::new(),
+            network_passphrase: passphrase::LOCAL.to_string(),
+            rpc_signing_secret: None,
+        };
+        assert_eq!(default_url(&network).unwrap(), "http://localhost:8000/");
+    }
+
+    #[test]
+    fn client_url_ends_with_slash() {
+        let client = Client::new("https://example.com/horizon").unwrap();
+        assert_eq!(
+            client.url().join("accounts/x").unwrap().as_str(),
+            "https://example.com/horizon/accounts/x"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Whether the CLI may prompt. With `--no-input`, or when not run in a terminal, prompts fail
+//! with the flag to pass instead, rather than wait for an answer that never comes, e.g. in CI.
+use std::{
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+
+/// Turn prompts off for the rest of the run, see `--no-input`.
+pub fn disable() {
+    NO_INPUT.store(true, Ordering::Relaxed);
+}
+
+/// Whether prompts can be answered: `--no-input` is not set, and stdin and stdout are terminals.
+pub fn interactive() -> bool {
+    !NO_INPUT.load(Ordering::Relaxed) && io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Whether reading stdin would wait for someone to type, when prompts cannot be answered.
+pub fn stdin_would_wait() -> bool {
+    io::stdin().is_terminal() && !interactive()
+}
@@ -0,0 +1,115 @@
+//! Aborting a command on Ctrl-C or when its `--timeout` runs out, telling whether a transaction
+//! it was submitting may still land.
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use crate::{print::Print, xdr::Hash};
+
+static DEADLINE: OnceLock<Instant> = OnceLock::new();
+static SUBMISSION: Mutex<Option<Submission>> = Mutex::new(None);
+
+#[derive(Clone, Debug)]
+enum Submission {
+    /// The transaction is about to be sent, or is being sent.
+    Sending(Hash),
+    /// The transaction was accepted by the RPC server, and is waited for.
+    Sent(Hash),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Abort {
+    Interrupted,
+    TimedOut(Duration),
+}
+
+impl Display for Abort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Abort::Interrupted => write!(f, "interrupted"),
+            Abort::TimedOut(timeout) => {
+                write!(
+                    f,
+                    "timed out after {}",
+                    humantime::format_duration(*timeout)
+                )
+            }
+        }
+    }
+}
+
+impl Abort {
+    /// The exit code of the aborted command, 130 on Ctrl-C like shells use for `SIGINT`.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Abort::Interrupted => 130,
+            Abort::TimedOut(_) => 1,
+        }
+    }
+}
+
+/// Resolve on Ctrl-C, or when `timeout` runs out from now, with why to abort the command. The
+/// deadline is known to [`remaining`] as soon as this is called.
+pub fn abort(timeout: Option<Duration>) -> impl Future<Output = Abort> {
+    if let Some(timeout) = timeout {
+        let _ = DEADLINE.set(Instant::now() + timeout);
+    }
+    async move {
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => Abort::Interrupted,
+            () = deadline => Abort::TimedOut(timeout.unwrap_or_default()),
+        }
+    }
+}
+
+/// The time left before the command times out, if it has a `--timeout`.
+pub fn remaining() -> Option<Duration> {
+    DEADLINE
+        .get()
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Record that the transaction with `hash` is about to be sent.
+pub fn sending(hash: Hash) {
+    set(Some(Submission::Sending(hash)));
+}
+
+/// Record that the transaction with `hash` was accepted and is waited for.
+pub fn sent(hash: Hash) {
+    set(Some(Submission::Sent(hash)));
+}
+
+/// Record that the result of the transaction being submitted is known.
+pub fn done() {
+    set(None);
+}
+
+fn set(submission: Option<Submission>) {
+    if let Ok(mut current) = SUBMISSION.lock() {
+        *current = submission;
+    }
+}
+
+/// Print why the command is aborted and, if a transaction was being submitted, that it may still
+/// land and how to check on it.
+pub fn report(print: &Print, abort: Abort) {
+    print.errorln(format!("error: {abort}"));
+    let submission = SUBMISSION.lock().ok().and_then(|s| s.clone());
+    let (hash, state) = match submission {
+        None => return,
+        Some(Submission::Sending(hash)) => (hash, "was being sent"),
+        Some(Submission::Sent(hash)) => (hash, "was submitted"),
+    };
+    print.warnln(format!(
+        "Transaction {hash} {state} and may still land, check on it with `stellar tx wait {hash}`"
+    ));
+}
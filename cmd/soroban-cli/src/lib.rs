@@ -14,11 +14,19 @@ pub use cli::main;
 pub mod assembled;
 pub mod commands;
 pub mod config;
+pub mod diagnostic;
 pub mod fee;
 pub mod get_spec;
+pub mod horizon;
+pub mod input;
+pub mod interrupt;
 pub mod key;
 pub mod log;
+pub mod pagination;
+pub mod parquet;
+pub mod passkey;
 pub mod print;
+pub mod sep;
 pub mod signer;
 pub mod toid;
 pub mod tx;
@@ -3,7 +3,9 @@ use crate::xdr;
 pub mod auth;
 pub mod cost;
 pub mod event;
+pub mod file;
 pub mod footprint;
+pub mod otel;
 
 pub use auth::*;
 pub use cost::*;
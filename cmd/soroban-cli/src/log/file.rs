@@ -0,0 +1,235 @@
+//! Structured log of a run in JSON lines, written with `--log-file` or `-vv`: the command that was
+//! run, every RPC request and response, and the time each event happened since the start of the
+//! run, with secret keys, seed phrases, and RPC header values redacted.
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::config::{data, secret::Secret};
+
+/// Targets and levels written to the log file, including the bodies of the requests and responses
+/// that `jsonrpsee` traces.
+pub const FILTER: &str =
+    "stellar_cli=debug,soroban_cli=debug,stellar_rpc_client=debug,jsonrpsee_core::tracing=trace";
+
+/// Target of the events about the command being run.
+pub const COMMAND_TARGET: &str = "stellar_cli::log::command";
+
+const REDACTED: &str = "<redacted>";
+
+/// Log file written with `-vv` when `--log-file` is not set, which `stellar bug-report` bundles by
+/// default.
+pub fn default_path() -> Result<PathBuf, data::Error> {
+    Ok(data::data_local_dir()?.join("logs").join("last-run.jsonl"))
+}
+
+/// Layer writing each event as a line of JSON.
+pub struct JsonLines {
+    file: Mutex<File>,
+    start: Instant,
+}
+
+impl JsonLines {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            file: Mutex::new(File::create(path)?),
+            start: Instant::now(),
+        })
+    }
+}
+
+impl<S: Subscriber> Layer<S> for JsonLines {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let line = serde_json::json!({
+            "time": chrono::Utc::now().to_rfc3339(),
+            "elapsed_ms": self.start.elapsed().as_millis(),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": fields.0,
+        });
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", redact(&line.to_string()));
+        }
+    }
+}
+
+#[derive(Default)]
+struct Fields(Map<String, Value>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        // The arguments of the command and the requests and responses traced by `jsonrpsee` are
+        // JSON themselves
+        let value = match field.name() {
+            "args" | "send" | "recv" => {
+                serde_json::from_str(value).unwrap_or_else(|_| value.into())
+            }
+            _ => value.into(),
+        };
+        self.0.insert(field.name().to_string(), value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+/// Replace the secret keys in `s`, and the RPC signing secret of the environment.
+pub fn redact(s: &str) -> String {
+    static SECRET_KEY: OnceLock<Regex> = OnceLock::new();
+    let redacted = SECRET_KEY
+        .get_or_init(|| Regex::new(r"\bS[A-Z2-7]{55}\b").unwrap())
+        .replace_all(s, REDACTED);
+    match std::env::var("STELLAR_RPC_SIGNING_SECRET") {
+        Ok(secret) if !secret.is_empty() => redacted.replace(&secret, REDACTED),
+        _ => redacted.into_owned(),
+    }
+}
+
+/// The arguments of the command, with the secrets, the RPC signing secret, and the values of the
+/// RPC headers redacted, and the RPC and archive URLs cut down to their origin.
+pub fn redact_args(args: impl IntoIterator<Item = OsString>) -> Vec<String> {
+    let mut header_next = false;
+    let mut signing_secret_next = false;
+    let mut url_next = false;
+    args.into_iter()
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            let redacted = if header_next {
+                redact_header(&arg)
+            } else if signing_secret_next {
+                REDACTED.to_string()
+            } else if url_next {
+                redact_url(&arg)
+            } else if let Some(header) = arg.strip_prefix("--rpc-header=") {
+                format!("--rpc-header={}", redact_header(header))
+            } else if let Some((flag, url)) = URL_FLAGS
+                .iter()
+                .find_map(|flag| Some((flag, arg.strip_prefix(flag)?.strip_prefix('=')?)))
+            {
+                format!("{flag}={}", redact_url(url))
+            } else if arg.starts_with("--rpc-signing-secret=") {
+                format!("--rpc-signing-secret={REDACTED}")
+            } else if Secret::from_str(&arg).is_ok() {
+                REDACTED.to_string()
+            } else {
+                redact(&arg)
+            };
+            header_next = arg == "--rpc-header";
+            signing_secret_next = arg == "--rpc-signing-secret";
+            url_next = URL_FLAGS.contains(&arg.as_ref());
+            redacted
+        })
+        .collect()
+}
+
+/// Flags taking a URL, whose path or query can hold API keys.
+const URL_FLAGS: [&str; 2] = ["--rpc-url", "--archive-url"];
+
+/// Only the origin of `url`, since its path or query can hold API keys.
+pub fn redact_url(url: &str) -> String {
+    url::Url::parse(url).map_or_else(
+        |_| REDACTED.to_string(),
+        |u| u.origin().ascii_serialization(),
+    )
+}
+
+fn redact_header(header: &str) -> String {
+    match header.split_once(':') {
+        Some((name, _)) => format!("{name}:{REDACTED}"),
+        None => REDACTED.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secrets() {
+        let secret = "SBF3ISHUUAHHQZ32WVGTSCL7P3KXZBJFWD6RZ6DKNRHCTLXQKMMJQNWU";
+        let Secret::SeedPhrase { seed_phrase } = Secret::test_seed_phrase().unwrap() else {
+            panic!("expected a seed phrase");
+        };
+        assert_eq!(
+            redact(&format!(r#"{{"secret":"{secret}"}}"#)),
+            r#"{"secret":"<redacted>"}"#
+        );
+        let args = [
+            "stellar",
+            "tx",
+            "send",
+            "--source",
+            secret,
+            "--rpc-header",
+            "Authorization: Bearer abc",
+            "--rpc-header=X-Api-Key:def",
+            "--source-account",
+            &seed_phrase,
+            "--network",
+            "testnet",
+            "--rpc-signing-secret",
+            "hunter2",
+            "--rpc-signing-secret=hunter2",
+            "--rpc-url",
+            "https://rpc.example.com/v1/abc123?key=def",
+            "--archive-url=https://archive.example.com:8443/abc123",
+        ];
+        assert_eq!(
+            redact_args(args.map(OsString::from)),
+            [
+                "stellar",
+                "tx",
+                "send",
+                "--source",
+                "<redacted>",
+                "--rpc-header",
+                "Authorization:<redacted>",
+                "--rpc-header=X-Api-Key:<redacted>",
+                "--source-account",
+                "<redacted>",
+                "--network",
+                "testnet",
+                "--rpc-signing-secret",
+                "<redacted>",
+                "--rpc-signing-secret=<redacted>",
+                "--rpc-url",
+                "https://rpc.example.com",
+                "--archive-url=https://archive.example.com:8443",
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Export of the spans of a run, such as simulating, signing, submitting, and polling for
+//! transactions, to an OpenTelemetry collector over OTLP/HTTP with JSON encoding, e.g. Jaeger or
+//! Tempo.
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+use serde_json::{json, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::{commands::version, utils::http};
+
+/// Env var with the headers to send to the collector, e.g. `authorization=Basic abc,x-org=1`.
+pub const HEADERS_ENV: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+
+/// Targets and levels of the spans exported, and of the events with the errors they end with.
+pub const FILTER: &str = "stellar_cli=debug,soroban_cli=debug";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    #[error("exporting traces to {endpoint}: {error}")]
+    Export {
+        endpoint: url::Url,
+        error: reqwest::Error,
+    },
+}
+
+/// Layer collecting the spans of the run in a single trace, to export with [`Otlp::export`] once
+/// the run is done.
+#[derive(Clone)]
+pub struct Otlp {
+    endpoint: url::Url,
+    trace_id: [u8; 16],
+    spans: Arc<Mutex<Vec<Value>>>,
+}
+
+struct SpanData {
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start: u128,
+    attributes: Vec<Value>,
+    error: Option<String>,
+}
+
+impl Otlp {
+    /// Collector at `endpoint`, the base URL of the OTLP/HTTP receiver, e.g.
+    /// `http://localhost:4318`.
+    pub fn new(endpoint: &str) -> Result<Self, Error> {
+        let endpoint = url::Url::parse(endpoint)?;
+        let endpoint = endpoint.join(&format!(
+            "{}/v1/traces",
+            endpoint.path().trim_end_matches('/')
+        ))?;
+        Ok(Self {
+            endpoint,
+            trace_id: rand::thread_rng().gen(),
+            spans: Arc::default(),
+        })
+    }
+
+    /// Send the spans closed so far to the collector.
+    pub async fn export(&self) -> Result<(), Error> {
+        let spans = std::mem::take(&mut *self.spans.lock().unwrap());
+        if spans.is_empty() {
+            return Ok(());
+        }
+        let body = json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [
+                        attribute("service.name", "stellar-cli".into()),
+                        attribute("service.version", version::pkg().into()),
+                    ],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "stellar-cli", "version": version::pkg() },
+                    "spans": spans,
+                }],
+            }],
+        });
+        let mut request = http::client().post(self.endpoint.clone()).json(&body);
+        for (name, value) in std::env::var(HEADERS_ENV)
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|header| header.split_once('='))
+        {
+            request = request.header(name.trim(), value.trim());
+        }
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| Error::Export {
+                endpoint: self.endpoint.clone(),
+                error,
+            })?;
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for Otlp
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent_span_id = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanData>().map(|d| d.span_id));
+        let mut attributes = Attributes::default();
+        attrs.record(&mut attributes);
+        span.extensions_mut().insert(SpanData {
+            span_id: rand::thread_rng().gen(),
+            parent_span_id,
+            start: now(),
+            attributes: attributes.0,
+            error: None,
+        });
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                let mut attributes = Attributes::default();
+                values.record(&mut attributes);
+                data.attributes.extend(attributes.0);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // Errors logged in a span, or returned by an instrumented function, mark it as failed
+        let mut error = SpanError::default();
+        event.record(&mut error);
+        let error = error.error.or(error
+            .message
+            .filter(|_| *event.metadata().level() == Level::ERROR));
+        if let (Some(error), Some(span)) = (error, ctx.event_span(event)) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                data.error = Some(error);
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        let status = match data.error {
+            Some(message) => json!({ "code": 2, "message": message }),
+            None => json!({ "code": 1 }),
+        };
+        let mut json = json!({
+            "traceId": hex::encode(self.trace_id),
+            "spanId": hex::encode(data.span_id),
+            "name": span.name(),
+            "kind": 1,
+            "startTimeUnixNano": data.start.to_string(),
+            "endTimeUnixNano": now().to_string(),
+            "attributes": data.attributes,
+            "status": status,
+        });
+        if let Some(parent) = data.parent_span_id {
+            json["parentSpanId"] = hex::encode(parent).into();
+        }
+        self.spans.lock().unwrap().push(json);
+    }
+}
+
+fn now() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+fn attribute(key: &str, value: Value) -> Value {
+    let value = match value {
+        Value::Bool(b) => json!({ "boolValue": b }),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "intValue": n.to_string() }),
+        Value::Number(n) => json!({ "doubleValue": n }),
+        Value::String(s) => json!({ "stringValue": s }),
+        value => json!({ "stringValue": value.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+#[derive(Default)]
+struct Attributes(Vec<Value>);
+
+impl Visit for Attributes {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push(attribute(field.name(), format!("{value:?}").into()));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(attribute(field.name(), value.into()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.push(attribute(field.name(), value.into()));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.push(attribute(field.name(), value.into()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.push(attribute(field.name(), value.into()));
+    }
+}
+
+#[derive(Default)]
+struct SpanError {
+    error: Option<String>,
+    message: Option<String>,
+}
+
+impl Visit for SpanError {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "error" => self.error = Some(format!("{value:?}")),
+            "message" => self.message = Some(format!("{value:?}")),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn collects_nested_spans() {
+        let otlp = Otlp::new("http://localhost:4318/").unwrap();
+        assert_eq!(otlp.endpoint.as_str(), "http://localhost:4318/v1/traces");
+        let subscriber = tracing_subscriber::registry().with(otlp.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let command = tracing::info_span!("command", command = "tx send").entered();
+            tracing::info_span!("submit", hash = tracing::field::Empty).in_scope(|| {
+                tracing::Span::current().record("hash", "abc");
+                tracing::error!("failed");
+            });
+            command.exit();
+        });
+        let spans = otlp.spans.lock().unwrap();
+        let [submit, command] = &spans[..] else {
+            panic!("expected two spans, got {spans:?}");
+        };
+        assert_eq!(submit["name"], "submit");
+        assert_eq!(submit["parentSpanId"], command["spanId"]);
+        assert_eq!(submit["traceId"], command["traceId"]);
+        assert_eq!(
+            submit["attributes"][0],
+            json!({ "key": "hash", "value": { "stringValue": "abc" } })
+        );
+        assert_eq!(submit["status"], json!({ "code": 2, "message": "failed" }));
+        assert_eq!(
+            command["attributes"][0],
+            json!({ "key": "command", "value": { "stringValue": "tx send" } })
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Options and helpers shared by the commands that page through RPC results, such as `events`
+//! and `tx ls`, so that historical scans can run unattended within the rate limits of RPC
+//! providers.
+use std::time::{Duration, Instant};
+
+use clap::arg;
+use jsonrpsee_core::params::ObjectParams;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+
+use crate::{
+    config::network::{self, Network},
+    xdr::{self, Limits, ReadXdr, ScVal},
+};
+
+/// Largest number of transactions requested per page of `getTransactions`.
+const TRANSACTIONS_PAGE_LIMIT: usize = 200;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Bounds and pace of a scan across pages.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Last ledger to include. Pages are read until a later ledger or the latest ledger is reached
+    #[arg(long)]
+    pub end_ledger: Option<u32>,
+    /// Maximum number of RPC requests per second while reading pages, e.g. `2` or `0.5`
+    #[arg(long, value_parser = parse_rps)]
+    pub rps: Option<f64>,
+}
+
+impl Args {
+    pub fn rate_limiter(&self) -> RateLimiter {
+        RateLimiter {
+            interval: self.rps.map(|rps| Duration::from_secs_f64(1.0 / rps)),
+            last: None,
+        }
+    }
+
+    /// Whether `ledger` is past the end of the scan.
+    pub fn past_end(&self, ledger: u32) -> bool {
+        self.end_ledger.is_some_and(|end| ledger > end)
+    }
+}
+
+fn parse_rps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rps) if rps > 0.0 && rps.is_finite() => Ok(rps),
+        _ => Err(format!(
+            "expected a positive number of requests per second, got {s:?}"
+        )),
+    }
+}
+
+/// Parse the maximum number of results of a scan, which RPC would take as its default page size
+/// if it were 0.
+pub fn parse_limit(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(format!("expected a positive number of results, got {s:?}")),
+    }
+}
+
+/// Spaces requests out to a maximum rate.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Option<Duration>,
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Wait until the next request can be sent.
+    pub async fn wait(&mut self) {
+        if let (Some(interval), Some(last)) = (self.interval, self.last) {
+            let elapsed = last.elapsed();
+            if elapsed < interval {
+                tokio::time::sleep(interval - elapsed).await;
+            }
+        }
+        self.last = Some(Instant::now());
+    }
+}
+
+/// A page of `getTransactions` as the RPC server returns it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionsPage<T> {
+    #[serde(default = "Vec::new")]
+    transactions: Vec<T>,
+    cursor: String,
+}
+
+/// Reads the transactions of `getTransactions` page by page, within the rate limit of the scan
+/// and up to a limit.
+pub struct TransactionPages<'a> {
+    network: &'a Network,
+    limiter: RateLimiter,
+    start_ledger: u32,
+    cursor: Option<String>,
+    remaining: usize,
+    done: bool,
+}
+
+impl<'a> TransactionPages<'a> {
+    /// Pages from `cursor`, or from `start_ledger` without one, of at most `limit` transactions
+    /// in all.
+    pub fn new(
+        network: &'a Network,
+        args: &Args,
+        start_ledger: u32,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> Self {
+        TransactionPages {
+            network,
+            limiter: args.rate_limiter(),
+            start_ledger,
+            cursor,
+            remaining: limit.unwrap_or(usize::MAX),
+            done: false,
+        }
+    }
+
+    /// The next page, `None` after the last page or once the limit is read.
+    pub async fn next<T: DeserializeOwned>(&mut self) -> Result<Option<Vec<T>>, Error> {
+        if self.done || self.remaining == 0 {
+            return Ok(None);
+        }
+        let page_limit = self.remaining.min(TRANSACTIONS_PAGE_LIMIT);
+        let mut params = ObjectParams::new();
+        let mut pagination = json!({ "limit": page_limit });
+        match &self.cursor {
+            Some(cursor) => pagination["cursor"] = json!(cursor),
+            None => params.insert("startLedger", self.start_ledger)?,
+        }
+        params.insert("pagination", pagination)?;
+        self.limiter.wait().await;
+        let page: TransactionsPage<T> = self.network.rpc_request("getTransactions", params).await?;
+        self.done = page.transactions.len() < page_limit;
+        self.remaining = self.remaining.saturating_sub(page.transactions.len());
+        self.cursor = Some(page.cursor);
+        Ok(Some(page.transactions))
+    }
+
+    /// The cursor to continue reading after the pages read so far.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+}
+
+/// A page of `getEvents` as the RPC server returns it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsPage {
+    #[serde(default)]
+    pub events: Vec<Event>,
+    pub latest_ledger: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// An event of a `getEvents` page, keeping the transaction hash that the RPC client does not
+/// decode.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    #[serde(default)]
+    pub contract_id: String,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    pub topic: Vec<String>,
+    pub value: String,
+}
+
+impl Event {
+    /// The topics and value of the event, decoded from XDR.
+    pub fn decode(&self) -> Result<(Vec<ScVal>, ScVal), xdr::Error> {
+        let topics = self
+            .topic
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = ScVal::from_xdr_base64(&self.value, Limits::none())?;
+        Ok((topics, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rate_limiter_spaces_requests() {
+        let args = Args {
+            end_ledger: Some(10),
+            rps: Some(50.0),
+        };
+        assert!(!args.past_end(10));
+        assert!(args.past_end(11));
+        let mut limiter = args.rate_limiter();
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.wait().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        assert!(parse_rps("0").is_err());
+        assert_eq!(parse_rps("0.5"), Ok(0.5));
+    }
+}
@@ -0,0 +1,359 @@
+//! A minimal writer of Parquet files for exports, with required columns of 64-bit integers,
+//! booleans, and UTF-8 strings in a single row group, PLAIN encoded and uncompressed, which
+//! readers such as `DuckDB`, `pandas`, or `Spark` load as is.
+use std::io::{self, Write};
+
+use crate::commands::version;
+
+const MAGIC: &[u8] = b"PAR1";
+
+/// Values of a column, one per row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Int64(Vec<i64>),
+    Boolean(Vec<bool>),
+    Utf8(Vec<String>),
+}
+
+impl Column {
+    fn len(&self) -> usize {
+        match self {
+            Column::Int64(values) => values.len(),
+            Column::Boolean(values) => values.len(),
+            Column::Utf8(values) => values.len(),
+        }
+    }
+
+    /// The physical type of the column in the schema.
+    fn physical_type(&self) -> i32 {
+        match self {
+            Column::Boolean(_) => 0,
+            Column::Int64(_) => 2,
+            Column::Utf8(_) => 6,
+        }
+    }
+
+    /// The values, PLAIN encoded.
+    fn plain(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        match self {
+            Column::Int64(values) => {
+                for value in values {
+                    data.extend(value.to_le_bytes());
+                }
+            }
+            Column::Boolean(values) => {
+                for chunk in values.chunks(8) {
+                    let byte = chunk
+                        .iter()
+                        .enumerate()
+                        .fold(0u8, |byte, (i, value)| byte | (u8::from(*value) << i));
+                    data.push(byte);
+                }
+            }
+            Column::Utf8(values) => {
+                for value in values {
+                    data.extend(u32::try_from(value.len()).unwrap_or(u32::MAX).to_le_bytes());
+                    data.extend(value.as_bytes());
+                }
+            }
+        }
+        data
+    }
+}
+
+/// Write `columns`, named and of the same length, as a Parquet file.
+pub fn write(out: &mut impl Write, columns: &[(&str, Column)]) -> io::Result<()> {
+    let rows = columns.first().map_or(0, |(_, column)| column.len());
+    if columns.iter().any(|(_, column)| column.len() != rows) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "columns of different lengths",
+        ));
+    }
+    let rows = i64::try_from(rows).unwrap_or(i64::MAX);
+    let mut file = MAGIC.to_vec();
+    // Offset and size of the single data page of each column.
+    let mut chunks = Vec::new();
+    for (_, column) in columns {
+        let data = column.plain();
+        let mut header = Compact::default();
+        header.begin();
+        header.i32(1, 0); // DATA_PAGE
+        header.i32(2, size(data.len()));
+        header.i32(3, size(data.len()));
+        header.struct_field(5);
+        header.i32(1, size(column.len()));
+        header.i32(2, 0); // PLAIN
+        header.i32(3, 3); // RLE, no definition levels as the column is required
+        header.i32(4, 3); // RLE, no repetition levels
+        header.end();
+        header.end();
+        let offset = i64::try_from(file.len()).unwrap_or(i64::MAX);
+        file.extend(&header.buf);
+        file.extend(&data);
+        chunks.push((
+            offset,
+            i64::try_from(file.len()).unwrap_or(i64::MAX) - offset,
+        ));
+    }
+
+    let mut meta = Compact::default();
+    meta.begin();
+    meta.i32(1, 1); // version
+    meta.list(2, STRUCT, columns.len() + 1);
+    meta.begin();
+    meta.string(4, "schema");
+    meta.i32(5, size(columns.len()));
+    meta.end();
+    for (name, column) in columns {
+        meta.begin();
+        meta.i32(1, column.physical_type());
+        meta.i32(3, 0); // REQUIRED
+        meta.string(4, name);
+        if let Column::Utf8(_) = column {
+            meta.i32(6, 0); // UTF8
+        }
+        meta.end();
+    }
+    meta.i64(3, rows);
+    meta.list(4, STRUCT, 1);
+    meta.begin();
+    meta.list(1, STRUCT, columns.len());
+    for ((name, column), (offset, len)) in columns.iter().zip(&chunks) {
+        meta.begin();
+        meta.i64(2, *offset);
+        meta.struct_field(3);
+        meta.i32(1, column.physical_type());
+        meta.list(2, I32, 2);
+        meta.zigzag(0); // PLAIN
+        meta.zigzag(3); // RLE
+        meta.list(3, BINARY, 1);
+        meta.bytes(name.as_bytes());
+        meta.i32(4, 0); // UNCOMPRESSED
+        meta.i64(5, rows);
+        meta.i64(6, *len);
+        meta.i64(7, *len);
+        meta.i64(9, *offset);
+        meta.end();
+        meta.end();
+    }
+    meta.i64(2, chunks.iter().map(|(_, len)| len).sum());
+    meta.i64(3, rows);
+    meta.end();
+    meta.string(6, &format!("stellar-cli version {}", version::pkg()));
+    meta.end();
+
+    file.extend(&meta.buf);
+    file.extend(
+        u32::try_from(meta.buf.len())
+            .unwrap_or(u32::MAX)
+            .to_le_bytes(),
+    );
+    file.extend(MAGIC);
+    out.write_all(&file)
+}
+
+fn size(len: usize) -> i32 {
+    i32::try_from(len).unwrap_or(i32::MAX)
+}
+
+const I32: u8 = 5;
+const I64: u8 = 6;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const STRUCT: u8 = 12;
+
+/// Encoder of the Thrift compact protocol of the Parquet metadata.
+#[derive(Default)]
+struct Compact {
+    buf: Vec<u8>,
+    /// The last field id written in each of the structs being written.
+    last_ids: Vec<i16>,
+}
+
+impl Compact {
+    #[allow(clippy::cast_possible_truncation)]
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn zigzag(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn header(&mut self, id: i16, kind: u8) {
+        let last = self.last_ids.last_mut().expect("field outside of a struct");
+        let delta = id - *last;
+        *last = id;
+        if (1..=15).contains(&delta) {
+            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+            self.buf.push(((delta as u8) << 4) | kind);
+        } else {
+            self.buf.push(kind);
+            self.zigzag(id.into());
+        }
+    }
+
+    /// Start a struct, at the top level or as an element of a list.
+    fn begin(&mut self) {
+        self.last_ids.push(0);
+    }
+
+    fn end(&mut self) {
+        self.buf.push(0);
+        self.last_ids.pop();
+    }
+
+    /// Start a struct as the field `id`, ended with [`Compact::end`].
+    fn struct_field(&mut self, id: i16) {
+        self.header(id, STRUCT);
+        self.begin();
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.header(id, I32);
+        self.zigzag(value.into());
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.header(id, I64);
+        self.zigzag(value);
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.varint(value.len() as u64);
+        self.buf.extend(value);
+    }
+
+    fn string(&mut self, id: i16, value: &str) {
+        self.header(id, BINARY);
+        self.bytes(value.as_bytes());
+    }
+
+    /// Start a list of `len` elements of `kind` as the field `id`, followed by its elements.
+    fn list(&mut self, id: i16, kind: u8, len: usize) {
+        self.header(id, LIST);
+        if len < 15 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.buf.push(((len as u8) << 4) | kind);
+        } else {
+            self.buf.push(0xf0 | kind);
+            self.varint(len as u64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_columns_and_footer() {
+        let mut out = Vec::new();
+        write(
+            &mut out,
+            &[
+                ("ledger", Column::Int64(vec![1, 2])),
+                (
+                    "hash",
+                    Column::Utf8(vec!["ab".to_string(), "c".to_string()]),
+                ),
+                ("soroban", Column::Boolean(vec![true, false])),
+            ],
+        )
+        .unwrap();
+        assert!(out.starts_with(MAGIC) && out.ends_with(MAGIC));
+        let footer = out.len() - 8;
+        let meta_len = u32::from_le_bytes(out[footer..footer + 4].try_into().unwrap()) as usize;
+        let meta = &out[footer - meta_len..footer];
+        // The file metadata is a struct starting with its version, 1.
+        assert_eq!(&meta[..2], &[0x15, 0x02]);
+        // The first page holds the two integers after its header.
+        let ints = [1i64.to_le_bytes(), 2i64.to_le_bytes()].concat();
+        assert!(out.windows(16).any(|w| w == ints.as_slice()));
+        assert!(out
+            .windows(11)
+            .any(|w| w == b"\x02\x00\x00\x00ab\x01\x00\x00\x00c"));
+
+        let mut compact = Compact::default();
+        compact.zigzag(-1);
+        compact.zigzag(300);
+        assert_eq!(compact.buf, [0x01, 0xd8, 0x04]);
+        assert!(write(
+            &mut Vec::new(),
+            &[
+                ("a", Column::Int64(vec![1])),
+                ("b", Column::Boolean(Vec::new()))
+            ]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reads_back_with_a_parquet_reader() {
+        use ::parquet::{
+            file::reader::{FileReader, SerializedFileReader},
+            record::Field,
+        };
+
+        let hashes = (0..20).map(|i| "h".repeat(i)).collect::<Vec<_>>();
+        let mut out = tempfile::tempfile().unwrap();
+        write(
+            &mut out,
+            &[
+                (
+                    "ledger",
+                    Column::Int64((0..20).map(|i| i * 1000 - 3).collect()),
+                ),
+                ("hash", Column::Utf8(hashes.clone())),
+                (
+                    "soroban",
+                    Column::Boolean((0..20).map(|i| i % 3 == 0).collect()),
+                ),
+            ],
+        )
+        .unwrap();
+
+        let reader = SerializedFileReader::new(out).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 20);
+        assert_eq!(metadata.num_row_groups(), 1);
+        let columns = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["ledger", "hash", "soroban"]);
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .into_columns()
+                    .into_iter()
+                    .map(|(_, field)| field)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 20);
+        for (i, row) in rows.into_iter().enumerate() {
+            let i64_ = i64::try_from(i).unwrap();
+            assert_eq!(
+                row,
+                [
+                    Field::Long(i64_ * 1000 - 3),
+                    Field::Str(hashes[i].clone()),
+                    Field::Bool(i % 3 == 0),
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! secp256r1 (passkey) keys and `WebAuthn` assertions for smart wallet contracts.
+//!
+//! Passkeys sign a `WebAuthn` assertion rather than the payload itself: the
+//! authenticator signs `authenticator_data || sha256(client_data_json)`, where
+//! the client data embeds the payload as its base64url encoded `challenge`.
+//! The resulting signature follows the layout expected by the `webauthn`
+//! wallet in soroban-examples, a contract type struct with the fields
+//! `authenticator_data`, `client_data_json`, `id`, and `signature`.
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use p256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+use sha2::{Digest, Sha256};
+
+use crate::xdr::{self, ScBytes, ScMap, ScSymbol, ScVal};
+
+/// Flags of the authenticator data: user present and user verified.
+const FLAGS_UP_UV: u8 = 0b0000_0101;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid secp256r1 secret key")]
+    InvalidSecretKey,
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+pub fn generate() -> SigningKey {
+    SigningKey::random(&mut rand::rngs::OsRng)
+}
+
+pub fn from_hex(secret_key: &str) -> Result<SigningKey, Error> {
+    let bytes = hex::decode(secret_key).map_err(|_| Error::InvalidSecretKey)?;
+    SigningKey::from_slice(&bytes).map_err(|_| Error::InvalidSecretKey)
+}
+
+pub fn to_hex(key: &SigningKey) -> String {
+    hex::encode(key.to_bytes())
+}
+
+/// The uncompressed SEC1 encoding of the public key (`0x04 || x || y`), as stored by wallets.
+pub fn public_key(key: &SigningKey) -> [u8; 65] {
+    let point = key.verifying_key().to_encoded_point(false);
+    point
+        .as_bytes()
+        .try_into()
+        .expect("uncompressed point is 65 bytes")
+}
+
+/// The credential id used for a key, derived from its public key.
+pub fn credential_id(key: &SigningKey) -> [u8; 32] {
+    Sha256::digest(public_key(key)).into()
+}
+
+/// A `WebAuthn` assertion over a Soroban authorization payload.
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+    pub id: Vec<u8>,
+    /// Compact `r || s` signature with a low `s`.
+    pub signature: [u8; 64],
+}
+
+/// Produce the assertion a passkey would for `payload` on the relying party `rp_id` at `origin`.
+pub fn sign(
+    key: &SigningKey,
+    payload: &[u8; 32],
+    rp_id: &str,
+    origin: &str,
+) -> Result<Assertion, Error> {
+    let mut authenticator_data = Sha256::digest(rp_id.as_bytes()).to_vec();
+    authenticator_data.push(FLAGS_UP_UV);
+    authenticator_data.extend_from_slice(&0u32.to_be_bytes());
+
+    let client_data_json = serde_json::to_vec(&serde_json::json!({
+        "type": "webauthn.get",
+        "challenge": URL_SAFE_NO_PAD.encode(payload),
+        "origin": origin,
+        "crossOrigin": false,
+    }))?;
+
+    let mut message = authenticator_data.clone();
+    message.extend_from_slice(&Sha256::digest(&client_data_json));
+    let signature: Signature = key.sign(&message);
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    Ok(Assertion {
+        authenticator_data,
+        client_data_json,
+        id: credential_id(key).to_vec(),
+        signature: signature.to_bytes().into(),
+    })
+}
+
+impl Assertion {
+    /// Encode the assertion as the `Signature` contract type of the smart wallet.
+    pub fn to_scval(&self) -> Result<ScVal, Error> {
+        let bytes = |b: &[u8]| -> Result<ScVal, Error> { Ok(ScVal::Bytes(ScBytes(b.try_into()?))) };
+        let field =
+            |name: &str| -> Result<ScVal, Error> { Ok(ScVal::Symbol(ScSymbol(name.try_into()?))) };
+        Ok(ScVal::Map(Some(ScMap::sorted_from(vec![
+            (
+                field("authenticator_data")?,
+                bytes(&self.authenticator_data)?,
+            ),
+            (field("client_data_json")?, bytes(&self.client_data_json)?),
+            (field("id")?, bytes(&self.id)?),
+            (field("signature")?, bytes(&self.signature)?),
+        ])?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::{signature::Verifier, VerifyingKey};
+
+    #[test]
+    fn assertion_verifies_against_public_key() {
+        let key = generate();
+        let payload = [9u8; 32];
+        let assertion = sign(&key, &payload, "example.com", "https://example.com").unwrap();
+
+        let client_data: serde_json::Value =
+            serde_json::from_slice(&assertion.client_data_json).unwrap();
+        assert_eq!(client_data["challenge"], URL_SAFE_NO_PAD.encode(payload));
+        assert_eq!(assertion.authenticator_data.len(), 37);
+
+        let mut message = assertion.authenticator_data.clone();
+        message.extend_from_slice(&Sha256::digest(&assertion.client_data_json));
+        let verifying_key = VerifyingKey::from_sec1_bytes(&public_key(&key)).unwrap();
+        let signature = Signature::from_slice(&assertion.signature).unwrap();
+        assert!(signature.normalize_s().is_none());
+        verifying_key.verify(&message, &signature).unwrap();
+    }
+
+    #[test]
+    fn round_trips_hex_secret_key() {
+        let key = generate();
+        assert_eq!(from_hex(&to_hex(&key)).unwrap(), key);
+    }
+}
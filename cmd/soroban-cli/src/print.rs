@@ -1,6 +1,6 @@
 use std::{env, fmt::Display};
 
-use crate::xdr::{Error as XdrError, Transaction};
+use crate::xdr::{Error as XdrError, Transaction, TransactionEnvelope, TransactionExt};
 
 use crate::{
     config::network::Network, utils::explorer_url_for_transaction, utils::transaction_hash,
@@ -73,6 +73,42 @@ impl Print {
 
         Ok(())
     }
+
+    /// Log what `--dry-run` would have sent: the RPC endpoint, hash, fees, and signatures of
+    /// `tx_env`.
+    ///
+    /// # Errors
+    ///
+    /// Might return an error
+    pub fn log_dry_run(
+        &self,
+        tx_env: &TransactionEnvelope,
+        network: &Network,
+    ) -> Result<(), XdrError> {
+        self.infoln(format!(
+            "Dry run, not sending the transaction to {}",
+            network.rpc_url
+        ));
+        let TransactionEnvelope::Tx(env) = tx_env else {
+            return Ok(());
+        };
+        self.log_transaction(&env.tx, network, false)?;
+        match &env.tx.ext {
+            TransactionExt::V1(data) => self.infoln(format!(
+                "Fee is {} stroops, {} inclusion fee and {} resource fee",
+                env.tx.fee,
+                i64::from(env.tx.fee) - data.resource_fee,
+                data.resource_fee
+            )),
+            TransactionExt::V0 => self.infoln(format!("Fee is {} stroops", env.tx.fee)),
+        }
+        self.infoln(format!(
+            "{} operation(s), {} signature(s)",
+            env.tx.operations.len(),
+            env.signatures.len()
+        ));
+        Ok(())
+    }
 }
 
 macro_rules! create_print_functions {
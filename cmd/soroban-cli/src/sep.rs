@@ -0,0 +1,13 @@
+//! Clients for Stellar Ecosystem Proposals (SEPs) implemented by anchors,
+//! wallets, and other off-chain services.
+pub mod federation;
+#[allow(clippy::module_name_repetitions)]
+pub mod sep10;
+#[allow(clippy::module_name_repetitions)]
+pub mod sep53;
+#[allow(clippy::module_name_repetitions)]
+pub mod sep7;
+pub mod stellar_toml;
+pub mod transfer;
+
+pub use stellar_toml::StellarToml;
@@ -0,0 +1,230 @@
+//! SEP-2 Federation client, resolving `name*domain.com` addresses to accounts.
+//!
+//! See <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0002.md>
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::{stellar_toml, StellarToml};
+use crate::{utils::http, xdr};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid federation address {0:?}, expected `name*domain.com`")]
+    InvalidAddress(String),
+    #[error(transparent)]
+    StellarToml(#[from] stellar_toml::Error),
+    #[error("invalid federation server {0}")]
+    InvalidServer(String),
+    #[error("request to {url} failed: {error}")]
+    Request { url: Url, error: reqwest::Error },
+    #[error("federation server {url} responded with {status}: {body}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    #[error("federation server returned invalid account {account_id} for {address}")]
+    InvalidAccount { address: String, account_id: String },
+    #[error("federation server returned invalid {memo_type} memo {memo:?} for {address}")]
+    InvalidMemo {
+        address: String,
+        memo_type: String,
+        memo: String,
+    },
+    #[error("failed to resolve {address}: {error}")]
+    Runtime {
+        address: String,
+        error: std::io::Error,
+    },
+    #[error("failed to resolve {address}: the lookup panicked: {message}")]
+    Panicked { address: String, message: String },
+}
+
+/// Response of a federation server to a `type=name` lookup.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Record {
+    pub stellar_address: Option<String>,
+    pub account_id: String,
+    pub memo_type: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl Record {
+    pub fn muxed_account(&self) -> Result<xdr::MuxedAccount, Error> {
+        xdr::MuxedAccount::from_str(&self.account_id).map_err(|_| Error::InvalidAccount {
+            address: self.stellar_address.clone().unwrap_or_default(),
+            account_id: self.account_id.clone(),
+        })
+    }
+
+    /// The memo that transactions to the account must carry, e.g. the deposit memo of an
+    /// exchange. Hash memos are base64 encoded, as the SEP requires.
+    pub fn memo(&self) -> Result<Option<xdr::Memo>, Error> {
+        let Some(memo) = &self.memo else {
+            return Ok(None);
+        };
+        let memo_type = self.memo_type.as_deref().unwrap_or("text");
+        let parsed = match memo_type {
+            "text" => memo.as_str().try_into().ok().map(xdr::Memo::Text),
+            "id" => memo.parse().ok().map(xdr::Memo::Id),
+            "hash" => base64
+                .decode(memo)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(|bytes| xdr::Memo::Hash(xdr::Hash(bytes))),
+            _ => None,
+        };
+        parsed.map(Some).ok_or_else(|| Error::InvalidMemo {
+            address: self.stellar_address.clone().unwrap_or_default(),
+            memo_type: memo_type.to_string(),
+            memo: memo.clone(),
+        })
+    }
+}
+
+/// Whether `s` looks like a federation address, i.e. `name*domain.com`.
+pub fn is_federation_address(s: &str) -> bool {
+    s.rsplit_once('*')
+        .is_some_and(|(name, domain)| !name.is_empty() && domain.contains('.'))
+}
+
+/// Resolve a federation address using the `FEDERATION_SERVER` of its domain.
+pub async fn resolve(address: &str) -> Result<Record, Error> {
+    let Some((_, domain)) = address
+        .rsplit_once('*')
+        .filter(|_| is_federation_address(address))
+    else {
+        return Err(Error::InvalidAddress(address.to_string()));
+    };
+    let toml = StellarToml::fetch(domain).await?;
+    let server = toml.federation_server(domain)?;
+    let mut url = Url::parse(server).map_err(|_| Error::InvalidServer(server.to_string()))?;
+    url.query_pairs_mut()
+        .append_pair("q", address)
+        .append_pair("type", "name");
+    tracing::debug!("resolving {address} with {url}");
+    let response = http::client()
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|error| Error::Request {
+            url: url.clone(),
+            error,
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::Status { url, status, body });
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| Error::Request { url, error })
+}
+
+/// Resolve a federation address from synchronous code, such as address resolution.
+///
+/// The lookup runs on its own thread and runtime so that it can be called from
+/// within the CLI's async runtime without blocking it.
+pub fn resolve_blocking(address: &str) -> Result<Record, Error> {
+    let owned = address.to_string();
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|error| Error::Runtime {
+                address: owned.clone(),
+                error,
+            })?
+            .block_on(resolve(&owned))
+    })
+    .join()
+    .unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(Error::Panicked {
+            address: address.to_string(),
+            message,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_federation_addresses() {
+        assert!(is_federation_address("bob*stellar.org"));
+        assert!(is_federation_address("bob@example.com*stellar.org"));
+        assert!(!is_federation_address("bob"));
+        assert!(!is_federation_address("*stellar.org"));
+        assert!(!is_federation_address("bob*localhost"));
+        assert!(!is_federation_address(
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+        ));
+    }
+
+    #[test]
+    fn parses_required_memos() {
+        let record = |memo_type: Option<&str>, memo: Option<&str>| Record {
+            stellar_address: Some("bob*stellar.org".to_string()),
+            account_id: "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF".to_string(),
+            memo_type: memo_type.map(str::to_string),
+            memo: memo.map(str::to_string),
+        };
+        assert_eq!(record(None, None).memo().unwrap(), None);
+        assert_eq!(
+            record(Some("id"), Some("42")).memo().unwrap(),
+            Some(xdr::Memo::Id(42))
+        );
+        assert_eq!(
+            record(None, Some("deposit")).memo().unwrap(),
+            Some(xdr::Memo::Text("deposit".try_into().unwrap()))
+        );
+        assert_eq!(
+            record(Some("hash"), Some(&base64.encode([7; 32])))
+                .memo()
+                .unwrap(),
+            Some(xdr::Memo::Hash(xdr::Hash([7; 32])))
+        );
+        assert!(record(Some("id"), Some("bob")).memo().is_err());
+        assert!(record(Some("text"), Some(&"x".repeat(29))).memo().is_err());
+    }
+
+    #[test]
+    fn resolves_the_account_and_memo_of_an_address() {
+        let mut server = mockito::Server::new();
+        let account_id = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+        let address = format!("bob*{}", server.url());
+        let _toml = server
+            .mock("GET", "/.well-known/stellar.toml")
+            .with_body(format!(
+                "FEDERATION_SERVER = \"{}/federation\"",
+                server.url()
+            ))
+            .create();
+        let _federation = server
+            .mock("GET", "/federation")
+            .match_query(mockito::Matcher::UrlEncoded("q".into(), address.clone()))
+            .with_body(
+                serde_json::json!({
+                    "stellar_address": address,
+                    "account_id": account_id,
+                    "memo_type": "id",
+                    "memo": "1234",
+                })
+                .to_string(),
+            )
+            .create();
+        let record = resolve_blocking(&address).unwrap();
+        assert_eq!(record.muxed_account().unwrap().to_string(), account_id);
+        assert_eq!(record.memo().unwrap(), Some(xdr::Memo::Id(1234)));
+    }
+}
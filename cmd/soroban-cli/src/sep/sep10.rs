@@ -0,0 +1,384 @@
+//! SEP-10 Stellar Web Authentication client.
+//!
+//! See <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0010.md>
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::Verifier;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    utils::{http, transaction_hash},
+    xdr::{
+        self, Limits, ManageDataOp, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+        ReadXdr, TimeBounds, Transaction, TransactionEnvelope, TransactionV1Envelope, WriteXdr,
+    },
+};
+
+/// Name of the `ManageData` operation carrying the web auth domain.
+pub const WEB_AUTH_DOMAIN: &str = "web_auth_domain";
+/// Name of the `ManageData` operation carrying the client domain.
+pub const CLIENT_DOMAIN: &str = "client_domain";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("request to {url} failed: {error}")]
+    Request { url: Url, error: reqwest::Error },
+    #[error("request to {url} failed with {status}: {body}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    #[error("invalid auth endpoint {0}")]
+    InvalidEndpoint(String),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("challenge network passphrase {server:?} does not match {expected:?}")]
+    NetworkPassphraseMismatch { expected: String, server: String },
+    #[error("invalid challenge: {0}")]
+    InvalidChallenge(&'static str),
+    #[error("invalid challenge: operation {index} {reason}")]
+    InvalidChallengeOperation { index: usize, reason: &'static str },
+}
+
+/// Response to a `GET` on the auth endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Challenge {
+    pub transaction: String,
+    pub network_passphrase: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    token: String,
+}
+
+/// Parameters of the challenge request.
+#[derive(Debug, Clone)]
+pub struct ChallengeRequest<'a> {
+    /// Account being authenticated, either a `G…` or `M…` address.
+    pub account: &'a MuxedAccount,
+    pub home_domain: &'a str,
+    pub client_domain: Option<&'a str>,
+    pub memo: Option<u64>,
+}
+
+/// Everything needed to validate a challenge issued by a server.
+#[derive(Debug, Clone)]
+pub struct Validation<'a> {
+    /// The `SIGNING_KEY` of the server, from its `stellar.toml`.
+    pub server_key: [u8; 32],
+    pub client_account: &'a MuxedAccount,
+    pub home_domain: &'a str,
+    /// Host (and port) of the auth endpoint.
+    pub web_auth_domain: &'a str,
+    pub network_passphrase: &'a str,
+    /// The `SIGNING_KEY` of the client domain, if one was requested.
+    pub client_domain_key: Option<[u8; 32]>,
+    pub memo: Option<u64>,
+}
+
+/// Request a challenge transaction from `endpoint`.
+pub async fn fetch_challenge(
+    endpoint: &Url,
+    request: &ChallengeRequest<'_>,
+) -> Result<Challenge, Error> {
+    let mut url = endpoint.clone();
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("account", &request.account.to_string())
+            .append_pair("home_domain", request.home_domain);
+        if let Some(client_domain) = request.client_domain {
+            query.append_pair("client_domain", client_domain);
+        }
+        if let Some(memo) = request.memo {
+            query.append_pair("memo", &memo.to_string());
+        }
+    }
+    tracing::debug!("fetching challenge from {url}");
+    let response = http::client()
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|error| Error::Request {
+            url: url.clone(),
+            error,
+        })?;
+    json_response(url, response).await
+}
+
+/// Submit a signed challenge to `endpoint`, returning the JWT.
+pub async fn submit_challenge(
+    endpoint: &Url,
+    envelope: &TransactionEnvelope,
+) -> Result<String, Error> {
+    let body = serde_json::json!({ "transaction": envelope.to_xdr_base64(Limits::none())? });
+    let response = http::client()
+        .post(endpoint.clone())
+        .json(&body)
+        .send()
+        .await
+        .map_err(|error| Error::Request {
+            url: endpoint.clone(),
+            error,
+        })?;
+    let Token { token } = json_response(endpoint.clone(), response).await?;
+    Ok(token)
+}
+
+async fn json_response<T: serde::de::DeserializeOwned>(
+    url: Url,
+    response: reqwest::Response,
+) -> Result<T, Error> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::Status { url, status, body });
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| Error::Request { url, error })
+}
+
+impl Challenge {
+    pub fn envelope(&self) -> Result<TransactionEnvelope, Error> {
+        Ok(TransactionEnvelope::from_xdr_base64(
+            &self.transaction,
+            Limits::none(),
+        )?)
+    }
+}
+
+/// Validate a challenge according to the rules in SEP-10, returning the
+/// transaction to be signed by the client.
+pub fn validate_challenge(
+    envelope: &TransactionEnvelope,
+    validation: &Validation<'_>,
+) -> Result<Transaction, Error> {
+    let TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) = envelope else {
+        return Err(Error::InvalidChallenge("must be a v1 transaction envelope"));
+    };
+    let server = MuxedAccount::Ed25519(xdr::Uint256(validation.server_key));
+    if tx.source_account != server {
+        return Err(Error::InvalidChallenge(
+            "source account is not the server signing key",
+        ));
+    }
+    if tx.seq_num.0 != 0 {
+        return Err(Error::InvalidChallenge("sequence number must be zero"));
+    }
+    validate_time_bounds(&tx.cond)?;
+    let expected_memo = validation.memo.map_or(Memo::None, Memo::Id);
+    if tx.memo != expected_memo {
+        return Err(Error::InvalidChallenge("memo does not match the request"));
+    }
+
+    let auth_key = format!("{} auth", validation.home_domain);
+    for (
+        index,
+        Operation {
+            source_account,
+            body,
+        },
+    ) in tx.operations.iter().enumerate()
+    {
+        let invalid = |reason| Error::InvalidChallengeOperation { index, reason };
+        let OperationBody::ManageData(ManageDataOp {
+            data_name,
+            data_value,
+        }) = body
+        else {
+            return Err(invalid("is not a manage data operation"));
+        };
+        let name = data_name.to_utf8_string_lossy();
+        let value = data_value
+            .as_ref()
+            .map(|v| v.as_slice())
+            .unwrap_or_default();
+        if index == 0 {
+            if source_account.as_ref() != Some(validation.client_account) {
+                return Err(invalid("source is not the client account"));
+            }
+            if name != auth_key {
+                return Err(invalid("is not for the requested home domain"));
+            }
+            if value.len() != 64 {
+                return Err(invalid("nonce must be 64 bytes"));
+            }
+            continue;
+        }
+        match name.as_str() {
+            CLIENT_DOMAIN => {
+                let Some(client_domain_key) = validation.client_domain_key else {
+                    return Err(invalid("has an unexpected client domain"));
+                };
+                if source_account != &Some(MuxedAccount::Ed25519(xdr::Uint256(client_domain_key))) {
+                    return Err(invalid("source is not the client domain signing key"));
+                }
+            }
+            WEB_AUTH_DOMAIN => {
+                if source_account != &Some(server.clone()) {
+                    return Err(invalid("source is not the server signing key"));
+                }
+                if value != validation.web_auth_domain.as_bytes() {
+                    return Err(invalid("web auth domain does not match the endpoint"));
+                }
+            }
+            _ => {
+                if source_account != &Some(server.clone()) {
+                    return Err(invalid("source is not the server signing key"));
+                }
+            }
+        }
+    }
+
+    let hash = transaction_hash(tx, validation.network_passphrase)?;
+    let server_key = ed25519_dalek::VerifyingKey::from_bytes(&validation.server_key)
+        .map_err(|_| Error::InvalidChallenge("server signing key is invalid"))?;
+    let signed = signatures.iter().any(|sig| {
+        sig.hint.0 == validation.server_key[28..]
+            && ed25519_dalek::Signature::from_slice(&sig.signature)
+                .is_ok_and(|s| server_key.verify(&hash, &s).is_ok())
+    });
+    if !signed {
+        return Err(Error::InvalidChallenge("not signed by the server"));
+    }
+    Ok(tx.clone())
+}
+
+fn validate_time_bounds(cond: &Preconditions) -> Result<(), Error> {
+    let (Preconditions::Time(TimeBounds { min_time, max_time })
+    | Preconditions::V2(xdr::PreconditionsV2 {
+        time_bounds: Some(TimeBounds { min_time, max_time }),
+        ..
+    })) = cond
+    else {
+        return Err(Error::InvalidChallenge("missing time bounds"));
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Allow for a small amount of clock skew between the client and server.
+    let grace = 5 * 60;
+    if min_time.0 > now + grace {
+        return Err(Error::InvalidChallenge("time bounds are in the future"));
+    }
+    if max_time.0 == 0 || max_time.0 < now {
+        return Err(Error::InvalidChallenge("challenge has expired"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalKey;
+    use ed25519_dalek::SigningKey;
+    use std::str::FromStr;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn manage_data(source: &SigningKey, name: &str, value: &[u8]) -> Operation {
+        Operation {
+            source_account: Some(MuxedAccount::Ed25519(xdr::Uint256(
+                source.verifying_key().to_bytes(),
+            ))),
+            body: OperationBody::ManageData(ManageDataOp {
+                data_name: xdr::StringM::<64>::from_str(name).unwrap().into(),
+                data_value: Some(value.to_vec().try_into().unwrap()),
+            }),
+        }
+    }
+
+    fn challenge(
+        server: &SigningKey,
+        client: &SigningKey,
+        home_domain: &str,
+    ) -> TransactionEnvelope {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let tx = Transaction {
+            source_account: MuxedAccount::Ed25519(xdr::Uint256(server.verifying_key().to_bytes())),
+            fee: 200,
+            seq_num: xdr::SequenceNumber(0),
+            cond: Preconditions::Time(TimeBounds {
+                min_time: xdr::TimePoint(now),
+                max_time: xdr::TimePoint(now + 900),
+            }),
+            memo: Memo::None,
+            operations: vec![
+                manage_data(client, &format!("{home_domain} auth"), &[b'a'; 64]),
+                manage_data(server, WEB_AUTH_DOMAIN, b"auth.example.com"),
+            ]
+            .try_into()
+            .unwrap(),
+            ext: xdr::TransactionExt::V0,
+        };
+        let hash = transaction_hash(&tx, PASSPHRASE).unwrap();
+        let signature = LocalKey {
+            key: server.clone(),
+        }
+        .sign_tx_hash(hash)
+        .unwrap();
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![signature].try_into().unwrap(),
+        })
+    }
+
+    fn validation<'a>(
+        server: &SigningKey,
+        client_account: &'a MuxedAccount,
+        home_domain: &'a str,
+    ) -> Validation<'a> {
+        Validation {
+            server_key: server.verifying_key().to_bytes(),
+            client_account,
+            home_domain,
+            web_auth_domain: "auth.example.com",
+            network_passphrase: PASSPHRASE,
+            client_domain_key: None,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn validates_well_formed_challenge() {
+        let server = SigningKey::from_bytes(&[1; 32]);
+        let client = SigningKey::from_bytes(&[2; 32]);
+        let account = MuxedAccount::Ed25519(xdr::Uint256(client.verifying_key().to_bytes()));
+        let envelope = challenge(&server, &client, "example.com");
+        assert!(
+            validate_challenge(&envelope, &validation(&server, &account, "example.com")).is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_challenge_for_other_home_domain() {
+        let server = SigningKey::from_bytes(&[1; 32]);
+        let client = SigningKey::from_bytes(&[2; 32]);
+        let account = MuxedAccount::Ed25519(xdr::Uint256(client.verifying_key().to_bytes()));
+        let envelope = challenge(&server, &client, "evil.com");
+        assert!(matches!(
+            validate_challenge(&envelope, &validation(&server, &account, "example.com")),
+            Err(Error::InvalidChallengeOperation { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_challenge_not_signed_by_server() {
+        let server = SigningKey::from_bytes(&[1; 32]);
+        let imposter = SigningKey::from_bytes(&[3; 32]);
+        let client = SigningKey::from_bytes(&[2; 32]);
+        let account = MuxedAccount::Ed25519(xdr::Uint256(client.verifying_key().to_bytes()));
+        let envelope = challenge(&imposter, &client, "example.com");
+        assert!(
+            validate_challenge(&envelope, &validation(&server, &account, "example.com")).is_err()
+        );
+    }
+}
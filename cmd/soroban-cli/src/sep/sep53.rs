@@ -0,0 +1,55 @@
+//! SEP-53 signing of arbitrary messages with Stellar keys.
+//!
+//! See <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0053.md>
+use ed25519_dalek::{Signer, Verifier};
+use sha2::{Digest, Sha256};
+
+/// Prefix of signed messages, which keeps them from being valid transaction signatures.
+pub const PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+/// The hash that is signed for `message`, of the prefix followed by the message.
+pub fn hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(PREFIX);
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+pub fn sign(key: &ed25519_dalek::SigningKey, message: &[u8]) -> ed25519_dalek::Signature {
+    key.sign(&hash(message))
+}
+
+pub fn verify(
+    key: &ed25519_dalek::VerifyingKey,
+    message: &[u8],
+    signature: &ed25519_dalek::Signature,
+) -> bool {
+    key.verify(&hash(message), signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+
+    use super::*;
+
+    #[test]
+    fn signs_like_the_sep_examples() {
+        let secret = stellar_strkey::ed25519::PrivateKey::from_string(
+            "SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW",
+        )
+        .unwrap();
+        let key = ed25519_dalek::SigningKey::from_bytes(&secret.0);
+        assert_eq!(
+            stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string(),
+            "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L"
+        );
+        let signature = sign(&key, b"Hello, World!");
+        assert_eq!(
+            base64.encode(signature.to_bytes()),
+            "fO5dbYhXUhBMhe6kId/cuVq/AfEnHRHEvsP8vXh03M1uLpi5e46yO2Q8rEBzu3feXQewcQE5GArp88u6ePK6BA=="
+        );
+        assert!(verify(&key.verifying_key(), b"Hello, World!", &signature));
+        assert!(!verify(&key.verifying_key(), b"Hello, World", &signature));
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::utils::http;
+
+/// Path of the SEP-1 `stellar.toml` relative to a home domain.
+pub const WELL_KNOWN_PATH: &str = ".well-known/stellar.toml";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid domain {0}")]
+    InvalidDomain(String),
+    #[error("failed to fetch {url}: {error}")]
+    Fetch { url: Url, error: reqwest::Error },
+    #[error("failed to fetch {url}: server responded with {status}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+    },
+    #[error("failed to parse {url}: {error}")]
+    Parse {
+        url: Url,
+        error: Box<toml::de::Error>,
+    },
+    #[error("stellar.toml of {domain} does not define {field}")]
+    MissingField { domain: String, field: &'static str },
+}
+
+/// The subset of a SEP-1 `stellar.toml` used by the CLI.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct StellarToml {
+    #[serde(rename = "NETWORK_PASSPHRASE")]
+    pub network_passphrase: Option<String>,
+    #[serde(rename = "SIGNING_KEY")]
+    pub signing_key: Option<String>,
+    #[serde(rename = "WEB_AUTH_ENDPOINT")]
+    pub web_auth_endpoint: Option<String>,
+    #[serde(rename = "TRANSFER_SERVER")]
+    pub transfer_server: Option<String>,
+    #[serde(rename = "TRANSFER_SERVER_SEP0024")]
+    pub transfer_server_sep0024: Option<String>,
+    #[serde(rename = "FEDERATION_SERVER")]
+    pub federation_server: Option<String>,
+    #[serde(rename = "URI_REQUEST_SIGNING_KEY")]
+    pub uri_request_signing_key: Option<String>,
+}
+
+impl StellarToml {
+    /// Fetch and parse the `stellar.toml` published by `domain`.
+    ///
+    /// The domain may include a scheme (e.g. `http://localhost:8000`) which is
+    /// useful when testing against a local anchor; `https` is assumed otherwise.
+    pub async fn fetch(domain: &str) -> Result<Self, Error> {
+        let url = base_url(domain)?
+            .join(WELL_KNOWN_PATH)
+            .map_err(|_| Error::InvalidDomain(domain.to_string()))?;
+        tracing::debug!("fetching {url}");
+        let response = http::client()
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|error| Error::Fetch {
+                url: url.clone(),
+                error,
+            })?;
+        if !response.status().is_success() {
+            return Err(Error::Status {
+                url,
+                status: response.status(),
+            });
+        }
+        let body = response.text().await.map_err(|error| Error::Fetch {
+            url: url.clone(),
+            error,
+        })?;
+        toml::from_str(&body).map_err(|error| Error::Parse {
+            url,
+            error: Box::new(error),
+        })
+    }
+
+    pub fn signing_key(&self, domain: &str) -> Result<&str, Error> {
+        self.signing_key
+            .as_deref()
+            .ok_or_else(|| missing(domain, "SIGNING_KEY"))
+    }
+
+    pub fn web_auth_endpoint(&self, domain: &str) -> Result<&str, Error> {
+        self.web_auth_endpoint
+            .as_deref()
+            .ok_or_else(|| missing(domain, "WEB_AUTH_ENDPOINT"))
+    }
+
+    pub fn transfer_server(&self, domain: &str) -> Result<&str, Error> {
+        self.transfer_server
+            .as_deref()
+            .ok_or_else(|| missing(domain, "TRANSFER_SERVER"))
+    }
+
+    pub fn transfer_server_sep0024(&self, domain: &str) -> Result<&str, Error> {
+        self.transfer_server_sep0024
+            .as_deref()
+            .ok_or_else(|| missing(domain, "TRANSFER_SERVER_SEP0024"))
+    }
+
+    pub fn federation_server(&self, domain: &str) -> Result<&str, Error> {
+        self.federation_server
+            .as_deref()
+            .ok_or_else(|| missing(domain, "FEDERATION_SERVER"))
+    }
+
+    pub fn uri_request_signing_key(&self, domain: &str) -> Result<&str, Error> {
+        self.uri_request_signing_key
+            .as_deref()
+            .ok_or_else(|| missing(domain, "URI_REQUEST_SIGNING_KEY"))
+    }
+}
+
+fn missing(domain: &str, field: &'static str) -> Error {
+    Error::MissingField {
+        domain: domain.to_string(),
+        field,
+    }
+}
+
+/// Build the base URL for a home domain, defaulting to `https`.
+pub fn base_url(domain: &str) -> Result<Url, Error> {
+    let url = if domain.starts_with("http://") || domain.starts_with("https://") {
+        domain.to_string()
+    } else {
+        format!("https://{domain}")
+    };
+    let mut url = Url::parse(&url).map_err(|_| Error::InvalidDomain(domain.to_string()))?;
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(url)
+}
+
+/// The bare domain (host and optional port) of a home domain, as used in
+/// SEP-10 challenge `ManageData` operation names.
+pub fn bare_domain(domain: &str) -> Result<String, Error> {
+    let url = base_url(domain)?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| Error::InvalidDomain(domain.to_string()))?;
+    Ok(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    })
+}
@@ -0,0 +1,266 @@
+//! SEP-6 and SEP-24 transfer server clients, used to move assets on and off
+//! the network through an anchor.
+//!
+//! See <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0006.md>
+//! and <https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0024.md>
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::utils::http;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid transfer server url {0}")]
+    InvalidUrl(String),
+    #[error("request to {url} failed: {error}")]
+    Request { url: Url, error: reqwest::Error },
+    #[error("request to {url} failed with {status}: {body}")]
+    Status {
+        url: Url,
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    #[error("unexpected response from {url}: {error}")]
+    Response { url: Url, error: serde_json::Error },
+}
+
+/// Direction of a transfer, from the point of view of the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Deposit,
+    Withdraw,
+}
+
+impl Kind {
+    fn path(self) -> &'static str {
+        match self {
+            Kind::Deposit => "deposit",
+            Kind::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// Response to a SEP-24 interactive deposit or withdraw request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Interactive {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+    pub id: String,
+}
+
+/// Statuses after which a transaction will not change again.
+pub const FINAL_STATUSES: &[&str] = &[
+    "completed",
+    "refunded",
+    "expired",
+    "error",
+    "no_market",
+    "too_small",
+    "too_large",
+];
+
+/// Statuses that wait on the user rather than the anchor. `incomplete` is left
+/// out as it only means the interactive flow has not been finished yet.
+pub const USER_ACTION_STATUSES: &[&str] = &[
+    "pending_user_transfer_start",
+    "pending_customer_info_update",
+    "pending_transaction_info_update",
+];
+
+/// A SEP-6 or SEP-24 `TRANSFER_SERVER`, optionally authenticated with a SEP-10 JWT.
+#[derive(Debug, Clone)]
+pub struct Server {
+    url: Url,
+    token: Option<String>,
+}
+
+impl Server {
+    pub fn new(url: &str, token: Option<String>) -> Result<Self, Error> {
+        let mut url = Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_string()))?;
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self { url, token })
+    }
+
+    /// `GET /info`, describing the assets and fields supported by the anchor.
+    pub async fn info(&self) -> Result<Value, Error> {
+        self.get("info", &[]).await
+    }
+
+    /// SEP-6 `GET /deposit` or `GET /withdraw`, returning the anchor's instructions.
+    pub async fn sep6(&self, kind: Kind, params: &[(String, String)]) -> Result<Value, Error> {
+        self.get(kind.path(), params).await
+    }
+
+    /// SEP-24 `POST /transactions/{deposit,withdraw}/interactive`.
+    pub async fn interactive(
+        &self,
+        kind: Kind,
+        params: &[(String, String)],
+    ) -> Result<Interactive, Error> {
+        let url = self.join(&format!("transactions/{}/interactive", kind.path()))?;
+        tracing::debug!("posting to {url}");
+        let request = self.authorize(http::client().post(url.clone()).form(params));
+        let value = send(url.clone(), request).await?;
+        serde_json::from_value(value).map_err(|error| Error::Response { url, error })
+    }
+
+    /// `GET /transaction`, returning the `transaction` object of the response.
+    pub async fn transaction(&self, id: &str) -> Result<Value, Error> {
+        let mut value = self
+            .get("transaction", &[("id".to_string(), id.to_string())])
+            .await?;
+        Ok(value
+            .get_mut("transaction")
+            .map(Value::take)
+            .unwrap_or(value))
+    }
+
+    async fn get(&self, path: &str, params: &[(String, String)]) -> Result<Value, Error> {
+        let mut url = self.join(path)?;
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+        tracing::debug!("fetching {url}");
+        let request = self.authorize(http::client().get(url.clone()));
+        send(url, request).await
+    }
+
+    fn join(&self, path: &str) -> Result<Url, Error> {
+        self.url
+            .join(path)
+            .map_err(|_| Error::InvalidUrl(format!("{}{path}", self.url)))
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+async fn send(url: Url, request: reqwest::RequestBuilder) -> Result<Value, Error> {
+    let response = request.send().await.map_err(|error| Error::Request {
+        url: url.clone(),
+        error,
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::Status { url, status, body });
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| Error::Request { url, error })
+}
+
+/// The `status` field of a transaction object.
+pub fn status(transaction: &Value) -> Option<&str> {
+    transaction.get("status").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn joins_paths_under_the_server_url() {
+        let server = Server::new("https://anchor.example/sep24", None).unwrap();
+        assert_eq!(
+            server.join("info").unwrap().as_str(),
+            "https://anchor.example/sep24/info"
+        );
+        assert_eq!(
+            server
+                .join("transactions/deposit/interactive")
+                .unwrap()
+                .as_str(),
+            "https://anchor.example/sep24/transactions/deposit/interactive"
+        );
+        assert!(matches!(
+            Server::new("anchor.example", None),
+            Err(Error::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn reads_statuses() {
+        assert_eq!(
+            status(&json!({ "status": "pending_anchor" })),
+            Some("pending_anchor")
+        );
+        assert_eq!(status(&json!({ "status": 1 })), None);
+        assert_eq!(status(&json!({})), None);
+        assert!(FINAL_STATUSES.contains(&"completed"));
+        assert!(!FINAL_STATUSES.contains(&"incomplete"));
+        assert!(!USER_ACTION_STATUSES.contains(&"incomplete"));
+    }
+
+    #[tokio::test]
+    async fn fetches_transactions_with_the_token() {
+        let mut server = mockito::Server::new_async().await;
+        let _transaction = server
+            .mock("GET", "/transaction")
+            .match_query(mockito::Matcher::UrlEncoded("id".into(), "82fh".into()))
+            .match_header("authorization", "Bearer jwt")
+            .with_body(
+                json!({ "transaction": { "id": "82fh", "status": "incomplete" } }).to_string(),
+            )
+            .create_async()
+            .await;
+        let anchor = Server::new(&server.url(), Some("jwt".to_string())).unwrap();
+        let transaction = anchor.transaction("82fh").await.unwrap();
+        assert_eq!(transaction, json!({ "id": "82fh", "status": "incomplete" }));
+
+        let _missing = server
+            .mock("GET", "/transaction")
+            .match_query(mockito::Matcher::UrlEncoded("id".into(), "nope".into()))
+            .with_status(404)
+            .with_body("not found")
+            .create_async()
+            .await;
+        let err = anchor.transaction("nope").await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Status { status, body, .. } if status.as_u16() == 404 && body == "not found"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn starts_interactive_flows() {
+        let mut server = mockito::Server::new_async().await;
+        let _interactive = server
+            .mock("POST", "/transactions/withdraw/interactive")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "asset_code".into(),
+                "USDC".into(),
+            ))
+            .with_body(
+                json!({
+                    "type": "interactive_customer_info_needed",
+                    "url": "https://anchor.example/flow",
+                    "id": "82fh",
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let anchor = Server::new(&server.url(), None).unwrap();
+        let interactive = anchor
+            .interactive(
+                Kind::Withdraw,
+                &[("asset_code".to_string(), "USDC".to_string())],
+            )
+            .await
+            .unwrap();
+        assert_eq!(interactive.kind, "interactive_customer_info_needed");
+        assert_eq!(interactive.url, "https://anchor.example/flow");
+        assert_eq!(interactive.id, "82fh");
+    }
+}
@@ -5,8 +5,8 @@ use crate::xdr::{
     self, AccountId, DecoratedSignature, Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization,
     InvokeHostFunctionOp, Limits, Operation, OperationBody, PublicKey, ScAddress, ScMap, ScSymbol,
     ScVal, Signature, SignatureHint, SorobanAddressCredentials, SorobanAuthorizationEntry,
-    SorobanAuthorizedFunction, SorobanCredentials, Transaction, TransactionEnvelope,
-    TransactionV1Envelope, Uint256, VecM, WriteXdr,
+    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, Transaction,
+    TransactionEnvelope, TransactionV1Envelope, Uint256, VecM, WriteXdr,
 };
 
 use crate::{config::network::Network, print::Print, utils::transaction_hash};
@@ -138,7 +138,7 @@ pub fn sign_soroban_authorizations(
     Ok(Some(tx))
 }
 
-fn sign_soroban_authorization_entry(
+pub fn sign_soroban_authorization_entry(
     raw: &SorobanAuthorizationEntry,
     signer: &ed25519_dalek::SigningKey,
     signature_expiration_ledger: u32,
@@ -155,15 +155,12 @@ fn sign_soroban_authorization_entry(
     };
     let SorobanAddressCredentials { nonce, .. } = credentials;
 
-    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
-        network_id: network_id.clone(),
-        invocation: auth.root_invocation.clone(),
-        nonce: *nonce,
+    let payload = authorization_payload(
+        &auth.root_invocation,
+        *nonce,
         signature_expiration_ledger,
-    })
-    .to_xdr(Limits::none())?;
-
-    let payload = Sha256::digest(preimage);
+        network_id,
+    )?;
     let signature = signer.sign(&payload);
 
     let map = ScMap::sorted_from(vec![
@@ -198,6 +195,23 @@ fn sign_soroban_authorization_entry(
     Ok(auth)
 }
 
+/// The hash signed to authorize an invocation, passed to `__check_auth` as the `signature_payload`.
+pub fn authorization_payload(
+    invocation: &SorobanAuthorizedInvocation,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    network_id: &Hash,
+) -> Result<[u8; 32], Error> {
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: network_id.clone(),
+        invocation: invocation.clone(),
+        nonce,
+        signature_expiration_ledger,
+    })
+    .to_xdr(Limits::none())?;
+    Ok(Sha256::digest(preimage).into())
+}
+
 pub struct Signer {
     pub kind: SignerKind,
     pub print: Print,
@@ -222,6 +236,7 @@ impl Signer {
         self.sign_tx_env(&tx_env, network)
     }
 
+    #[tracing::instrument(name = "sign", skip_all, err(level = "debug"))]
     pub fn sign_tx_env(
         &self,
         tx_env: &TransactionEnvelope,
@@ -270,17 +285,21 @@ impl Lab {
         network: &Network,
         printer: &Print,
     ) -> Result<DecoratedSignature, Error> {
-        let xdr = tx_env.to_xdr_base64(Limits::none())?;
-
-        let mut url = url::Url::parse(Self::URL)?;
-        url.query_pairs_mut()
-            .append_pair("networkPassphrase", &network.network_passphrase)
-            .append_pair("xdr", &xdr);
-        let url = url.to_string();
+        let url = Self::sign_url(tx_env, network)?.to_string();
 
         printer.globeln(format!("Opening lab to sign transaction: {url}"));
         open::that(url)?;
 
         Err(Error::ReturningSignatureFromLab)
     }
+
+    /// Link to the page of Lab that signs and submits a transaction, prefilled with `tx_env`.
+    pub fn sign_url(tx_env: &TransactionEnvelope, network: &Network) -> Result<url::Url, Error> {
+        let xdr = tx_env.to_xdr_base64(Limits::none())?;
+        let mut url = url::Url::parse(Self::URL)?;
+        url.query_pairs_mut()
+            .append_pair("networkPassphrase", &network.network_passphrase)
+            .append_pair("xdr", &xdr);
+        Ok(url)
+    }
 }
@@ -1,7 +1,10 @@
 pub mod asset;
+pub mod claim_predicate;
+pub mod sponsorship;
 pub mod transaction;
 
 pub use asset::Asset;
+pub use claim_predicate::ClaimPredicate;
 pub use transaction::TxExt;
 
 #[derive(thiserror::Error, Debug)]
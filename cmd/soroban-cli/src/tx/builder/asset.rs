@@ -48,3 +48,23 @@ impl From<&Asset> for xdr::Asset {
         builder.clone().into()
     }
 }
+
+impl From<&Asset> for xdr::ChangeTrustAsset {
+    fn from(builder: &Asset) -> Self {
+        match builder.0.clone() {
+            xdr::Asset::CreditAlphanum4(asset) => xdr::ChangeTrustAsset::CreditAlphanum4(asset),
+            xdr::Asset::CreditAlphanum12(asset) => xdr::ChangeTrustAsset::CreditAlphanum12(asset),
+            xdr::Asset::Native => xdr::ChangeTrustAsset::Native,
+        }
+    }
+}
+
+impl From<&Asset> for xdr::TrustLineAsset {
+    fn from(builder: &Asset) -> Self {
+        match builder.0.clone() {
+            xdr::Asset::CreditAlphanum4(asset) => xdr::TrustLineAsset::CreditAlphanum4(asset),
+            xdr::Asset::CreditAlphanum12(asset) => xdr::TrustLineAsset::CreditAlphanum12(asset),
+            xdr::Asset::Native => xdr::TrustLineAsset::Native,
+        }
+    }
+}
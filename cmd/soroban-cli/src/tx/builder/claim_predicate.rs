@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use crate::xdr;
+
+/// A claim predicate of a claimable balance, written as an expression:
+///
+/// - `unconditional`
+/// - `before:<time>`, claimable before a unix timestamp or RFC 3339 time
+/// - `after:<time>`, claimable from a unix timestamp or RFC 3339 time, same as `not(before:<time>)`
+/// - `within:<duration>`, claimable for a number of seconds or a duration such as `7d` after the
+///   balance is created
+/// - `not(<predicate>)`, `and(<predicate>,<predicate>)`, and `or(<predicate>,<predicate>)`
+#[derive(Clone, Debug)]
+pub struct ClaimPredicate(pub xdr::ClaimPredicate);
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot parse claim predicate {0:?}, expected `unconditional`, `before:<time>`, `after:<time>`, `within:<duration>`, `not(..)`, `and(..,..)`, or `or(..,..)`")]
+    CannotParse(String),
+    #[error("invalid time {0:?}, expected a unix timestamp or RFC 3339 time")]
+    InvalidTime(String),
+    #[error("invalid duration {0:?}, expected a number of seconds or a duration such as `7d`")]
+    InvalidDuration(String),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+impl FromStr for ClaimPredicate {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let compact = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        match parse(&compact)? {
+            (predicate, "") => Ok(ClaimPredicate(predicate)),
+            _ => Err(Error::CannotParse(value.to_string())),
+        }
+    }
+}
+
+impl From<ClaimPredicate> for xdr::ClaimPredicate {
+    fn from(builder: ClaimPredicate) -> Self {
+        builder.0
+    }
+}
+
+/// Parse one predicate from the start of `s`, returning it with the remaining input.
+fn parse(s: &str) -> Result<(xdr::ClaimPredicate, &str), Error> {
+    let cannot_parse = || Error::CannotParse(s.to_string());
+    if let Some(rest) = s.strip_prefix("unconditional") {
+        return Ok((xdr::ClaimPredicate::Unconditional, rest));
+    }
+    if let Some(rest) = s.strip_prefix("not(") {
+        let (inner, rest) = parse(rest)?;
+        let rest = rest.strip_prefix(')').ok_or_else(cannot_parse)?;
+        return Ok((xdr::ClaimPredicate::Not(Some(Box::new(inner))), rest));
+    }
+    for (prefix, is_and) in [("and(", true), ("or(", false)] {
+        if let Some(rest) = s.strip_prefix(prefix) {
+            let (left, rest) = parse(rest)?;
+            let rest = rest.strip_prefix(',').ok_or_else(cannot_parse)?;
+            let (right, rest) = parse(rest)?;
+            let rest = rest.strip_prefix(')').ok_or_else(cannot_parse)?;
+            let both = vec![left, right].try_into()?;
+            let predicate = if is_and {
+                xdr::ClaimPredicate::And(both)
+            } else {
+                xdr::ClaimPredicate::Or(both)
+            };
+            return Ok((predicate, rest));
+        }
+    }
+    let (name, rest) = s.split_once(':').ok_or_else(cannot_parse)?;
+    let end = rest.find([',', ')']).unwrap_or(rest.len());
+    let (arg, rest) = rest.split_at(end);
+    let predicate = match name {
+        "before" => xdr::ClaimPredicate::BeforeAbsoluteTime(time(arg)?),
+        "after" => xdr::ClaimPredicate::Not(Some(Box::new(
+            xdr::ClaimPredicate::BeforeAbsoluteTime(time(arg)?),
+        ))),
+        "within" => xdr::ClaimPredicate::BeforeRelativeTime(duration(arg)?),
+        _ => return Err(cannot_parse()),
+    };
+    Ok((predicate, rest))
+}
+
+fn time(value: &str) -> Result<i64, Error> {
+    if let Ok(seconds) = value.parse() {
+        return Ok(seconds);
+    }
+    let time =
+        humantime::parse_rfc3339_weak(value).map_err(|_| Error::InvalidTime(value.to_string()))?;
+    let seconds = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|_| Error::InvalidTime(value.to_string()))?
+        .as_secs();
+    i64::try_from(seconds).map_err(|_| Error::InvalidTime(value.to_string()))
+}
+
+fn duration(value: &str) -> Result<i64, Error> {
+    if let Ok(seconds) = value.parse() {
+        return Ok(seconds);
+    }
+    let duration =
+        humantime::parse_duration(value).map_err(|_| Error::InvalidDuration(value.to_string()))?;
+    i64::try_from(duration.as_secs()).map_err(|_| Error::InvalidDuration(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xdr::ClaimPredicate as P;
+
+    fn parse(s: &str) -> P {
+        s.parse::<ClaimPredicate>().unwrap().0
+    }
+
+    #[test]
+    fn parses_nested_predicates() {
+        assert_eq!(parse("unconditional"), P::Unconditional);
+        assert_eq!(parse("within:1h"), P::BeforeRelativeTime(3600));
+        assert_eq!(
+            parse("after:1970-01-02T00:00:00Z"),
+            P::Not(Some(Box::new(P::BeforeAbsoluteTime(86_400))))
+        );
+        assert_eq!(
+            parse("or(before:100, and(within:60, not(unconditional)))"),
+            P::Or(
+                vec![
+                    P::BeforeAbsoluteTime(100),
+                    P::And(
+                        vec![
+                            P::BeforeRelativeTime(60),
+                            P::Not(Some(Box::new(P::Unconditional)))
+                        ]
+                        .try_into()
+                        .unwrap()
+                    ),
+                ]
+                .try_into()
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_input() {
+        assert!("and(unconditional,unconditional))"
+            .parse::<ClaimPredicate>()
+            .is_err());
+        assert!("before:soon".parse::<ClaimPredicate>().is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::xdr::{self, AccountId, MuxedAccount, Operation, OperationBody};
+
+use super::{Error, TxExt};
+
+/// Wrap the operations of `tx` in a begin and end sponsoring future reserves sandwich, so that
+/// `sponsor` pays the reserves of the entries the operations create for `sponsored`.
+///
+/// Both accounts must sign the transaction: the sponsor begins the sponsorship and the sponsored
+/// account ends it. The fee per operation of `tx` is kept for the added operations.
+pub fn wrap(
+    tx: xdr::Transaction,
+    sponsor: AccountId,
+    sponsored: AccountId,
+) -> Result<xdr::Transaction, Error> {
+    let source_id = tx.source_account.clone().account_id();
+    let source_of = |account: AccountId| {
+        (account != source_id).then(|| MuxedAccount::Ed25519(account_key(account)))
+    };
+    let mut operations = vec![Operation {
+        source_account: source_of(sponsor),
+        body: OperationBody::BeginSponsoringFutureReserves(xdr::BeginSponsoringFutureReservesOp {
+            sponsored_id: sponsored.clone(),
+        }),
+    }];
+    operations.extend(tx.operations.iter().cloned());
+    operations.push(Operation {
+        source_account: source_of(sponsored),
+        body: OperationBody::EndSponsoringFutureReserves,
+    });
+    tx.with_operations_keeping_fee_per_op(operations)
+}
+
+fn account_key(account: AccountId) -> xdr::Uint256 {
+    let AccountId(xdr::PublicKey::PublicKeyTypeEd25519(key)) = account;
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_operation_in_sponsorship() {
+        let source: AccountId = "GC2BKLYOOYPDEFJKLKY6FNNRQMGFLVHJKQRGNSSRRGSMPGF32LHCQVGF"
+            .parse()
+            .unwrap();
+        let sponsor: AccountId = "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI"
+            .parse()
+            .unwrap();
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::BumpSequence(xdr::BumpSequenceOp {
+                bump_to: xdr::SequenceNumber(2),
+            }),
+        };
+        let tx = xdr::Transaction::new_tx(
+            MuxedAccount::Ed25519(account_key(source.clone())),
+            100,
+            1,
+            op.clone(),
+        );
+        let tx = wrap(tx, sponsor.clone(), source).unwrap();
+        assert_eq!(tx.fee, 300);
+        let [begin, middle, end] = tx.operations.as_slice() else {
+            panic!("expected 3 operations");
+        };
+        assert_eq!(
+            begin.source_account,
+            Some(MuxedAccount::Ed25519(account_key(sponsor)))
+        );
+        assert_eq!(middle, &op);
+        assert_eq!(end.source_account, None);
+        assert_eq!(end.body, OperationBody::EndSponsoringFutureReserves);
+    }
+}
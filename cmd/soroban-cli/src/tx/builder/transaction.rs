@@ -12,6 +12,13 @@ pub trait TxExt {
 
     fn add_operation(self, operation: xdr::Operation) -> Result<xdr::Transaction, Error>;
 
+    /// Replace the operations of the transaction, scaling its fee so that the fee per operation
+    /// stays the same.
+    fn with_operations_keeping_fee_per_op(
+        self,
+        operations: Vec<xdr::Operation>,
+    ) -> Result<xdr::Transaction, Error>;
+
     fn add_memo(self, memo: Memo) -> xdr::Transaction;
 
     fn add_cond(self, cond: xdr::Preconditions) -> xdr::Transaction;
@@ -42,6 +49,21 @@ impl TxExt for xdr::Transaction {
         Ok(self)
     }
 
+    fn with_operations_keeping_fee_per_op(
+        self,
+        operations: Vec<xdr::Operation>,
+    ) -> Result<Self, Error> {
+        let fee_per_op = self.fee / u32::try_from(self.operations.len().max(1)).unwrap_or(1);
+        let count = u32::try_from(operations.len()).map_err(|_| Error::TooManyOperations)?;
+        Ok(xdr::Transaction {
+            fee: fee_per_op.saturating_mul(count),
+            operations: operations
+                .try_into()
+                .map_err(|_| Error::TooManyOperations)?,
+            ..self
+        })
+    }
+
     fn add_memo(mut self, memo: Memo) -> Self {
         self.memo = memo;
         self
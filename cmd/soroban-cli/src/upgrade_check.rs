@@ -1,4 +1,4 @@
-use crate::config::upgrade_check::UpgradeCheck;
+use crate::config::{self, upgrade_check::UpgradeCheck};
 use crate::print::Print;
 use crate::utils::http;
 use semver::Version;
@@ -46,8 +46,10 @@ pub async fn upgrade_check(quiet: bool) {
     }
 
     // We should skip the upgrade check if the user has disabled it by setting
-    // the environment variable (STELLAR_NO_UPDATE_CHECK)
-    if std::env::var(NO_UPDATE_CHECK_ENV_VAR).is_ok() {
+    // the environment variable (STELLAR_NO_UPDATE_CHECK) or `upgrade.check`
+    if std::env::var(NO_UPDATE_CHECK_ENV_VAR).is_ok()
+        || !config::Config::new().map_or(true, |c| c.upgrade.check())
+    {
         return;
     }
 
@@ -97,6 +99,33 @@ pub async fn upgrade_check(quiet: bool) {
     tracing::debug!("finished upgrade check");
 }
 
+/// The latest release of the CLI, fetched from crates.io now rather than at most once a day like
+/// the background check, whose data is updated with it.
+pub async fn latest_version() -> Result<Version, Box<dyn Error>> {
+    let c = fetch_latest_crate_info().await?;
+    let stats = UpgradeCheck {
+        latest_check_time: chrono::Utc::now(),
+        max_stable_version: c.max_stable_version,
+        max_version: c.max_version,
+    };
+    if let Err(e) = stats.save() {
+        tracing::debug!("Failed to save upgrade check data: {e}");
+    }
+    let current_version = Version::parse(crate::commands::version::pkg())?;
+    Ok(get_latest_version(&current_version, &stats).clone())
+}
+
+/// A warning for networks on a protocol newer than the XDR of the CLI, whose transactions and
+/// ledger entries it may fail to build or decode.
+pub fn protocol_warning(network_protocol: u32) -> Option<String> {
+    let supported = crate::commands::version::protocol();
+    (network_protocol > supported).then(|| {
+        format!(
+            "The network is on protocol {network_protocol}, newer than protocol {supported} that this version of stellar-cli supports, so commands may fail on XDR it does not know. Check for a newer release with `stellar version --check`"
+        )
+    })
+}
+
 fn get_latest_version<'a>(current_version: &Version, stats: &'a UpgradeCheck) -> &'a Version {
     if current_version.pre.is_empty() {
         // If we are currently using a non-preview version
@@ -145,6 +174,17 @@ mod tests {
         assert_eq!(*latest_version, Version::parse("1.1.0-rc.1").unwrap());
     }
 
+    #[test]
+    fn test_protocol_warning() {
+        let supported = crate::commands::version::protocol();
+        assert!(supported >= 22);
+        assert_eq!(protocol_warning(supported - 1), None);
+        assert_eq!(protocol_warning(supported), None);
+        assert!(protocol_warning(supported + 1)
+            .unwrap()
+            .contains(&format!("protocol {}", supported + 1)));
+    }
+
     #[test]
     fn test_semver_compare() {
         assert!(Version::parse("0.1.0").unwrap() < Version::parse("0.2.0").unwrap());
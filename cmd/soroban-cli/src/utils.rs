@@ -4,7 +4,7 @@ use stellar_strkey::ed25519::PrivateKey;
 
 use crate::xdr::{
     self, Asset, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId, Limits, ScMap,
-    ScMapEntry, ScVal, Transaction, TransactionSignaturePayload,
+    ScMapEntry, ScVal, Transaction, TransactionEnvelope, TransactionSignaturePayload,
     TransactionSignaturePayloadTaggedTransaction, WriteXdr,
 };
 
@@ -33,6 +33,34 @@ pub fn transaction_hash(
     Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
 }
 
+/// The hash of the transaction in `tx_env`, or of the fee bump transaction wrapping it. `None`
+/// for pre-protocol 13 envelopes.
+///
+/// # Errors
+///
+/// Might return an error
+pub fn envelope_hash(
+    tx_env: &TransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<Option<[u8; 32]>, xdr::Error> {
+    let tagged_transaction = match tx_env {
+        TransactionEnvelope::Tx(env) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(env.tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(env) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(env.tx.clone())
+        }
+        TransactionEnvelope::TxV0(_) => return Ok(None),
+    };
+    let signature_payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction,
+    };
+    Ok(Some(
+        Sha256::digest(signature_payload.to_xdr(Limits::none())?).into(),
+    ))
+}
+
 static EXPLORERS: phf::Map<&'static str, &'static str> = phf_map! {
     "Test SDF Network ; September 2015" => "https://stellar.expert/explorer/testnet",
     "Public Global Stellar Network ; September 2015" => "https://stellar.expert/explorer/public",
@@ -85,7 +113,7 @@ pub fn find_config_dir(mut pwd: std::path::PathBuf) -> std::io::Result<std::path
         let soroban_exists = soroban_dir.exists();
 
         if stellar_exists && soroban_exists {
-            tracing::warn!("the .stellar and .soroban config directories exist at path {pwd:?}, using the .stellar");
+            tracing::warn!("the .stellar and .soroban config directories exist at path {pwd:?}, using the .stellar, see `stellar config migrate`");
         }
 
         if stellar_exists {
@@ -277,4 +305,40 @@ mod tests {
             Err(err) => panic!("Failed to parse contract id: {err}"),
         }
     }
+
+    #[test]
+    fn test_envelope_hash() {
+        let tx = Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([1; 32])),
+            fee: 100,
+            seq_num: xdr::SequenceNumber(1),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: xdr::VecM::default(),
+            ext: xdr::TransactionExt::V0,
+        };
+        let passphrase = "Test SDF Network ; September 2015";
+        let tx_env = TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: xdr::VecM::default(),
+        });
+        assert_eq!(
+            envelope_hash(&tx_env, passphrase).unwrap(),
+            Some(transaction_hash(&tx, passphrase).unwrap())
+        );
+        let fee_bump = TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope {
+            tx: xdr::FeeBumpTransaction {
+                fee_source: xdr::MuxedAccount::Ed25519(xdr::Uint256([2; 32])),
+                fee: 200,
+                inner_tx: xdr::FeeBumpTransactionInnerTx::Tx(xdr::TransactionV1Envelope {
+                    tx: tx.clone(),
+                    signatures: xdr::VecM::default(),
+                }),
+                ext: xdr::FeeBumpTransactionExt::V0,
+            },
+            signatures: xdr::VecM::default(),
+        });
+        let fee_bump_hash = envelope_hash(&fee_bump, passphrase).unwrap().unwrap();
+        assert_ne!(fee_bump_hash, transaction_hash(&tx, passphrase).unwrap());
+    }
 }
@@ -0,0 +1,232 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
+    Address, Env,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    TokensNotOrdered = 1,
+    AmountTooLow = 2,
+    AmountTooHigh = 3,
+    InsufficientShares = 4,
+    InsufficientLiquidity = 5,
+}
+
+#[contracttype]
+enum DataKey {
+    TokenA,
+    TokenB,
+    ReserveA,
+    ReserveB,
+    TotalShares,
+    Shares(Address),
+}
+
+/// Constant product pool of two tokens, charging a 0.3% fee on swaps. The shares of the
+/// depositors are kept by the pool.
+#[contract]
+pub struct LiquidityPool;
+
+#[contractimpl]
+impl LiquidityPool {
+    /// `token_a` must be less than `token_b`, so there is a single pool for each pair.
+    pub fn __constructor(env: Env, token_a: Address, token_b: Address) {
+        if token_a >= token_b {
+            panic_with_error!(&env, Error::TokensNotOrdered);
+        }
+        let storage = env.storage().instance();
+        storage.set(&DataKey::TokenA, &token_a);
+        storage.set(&DataKey::TokenB, &token_b);
+        storage.set(&DataKey::ReserveA, &0_i128);
+        storage.set(&DataKey::ReserveB, &0_i128);
+        storage.set(&DataKey::TotalShares, &0_i128);
+    }
+
+    /// Deposit up to `desired_a` and `desired_b` in the ratio of the reserves, at least `min_a`
+    /// and `min_b`. Returns the shares minted to `to`.
+    pub fn deposit(
+        env: Env,
+        to: Address,
+        desired_a: i128,
+        min_a: i128,
+        desired_b: i128,
+        min_b: i128,
+    ) -> i128 {
+        to.require_auth();
+        let (reserve_a, reserve_b) = reserves(&env);
+        let (amount_a, amount_b) = if reserve_a == 0 && reserve_b == 0 {
+            (desired_a, desired_b)
+        } else {
+            let optimal_b = desired_a * reserve_b / reserve_a;
+            if optimal_b <= desired_b {
+                if optimal_b < min_b {
+                    panic_with_error!(&env, Error::AmountTooLow);
+                }
+                (desired_a, optimal_b)
+            } else {
+                let optimal_a = desired_b * reserve_a / reserve_b;
+                if optimal_a < min_a {
+                    panic_with_error!(&env, Error::AmountTooLow);
+                }
+                (optimal_a, desired_b)
+            }
+        };
+        if amount_a <= 0 || amount_b <= 0 || amount_a < min_a || amount_b < min_b {
+            panic_with_error!(&env, Error::AmountTooLow);
+        }
+
+        let contract = env.current_contract_address();
+        token_a(&env).transfer(&to, &contract, &amount_a);
+        token_b(&env).transfer(&to, &contract, &amount_b);
+
+        let total_shares = total_shares(&env);
+        let minted = if total_shares == 0 {
+            sqrt(amount_a * amount_b)
+        } else {
+            (amount_a * total_shares / reserve_a).min(amount_b * total_shares / reserve_b)
+        };
+        set_shares(&env, &to, shares(&env, &to) + minted);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares + minted));
+        set_reserves(&env, reserve_a + amount_a, reserve_b + amount_b);
+        env.events()
+            .publish((symbol_short!("deposit"), to), (amount_a, amount_b, minted));
+        minted
+    }
+
+    /// Swap at most `in_max` of one token for `out` of the other, buying token A if `buy_a`.
+    /// Returns the amount sold.
+    pub fn swap(env: Env, to: Address, buy_a: bool, out: i128, in_max: i128) -> i128 {
+        to.require_auth();
+        let (reserve_a, reserve_b) = reserves(&env);
+        let (reserve_in, reserve_out) = if buy_a {
+            (reserve_b, reserve_a)
+        } else {
+            (reserve_a, reserve_b)
+        };
+        if out <= 0 {
+            panic_with_error!(&env, Error::AmountTooLow);
+        }
+        if out >= reserve_out {
+            panic_with_error!(&env, Error::InsufficientLiquidity);
+        }
+        // The amount in, after the fee, keeps the product of the reserves constant.
+        let amount_in = reserve_in * out * 1000 / ((reserve_out - out) * 997) + 1;
+        if amount_in > in_max {
+            panic_with_error!(&env, Error::AmountTooHigh);
+        }
+
+        let contract = env.current_contract_address();
+        let (sell, buy) = if buy_a {
+            (token_b(&env), token_a(&env))
+        } else {
+            (token_a(&env), token_b(&env))
+        };
+        sell.transfer(&to, &contract, &amount_in);
+        buy.transfer(&contract, &to, &out);
+        if buy_a {
+            set_reserves(&env, reserve_a - out, reserve_b + amount_in);
+        } else {
+            set_reserves(&env, reserve_a + amount_in, reserve_b - out);
+        }
+        env.events()
+            .publish((symbol_short!("swap"), to), (buy_a, amount_in, out));
+        amount_in
+    }
+
+    /// Burn `shares` of `to` for their part of the reserves, at least `min_a` and `min_b`.
+    pub fn withdraw(env: Env, to: Address, shares: i128, min_a: i128, min_b: i128) -> (i128, i128) {
+        to.require_auth();
+        let balance = self::shares(&env, &to);
+        if shares <= 0 || shares > balance {
+            panic_with_error!(&env, Error::InsufficientShares);
+        }
+        let (reserve_a, reserve_b) = reserves(&env);
+        let total_shares = total_shares(&env);
+        let amount_a = reserve_a * shares / total_shares;
+        let amount_b = reserve_b * shares / total_shares;
+        if amount_a < min_a || amount_b < min_b {
+            panic_with_error!(&env, Error::AmountTooLow);
+        }
+
+        set_shares(&env, &to, balance - shares);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares - shares));
+        set_reserves(&env, reserve_a - amount_a, reserve_b - amount_b);
+        let contract = env.current_contract_address();
+        token_a(&env).transfer(&contract, &to, &amount_a);
+        token_b(&env).transfer(&contract, &to, &amount_b);
+        env.events()
+            .publish((symbol_short!("withdraw"), to), (amount_a, amount_b, shares));
+        (amount_a, amount_b)
+    }
+
+    pub fn get_reserves(env: Env) -> (i128, i128) {
+        reserves(&env)
+    }
+
+    pub fn shares(env: Env, id: Address) -> i128 {
+        shares(&env, &id)
+    }
+}
+
+fn token_a(env: &Env) -> token::Client {
+    let id: Address = env.storage().instance().get(&DataKey::TokenA).unwrap();
+    token::Client::new(env, &id)
+}
+
+fn token_b(env: &Env) -> token::Client {
+    let id: Address = env.storage().instance().get(&DataKey::TokenB).unwrap();
+    token::Client::new(env, &id)
+}
+
+fn reserves(env: &Env) -> (i128, i128) {
+    let storage = env.storage().instance();
+    (
+        storage.get(&DataKey::ReserveA).unwrap(),
+        storage.get(&DataKey::ReserveB).unwrap(),
+    )
+}
+
+fn set_reserves(env: &Env, reserve_a: i128, reserve_b: i128) {
+    let storage = env.storage().instance();
+    storage.set(&DataKey::ReserveA, &reserve_a);
+    storage.set(&DataKey::ReserveB, &reserve_b);
+}
+
+fn total_shares(env: &Env) -> i128 {
+    env.storage().instance().get(&DataKey::TotalShares).unwrap()
+}
+
+fn shares(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Shares(id.clone()))
+        .unwrap_or(0)
+}
+
+fn set_shares(env: &Env, id: &Address, shares: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shares(id.clone()), &shares);
+}
+
+fn sqrt(n: i128) -> i128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+mod test;
@@ -0,0 +1,41 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
+
+fn create_token(env: &Env, admin: &Address) -> Address {
+    env.register_stellar_asset_contract_v2(admin.clone())
+        .address()
+}
+
+#[test]
+fn test_deposit_swap_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let mut token_a = create_token(&env, &admin);
+    let mut token_b = create_token(&env, &admin);
+    if token_b < token_a {
+        core::mem::swap(&mut token_a, &mut token_b);
+    }
+    StellarAssetClient::new(&env, &token_a).mint(&user, &1000);
+    StellarAssetClient::new(&env, &token_b).mint(&user, &1000);
+
+    let pool_id = env.register(LiquidityPool, (&token_a, &token_b));
+    let pool = LiquidityPoolClient::new(&env, &pool_id);
+
+    assert_eq!(pool.deposit(&user, &100, &100, &100, &100), 100);
+    assert_eq!(pool.get_reserves(), (100, 100));
+
+    let sold = pool.swap(&user, &false, &49, &100);
+    assert_eq!(sold, 97);
+    assert_eq!(pool.get_reserves(), (197, 51));
+
+    // The only depositor gets back what it swapped.
+    assert_eq!(pool.withdraw(&user, &100, &0, &0), (197, 51));
+    assert_eq!(pool.shares(&user), 0);
+    assert_eq!(token::Client::new(&env, &token_a).balance(&user), 1000);
+    assert_eq!(token::Client::new(&env, &token_b).balance(&user), 1000);
+}
@@ -0,0 +1,109 @@
+#![no_std]
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype,
+    crypto::Hash,
+    Bytes, BytesN, Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    ClientDataJsonChallengeIncorrect = 1,
+}
+
+/// WebAuthn assertion of a passkey, as returned by `navigator.credentials.get`, with the
+/// signature converted from DER to its raw, low-S, 64 byte form.
+#[contracttype]
+pub struct Signature {
+    pub authenticator_data: Bytes,
+    pub client_data_json: Bytes,
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+enum DataKey {
+    PublicKey,
+}
+
+/// Smart wallet whose transactions are authorized with a passkey, a secp256r1 key kept by the
+/// authenticator of the user's device.
+#[contract]
+pub struct PasskeyWallet;
+
+#[contractimpl]
+impl PasskeyWallet {
+    /// `public_key` is the uncompressed SEC 1 encoding of the passkey's public key.
+    pub fn __constructor(env: Env, public_key: BytesN<65>) {
+        env.storage()
+            .instance()
+            .set(&DataKey::PublicKey, &public_key);
+    }
+
+    pub fn public_key(env: Env) -> BytesN<65> {
+        env.storage().instance().get(&DataKey::PublicKey).unwrap()
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for PasskeyWallet {
+    type Signature = Signature;
+    type Error = Error;
+
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: Signature,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        // The challenge of the assertion must be the payload being authorized.
+        let challenge = base64_url(&signature_payload.to_array());
+        let client_data = signature.client_data_json.to_buffer::<1024>();
+        if !contains(client_data.as_slice(), &challenge) {
+            return Err(Error::ClientDataJsonChallengeIncorrect);
+        }
+
+        // The authenticator signs its data followed by the hash of the client data.
+        let mut signed = signature.authenticator_data.clone();
+        signed.extend_from_array(
+            &env.crypto()
+                .sha256(&signature.client_data_json)
+                .to_array(),
+        );
+        let digest = env.crypto().sha256(&signed);
+        let public_key = env.storage().instance().get(&DataKey::PublicKey).unwrap();
+        env.crypto()
+            .secp256r1_verify(&public_key, &digest, &signature.signature);
+        Ok(())
+    }
+}
+
+/// Unpadded base64url encoding of a 32 byte payload, as WebAuthn encodes challenges.
+fn base64_url(data: &[u8; 32]) -> [u8; 43] {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = [0u8; 43];
+    let mut bits = 0u32;
+    let mut len = 0;
+    let mut i = 0;
+    for byte in data {
+        bits = (bits << 8) | u32::from(*byte);
+        len += 8;
+        while len >= 6 {
+            len -= 6;
+            out[i] = ALPHABET[((bits >> len) & 0x3f) as usize];
+            i += 1;
+        }
+    }
+    if len > 0 {
+        out[i] = ALPHABET[((bits << (6 - len)) & 0x3f) as usize];
+    }
+    out
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+mod test;
@@ -0,0 +1,20 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{BytesN, Env};
+
+#[test]
+fn test_public_key() {
+    let env = Env::default();
+    let public_key = BytesN::from_array(&env, &[4; 65]);
+    let contract_id = env.register(PasskeyWallet, (&public_key,));
+    let client = PasskeyWalletClient::new(&env, &contract_id);
+    assert_eq!(client.public_key(), public_key);
+}
+
+#[test]
+fn test_base64_url() {
+    let encoded = base64_url(&[0xfb; 32]);
+    assert_eq!(&encoded[..4], b"-_v7");
+    assert_eq!(encoded[42], b's');
+}
@@ -0,0 +1,121 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
+    Address, Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyDeposited = 1,
+    TooManyClaimants = 2,
+    NothingToClaim = 3,
+    NotClaimant = 4,
+    OutsideTimeBound = 5,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum TimeBoundKind {
+    Before,
+    After,
+}
+
+/// The balance can be claimed before or after `timestamp`, in seconds since the unix epoch.
+#[derive(Clone)]
+#[contracttype]
+pub struct TimeBound {
+    pub kind: TimeBoundKind,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimableBalance {
+    pub token: Address,
+    pub amount: i128,
+    pub claimants: Vec<Address>,
+    pub time_bound: TimeBound,
+}
+
+#[contracttype]
+enum DataKey {
+    Balance,
+}
+
+/// Locks a token balance that one of the claimants can claim within a time bound.
+#[contract]
+pub struct Timelock;
+
+#[contractimpl]
+impl Timelock {
+    pub fn deposit(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        claimants: Vec<Address>,
+        time_bound: TimeBound,
+    ) {
+        from.require_auth();
+        if claimants.len() > 10 {
+            panic_with_error!(&env, Error::TooManyClaimants);
+        }
+        if env.storage().instance().has(&DataKey::Balance) {
+            panic_with_error!(&env, Error::AlreadyDeposited);
+        }
+        token::Client::new(&env, &token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        env.storage().instance().set(
+            &DataKey::Balance,
+            &ClaimableBalance {
+                token,
+                amount,
+                claimants,
+                time_bound,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("deposit"), from), amount);
+    }
+
+    pub fn claim(env: Env, claimant: Address) {
+        claimant.require_auth();
+        let Some(balance) = env
+            .storage()
+            .instance()
+            .get::<_, ClaimableBalance>(&DataKey::Balance)
+        else {
+            panic_with_error!(&env, Error::NothingToClaim);
+        };
+        if !balance.claimants.contains(&claimant) {
+            panic_with_error!(&env, Error::NotClaimant);
+        }
+        let now = env.ledger().timestamp();
+        let within_bound = match balance.time_bound.kind {
+            TimeBoundKind::Before => now <= balance.time_bound.timestamp,
+            TimeBoundKind::After => now >= balance.time_bound.timestamp,
+        };
+        if !within_bound {
+            panic_with_error!(&env, Error::OutsideTimeBound);
+        }
+        env.storage().instance().remove(&DataKey::Balance);
+        token::Client::new(&env, &balance.token).transfer(
+            &env.current_contract_address(),
+            &claimant,
+            &balance.amount,
+        );
+        env.events()
+            .publish((symbol_short!("claim"), claimant), balance.amount);
+    }
+
+    pub fn balance(env: Env) -> Option<ClaimableBalance> {
+        env.storage().instance().get(&DataKey::Balance)
+    }
+}
+
+mod test;
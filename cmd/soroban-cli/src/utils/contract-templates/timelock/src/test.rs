@@ -0,0 +1,42 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env,
+};
+
+#[test]
+fn test_claim_after_time_bound() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let claimant = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let timelock = TimelockClient::new(&env, &env.register(Timelock, ()));
+    timelock.deposit(
+        &depositor,
+        &token,
+        &800,
+        &vec![&env, claimant.clone()],
+        &TimeBound {
+            kind: TimeBoundKind::After,
+            timestamp: 100,
+        },
+    );
+
+    assert_eq!(
+        timelock.try_claim(&claimant),
+        Err(Ok(Error::OutsideTimeBound.into()))
+    );
+    env.ledger().set_timestamp(100);
+    timelock.claim(&claimant);
+    assert_eq!(token::Client::new(&env, &token).balance(&claimant), 800);
+    assert!(timelock.balance().is_none());
+}
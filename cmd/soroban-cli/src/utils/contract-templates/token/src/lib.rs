@@ -0,0 +1,223 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
+    token::TokenInterface, Address, Env, String,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    NegativeAmount = 1,
+    InsufficientBalance = 2,
+    InsufficientAllowance = 3,
+    InvalidExpiration = 4,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    Decimals,
+    Name,
+    Symbol,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contracttype]
+struct Allowance {
+    amount: i128,
+    expiration_ledger: u32,
+}
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[contract]
+pub struct Token;
+
+#[contractimpl]
+impl Token {
+    pub fn __constructor(env: Env, admin: Address, decimals: u32, name: String, symbol: String) {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::Decimals, &decimals);
+        storage.set(&DataKey::Name, &name);
+        storage.set(&DataKey::Symbol, &symbol);
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        check_nonnegative(&env, amount);
+        let admin = admin(&env);
+        admin.require_auth();
+        add_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+    }
+
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin = admin(&env);
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.events()
+            .publish((symbol_short!("set_admin"), admin), new_admin);
+    }
+
+    pub fn admin(env: Env) -> Address {
+        admin(&env)
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for Token {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        allowance(&env, &from, &spender)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        check_nonnegative(&env, amount);
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidExpiration);
+        }
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &Allowance {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        }
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative(&env, amount);
+        spend_balance(&env, &from, amount);
+        add_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        add_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative(&env, amount);
+        spend_balance(&env, &from, amount);
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Decimals).unwrap()
+    }
+
+    fn name(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Name).unwrap()
+    }
+
+    fn symbol(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Symbol).unwrap()
+    }
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+fn check_nonnegative(env: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, Error::NegativeAmount);
+    }
+}
+
+fn balance(env: &Env, id: &Address) -> i128 {
+    let key = DataKey::Balance(id.clone());
+    let balance = env.storage().persistent().get(&key);
+    if balance.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    balance.unwrap_or(0)
+}
+
+fn add_balance(env: &Env, id: &Address, amount: i128) {
+    let key = DataKey::Balance(id.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &(balance(env, id) + amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+fn spend_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = balance(env, id);
+    if balance < amount {
+        panic_with_error!(env, Error::InsufficientBalance);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &(balance - amount));
+}
+
+fn allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match env.storage().temporary().get::<_, Allowance>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => {
+            allowance.amount
+        }
+        _ => 0,
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    let Some(allowance) = env.storage().temporary().get::<_, Allowance>(&key) else {
+        panic_with_error!(env, Error::InsufficientAllowance);
+    };
+    if allowance.expiration_ledger < env.ledger().sequence() || allowance.amount < amount {
+        panic_with_error!(env, Error::InsufficientAllowance);
+    }
+    if amount > 0 {
+        env.storage().temporary().set(
+            &key,
+            &Allowance {
+                amount: allowance.amount - amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+        );
+    }
+}
+
+mod test;
@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn create_token<'a>(env: &Env, admin: &Address) -> TokenClient<'a> {
+    let contract_id = env.register(
+        Token,
+        (
+            admin,
+            7_u32,
+            String::from_str(env, "Token"),
+            String::from_str(env, "TOK"),
+        ),
+    );
+    TokenClient::new(env, &contract_id)
+}
+
+#[test]
+fn test_mint_transfer_and_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = create_token(&env, &admin);
+
+    token.mint(&alice, &1000);
+    token.transfer(&alice, &bob, &600);
+    token.burn(&bob, &100);
+
+    assert_eq!(token.balance(&alice), 400);
+    assert_eq!(token.balance(&bob), 500);
+    assert_eq!(token.symbol(), String::from_str(&env, "TOK"));
+}
+
+#[test]
+fn test_transfer_from_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = create_token(&env, &admin);
+
+    token.mint(&alice, &1000);
+    token.approve(&alice, &bob, &300, &200);
+    token.transfer_from(&bob, &alice, &bob, &200);
+
+    assert_eq!(token.allowance(&alice, &bob), 100);
+    assert_eq!(token.balance(&bob), 200);
+    assert_eq!(
+        token.try_transfer_from(&bob, &alice, &bob, &200),
+        Err(Ok(Error::InsufficientAllowance.into()))
+    );
+}
@@ -0,0 +1,8 @@
+import { defineConfig } from "astro/config";
+
+export default defineConfig({
+  vite: {
+    // The bindings use node's Buffer, which the browser does not have.
+    define: { global: "window" },
+  },
+});
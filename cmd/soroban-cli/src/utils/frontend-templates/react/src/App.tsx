@@ -0,0 +1,26 @@
+import { Client, networks } from "%contract-template%";
+
+const client = new Client({
+  ...networks.testnet,
+  rpcUrl: "https://soroban-testnet.stellar.org",
+});
+
+export default function App() {
+  const methods = client.spec.funcs().map((f) => f.name().toString());
+  return (
+    <main>
+      <h1>%contract-template%</h1>
+      <p>
+        Contract <code>{networks.testnet.contractId}</code> on testnet
+      </p>
+      <h2>Methods</h2>
+      <ul>
+        {methods.map((method) => (
+          <li key={method}>
+            <code>client.{method}()</code>
+          </li>
+        ))}
+      </ul>
+    </main>
+  );
+}
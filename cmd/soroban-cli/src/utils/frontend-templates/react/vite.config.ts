@@ -0,0 +1,8 @@
+import { defineConfig } from "vite";
+import react from "@vitejs/plugin-react";
+
+export default defineConfig({
+  plugins: [react()],
+  // The bindings use node's Buffer, which the browser does not have.
+  define: { global: "window" },
+});
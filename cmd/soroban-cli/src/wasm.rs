@@ -10,7 +10,7 @@ use stellar_xdr::curr::{ContractDataEntry, ContractExecutable, ScVal};
 
 use crate::{
     config::{
-        locator,
+        data, locator,
         network::{Error as NetworkError, Network},
     },
     utils::{self, rpc::get_remote_wasm_from_hash},
@@ -50,6 +50,8 @@ pub enum Error {
     ContractIsStellarAsset,
     #[error(transparent)]
     Network(#[from] NetworkError),
+    #[error(transparent)]
+    Data(#[from] data::Error),
 }
 
 #[derive(Debug, clap::Args, Clone)]
@@ -120,19 +122,38 @@ pub fn len(p: &Path) -> Result<u64, Error> {
         .len())
 }
 
+/// Fetch the Wasm with `hash`, reading it from and adding it to the cache of fetched Wasm unless
+/// `no_cache`.
+pub async fn fetch_from_hash(
+    client: &soroban_rpc::Client,
+    hash: &Hash,
+    no_cache: bool,
+) -> Result<Vec<u8>, Error> {
+    let hash_str = hash.to_string();
+    if !no_cache {
+        if let Some(wasm) = data::read_wasm(&hash_str)? {
+            return Ok(wasm);
+        }
+    }
+    let wasm = get_remote_wasm_from_hash(client, hash).await?;
+    if !no_cache {
+        data::write_wasm(&hash_str, &wasm)?;
+    }
+    Ok(wasm)
+}
+
 pub async fn fetch_from_contract(
     stellar_strkey::Contract(contract_id): &stellar_strkey::Contract,
     network: &Network,
+    no_cache: bool,
 ) -> Result<Vec<u8>, Error> {
     tracing::trace!(?network);
     let client = network.rpc_client()?;
-    client
-        .verify_network_passphrase(Some(&network.network_passphrase))
-        .await?;
+    network.verify_rpc_passphrase(&client, no_cache).await?;
     let data_entry = client.get_contract_data(contract_id).await?;
     if let ScVal::ContractInstance(contract) = &data_entry.val {
         return match &contract.executable {
-            ContractExecutable::Wasm(hash) => Ok(get_remote_wasm_from_hash(&client, hash).await?),
+            ContractExecutable::Wasm(hash) => Ok(fetch_from_hash(&client, hash, no_cache).await?),
             ContractExecutable::StellarAsset => Err(ContractIsStellarAsset),
         };
     }